@@ -1,4 +1,5 @@
 use std::fmt::{self, Display, Formatter};
+use std::ops::RangeInclusive;
 use std::path::PathBuf;
 
 use clap::{ArgAction, Parser, Subcommand, ValueEnum};
@@ -41,6 +42,46 @@ impl Display for DiagnosticFormat {
     }
 }
 
+/// How to embed fonts into a PDF.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, ValueEnum)]
+pub enum FontEmbedding {
+    /// Only embed the used glyphs.
+    Subset,
+    /// Embed the complete fonts.
+    Full,
+    /// Don't embed fonts that only use Latin-1 characters (others are subsetted).
+    None,
+}
+
+impl Display for FontEmbedding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
+
+/// Which standard a PDF conforms to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, ValueEnum)]
+pub enum PdfStandard {
+    /// PDF 1.7.
+    #[value(name = "1.7")]
+    V1_7,
+    /// PDF/A-2b for long-term archival.
+    #[value(name = "a-2b")]
+    A2b,
+}
+
+impl Display for PdfStandard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
+
 /// What to do.
 #[derive(Debug, Clone, Subcommand)]
 #[command()]
@@ -90,6 +131,30 @@ pub struct CompileCommand {
     #[arg(long = "ppi")]
     pub ppi: Option<f32>,
 
+    /// Which pages to export, e.g. `5-10`, `5-` or `7`
+    #[arg(long = "pages", value_name = "RANGE", value_parser = parse_page_range)]
+    pub pages: Option<RangeInclusive<usize>>,
+
+    /// Keeps links to pages that are not exported instead of dropping them
+    #[arg(long = "keep-dangling-links")]
+    pub keep_dangling_links: bool,
+
+    /// How to embed fonts into the PDF
+    #[clap(
+        long = "font-embedding",
+        default_value_t = FontEmbedding::Subset,
+        value_parser = clap::value_parser!(FontEmbedding)
+    )]
+    pub font_embedding: FontEmbedding,
+
+    /// Which standard the PDF conforms to
+    #[clap(
+        long = "pdf-standard",
+        default_value_t = PdfStandard::V1_7,
+        value_parser = clap::value_parser!(PdfStandard)
+    )]
+    pub pdf_standard: PdfStandard,
+
     /// In which format to emit diagnostics
     #[clap(
         long,
@@ -103,6 +168,26 @@ pub struct CompileCommand {
     pub flamegraph: Option<Option<PathBuf>>,
 }
 
+/// Parses a one-based, inclusive page range.
+fn parse_page_range(value: &str) -> Result<RangeInclusive<usize>, String> {
+    let parse = |part: &str| match part.trim().parse::<usize>() {
+        Ok(number) if number > 0 => Ok(number),
+        _ => Err(format!("invalid page number: {part}")),
+    };
+
+    match value.split_once('-') {
+        None => parse(value).map(|page| page..=page),
+        Some((start, end)) => {
+            let start = if start.is_empty() { 1 } else { parse(start)? };
+            let end = if end.is_empty() { usize::MAX } else { parse(end)? };
+            if start > end {
+                return Err("page range must not be empty".into());
+            }
+            Ok(start..=end)
+        }
+    }
+}
+
 /// List all discovered fonts in system and custom font paths
 #[derive(Debug, Clone, Parser)]
 pub struct FontsCommand {
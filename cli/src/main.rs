@@ -6,6 +6,7 @@ use std::collections::HashMap;
 use std::fs::{self, File};
 use std::hash::Hash;
 use std::io::{self, IsTerminal, Write};
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
@@ -24,14 +25,18 @@ use termcolor::{ColorChoice, StandardStream, WriteColor};
 use typst::diag::{FileError, FileResult, SourceError, StrResult};
 use typst::doc::Document;
 use typst::eval::{Datetime, Library};
+use typst::export::PdfOptions;
 use typst::font::{Font, FontBook, FontInfo, FontVariant};
 use typst::geom::Color;
 use typst::syntax::{Source, SourceId};
 use typst::util::{Buffer, PathExt};
 use typst::World;
+use typst_library::layout::CacheStats;
 use walkdir::WalkDir;
 
-use crate::args::{CliArguments, Command, CompileCommand, DiagnosticFormat};
+use crate::args::{
+    CliArguments, Command, CompileCommand, DiagnosticFormat, FontEmbedding, PdfStandard,
+};
 
 type CodespanResult<T> = Result<T, CodespanError>;
 type CodespanError = codespan_reporting::files::Error;
@@ -106,6 +111,14 @@ struct CompileSettings {
     ppi: Option<f32>,
     /// In which format to emit diagnostics.
     diagnostic_format: DiagnosticFormat,
+    /// Which pages to export.
+    pages: Option<RangeInclusive<usize>>,
+    /// Whether to keep links to pages that are not exported.
+    keep_dangling_links: bool,
+    /// How to embed fonts into the PDF.
+    font_embedding: FontEmbedding,
+    /// Which standard the PDF conforms to.
+    pdf_standard: PdfStandard,
 }
 
 impl CompileSettings {
@@ -120,6 +133,10 @@ impl CompileSettings {
         open: Option<Option<String>>,
         ppi: Option<f32>,
         diagnostic_format: DiagnosticFormat,
+        pages: Option<RangeInclusive<usize>>,
+        keep_dangling_links: bool,
+        font_embedding: FontEmbedding,
+        pdf_standard: PdfStandard,
     ) -> Self {
         let output = match output {
             Some(path) => path,
@@ -134,21 +151,40 @@ impl CompileSettings {
             open,
             diagnostic_format,
             ppi,
+            pages,
+            keep_dangling_links,
+            font_embedding,
+            pdf_standard,
         }
     }
 
+    /// Whether the page with the given one-based number should be exported.
+    fn exports(&self, page: usize) -> bool {
+        self.pages.as_ref().map_or(true, |range| range.contains(&page))
+    }
+
     /// Create a new compile settings from the CLI arguments and a compile command.
     ///
     /// # Panics
     /// Panics if the command is not a compile or watch command.
     fn with_arguments(args: CliArguments) -> Self {
         let watch = matches!(args.command, Command::Watch(_));
-        let CompileCommand { input, output, open, ppi, diagnostic_format, .. } =
-            match args.command {
-                Command::Compile(command) => command,
-                Command::Watch(command) => command,
-                _ => unreachable!(),
-            };
+        let CompileCommand {
+            input,
+            output,
+            open,
+            ppi,
+            pages,
+            keep_dangling_links,
+            font_embedding,
+            pdf_standard,
+            diagnostic_format,
+            ..
+        } = match args.command {
+            Command::Compile(command) => command,
+            Command::Watch(command) => command,
+            _ => unreachable!(),
+        };
 
         Self::new(
             input,
@@ -159,6 +195,10 @@ impl CompileSettings {
             open,
             ppi,
             diagnostic_format,
+            pages,
+            keep_dangling_links,
+            font_embedding,
+            pdf_standard,
         )
     }
 }
@@ -285,7 +325,23 @@ fn compile_once(world: &mut SystemWorld, command: &CompileSettings) -> StrResult
     world.reset();
     world.main = world.resolve(&command.input).map_err(|err| err.to_string())?;
 
-    match typst::compile(world) {
+    CacheStats::reset();
+    let result = typst::compile(world);
+    let stats = CacheStats::get();
+    tracing::info!(
+        "Reused {} of {} layouts ({:.1}%)",
+        stats.hits(),
+        stats.requests,
+        100.0 * stats.hit_rate(),
+    );
+    tracing::info!(
+        "Reused {} of {} shaped text runs ({:.1}%)",
+        stats.shape_hits(),
+        stats.shape_requests,
+        100.0 * stats.shape_hit_rate(),
+    );
+
+    match result {
         // Export the PDF / PNG.
         Ok(document) => {
             export(&document, command)?;
@@ -313,7 +369,9 @@ fn export(document: &Document, command: &CompileSettings) -> StrResult<()> {
             // Determine whether we have a `{n}` numbering.
             let string = command.output.to_str().unwrap_or_default();
             let numbered = string.contains("{n}");
-            if !numbered && document.pages.len() > 1 {
+            let exported =
+                (1..=document.pages.len()).filter(|n| command.exports(*n)).count();
+            if !numbered && exported > 1 {
                 Err("cannot export multiple PNGs without `{n}` in output path")?;
             }
 
@@ -325,6 +383,10 @@ fn export(document: &Document, command: &CompileSettings) -> StrResult<()> {
             let mut storage;
 
             for (i, frame) in document.pages.iter().enumerate() {
+                if !command.exports(i + 1) {
+                    continue;
+                }
+
                 let pixmap = typst::export::render(frame, ppi, Color::WHITE);
                 let path = if numbered {
                     storage = string.replace("{n}", &format!("{:0width$}", i + 1));
@@ -336,7 +398,20 @@ fn export(document: &Document, command: &CompileSettings) -> StrResult<()> {
             }
         }
         _ => {
-            let buffer = typst::export::pdf(document);
+            let options = PdfOptions {
+                pages: command.pages.clone(),
+                keep_dangling_links: command.keep_dangling_links,
+                font_embedding: match command.font_embedding {
+                    FontEmbedding::Subset => typst::export::FontEmbedding::Subset,
+                    FontEmbedding::Full => typst::export::FontEmbedding::Full,
+                    FontEmbedding::None => typst::export::FontEmbedding::None,
+                },
+                standard: match command.pdf_standard {
+                    PdfStandard::V1_7 => typst::export::PdfStandard::V1_7,
+                    PdfStandard::A2b => typst::export::PdfStandard::A2b,
+                },
+            };
+            let buffer = typst::export::pdf_with_options(document, &options)?;
             fs::write(&command.output, buffer).map_err(|_| "failed to write PDF file")?;
         }
     }
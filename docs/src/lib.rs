@@ -940,6 +940,7 @@ const TYPE_ORDER: &[&str] = &[
     "fraction",
     "color",
     "datetime",
+    "duration",
     "string",
     "regex",
     "label",
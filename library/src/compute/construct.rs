@@ -1,9 +1,10 @@
 use std::num::NonZeroI64;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use time::{Month, PrimitiveDateTime};
 
-use typst::eval::{Datetime, Regex};
+use typst::eval::{Datetime, Duration, Regex};
 
 use crate::prelude::*;
 
@@ -338,6 +339,59 @@ pub fn datetime_today(
         .ok_or("unable to get the current date")?)
 }
 
+/// Create a new duration.
+///
+/// A [duration]($type/duration) can be added to or subtracted from a
+/// [datetime]($type/datetime). Subtracting two datetimes of the same kind
+/// also yields a duration.
+///
+/// ## Example
+/// ```example
+/// #let deadline = datetime(
+///   year: 2023,
+///   month: 7,
+///   day: 1,
+/// )
+///
+/// #(deadline - duration(days: 30)).display()
+///
+/// #duration(hours: 36).days()
+/// ```
+///
+/// Display: Duration
+/// Category: construct
+#[func]
+pub fn duration(
+    /// The number of seconds.
+    #[named]
+    #[default(0)]
+    seconds: i64,
+    /// The number of minutes.
+    #[named]
+    #[default(0)]
+    minutes: i64,
+    /// The number of hours.
+    #[named]
+    #[default(0)]
+    hours: i64,
+    /// The number of days.
+    #[named]
+    #[default(0)]
+    days: i64,
+    /// The number of weeks.
+    #[named]
+    #[default(0)]
+    weeks: i64,
+) -> StrResult<Duration> {
+    [(seconds, 1), (minutes, 60), (hours, 3600), (days, 86400), (weeks, 604800)]
+        .into_iter()
+        .try_fold(0i64, |total, (count, unit)| {
+            count.checked_mul(unit)?.checked_add(total)
+        })
+        .map(Duration::from_seconds)
+        .ok_or_else(|| "duration is too large".into())
+}
+
 /// Create a CMYK color.
 ///
 /// This is useful if you want to target a specific printer. The conversion
@@ -379,6 +433,160 @@ cast! {
     },
 }
 
+/// Create a gradient.
+///
+/// A gradient can be used anywhere a color is accepted, for example as the
+/// fill of a shape or of text. Its colors are positioned relative to the
+/// bounding box of the element it paints. Calling `gradient` directly creates
+/// a [linear gradient]($func/gradient.linear).
+///
+/// Each stop is either a color or an array of a color and its offset along
+/// the gradient. Stops without an offset are distributed evenly.
+///
+/// ## Example { #example }
+/// ```example
+/// #rect(fill: gradient(red, blue))
+/// #circle(fill: gradient.radial(white, blue))
+/// #text(fill: gradient(teal, purple))[Gradient]
+/// ```
+///
+/// Display: Gradient
+/// Category: construct
+#[func]
+#[scope(
+    scope.define("linear", gradient_linear_func());
+    scope.define("radial", gradient_radial_func());
+    scope
+)]
+pub fn gradient(
+    /// The color stops of the gradient.
+    #[variadic]
+    stops: Vec<Spanned<Stop>>,
+    /// The direction of the gradient. At `{0deg}`, it runs from left to
+    /// right. Angles increase clockwise.
+    #[named]
+    #[default(Angle::zero())]
+    angle: Angle,
+    /// The callsite span.
+    span: Span,
+) -> SourceResult<Gradient> {
+    gradient_linear(stops, angle, span)
+}
+
+/// Create a linear gradient.
+///
+/// The gradient transitions between its stops along a line through the
+/// center of the bounding box. The line is long enough for the corners of
+/// the box to receive the colors of the first and last stop.
+///
+/// ## Example { #example }
+/// ```example
+/// #rect(fill: gradient.linear(
+///   (red, 0%), (yellow, 20%), (blue, 100%),
+///   angle: 90deg,
+/// ))
+/// ```
+///
+/// Display: Linear Gradient
+/// Category: construct
+#[func]
+pub fn gradient_linear(
+    /// The color stops of the gradient.
+    #[variadic]
+    stops: Vec<Spanned<Stop>>,
+    /// The direction of the gradient. At `{0deg}`, it runs from left to
+    /// right. Angles increase clockwise.
+    #[named]
+    #[default(Angle::zero())]
+    angle: Angle,
+    /// The callsite span.
+    span: Span,
+) -> SourceResult<Gradient> {
+    let stops = distribute(stops, span)?;
+    Ok(Gradient::Linear(Arc::new(LinearGradient { stops, angle })))
+}
+
+/// Create a radial gradient.
+///
+/// The gradient radiates outwards from its center, reaching its last stop at
+/// the given radius.
+///
+/// ## Example { #example }
+/// ```example
+/// #circle(fill: gradient.radial(white, blue))
+/// #square(fill: gradient.radial(
+///   yellow, red,
+///   center: (0%, 0%),
+///   radius: 100%,
+/// ))
+/// ```
+///
+/// Display: Radial Gradient
+/// Category: construct
+#[func]
+pub fn gradient_radial(
+    /// The color stops of the gradient.
+    #[variadic]
+    stops: Vec<Spanned<Stop>>,
+    /// The center of the gradient, relative to the bounding box of the
+    /// painted element.
+    #[named]
+    #[default(Axes::splat(Ratio::new(0.5)))]
+    center: Axes<Ratio>,
+    /// The radius of the gradient, relative to the larger side of the
+    /// bounding box of the painted element.
+    #[named]
+    #[default(Ratio::new(0.5))]
+    radius: Ratio,
+    /// The callsite span.
+    span: Span,
+) -> SourceResult<Gradient> {
+    let stops = distribute(stops, span)?;
+    Ok(Gradient::Radial(Arc::new(RadialGradient { stops, center, radius })))
+}
+
+/// A color stop of a gradient, optionally with an offset.
+pub struct Stop(Color, Option<Ratio>);
+
+cast! {
+    Stop,
+    color: Color => Self(color, None),
+    array: Array => {
+        let mut iter = array.into_iter();
+        match (iter.next(), iter.next(), iter.next()) {
+            (Some(a), Some(b), None) => Self(a.cast()?, Some(b.cast()?)),
+            _ => Err("stop must be a color or an array of a color and an offset")?,
+        }
+    },
+}
+
+/// Resolve the offsets of a gradient's stops, distributing stops without an
+/// offset evenly.
+fn distribute(
+    stops: Vec<Spanned<Stop>>,
+    span: Span,
+) -> SourceResult<Vec<(Color, Ratio)>> {
+    if stops.len() < 2 {
+        bail!(span, "a gradient must have at least two stops");
+    }
+
+    let last = (stops.len() - 1) as f64;
+    let mut resolved = Vec::with_capacity(stops.len());
+    let mut prev = Ratio::zero();
+    for (i, Spanned { v: Stop(color, offset), span }) in stops.into_iter().enumerate() {
+        let offset = offset.unwrap_or_else(|| Ratio::new(i as f64 / last));
+        if !(0.0..=1.0).contains(&offset.get()) {
+            bail!(span, "offset must be between 0% and 100%");
+        } else if offset < prev {
+            bail!(span, "offsets must be in ascending order");
+        }
+        prev = offset;
+        resolved.push((color, offset));
+    }
+
+    Ok(resolved)
+}
+
 /// Create a custom symbol with modifiers.
 ///
 /// ## Example { #example }
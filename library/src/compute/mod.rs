@@ -23,7 +23,9 @@ pub(super) fn define(global: &mut Scope) {
     global.define("luma", luma_func());
     global.define("rgb", rgb_func());
     global.define("cmyk", cmyk_func());
+    global.define("gradient", gradient_func());
     global.define("datetime", datetime_func());
+    global.define("duration", duration_func());
     global.define("symbol", symbol_func());
     global.define("str", str_func());
     global.define("label", label_func());
@@ -1,3 +1,5 @@
+use super::flow::find_footnotes;
+use crate::meta::{FootnoteElem, FootnoteEntry};
 use crate::prelude::*;
 use crate::text::TextElem;
 
@@ -7,7 +9,11 @@ use crate::text::TextElem;
 /// multiple columns. It will not equalize the height of the columns, instead,
 /// the columns will take up the height of their container or the remaining
 /// height on the page. The columns function can break across pages if
-/// necessary.
+/// necessary. To equalize the height of the columns in the last region, enable
+/// [balancing]($func/columns.balance).
+///
+/// Footnotes in page-level columns span the full width of the page and are
+/// placed below all columns.
 ///
 /// ## Example { #example }
 /// ```example
@@ -44,6 +50,19 @@ pub struct ColumnsElem {
     #[default(Ratio::new(0.04).into())]
     pub gutter: Rel<Length>,
 
+    /// Whether to balance the height of the columns in the last region.
+    ///
+    /// If enabled, the content of the last region is distributed evenly
+    /// across its columns instead of filling them one after another.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #set columns(balance: true)
+    /// #columns(2, lorem(20))
+    /// ```
+    #[default(false)]
+    pub balance: bool,
+
     /// The content that should be layouted into the columns.
     #[required]
     pub body: Content,
@@ -70,31 +89,97 @@ impl Layout for ColumnsElem {
         let gutter = self.gutter(styles).relative_to(regions.base().x);
         let width = (regions.size.x - gutter * (columns - 1) as f64) / columns as f64;
 
-        let backlog: Vec<_> = std::iter::once(&regions.size.y)
-            .chain(regions.backlog)
-            .flat_map(|&height| std::iter::repeat(height).take(columns))
-            .skip(1)
-            .collect();
+        // Root columns place the footnotes of each region across the full
+        // width below the columns. Because the footnotes depend on where
+        // the content ends up, the space for them is determined iteratively.
+        let mut reserved: Vec<Abs> = vec![];
+        if regions.root {
+            let mut prev: Vec<Vec<Location>> = vec![];
+            for _ in 0..3 {
+                let mut backlog = vec![];
+                let pod = column_regions(
+                    &regions,
+                    columns,
+                    width,
+                    &reserved,
+                    None,
+                    &mut backlog,
+                );
+                let frames = body.measure(vt, styles, pod)?.into_frames();
+                let notes = region_notes(&frames, columns);
+                let locations: Vec<Vec<Location>> = notes
+                    .iter()
+                    .map(|notes| {
+                        notes.iter().filter_map(|note| note.0.location()).collect()
+                    })
+                    .collect();
+                if locations == prev || locations.iter().all(Vec::is_empty) {
+                    break;
+                }
+
+                reserved.clear();
+                for notes in &notes {
+                    let block =
+                        layout_footnotes(vt, styles, notes, regions.size.x, true)?;
+                    reserved.push(block.map_or(Abs::zero(), |frame| frame.height()));
+                }
+
+                prev = locations;
+            }
+        }
+
+        // Balance the columns in the last region by searching for the
+        // smallest column height that fits its content.
+        let mut limit = None;
+        if self.balance(styles) {
+            let mut backlog = vec![];
+            let pod =
+                column_regions(&regions, columns, width, &reserved, None, &mut backlog);
+            let frames = body.measure(vt, styles, pod)?.into_frames();
+            let last = frames.iter().rposition(|frame| !frame.is_empty()).unwrap_or(0);
+            let k = last / columns;
+            let full = regions.iter().nth(k).map_or(Abs::zero(), |size| size.y)
+                - reserved.get(k).copied().unwrap_or_default();
+
+            if full.is_finite() {
+                let (mut lo, mut hi) = (Abs::zero(), full);
+                for _ in 0..12 {
+                    let mid = (lo + hi) / 2.0;
+                    let pod = column_regions(
+                        &regions,
+                        columns,
+                        width,
+                        &reserved,
+                        Some((k, mid)),
+                        &mut backlog,
+                    );
+                    let count = body.measure(vt, styles, pod)?.len();
+                    if count <= (k + 1) * columns {
+                        hi = mid;
+                    } else {
+                        lo = mid;
+                    }
+                }
+                limit = Some((k, hi));
+            }
+        }
 
         // Create the pod regions.
-        let pod = Regions {
-            size: Size::new(width, regions.size.y),
-            full: regions.full,
-            backlog: &backlog,
-            last: regions.last,
-            expand: Axes::new(true, regions.expand.y),
-            root: regions.root,
-        };
+        let mut backlog = vec![];
+        let pod =
+            column_regions(&regions, columns, width, &reserved, limit, &mut backlog);
 
         // Layout the children.
-        let mut frames = body.layout(vt, styles, pod)?.into_iter();
+        let frames = body.layout(vt, styles, pod)?.into_frames();
+        let notes = if regions.root { region_notes(&frames, columns) } else { vec![] };
+        let mut frames = frames.into_iter();
         let mut finished = vec![];
 
         let dir = TextElem::dir_in(styles);
         let total_regions = (frames.len() as f32 / columns as f32).ceil() as usize;
 
         // Stitch together the columns for each region.
-        for region in regions.iter().take(total_regions) {
+        for (i, region) in regions.iter().take(total_regions).enumerate() {
             // The height should be the parent height if we should expand.
             // Otherwise its the maximum column height for the frame. In that
             // case, the frame is first created with zero height and then
@@ -120,6 +205,20 @@ impl Layout for ColumnsElem {
                 cursor += width + gutter;
             }
 
+            // Place the footnotes below the columns.
+            let notes = notes.get(i).map_or(&[][..], Vec::as_slice);
+            if let Some(block) =
+                layout_footnotes(vt, styles, notes, regions.size.x, false)?
+            {
+                let y = if regions.expand.y {
+                    region.y - block.height()
+                } else {
+                    output.height()
+                };
+                output.size_mut().y.set_max(y + block.height());
+                output.push_frame(Point::with_y(y), block);
+            }
+
             finished.push(output);
         }
 
@@ -127,6 +226,107 @@ impl Layout for ColumnsElem {
     }
 }
 
+/// Create the regions for the columns of the given `regions`.
+///
+/// The `reserved` height is kept free at the bottom of each region. If a
+/// `limit` is given, the columns of the region with that index have the given
+/// height and no further regions follow.
+fn column_regions<'a>(
+    regions: &Regions,
+    columns: usize,
+    width: Abs,
+    reserved: &[Abs],
+    limit: Option<(usize, Abs)>,
+    backlog: &'a mut Vec<Abs>,
+) -> Regions<'a> {
+    let count = (1 + regions.backlog.len())
+        .max(reserved.len())
+        .max(limit.map_or(0, |(k, _)| k + 1));
+    let mut heights: Vec<Abs> = regions.iter().take(count).map(|size| size.y).collect();
+    for (height, reserved) in heights.iter_mut().zip(reserved) {
+        *height -= *reserved;
+    }
+
+    let mut last = regions.last;
+    if let Some((k, height)) = limit {
+        heights.truncate(k + 1);
+        if let Some(slot) = heights.get_mut(k) {
+            *slot = height;
+        }
+        last = Some(height);
+    }
+
+    backlog.clear();
+    backlog.extend(
+        heights
+            .iter()
+            .flat_map(|&height| std::iter::repeat(height).take(columns))
+            .skip(1),
+    );
+
+    let first = heights.first().copied().unwrap_or(regions.size.y);
+    Regions {
+        size: Size::new(width, first),
+        full: regions.full,
+        backlog,
+        last,
+        expand: Axes::new(true, regions.expand.y),
+        root: false,
+    }
+}
+
+/// Find the footnotes in each region's columns.
+fn region_notes(frames: &[Frame], columns: usize) -> Vec<Vec<FootnoteElem>> {
+    frames
+        .chunks(columns)
+        .map(|group| {
+            let mut notes = vec![];
+            for frame in group {
+                find_footnotes(&mut notes, frame);
+            }
+            notes
+        })
+        .collect()
+}
+
+/// Layout the footnotes of a region into one full-width block.
+fn layout_footnotes(
+    vt: &mut Vt,
+    styles: StyleChain,
+    notes: &[FootnoteElem],
+    width: Abs,
+    measure: bool,
+) -> SourceResult<Option<Frame>> {
+    if notes.is_empty() {
+        return Ok(None);
+    }
+
+    let pod = Regions::one(Size::new(width, Abs::inf()), Axes::new(true, false));
+    let mut layout = |content: Content| {
+        if measure {
+            content.measure(vt, styles, pod)
+        } else {
+            content.layout(vt, styles, pod)
+        }
+        .map(Fragment::into_frame)
+    };
+
+    let clearance = FootnoteEntry::clearance_in(styles);
+    let gap = FootnoteEntry::gap_in(styles);
+    let separator = layout(FootnoteEntry::separator_in(styles))?;
+    let mut output = Frame::new(Size::new(width, clearance + separator.height()));
+    output.push_frame(Point::with_y(clearance), separator);
+
+    for note in notes {
+        let entry = layout(FootnoteEntry::new(note.clone()).pack())?;
+        let y = output.height() + gap;
+        output.size_mut().y = y + entry.height();
+        output.push_frame(Point::with_y(y), entry);
+    }
+
+    Ok(Some(output))
+}
+
 /// A forced column break.
 ///
 /// The function will behave like a [page break]($func/pagebreak) when used in a
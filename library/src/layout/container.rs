@@ -1,6 +1,6 @@
 use typst::eval::AutoValue;
 
-use super::VElem;
+use super::{PageElem, VElem};
 use crate::layout::Spacing;
 use crate::prelude::*;
 
@@ -37,6 +37,16 @@ pub struct BoxElem {
     /// ```example
     /// Line in #box(width: 1fr, line(length: 100%)) between.
     /// ```
+    ///
+    /// With a dictionary like `{(max: 3cm)}`, the box fits its contents, but
+    /// is at most as wide as the given maximum. Its contents are broken into
+    /// lines accordingly.
+    ///
+    /// ```example
+    /// A #box(width: (max: 2cm))[
+    ///   narrow box with long text
+    /// ] B
+    /// ```
     pub width: Sizing,
 
     /// The height of the box.
@@ -92,8 +102,52 @@ pub struct BoxElem {
     pub outset: Sides<Option<Rel<Length>>>,
 
     /// Whether to clip the content inside the box.
-    #[default(false)]
-    pub clip: bool,
+    ///
+    /// Instead of `{true}`, this can also be a shape like a
+    /// [circle]($func/circle), a [polygon]($func/polygon) or a
+    /// [path]($func/path). The shape is laid out within the box's size,
+    /// starting at its top-left corner, and the content is clipped to the area
+    /// it covers.
+    ///
+    /// ```example
+    /// #box(
+    ///   clip: circle(radius: 1cm),
+    ///   image("tiger.jpg", width: 2cm),
+    /// )
+    /// ```
+    #[default(Clip::None)]
+    pub clip: Clip,
+
+    /// How opaque the box is, between `{0%}` and `{100%}`.
+    ///
+    /// The box is made transparent as a whole, including its fill and stroke,
+    /// so overlapping parts of its contents don't shine through each other.
+    ///
+    /// ```example
+    /// #box(opacity: 50%)[
+    ///   #circle(fill: red)
+    ///   #place(top + left, dx: 15pt, circle(fill: red))
+    /// ]
+    /// ```
+    #[default(Ratio::one())]
+    pub opacity: Ratio,
+
+    /// How the colors of the box are combined with what lies beneath it.
+    ///
+    /// The following blend modes are supported:
+    /// `{"normal"}`, `{"multiply"}`, `{"screen"}`, `{"overlay"}`,
+    /// `{"darken"}`, `{"lighten"}`, `{"color-dodge"}`, `{"color-burn"}`,
+    /// `{"hard-light"}`, `{"soft-light"}`, `{"difference"}` and
+    /// `{"exclusion"}`. They are exported in the same way to PDF and raster
+    /// images.
+    ///
+    /// ```example
+    /// #rect(fill: yellow)[
+    ///   #box(blend-mode: "multiply", rect(fill: aqua))
+    /// ]
+    /// ```
+    #[default(BlendMode::Normal)]
+    pub blend_mode: BlendMode,
 
     /// The contents of the box.
     #[positional]
@@ -109,7 +163,7 @@ impl Layout for BoxElem {
         regions: Regions,
     ) -> SourceResult<Fragment> {
         let width = match self.width(styles) {
-            Sizing::Auto => Smart::Auto,
+            Sizing::Auto | Sizing::Max(_) => Smart::Auto,
             Sizing::Rel(rel) => Smart::Custom(rel),
             Sizing::Fr(_) => Smart::Custom(Ratio::one().into()),
         };
@@ -117,12 +171,17 @@ impl Layout for BoxElem {
         // Resolve the sizing to a concrete size.
         let sizing = Axes::new(width, self.height(styles));
         let expand = sizing.as_ref().map(Smart::is_custom);
-        let size = sizing
+        let mut size = sizing
             .resolve(styles)
             .zip(regions.base())
             .map(|(s, b)| s.map(|v| v.relative_to(b)))
             .unwrap_or(regions.base());
 
+        // Limit automatic widths to the maximum.
+        if let Sizing::Max(max) = self.width(styles) {
+            size.x.set_min(max.resolve(styles).relative_to(regions.base().x));
+        }
+
         // Apply inset.
         let mut body = self.body(styles).unwrap_or_default();
         let inset = self.inset(styles);
@@ -130,6 +189,9 @@ impl Layout for BoxElem {
             body = body.padded(inset.map(|side| side.map(Length::from)));
         }
 
+        // Inline content is not snapped to the baseline grid.
+        body = body.styled(PageElem::set_baseline_grid(None));
+
         // Select the appropriate base and expansion for the child depending
         // on whether it is automatically or relatively sized.
         let pod = Regions::one(size, expand);
@@ -145,9 +207,7 @@ impl Layout for BoxElem {
         }
 
         // Clip the contents
-        if self.clip(styles) {
-            frame.clip();
-        }
+        self.clip(styles).apply(vt, styles, &mut frame, self.span())?;
 
         // Prepare fill and stroke.
         let fill = self.fill(styles);
@@ -160,6 +220,9 @@ impl Layout for BoxElem {
             frame.fill_and_stroke(fill, stroke, outset, radius, self.span());
         }
 
+        // Composite the whole box onto its backdrop.
+        frame.composite(opacity(self.opacity(styles)), self.blend_mode(styles));
+
         // Apply metadata.
         frame.meta(styles, false);
 
@@ -227,6 +290,20 @@ pub struct BlockElem {
     ///   fill: aqua,
     /// )
     /// ```
+    ///
+    /// Relative heights are resolved relative to the full height of the
+    /// surrounding region, i.e. the page without its margins, regardless of
+    /// how much of it is already used. Content in a block with a fixed height
+    /// can be [aligned]($func/align) vertically within it, for example to
+    /// center a title on its page:
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #block(height: 100%, width: 100%, align(center + horizon)[
+    ///   #text(16pt)[*Title*] \
+    ///   An Author
+    /// ])
+    /// ```
     pub height: Smart<Rel<Length>>,
 
     /// Whether the block can be broken and continue on the next page.
@@ -243,6 +320,38 @@ pub struct BlockElem {
     #[default(true)]
     pub breakable: bool,
 
+    /// Whether to move the block to the next page as a whole instead of
+    /// breaking it.
+    ///
+    /// Unlike a block that is not [breakable]($func/block.breakable), a block
+    /// that is kept together is still broken if it is larger than a page. In
+    /// this case, a warning is logged. With a show-set rule like
+    /// `{show raw: set block(keep-together: true)}`, you can keep together
+    /// arbitrary block-level elements.
+    ///
+    /// ```example
+    /// #set page(height: 80pt)
+    /// #lorem(10)
+    /// #block(keep-together: true, lorem(12))
+    /// ```
+    #[default(false)]
+    pub keep_together: bool,
+
+    /// Whether the block must stay on the same page as its successor.
+    ///
+    /// If the successor does not fit onto the page anymore, the block is
+    /// moved to the next page along with it. Headings keep with their
+    /// successor by default.
+    ///
+    /// ```example
+    /// #set page(height: 80pt)
+    /// #lorem(12)
+    /// #block(keep-with-next: true)[*Note:*]
+    /// #block(breakable: false, lorem(8))
+    /// ```
+    #[default(false)]
+    pub keep_with_next: bool,
+
     /// The block's background color. See the
     /// [rectangle's documentation]($func/rect.fill) for more details.
     pub fill: Option<Paint>,
@@ -271,6 +380,20 @@ pub struct BlockElem {
     #[fold]
     pub outset: Sides<Option<Rel<Length>>>,
 
+    /// A shadow to cast beneath the block. See the [rectangle's
+    /// documentation]($func/rect.shadow) for more details.
+    ///
+    /// ```example
+    /// #block(
+    ///   fill: white,
+    ///   inset: 8pt,
+    ///   radius: 4pt,
+    ///   shadow: (offset: (0pt, 2pt), blur: 6pt),
+    /// )[Raised]
+    /// ```
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
     /// The spacing around this block. This is shorthand to set `above` and
     /// `below` to the same value.
     ///
@@ -316,20 +439,24 @@ pub struct BlockElem {
     #[default(VElem::block_spacing(Em::new(1.2).into()))]
     pub below: VElem,
 
-    /// Whether to clip the content inside the block.
-    #[default(false)]
-    pub clip: bool,
+    /// Whether to clip the content inside the block. See the
+    /// [box's documentation]($func/box.clip) for more details.
+    #[default(Clip::None)]
+    pub clip: Clip,
+
+    /// How opaque the block is. See the
+    /// [box's documentation]($func/box.opacity) for more details.
+    #[default(Ratio::one())]
+    pub opacity: Ratio,
+
+    /// How the colors of the block are combined with what lies beneath it. See
+    /// the [box's documentation]($func/box.blend-mode) for more details.
+    #[default(BlendMode::Normal)]
+    pub blend_mode: BlendMode,
 
     /// The contents of the block.
     #[positional]
     pub body: Option<Content>,
-
-    /// Whether this block must stick to the following one.
-    ///
-    /// Use this to prevent page breaks between e.g. a heading and its body.
-    #[internal]
-    #[default(false)]
-    pub sticky: bool,
 }
 
 impl Layout for BlockElem {
@@ -410,23 +537,24 @@ impl Layout for BlockElem {
         };
 
         // Clip the contents
-        if self.clip(styles) {
-            for frame in frames.iter_mut() {
-                frame.clip();
-            }
+        let clip = self.clip(styles);
+        for frame in frames.iter_mut() {
+            clip.apply(vt, styles, frame, self.span())?;
         }
 
         // Prepare fill and stroke.
         let fill = self.fill(styles);
         let stroke = self.stroke(styles).map(|s| s.map(PartialStroke::unwrap_or_default));
 
+        // Skip the first region if it is empty and the content continues in
+        // later ones.
+        let mut skip = false;
+        if let [first, rest @ ..] = frames.as_slice() {
+            skip = first.is_empty() && rest.iter().any(|frame| !frame.is_empty());
+        }
+
         // Add fill and/or stroke.
         if fill.is_some() || stroke.iter().any(Option::is_some) {
-            let mut skip = false;
-            if let [first, rest @ ..] = frames.as_slice() {
-                skip = first.is_empty() && rest.iter().any(|frame| !frame.is_empty());
-            }
-
             let outset = self.outset(styles);
             let radius = self.radius(styles);
             for frame in frames.iter_mut().skip(skip as usize) {
@@ -440,6 +568,22 @@ impl Layout for BlockElem {
             }
         }
 
+        // Add the shadow beneath everything else.
+        if let Some(shadow) = self.shadow(styles) {
+            let outset = self.outset(styles);
+            let radius = self.radius(styles);
+            for frame in frames.iter_mut().skip(skip as usize) {
+                frame.shadow(shadow.clone(), outset, radius, self.span());
+            }
+        }
+
+        // Composite each of the block's frames onto its backdrop.
+        let opacity = opacity(self.opacity(styles));
+        let blend_mode = self.blend_mode(styles);
+        for frame in &mut frames {
+            frame.composite(opacity, blend_mode);
+        }
+
         // Apply metadata.
         for frame in &mut frames {
             frame.meta(styles, false);
@@ -460,6 +604,9 @@ pub enum Sizing {
     /// A track size specified as a fraction of the remaining free space in the
     /// parent.
     Fr(Fr),
+    /// A track that fits its cell's contents, but is at most of the given
+    /// size.
+    Max(Rel<Length>),
 }
 
 impl Sizing {
@@ -467,6 +614,11 @@ impl Sizing {
     pub fn is_fractional(self) -> bool {
         matches!(self, Self::Fr(_))
     }
+
+    /// Whether this sizing fits the contents, with or without a maximum.
+    pub fn is_auto(self) -> bool {
+        matches!(self, Self::Auto | Self::Max(_))
+    }
 }
 
 impl Default for Sizing {
@@ -490,8 +642,89 @@ cast! {
         Self::Auto => Value::Auto,
         Self::Rel(rel) => rel.into_value(),
         Self::Fr(fr) => fr.into_value(),
+        Self::Max(max) => dict! { "max" => max }.into_value(),
     },
     _: AutoValue => Self::Auto,
     v: Rel<Length> => Self::Rel(v),
     v: Fr => Self::Fr(v),
+    mut dict: Dict => {
+        let max = dict.take("max")?.cast()?;
+        dict.finish(&["max"])?;
+        Self::Max(max)
+    },
+}
+
+/// Clamp an opacity to the valid range.
+fn opacity(ratio: Ratio) -> Ratio {
+    Ratio::new(ratio.get().clamp(0.0, 1.0))
+}
+
+/// How to clip the contents of a container.
+#[derive(Debug, Clone, Hash)]
+pub enum Clip {
+    /// Don't clip.
+    None,
+    /// Clip to the container's bounds.
+    Bounds,
+    /// Clip to the area covered by the shapes in the content.
+    Shape(Content),
+}
+
+impl Clip {
+    /// Clip a container's frame.
+    fn apply(
+        &self,
+        vt: &mut Vt,
+        styles: StyleChain,
+        frame: &mut Frame,
+        span: Span,
+    ) -> SourceResult<()> {
+        match self {
+            Self::None => {}
+            Self::Bounds => frame.clip(),
+            Self::Shape(shape) => {
+                let pod = Regions::one(frame.size(), Axes::splat(false));
+                let outline = shape.layout(vt, styles, pod)?.into_frame();
+                let mut path = Path::new();
+                collect_outline(&mut path, &outline, Transform::identity());
+                if path.0.is_empty() {
+                    bail!(span, "clip content must contain a shape");
+                }
+                frame.clip_to(path);
+            }
+        }
+        Ok(())
+    }
+}
+
+cast! {
+    Clip,
+    self => match self {
+        Self::None => false.into_value(),
+        Self::Bounds => true.into_value(),
+        Self::Shape(shape) => shape.into_value(),
+    },
+    v: bool => if v { Self::Bounds } else { Self::None },
+    v: Content => Self::Shape(v),
+}
+
+/// Collect the outlines of all shapes in a frame into a single path.
+fn collect_outline(path: &mut Path, frame: &Frame, ts: Transform) {
+    for (pos, item) in frame.items() {
+        let ts = ts.pre_concat(Transform::translate(pos.x, pos.y));
+        match item {
+            FrameItem::Group(group) => {
+                collect_outline(path, &group.frame, ts.pre_concat(group.transform));
+            }
+            FrameItem::Shape(shape, _) => {
+                let outline = match &shape.geometry {
+                    Geometry::Line(_) => continue,
+                    Geometry::Rect(size) => Path::rect(*size),
+                    Geometry::Path(path) => path.clone(),
+                };
+                path.0.extend(outline.transform(ts).0);
+            }
+            _ => {}
+        }
+    }
 }
@@ -61,7 +61,7 @@ use super::GridLayouter;
 ///
 /// Display: Numbered List
 /// Category: layout
-#[element(Layout)]
+#[element(Locatable, Synthesize, Layout)]
 #[scope(
     scope.define("item", EnumItem::func());
     scope
@@ -113,15 +113,27 @@ pub struct EnumElem {
 
     /// Which number to start the enumeration with.
     ///
+    /// If set to `{auto}`, the enumeration continues where the previous
+    /// enumeration at the same nesting level left off. This is useful to
+    /// resume a list that is interrupted by a paragraph or code block.
+    ///
     /// ```example
     /// #enum(
     ///   start: 3,
     ///   [Skipping],
     ///   [Ahead],
     /// )
+    ///
+    /// + First
+    /// + Second
+    ///
+    /// An interruption.
+    ///
+    /// #set enum(start: auto)
+    /// + Third
     /// ```
-    #[default(1)]
-    pub start: usize,
+    #[default(Smart::Custom(1))]
+    pub start: Smart<usize>,
 
     /// Whether to display the full numbering, including the numbers of
     /// all parent enumerations.
@@ -194,6 +206,61 @@ pub struct EnumElem {
     #[internal]
     #[fold]
     parents: Parent,
+
+    /// The nesting level of the enumeration.
+    #[synthesized]
+    depth: usize,
+}
+
+impl EnumElem {
+    /// Determine the number of the first item.
+    ///
+    /// An automatic start continues the previous enumeration at the same
+    /// nesting level. Because the previous enumerations may continue their
+    /// predecessors in turn, all of them are counted through from the start
+    /// of the document.
+    fn resolve_start(&self, vt: &Vt, styles: StyleChain) -> usize {
+        match self.start(styles) {
+            Smart::Custom(start) => start,
+            Smart::Auto => self.continued(vt, styles),
+        }
+    }
+
+    /// The number with which the previous enumeration at the same nesting
+    /// level would continue.
+    fn continued(&self, vt: &Vt, styles: StyleChain) -> usize {
+        let Some(location) = self.0.location() else { return 1 };
+        let depth = self.parents(styles).len();
+        let mut next = 1;
+        for elem in vt.introspector.query(&Self::func().select()) {
+            if elem.location() == Some(location) {
+                break;
+            }
+
+            let elem = elem.to::<Self>().unwrap();
+            if elem.depth() == depth {
+                let start = elem.start(StyleChain::default()).unwrap_or(next);
+                next = elem.next_number(start);
+            }
+        }
+        next
+    }
+
+    /// The number that follows the last item if the enumeration starts with
+    /// the given number.
+    fn next_number(&self, start: usize) -> usize {
+        self.children().iter().fold(start, |number, item| {
+            item.number(StyleChain::default()).unwrap_or(number).saturating_add(1)
+        })
+    }
+}
+
+impl Synthesize for EnumElem {
+    fn synthesize(&mut self, _vt: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        self.push_start(self.start(styles));
+        self.push_depth(self.parents(styles).len());
+        Ok(())
+    }
 }
 
 impl Layout for EnumElem {
@@ -215,7 +282,7 @@ impl Layout for EnumElem {
         };
 
         let mut cells = vec![];
-        let mut number = self.start(styles);
+        let mut number = self.resolve_start(vt, styles);
         let mut parents = self.parents(styles);
         let full = self.full(styles);
 
@@ -267,9 +334,12 @@ impl Layout for EnumElem {
             &cells,
             regions,
             styles,
-        );
+        )
+        .tagged(Tag::ListItem, None);
 
-        Ok(layouter.layout()?.fragment)
+        let mut fragment = layouter.layout()?.fragment;
+        fragment.tag(Tag::List);
+        Ok(fragment)
     }
 }
 
@@ -1,7 +1,8 @@
 use std::mem;
 
 use super::{
-    AlignElem, BlockElem, ColbreakElem, ColumnsElem, ParElem, PlaceElem, Spacing, VElem,
+    AlignElem, BlockElem, ColbreakElem, ColumnsElem, Exclusion, PageElem, ParElem,
+    PlaceElem, Spacing, VElem,
 };
 use crate::meta::{FootnoteElem, FootnoteEntry};
 use crate::prelude::*;
@@ -97,8 +98,13 @@ struct FlowLayouter<'a> {
     initial: Size,
     /// Whether the last block was a paragraph.
     last_was_par: bool,
+    /// The step of the baseline grid, if any.
+    grid: Option<Abs>,
     /// Spacing and layouted blocks for the current region.
     items: Vec<FlowItem>,
+    /// Placed elements in the current region that text flows around: The side
+    /// they are attached to, the width they occupy and their bottom edge.
+    wraps: Vec<(Align, Abs, Abs)>,
     /// Whether we have any footnotes in the current region.
     has_footnotes: bool,
     /// Footnote configuration.
@@ -127,6 +133,9 @@ enum FlowItem {
     Frame { frame: Frame, aligns: Axes<Align>, sticky: bool, movable: bool },
     /// An absolutely placed frame.
     Placed(Frame),
+    /// Space reserved at the top or bottom of the region for a floating
+    /// placed element.
+    Reserved(Align, Abs),
     /// A footnote frame (can also be the separator).
     Footnote(Frame),
 }
@@ -137,6 +146,7 @@ impl FlowItem {
         match self {
             Self::Absolute(v, _) => *v,
             Self::Fractional(_) | Self::Placed(_) => Abs::zero(),
+            Self::Reserved(_, v) => *v,
             Self::Frame { frame, .. } | Self::Footnote(frame) => frame.height(),
         }
     }
@@ -158,7 +168,11 @@ impl<'a> FlowLayouter<'a> {
             expand,
             initial: regions.size,
             last_was_par: false,
+            grid: PageElem::baseline_grid_in(styles)
+                .map(|grid| grid.step.resolve(styles))
+                .filter(|&step| step > Abs::zero()),
             items: vec![],
+            wraps: vec![],
             has_footnotes: false,
             footnote_config: FootnoteConfig {
                 separator: FootnoteEntry::separator_in(styles),
@@ -200,34 +214,75 @@ impl<'a> FlowLayouter<'a> {
         let aligns = AlignElem::alignment_in(styles).resolve(styles);
         let leading = ParElem::leading_in(styles);
         let consecutive = self.last_was_par;
-        let lines = par
-            .layout(vt, styles, consecutive, self.regions.base(), self.regions.expand.x)?
+        let region = self.regions.base();
+        let expand = self.regions.expand.x;
+
+        // Shorten the lines next to wrapped placed elements. How many lines
+        // are affected depends on their heights, so we lay out again until
+        // the number stabilizes.
+        let mut exclusion = self.exclusion(None, leading);
+        let mut lines = par
+            .layout(vt, styles, consecutive, region, expand, exclusion)?
             .into_frames();
-
-        let mut sticky = self.items.len();
-        for (i, item) in self.items.iter().enumerate().rev() {
-            match *item {
-                FlowItem::Absolute(_, _) => {}
-                FlowItem::Frame { sticky: true, .. } => sticky = i,
-                _ => break,
+        for _ in 0..2 {
+            let next = self.exclusion(Some(&lines), leading);
+            if next == exclusion {
+                break;
             }
+            exclusion = next;
+            lines = par
+                .layout(vt, styles, consecutive, region, expand, exclusion)?
+                .into_frames();
         }
 
-        if let Some(first) = lines.first() {
-            if !self.regions.size.y.fits(first.height()) && !self.regions.in_last() {
-                let carry: Vec<_> = self.items.drain(sticky..).collect();
-                self.finish_region()?;
-                for item in carry {
-                    self.layout_item(vt, item)?;
-                }
-            }
-        }
+        // Determine how many lines at the start and end of the paragraph have
+        // to stay together to prevent orphans and widows. A paragraph that is
+        // too short to be split without violating either is kept together.
+        let len = lines.len();
+        let orphans = ParElem::orphans_in(styles).get();
+        let widows = ParElem::widows_in(styles).get();
+        let (front, back) =
+            if len < orphans + widows { (len, 0) } else { (orphans, widows) };
+
+        // The height of a group of consecutive lines.
+        let heights: Vec<_> = lines.iter().map(Frame::height).collect();
+        let group = |range: std::ops::Range<usize>| {
+            let count = range.len();
+            heights[range].iter().copied().sum::<Abs>()
+                + leading * count.saturating_sub(1) as f64
+        };
 
         for (i, frame) in lines.into_iter().enumerate() {
             if i > 0 {
                 self.layout_item(vt, FlowItem::Absolute(leading, true))?;
             }
 
+            // The lines that need to fit into the region together with this
+            // one.
+            let mut needed = if i == 0 {
+                group(0..front)
+            } else if back > 1 && i >= front && i == len - back {
+                group(i..len)
+            } else {
+                frame.height()
+            };
+
+            // Drop the constraint if the lines wouldn't fit into the next
+            // region either, so that paragraphs larger than a region are
+            // still broken.
+            if !self.regions.iter().nth(1).map_or(false, |size| size.y.fits(needed)) {
+                needed = frame.height();
+            }
+
+            if !self.regions.size.y.fits(needed) && !self.regions.in_last() {
+                if i == 0 {
+                    self.advance_with_sticky(vt)?;
+                } else {
+                    self.finish_region()?;
+                }
+            }
+
+            self.snap(vt, frame.baseline(), frame.height())?;
             self.layout_item(
                 vt,
                 FlowItem::Frame { frame, aligns, sticky: false, movable: true },
@@ -247,9 +302,15 @@ impl<'a> FlowLayouter<'a> {
         styles: StyleChain,
     ) -> SourceResult<()> {
         let aligns = AlignElem::alignment_in(styles).resolve(styles);
-        let sticky = BlockElem::sticky_in(styles);
+        let sticky = BlockElem::keep_with_next_in(styles);
         let pod = Regions::one(self.regions.base(), Axes::splat(false));
-        let frame = content.layout(vt, styles, pod)?.into_frame();
+        let mut frame = content.layout(vt, styles, pod)?.into_frame();
+        self.clear_wraps(vt)?;
+        if !self.regions.size.y.fits(frame.height()) && !self.regions.in_last() {
+            self.advance_with_sticky(vt)?;
+        }
+        self.snap(vt, Abs::zero(), frame.height())?;
+        self.round_to_grid(&mut frame);
         self.layout_item(vt, FlowItem::Frame { frame, aligns, sticky, movable: true })?;
         self.last_was_par = false;
         Ok(())
@@ -265,7 +326,36 @@ impl<'a> FlowLayouter<'a> {
         // Placed elements that are out of flow produce placed items which
         // aren't aligned later.
         if let Some(placed) = block.to::<PlaceElem>() {
+            // Elements that text wraps around stay at their position in the
+            // flow, but take up no vertical space. Instead, they shorten the
+            // lines next to them.
+            if let Some(side) = placed.wrap_side(styles)? {
+                let pod = Regions::one(self.regions.base(), Axes::splat(false));
+                let size = placed.body().measure(vt, styles, pod)?.into_frame().size();
+                let clearance = placed.clearance(styles);
+                self.wraps.push((side, size.x + clearance, self.offset() + size.y));
+
+                let pod = Regions::one(self.regions.base(), Axes::new(true, false));
+                let frame = block.layout(vt, styles, pod)?.into_frame();
+                let aligns = Axes::new(Align::Left, Align::Top);
+                self.layout_item(
+                    vt,
+                    FlowItem::Frame { frame, aligns, sticky: false, movable: true },
+                )?;
+                return Ok(());
+            }
+
             if placed.out_of_flow(styles) {
+                // Floating elements reserve space for themselves in the
+                // region, such that they don't overlap the other content.
+                if let Some(align) = placed.float_align(styles)? {
+                    let pod = Regions::one(self.regions.base(), Axes::splat(false));
+                    let height =
+                        placed.body().measure(vt, styles, pod)?.into_frame().height();
+                    let clearance = placed.clearance(styles);
+                    self.layout_item(vt, FlowItem::Reserved(align, height + clearance))?;
+                }
+
                 let frame = block.layout(vt, styles, self.regions)?.into_frame();
                 self.layout_item(vt, FlowItem::Placed(frame))?;
                 return Ok(());
@@ -275,6 +365,8 @@ impl<'a> FlowLayouter<'a> {
             self.finish_region()?;
         }
 
+        self.clear_wraps(vt)?;
+
         // How to align the block.
         let aligns = if let Some(align) = block.to::<AlignElem>() {
             align.alignment(styles)
@@ -293,11 +385,44 @@ impl<'a> FlowLayouter<'a> {
         }
 
         // Layout the block itself.
-        let sticky = BlockElem::sticky_in(styles);
-        let fragment = block.layout(vt, styles, self.regions)?;
+        let (sticky, keep_together) = match block.to::<BlockElem>() {
+            Some(elem) => (elem.keep_with_next(styles), elem.keep_together(styles)),
+            None => (
+                BlockElem::keep_with_next_in(styles),
+                BlockElem::keep_together_in(styles),
+            ),
+        };
+        self.snap(vt, Abs::zero(), Abs::zero())?;
+        let mut fragment = block.layout(vt, styles, self.regions)?;
+
+        // Move the block to the next region if it doesn't even start in this
+        // one or if it was broken, but should and can be kept together.
+        if let Some(next) = self.regions.iter().nth(1).filter(|_| !self.regions.in_last())
+        {
+            let first = fragment.iter().next().map_or(Abs::zero(), Frame::height);
+            let mut advance = !self.regions.size.y.fits(first);
+            if keep_together && fragment.len() > 1 {
+                let pod = Regions::one(next, Axes::new(self.regions.expand.x, false));
+                let height = block.measure(vt, styles, pod)?.into_frame().height();
+                if next.y.fits(height) {
+                    advance = true;
+                } else {
+                    tracing::warn!(
+                        "Breaking block that is kept together, but doesn't fit into a region"
+                    );
+                }
+            }
+
+            if advance {
+                self.advance_with_sticky(vt)?;
+                self.snap(vt, Abs::zero(), Abs::zero())?;
+                fragment = block.layout(vt, styles, self.regions)?;
+            }
+        }
+
         let mut notes = Vec::new();
 
-        for (i, frame) in fragment.into_iter().enumerate() {
+        for (i, mut frame) in fragment.into_iter().enumerate() {
             // Find footnotes in the frame.
             if self.root {
                 find_footnotes(&mut notes, &frame);
@@ -307,6 +432,8 @@ impl<'a> FlowLayouter<'a> {
                 self.finish_region()?;
             }
 
+            self.round_to_grid(&mut frame);
+
             self.layout_item(
                 vt,
                 FlowItem::Frame { frame, aligns, sticky, movable: false },
@@ -325,6 +452,132 @@ impl<'a> FlowLayouter<'a> {
         Ok(())
     }
 
+    /// Finish the current region and carry the trailing items that stick to
+    /// the upcoming item, like a heading, over to the next one.
+    fn advance_with_sticky(&mut self, vt: &mut Vt) -> SourceResult<()> {
+        let mut sticky = self.items.len();
+        for (i, item) in self.items.iter().enumerate().rev() {
+            match *item {
+                FlowItem::Absolute(_, _) => {}
+                FlowItem::Frame { sticky: true, .. } => sticky = i,
+                _ => break,
+            }
+        }
+
+        // If nothing but sticky items is in the region, moving them along
+        // wouldn't help.
+        let carry: Vec<_> = if self.items[..sticky]
+            .iter()
+            .any(|item| matches!(item, FlowItem::Frame { .. }))
+        {
+            self.items.drain(sticky..).collect()
+        } else {
+            vec![]
+        };
+
+        self.finish_region()?;
+        for item in carry {
+            self.layout_item(vt, item)?;
+        }
+
+        Ok(())
+    }
+
+    /// The vertical position of the next item in the current region.
+    fn offset(&self) -> Abs {
+        self.items
+            .iter()
+            .map(|item| match *item {
+                FlowItem::Absolute(v, _) | FlowItem::Reserved(Align::Top, v) => v,
+                FlowItem::Frame { ref frame, .. } => frame.height(),
+                _ => Abs::zero(),
+            })
+            .sum()
+    }
+
+    /// Determine which space to keep free next to the lines of an upcoming
+    /// paragraph because of wrapped placed elements. Without laid out lines,
+    /// all lines are affected.
+    fn exclusion(&self, lines: Option<&[Frame]>, leading: Abs) -> Exclusion {
+        let top = self.offset();
+        let mut exclusion = Exclusion::default();
+        let mut bottom = top;
+        for &(side, width, end) in self.wraps.iter().filter(|wrap| wrap.2 > top) {
+            if side == Align::Left {
+                exclusion.left.set_max(width);
+            } else {
+                exclusion.right.set_max(width);
+            }
+            bottom.set_max(end);
+        }
+
+        if bottom == top {
+            return exclusion;
+        }
+
+        // Count the lines that start next to a wrapped element.
+        exclusion.lines = usize::MAX;
+        let mut y = top;
+        for (i, line) in lines.into_iter().flatten().enumerate() {
+            if y >= bottom {
+                exclusion.lines = i;
+                break;
+            }
+            y += line.height() + leading;
+        }
+
+        exclusion
+    }
+
+    /// Insert spacing such that the next item starts below all wrapped placed
+    /// elements.
+    fn clear_wraps(&mut self, vt: &mut Vt) -> SourceResult<()> {
+        let offset = self.offset();
+        let bottom = self.wraps.drain(..).map(|wrap| wrap.2).fold(offset, Abs::max);
+        if bottom > offset {
+            self.layout_item(vt, FlowItem::Absolute(bottom - offset, false))?;
+        }
+        Ok(())
+    }
+
+    /// Insert spacing such that an upcoming frame with the given baseline and
+    /// height sits on the baseline grid, if there is one.
+    fn snap(&mut self, vt: &mut Vt, baseline: Abs, height: Abs) -> SourceResult<()> {
+        let Some(step) = self.grid else { return Ok(()) };
+        let pad = |offset: Abs| {
+            let y = offset + baseline;
+            let pad = step * (y / step).ceil() - y;
+            if pad.approx_eq(step) {
+                Abs::zero()
+            } else {
+                pad
+            }
+        };
+
+        let mut amount = pad(self.offset());
+        if !self.regions.size.y.fits(amount + height) && !self.regions.in_last() {
+            self.finish_region()?;
+            amount = pad(Abs::zero());
+        }
+
+        if amount > Abs::zero() {
+            self.layout_item(vt, FlowItem::Absolute(amount, false))?;
+        }
+
+        Ok(())
+    }
+
+    /// Enlarge a block's frame to a whole multiple of the baseline grid's
+    /// step, if there is a grid and the enlarged frame still fits.
+    fn round_to_grid(&self, frame: &mut Frame) {
+        let Some(step) = self.grid else { return };
+        let height = frame.height();
+        let rounded = step * (height / step).ceil();
+        if !rounded.approx_eq(height) && self.regions.size.y.fits(rounded) {
+            frame.size_mut().y = rounded;
+        }
+    }
+
     /// Layout a finished frame.
     #[tracing::instrument(name = "FlowLayouter::layout_item", skip_all)]
     fn layout_item(&mut self, vt: &mut Vt, item: FlowItem) -> SourceResult<()> {
@@ -363,6 +616,12 @@ impl<'a> FlowLayouter<'a> {
                 }
             }
             FlowItem::Placed(_) => {}
+            FlowItem::Reserved(_, v) => {
+                if !self.regions.size.y.fits(v) && !self.regions.in_last() {
+                    self.finish_region()?;
+                }
+                self.regions.size.y -= v;
+            }
             FlowItem::Footnote(_) => {}
         }
 
@@ -386,6 +645,7 @@ impl<'a> FlowLayouter<'a> {
         let mut used = Size::zero();
         let mut footnote_height = Abs::zero();
         let mut first_footnote = true;
+        let mut reserved = Sides::splat(Abs::zero());
         for item in &self.items {
             match item {
                 FlowItem::Absolute(v, _) => used.y += *v,
@@ -396,6 +656,14 @@ impl<'a> FlowLayouter<'a> {
                     used.x.set_max(size.x);
                 }
                 FlowItem::Placed(_) => {}
+                FlowItem::Reserved(align, v) => {
+                    used.y += *v;
+                    if *align == Align::Top {
+                        reserved.top += *v;
+                    } else {
+                        reserved.bottom += *v;
+                    }
+                }
                 FlowItem::Footnote(frame) => {
                     let size = frame.size();
                     footnote_height += size.y;
@@ -418,9 +686,9 @@ impl<'a> FlowLayouter<'a> {
         }
 
         let mut output = Frame::new(size);
-        let mut offset = Abs::zero();
+        let mut offset = reserved.top;
         let mut ruler = Align::Top;
-        let mut footnote_offset = size.y - footnote_height;
+        let mut footnote_offset = size.y - footnote_height - reserved.bottom;
 
         // Place all frames.
         for item in self.items.drain(..) {
@@ -448,10 +716,12 @@ impl<'a> FlowLayouter<'a> {
                 FlowItem::Placed(frame) => {
                     output.push_frame(Point::zero(), frame);
                 }
+                FlowItem::Reserved(..) => {}
             }
         }
 
         // Advance to the next region.
+        self.wraps.clear();
         self.finished.push(output);
         self.regions.next();
         self.initial = self.regions.size;
@@ -562,7 +832,7 @@ impl FlowLayouter<'_> {
 
 /// Finds all footnotes in the frame.
 #[tracing::instrument(skip_all)]
-fn find_footnotes(notes: &mut Vec<FootnoteElem>, frame: &Frame) {
+pub(super) fn find_footnotes(notes: &mut Vec<FootnoteElem>, frame: &Frame) {
     for (_, item) in frame.items() {
         match item {
             FrameItem::Group(group) => find_footnotes(notes, &group.frame),
@@ -570,7 +840,9 @@ fn find_footnotes(notes: &mut Vec<FootnoteElem>, frame: &Frame) {
                 if !notes.iter().any(|note| note.0.location() == content.location()) =>
             {
                 let Some(footnote) = content.to::<FootnoteElem>() else { continue };
-                notes.push(footnote.clone());
+                if !footnote.is_ref() {
+                    notes.push(footnote.clone());
+                }
             }
             _ => {}
         }
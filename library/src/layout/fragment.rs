@@ -44,6 +44,23 @@ impl Fragment {
         self.0.iter()
     }
 
+    /// Mark the contents of the fragment as an element of the document's
+    /// logical structure.
+    ///
+    /// The markers go into the first and last non-empty frames so that
+    /// frames which are skipped for being empty stay empty.
+    pub fn tag(&mut self, tag: Tag) {
+        let Some(first) = self.0.iter().position(|frame| !frame.is_empty()) else {
+            return;
+        };
+
+        let last = self.0.iter().rposition(|frame| !frame.is_empty()).unwrap();
+        let start = FrameItem::Meta(Meta::TagStart(tag), Size::zero());
+        let end = FrameItem::Meta(Meta::TagEnd, Size::zero());
+        self.0[first].prepend(Point::zero(), start);
+        self.0[last].push(Point::zero(), end);
+    }
+
     /// Iterate over the contained frames.
     pub fn iter_mut(&mut self) -> std::slice::IterMut<Frame> {
         self.0.iter_mut()
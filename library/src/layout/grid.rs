@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use crate::text::TextElem;
 
-use super::Sizing;
+use super::{RotateElem, Sizing};
 
 /// Arrange content in a grid.
 ///
@@ -20,6 +20,11 @@ use super::Sizing;
 ///   which, and together they claim more than the available space, the `{auto}`
 ///   tracks will fairly distribute the available space among themselves.
 ///
+/// - A dictionary with a maximum size (e.g. `{(max: 4cm)}`): The track will be
+///   sized like an `{auto}` track, but is at most of the given size. Content
+///   that is wider than a column with a maximum is broken into lines. For
+///   rows, content that is taller overflows.
+///
 /// - A fixed or relative length (e.g. `{10pt}` or `{20% - 1cm}`): The track
 ///   will be exactly of this size.
 ///
@@ -62,6 +67,10 @@ use super::Sizing;
 /// Display: Grid
 /// Category: layout
 #[element(Layout)]
+#[scope(
+    scope.define("cell", CellElem::func());
+    scope
+)]
 pub struct GridElem {
     /// Defines the column sizes.
     ///
@@ -69,6 +78,14 @@ pub struct GridElem {
     /// with that many `{auto}`-sized columns. Note that opposed to rows and
     /// gutters, providing a single track size will only ever create a single
     /// column.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: (auto, (max: 3cm)),
+    ///   [Source], [https://example.com/a/very/long/path/to/the/dataset],
+    ///   [Short], [Fits],
+    /// )
+    /// ```
     pub columns: TrackSizings,
 
     /// Defines the row sizes.
@@ -94,6 +111,18 @@ pub struct GridElem {
     #[parse(args.named("row-gutter")?.or_else(|| gutter.clone()))]
     pub row_gutter: TrackSizings,
 
+    /// The number of leading rows that form the grid's header.
+    ///
+    /// When the grid breaks across pages or columns, the header rows are
+    /// repeated at the top of each subsequent region. Elements within the
+    /// repeated rows are only taken into account once, so they do not step
+    /// any counters a second time.
+    ///
+    /// A header is only repeated if it fits into the first region as a whole
+    /// and doesn't contain fractional rows.
+    #[default(0)]
+    pub header: usize,
+
     /// The contents of the table cells.
     ///
     /// The cells are populated in row-major order.
@@ -118,13 +147,138 @@ impl Layout for GridElem {
             &cells,
             regions,
             styles,
-        );
+        )
+        .repeat_header(self.header(styles));
 
         // Measure the columns and layout the grid row-by-row.
         Ok(layouter.layout()?.fragment)
     }
 }
 
+/// A cell in a grid or table that spans multiple columns or rows.
+///
+/// Wrap a cell's content in this function to merge it with the cells to its
+/// right or below. The spanned positions are skipped when placing the
+/// following cells. The function is available as `grid.cell` and
+/// `table.cell`.
+///
+/// ## Example { #example }
+/// ```example
+/// #table(
+///   columns: 3,
+///   table.cell(colspan: 2)[*Merged*], [C],
+///   [D], table.cell(rowspan: 2)[*Tall*], [F],
+///   [G], [I],
+/// )
+/// ```
+///
+/// Display: Cell
+/// Category: layout
+#[element(Show)]
+pub struct CellElem {
+    /// The cell's content.
+    #[required]
+    pub body: Content,
+
+    /// The number of columns the cell spans.
+    ///
+    /// The span is cut off at the end of the row.
+    #[default(NonZeroUsize::ONE)]
+    pub colspan: NonZeroUsize,
+
+    /// The number of rows the cell spans.
+    #[default(NonZeroUsize::ONE)]
+    pub rowspan: NonZeroUsize,
+
+    /// How much to rotate the cell's content.
+    ///
+    /// Unlike with the [`rotate`]($func/rotate) function, the cell is sized to
+    /// fit the rotated content, so rotated column headers make their row
+    /// taller instead of overlapping other cells. Positive angles rotate
+    /// clockwise: With `{90deg}`, text reads from top to bottom and with
+    /// `{-90deg}` from bottom to top.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 3,
+    ///   align: bottom,
+    ///   [], table.cell(rotate: -90deg)[*Bottom up*],
+    ///   table.cell(rotate: 90deg)[*Top down*],
+    ///   [Value], [1], [2],
+    /// )
+    /// ```
+    pub rotate: Angle,
+}
+
+impl CellElem {
+    /// Rotate the given body of the cell if requested.
+    pub fn rotated(&self, body: Content, styles: StyleChain) -> Content {
+        let angle = self.rotate(styles);
+        if angle.is_zero() {
+            return body;
+        }
+
+        RotateElem::new(body).with_angle(angle).with_reflow(true).pack()
+    }
+}
+
+impl Show for CellElem {
+    fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        Ok(self.rotated(self.body(), styles))
+    }
+}
+
+/// Determine the positions and spans of cells in a grid with `c` columns.
+///
+/// Cells are placed in row-major order, skipping positions that are already
+/// covered by a [cell](CellElem) spanning multiple columns or rows. Positions
+/// and spans are given in content columns and rows, without gutters.
+pub fn place_cells(
+    cells: &[Content],
+    c: usize,
+    styles: StyleChain,
+) -> Vec<(Axes<usize>, Axes<usize>)> {
+    let mut occupied: Vec<bool> = vec![];
+    let mut placed = Vec::with_capacity(cells.len());
+    let mut cursor = 0;
+
+    for cell in cells {
+        while occupied.get(cursor).copied().unwrap_or(false) {
+            cursor += 1;
+        }
+
+        let x = cursor % c;
+        let y = cursor / c;
+        let (colspan, rowspan) = match cell.to::<CellElem>() {
+            Some(cell) => (cell.colspan(styles).get(), cell.rowspan(styles).get()),
+            None => (1, 1),
+        };
+
+        // Cut the span off at the end of the row or at the first position
+        // that is already covered by another cell.
+        let mut width = 1;
+        while width < colspan.min(c - x)
+            && !occupied.get(cursor + width).copied().unwrap_or(false)
+        {
+            width += 1;
+        }
+
+        for dy in 0..rowspan {
+            for dx in 0..width {
+                let i = (y + dy) * c + x + dx;
+                if i >= occupied.len() {
+                    occupied.resize(i + 1, false);
+                }
+                occupied[i] = true;
+            }
+        }
+
+        placed.push((Axes::new(x, y), Axes::new(width, rowspan)));
+    }
+
+    placed
+}
+
 /// Track sizing definitions.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
 pub struct TrackSizings(pub Vec<Sizing>);
@@ -143,8 +297,16 @@ pub struct GridLayouter<'a, 'v> {
     vt: &'a mut Vt<'v>,
     /// The grid cells.
     cells: &'a [Content],
-    /// Whether this is an RTL grid.
-    is_rtl: bool,
+    /// For each track position, the index of the cell that starts there and
+    /// the number of tracks it spans.
+    entries: Vec<Option<(usize, Axes<usize>)>>,
+    /// For each track position, the position of the cell covering it.
+    origins: Vec<usize>,
+    /// The positions and spans of cells spanning multiple rows.
+    rowspans: Vec<(usize, usize, Axes<usize>)>,
+    /// Indices into `rowspans` whose layout waits for the fractional rows in
+    /// the current region to be resolved.
+    deferred: Vec<usize>,
     /// Whether this grid has gutters.
     has_gutter: bool,
     /// The column tracks including gutter tracks.
@@ -167,6 +329,18 @@ pub struct GridLayouter<'a, 'v> {
     initial: Size,
     /// Frames for finished regions.
     finished: Vec<Frame>,
+    /// The row that is currently being laid out.
+    current: usize,
+    /// The first row laid out in the current region.
+    start: usize,
+    /// The number of tracks (including gutter) that form the header.
+    header_len: usize,
+    /// Laid out header rows that are repeated in each subsequent region.
+    header: Vec<(Frame, usize)>,
+    /// The total height of the repeated header rows.
+    header_height: Abs,
+    /// The structure tags of content rows and cells, if any.
+    tags: Option<(Tag, Option<Tag>)>,
 }
 
 /// The resulting sizes of columns and rows in a grid.
@@ -178,6 +352,10 @@ pub struct GridLayout {
     pub cols: Vec<Abs>,
     /// The heights of the resulting rows segments, by region.
     pub rows: Vec<Vec<RowPiece>>,
+    /// For each track position `y * cols.len() + x`, the position at which
+    /// the cell covering it starts, in the same format. This only differs
+    /// from the position itself for cells spanning multiple columns or rows.
+    pub origins: Vec<usize>,
 }
 
 /// Details about a resulting row piece.
@@ -218,11 +396,11 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
 
         // Number of content rows: At least as many as given, but also at least
         // as many as needed to place each item.
+        let placed = place_cells(cells, c, styles);
         let r = {
-            let len = cells.len();
             let given = tracks.y.len();
-            let needed = len / c + (len % c).clamp(0, 1);
-            given.max(needed)
+            let needed = placed.iter().map(|(pos, span)| pos.y + span.y).max();
+            given.max(needed.unwrap_or(0))
         };
 
         let has_gutter = gutter.any(|tracks| !tracks.is_empty());
@@ -260,6 +438,33 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
             cols.reverse();
         }
 
+        // Determine the track positions of the cells.
+        let (nx, ny) = (cols.len(), rows.len());
+        let track = |i: usize| if has_gutter { 2 * i } else { i };
+        let mut entries = vec![None; nx * ny];
+        let mut origins: Vec<usize> = (0..nx * ny).collect();
+        let mut rowspans = vec![];
+        for (i, (pos, span)) in placed.into_iter().enumerate() {
+            let span = Axes::new(track(span.x - 1) + 1, track(span.y - 1) + 1);
+            let mut x = track(pos.x);
+            if is_rtl {
+                x = nx - x - span.x;
+            }
+
+            let y = track(pos.y);
+            let origin = y * nx + x;
+            entries[origin] = Some((i, span));
+            for ty in y..y + span.y {
+                for tx in x..x + span.x {
+                    origins[ty * nx + tx] = origin;
+                }
+            }
+
+            if span.y > 1 {
+                rowspans.push((x, y, span));
+            }
+        }
+
         let rcols = vec![Abs::zero(); cols.len()];
         let lrows = vec![];
 
@@ -271,7 +476,10 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
         Self {
             vt,
             cells,
-            is_rtl,
+            entries,
+            origins,
+            rowspans,
+            deferred: vec![],
             has_gutter,
             cols,
             rows,
@@ -283,14 +491,42 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
             lrows,
             initial: regions.size,
             finished: vec![],
+            current: 0,
+            start: 0,
+            header_len: 0,
+            header: vec![],
+            header_height: Abs::zero(),
+            tags: None,
         }
     }
 
+    /// Repeat the first `rows` content rows at the top of each region after
+    /// the first one.
+    pub fn repeat_header(mut self, rows: usize) -> Self {
+        let len = if self.has_gutter { 2 * rows } else { rows };
+        self.header_len = len.min(self.rows.len());
+        self
+    }
+
+    /// Mark each content row and, optionally, each cell as an element of the
+    /// document's logical structure.
+    pub fn tagged(mut self, row: Tag, cell: Option<Tag>) -> Self {
+        self.tags = Some((row, cell));
+        self
+    }
+
     /// Determines the columns sizes and then layouts the grid row-by-row.
     pub fn layout(mut self) -> SourceResult<GridLayout> {
         self.measure_columns()?;
 
         for y in 0..self.rows.len() {
+            self.current = y;
+
+            // Remember the header once all of its rows are laid out.
+            if y > 0 && y == self.header_len {
+                self.capture_header();
+            }
+
             // Skip to next region if current one is full, but only for content
             // rows, not for gutter rows.
             if y % 2 == 0 && self.regions.is_full() {
@@ -298,21 +534,57 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
             }
 
             match self.rows[y] {
-                Sizing::Auto => self.layout_auto_row(y)?,
+                Sizing::Auto | Sizing::Max(_) => self.layout_auto_row(y)?,
                 Sizing::Rel(v) => self.layout_relative_row(v, y)?,
                 Sizing::Fr(v) => self.lrows.push(Row::Fr(v, y)),
             }
+
+            self.layout_rowspans(y)?;
         }
 
+        // There are no more rows, so the header shouldn't be repeated anymore.
+        self.header.clear();
         self.finish_region()?;
 
         Ok(GridLayout {
             fragment: Fragment::frames(self.finished),
             cols: self.rcols,
             rows: self.rrows,
+            origins: self.origins,
         })
     }
 
+    /// Store the laid out header rows for repetition in subsequent regions.
+    fn capture_header(&mut self) {
+        // The header is only repeated if it fits into the first region.
+        if !self.finished.is_empty() {
+            return;
+        }
+
+        let mut header = vec![];
+        for row in &self.lrows {
+            match row {
+                Row::Frame(frame, y) => header.push((frame.clone(), *y)),
+                Row::Fr(..) => return,
+            }
+        }
+
+        self.header_height = header.iter().map(|(frame, _)| frame.height()).sum();
+        self.header = header;
+    }
+
+    /// The regions for a row that may break across regions. The space taken
+    /// up by the repeated header is subtracted from all followup regions.
+    fn row_regions<'b>(&self, backlog: &'b mut Vec<Abs>) -> Regions<'b> {
+        let height = self.header_height;
+        let mut regions =
+            self.regions.map(backlog, |size| Size::new(size.x, size.y - height));
+        regions.size = self.regions.size;
+        regions.full = self.regions.full;
+        regions.root = self.regions.root;
+        regions
+    }
+
     /// Determine all column sizes.
     #[tracing::instrument(name = "GridLayouter::measure_columns", skip_all)]
     fn measure_columns(&mut self) -> SourceResult<()> {
@@ -326,7 +598,7 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
         // fractional tracks.
         for (&col, rcol) in self.cols.iter().zip(&mut self.rcols) {
             match col {
-                Sizing::Auto => {}
+                Sizing::Auto | Sizing::Max(_) => {}
                 Sizing::Rel(v) => {
                     let resolved =
                         v.resolve(self.styles).relative_to(self.regions.base().x);
@@ -367,13 +639,26 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
         // Determine size of auto columns by laying out all cells in those
         // columns, measuring them and finding the largest one.
         for (x, &col) in self.cols.iter().enumerate() {
-            if col != Sizing::Auto {
+            if !col.is_auto() {
                 continue;
             }
 
+            // Columns with a maximum are measured with at most that width, so
+            // that their content is broken into lines accordingly.
+            let mut available = available;
+            if let Sizing::Max(max) = col {
+                let max = max.resolve(self.styles).relative_to(self.regions.base().x);
+                available.set_min(max);
+            }
+
             let mut resolved = Abs::zero();
             for y in 0..self.rows.len() {
-                if let Some(cell) = self.cell(x, y) {
+                if let Some((cell, span)) = self.entry(x, y) {
+                    // Cells spanning multiple columns are measured below.
+                    if span.x > 1 {
+                        continue;
+                    }
+
                     // For relative rows, we can already resolve the correct
                     // base and for auto and fr we could only guess anyway.
                     let height = match self.rows[y] {
@@ -390,11 +675,55 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
                 }
             }
 
+            // The column never exceeds its maximum. Content that cannot be
+            // broken overflows it.
+            if let Sizing::Max(_) = col {
+                resolved.set_min(available);
+            }
+
             self.rcols[x] = resolved;
             auto += resolved;
             count += 1;
         }
 
+        // Grow the auto columns spanned by a cell if it doesn't fit into them.
+        for y in 0..self.rows.len() {
+            for x in 0..self.cols.len() {
+                let (cell, span) = match self.entry(x, y) {
+                    Some((cell, span)) if span.x > 1 => (cell, span),
+                    _ => continue,
+                };
+
+                // Fractional columns absorb the remaining space anyway.
+                let tracks = x..x + span.x;
+                let sizings = &self.cols[tracks.clone()];
+                let autos = sizings.iter().filter(|col| col.is_auto()).count();
+                if autos == 0 || sizings.iter().any(|col| matches!(col, Sizing::Fr(_))) {
+                    continue;
+                }
+
+                let size = Size::new(available, self.regions.base().y);
+                let pod = Regions::one(size, Axes::splat(false));
+                let frame = cell.measure(self.vt, self.styles, pod)?.into_frame();
+                let current: Abs = self.rcols[tracks.clone()].iter().sum();
+                let excess = frame.width() - current;
+                if excess <= Abs::zero() {
+                    continue;
+                }
+
+                let share = excess / autos as f64;
+                for (&col, rcol) in
+                    self.cols[tracks.clone()].iter().zip(&mut self.rcols[tracks])
+                {
+                    if col.is_auto() {
+                        *rcol += share;
+                    }
+                }
+
+                auto += excess;
+            }
+        }
+
         Ok((auto, count))
     }
 
@@ -428,7 +757,7 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
             for (&col, &rcol) in self.cols.iter().zip(&self.rcols) {
                 // Remove an auto column if it is not overlarge (rcol <= fair),
                 // but also hasn't already been removed (rcol > last).
-                if col == Sizing::Auto && rcol <= fair && rcol > last {
+                if col.is_auto() && rcol <= fair && rcol > last {
                     redistribute -= rcol;
                     overlarge -= 1;
                     changed = true;
@@ -438,7 +767,7 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
 
         // Redistribute space fairly among overlarge columns.
         for (&col, rcol) in self.cols.iter().zip(&mut self.rcols) {
-            if col == Sizing::Auto && *rcol > fair {
+            if col.is_auto() && *rcol > fair {
                 *rcol = fair;
             }
         }
@@ -463,7 +792,11 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
         }
 
         // Layout into a single region.
-        if let &[first] = resolved.as_slice() {
+        if let &[mut first] = resolved.as_slice() {
+            if let Sizing::Max(max) = self.rows[y] {
+                first
+                    .set_min(max.resolve(self.styles).relative_to(self.regions.base().y));
+            }
             let frame = self.layout_single_row(first, y)?;
             self.push_row(frame, y);
             return Ok(());
@@ -472,8 +805,9 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
         // Expand all but the last region.
         // Skip the first region if the space is eaten up by an fr row.
         let len = resolved.len();
+        let mut backlog = vec![];
         for (region, target) in self
-            .regions
+            .row_regions(&mut backlog)
             .iter()
             .zip(&mut resolved[..len - 1])
             .skip(self.lrows.iter().any(|row| matches!(row, Row::Fr(..))) as usize)
@@ -502,11 +836,13 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
         can_skip: bool,
     ) -> SourceResult<Option<Vec<Abs>>> {
         let mut resolved: Vec<Abs> = vec![];
+        let mut backlog = vec![];
+        let regions = self.row_regions(&mut backlog);
 
-        for (x, &rcol) in self.rcols.iter().enumerate() {
-            if let Some(cell) = self.cell(x, y) {
-                let mut pod = self.regions;
-                pod.size.x = rcol;
+        for x in 0..self.rcols.len() {
+            if let Some((cell, width)) = self.cell(x, y) {
+                let mut pod = regions;
+                pod.size.x = width;
 
                 let frames = cell.measure(self.vt, self.styles, pod)?.into_frames();
 
@@ -532,9 +868,55 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
             }
         }
 
+        // Make sure that cells spanning multiple rows and ending in this row
+        // fit into the rows they span.
+        let needed = self.measure_rowspans(y)?;
+        if needed > Abs::zero() {
+            match resolved.as_mut_slice() {
+                [] => resolved.push(needed),
+                [single] => single.set_max(needed),
+                _ => {}
+            }
+        }
+
         Ok(Some(resolved))
     }
 
+    /// Determine the height the row `y` needs so that the cells spanning
+    /// multiple rows and ending in it fit. Only rows in the current region
+    /// are taken into account.
+    fn measure_rowspans(&mut self, y: usize) -> SourceResult<Abs> {
+        let mut needed = Abs::zero();
+
+        for i in 0..self.rowspans.len() {
+            let (x, start, span) = self.rowspans[i];
+            if start + span.y - 1 != y || start < self.start {
+                continue;
+            }
+
+            // The height of the rows before this one.
+            let prev: Abs = self
+                .lrows
+                .iter()
+                .filter_map(|row| match row {
+                    Row::Frame(frame, ry) if (start..y).contains(ry) => {
+                        Some(frame.height())
+                    }
+                    _ => None,
+                })
+                .sum();
+
+            let (cell, _) = self.entry(x, start).unwrap();
+            let width = self.rcols[x..x + span.x].iter().sum();
+            let size = Size::new(width, self.regions.base().y);
+            let pod = Regions::one(size, Axes::new(true, false));
+            let frame = cell.measure(self.vt, self.styles, pod)?.into_frame();
+            needed.set_max(frame.height() - prev);
+        }
+
+        Ok(needed)
+    }
+
     /// Layout a row with relative height. Such a row cannot break across
     /// multiple regions, but it may force a region break.
     fn layout_relative_row(&mut self, v: Rel<Length>, y: usize) -> SourceResult<()> {
@@ -563,20 +945,23 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
         let mut pos = Point::zero();
 
         for (x, &rcol) in self.rcols.iter().enumerate() {
-            if let Some(cell) = self.cell(x, y) {
-                let size = Size::new(rcol, height);
+            if let Some((cell, width)) = self.cell(x, y) {
+                let size = Size::new(width, height);
                 let mut pod = Regions::one(size, Axes::splat(true));
-                if self.rows[y] == Sizing::Auto {
+                if self.rows[y].is_auto() {
                     pod.full = self.regions.full;
                 }
-                let frame = cell.layout(self.vt, self.styles, pod)?.into_frame();
-                output.push_frame(pos, frame);
+                let mut fragment = cell.layout(self.vt, self.styles, pod)?;
+                self.tag_cell(&mut fragment);
+                output.push_frame(pos, fragment.into_frame());
             }
 
             pos.x += rcol;
         }
 
-        Ok(output)
+        let mut fragment = Fragment::frame(output);
+        self.tag_row(&mut fragment, y);
+        Ok(fragment.into_frame())
     }
 
     /// Layout a row spanning multiple regions.
@@ -596,11 +981,12 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
         // Layout the row.
         let mut pos = Point::zero();
         for (x, &rcol) in self.rcols.iter().enumerate() {
-            if let Some(cell) = self.cell(x, y) {
-                pod.size.x = rcol;
+            if let Some((cell, width)) = self.cell(x, y) {
+                pod.size.x = width;
 
                 // Push the layouted frames into the individual output frames.
-                let fragment = cell.layout(self.vt, self.styles, pod)?;
+                let mut fragment = cell.layout(self.vt, self.styles, pod)?;
+                self.tag_cell(&mut fragment);
                 for (output, frame) in outputs.iter_mut().zip(fragment) {
                     output.push_frame(pos, frame);
                 }
@@ -609,7 +995,27 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
             pos.x += rcol;
         }
 
-        Ok(Fragment::frames(outputs))
+        let mut fragment = Fragment::frames(outputs);
+        self.tag_row(&mut fragment, y);
+        Ok(fragment)
+    }
+
+    /// Mark a laid out content row as a structure element, if requested.
+    fn tag_row(&self, fragment: &mut Fragment, y: usize) {
+        if self.has_gutter && y % 2 == 1 {
+            return;
+        }
+
+        if let Some((tag, _)) = &self.tags {
+            fragment.tag(tag.clone());
+        }
+    }
+
+    /// Mark a laid out cell as a structure element, if requested.
+    fn tag_cell(&self, fragment: &mut Fragment) {
+        if let Some((_, Some(tag))) = &self.tags {
+            fragment.tag(tag.clone());
+        }
     }
 
     /// Push a row frame into the current region.
@@ -637,22 +1043,29 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
             size.y = self.initial.y;
         }
 
+        // Layout fractional rows.
+        for i in 0..self.lrows.len() {
+            if let Row::Fr(v, y) = self.lrows[i] {
+                let remaining = self.regions.full - used;
+                let height = v.share(fr, remaining);
+                self.lrows[i] = Row::Frame(self.layout_single_row(height, y)?, y);
+            }
+        }
+
+        // Now that all row heights are known, layout the cells spanning
+        // multiple rows that had to wait for them.
+        for i in std::mem::take(&mut self.deferred) {
+            self.layout_rowspan(i)?;
+        }
+
         // The frame for the region.
         let mut output = Frame::new(size);
         let mut pos = Point::zero();
         let mut rrows = vec![];
 
-        // Place finished rows and layout fractional rows.
+        // Place finished rows.
         for row in std::mem::take(&mut self.lrows) {
-            let (frame, y) = match row {
-                Row::Frame(frame, y) => (frame, y),
-                Row::Fr(v, y) => {
-                    let remaining = self.regions.full - used;
-                    let height = v.share(fr, remaining);
-                    (self.layout_single_row(height, y)?, y)
-                }
-            };
-
+            let Row::Frame(frame, y) = row else { unreachable!() };
             let height = frame.height();
             output.push_frame(pos, frame);
             rrows.push(RowPiece { height, y });
@@ -663,34 +1076,139 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
         self.rrows.push(rrows);
         self.regions.next();
         self.initial = self.regions.size;
+        self.start = self.current;
+
+        // Repeat the header at the top of the new region.
+        for (frame, y) in self.header.clone() {
+            self.push_row(frame, y);
+        }
 
         Ok(())
     }
 
-    /// Get the content of the cell in column `x` and row `y`.
+    /// Layout the cells spanning multiple rows that end in row `y`.
     ///
-    /// Returns `None` if it's a gutter cell.
+    /// If one of the spanned rows in the current region is fractional, its
+    /// height is not yet known and the layout is deferred until the region is
+    /// finished.
+    fn layout_rowspans(&mut self, y: usize) -> SourceResult<()> {
+        for i in 0..self.rowspans.len() {
+            let (_, start, span) = self.rowspans[i];
+            if start + span.y - 1 != y {
+                continue;
+            }
+
+            let rows = start..start + span.y;
+            if self
+                .lrows
+                .iter()
+                .any(|row| matches!(row, Row::Fr(_, ry) if rows.contains(ry)))
+            {
+                self.deferred.push(i);
+            } else {
+                self.layout_rowspan(i)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Layout a cell spanning multiple rows into the pieces of its rows in
+    /// the finished regions and the current one.
+    ///
+    /// The piece in the current region is attached to the frame of the first
+    /// spanned row in it, so that the cell is placed in row order.
+    fn layout_rowspan(&mut self, i: usize) -> SourceResult<()> {
+        let (x, y, span) = self.rowspans[i];
+        let rows = y..y + span.y;
+
+        // Find the pieces of the spanned rows in the finished regions.
+        let mut pieces = vec![];
+        for (r, region) in self.rrows.iter().enumerate() {
+            let mut offset = Abs::zero();
+            let mut start = None;
+            let mut height = Abs::zero();
+            for piece in region {
+                if rows.contains(&piece.y) {
+                    start.get_or_insert(offset);
+                    height += piece.height;
+                }
+                offset += piece.height;
+            }
+
+            if let Some(start) = start {
+                pieces.push((r, start, height));
+            }
+        }
+
+        // Find the spanned rows in the current region.
+        let mut first = None;
+        let mut height = Abs::zero();
+        for (k, row) in self.lrows.iter().enumerate() {
+            if let Row::Frame(frame, ry) = row {
+                if rows.contains(ry) {
+                    first.get_or_insert(k);
+                    height += frame.height();
+                }
+            }
+        }
+
+        let mut heights: Vec<_> = pieces.iter().map(|&(_, _, height)| height).collect();
+        if first.is_some() {
+            heights.push(height);
+        }
+
+        if heights.is_empty() {
+            return Ok(());
+        }
+
+        let (cell, _) = self.entry(x, y).unwrap();
+        let width = self.rcols[x..x + span.x].iter().sum();
+        let mut pod = Regions::one(Size::new(width, heights[0]), Axes::splat(true));
+        pod.backlog = &heights[1..];
+
+        let mut fragment = cell.layout(self.vt, self.styles, pod)?;
+        self.tag_cell(&mut fragment);
+
+        let mut fragment = fragment.into_iter();
+        let dx = self.rcols[..x].iter().sum();
+        for (&(r, dy, _), frame) in pieces.iter().zip(&mut fragment) {
+            self.finished[r].push_frame(Point::new(dx, dy), frame);
+        }
+
+        if let (Some(k), Some(frame)) = (first, fragment.next()) {
+            if let Row::Frame(output, _) = &mut self.lrows[k] {
+                output.push_frame(Point::with_x(dx), frame);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the cell starting in column `x` and row `y` along with the number
+    /// of tracks it spans.
+    ///
+    /// Returns `None` if it's a gutter cell or covered by another cell.
     #[track_caller]
-    fn cell(&self, mut x: usize, y: usize) -> Option<&'a Content> {
+    fn entry(&self, x: usize, y: usize) -> Option<(&'a Content, Axes<usize>)> {
         assert!(x < self.cols.len());
         assert!(y < self.rows.len());
+        let (i, span) = self.entries[y * self.cols.len() + x]?;
+        Some((&self.cells[i], span))
+    }
 
-        // Columns are reorder, but the cell slice is not.
-        if self.is_rtl {
-            x = self.cols.len() - 1 - x;
+    /// Get the content and width of the cell in column `x` and row `y`.
+    ///
+    /// Returns `None` if it's a gutter cell, covered by another cell or
+    /// spans multiple rows. The latter are laid out once their last row is
+    /// done.
+    #[track_caller]
+    fn cell(&self, x: usize, y: usize) -> Option<(&'a Content, Abs)> {
+        let (cell, span) = self.entry(x, y)?;
+        if span.y > 1 {
+            return None;
         }
 
-        if self.has_gutter {
-            // Even columns and rows are children, odd ones are gutter.
-            if x % 2 == 0 && y % 2 == 0 {
-                let c = 1 + self.cols.len() / 2;
-                self.cells.get((y / 2) * c + x / 2)
-            } else {
-                None
-            }
-        } else {
-            let c = self.cols.len();
-            self.cells.get(y * c + x)
-        }
+        Some((cell, self.rcols[x..x + span.x].iter().sum()))
     }
 }
@@ -81,7 +81,17 @@ pub struct ListElem {
     ///   - Nested
     ///   - Items
     /// - Items
+    ///
+    /// #set list(marker: depth => {
+    ///   ([•], [‣], [--]).at(calc.rem(depth, 3))
+    /// })
+    /// - Cycling
+    ///   - Through
+    ///     - Three
+    ///       - Markers
     /// ```
+    ///
+    /// In right-to-left text, the marker is placed to the right of the items.
     #[default(ListMarker::Content(vec![TextElem::packed('•')]))]
     pub marker: ListMarker,
 
@@ -139,8 +149,10 @@ impl Layout for ListElem {
         let marker = self
             .marker(styles)
             .resolve(vt, depth)?
-            // avoid '#set align' interference with the list
-            .aligned(Align::LEFT_TOP.into());
+            // Avoid '#set align' interference with the list. The marker is
+            // aligned to the start, so that it is mirrored in right-to-left
+            // text along with the columns.
+            .aligned(Axes::new(Some(GenAlign::Start), Some(Align::Top.into())));
 
         let mut cells = vec![];
         for item in self.children() {
@@ -162,9 +174,12 @@ impl Layout for ListElem {
             &cells,
             regions,
             styles,
-        );
+        )
+        .tagged(Tag::ListItem, None);
 
-        Ok(layouter.layout()?.fragment)
+        let mut fragment = layouter.layout()?.fragment;
+        fragment.tag(Tag::List);
+        Ok(fragment)
     }
 }
 
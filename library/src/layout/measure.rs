@@ -3,10 +3,14 @@ use crate::prelude::*;
 /// Measure the layouted size of content.
 ///
 /// The `measure` function lets you determine the layouted size of content.
-/// Note that an infinite space is assumed, therefore the measured height/width
-/// may not necessarily match the final height/width of the measured content.
-/// If you want to measure in the current layout dimensions, you can combined
-/// `measure` and [`layout`]($func/layout).
+/// By default, an infinite space is assumed, therefore the measured
+/// height/width may not necessarily match the final height/width of the
+/// measured content. You can restrict the available space with the `width` and
+/// `height` arguments. If you want to measure in the current layout
+/// dimensions, you can combine `measure` and [`layout`]($func/layout).
+///
+/// Measuring content lays it out, which can be costly for large content,
+/// especially when done repeatedly.
 ///
 /// # Example { #example }
 /// The same content can have a different size depending on the styles that
@@ -37,6 +41,14 @@ use crate::prelude::*;
 /// The measure function returns a dictionary with the entries `width` and
 /// `height`, both of type [`length`]($type/length).
 ///
+/// ```example
+/// #style(styles => {
+///   let body = lorem(12)
+///   let size = measure(body, styles, width: 100pt)
+///   rect(width: size.width, height: size.height, inset: 0pt, body)
+/// })
+/// ```
+///
 /// Display: Measure
 /// Category: layout
 #[func]
@@ -45,11 +57,23 @@ pub fn measure(
     content: Content,
     /// The styles with which to layout the content.
     styles: Styles,
+    /// The width available to the content. If `{auto}`, an infinite width is
+    /// assumed.
+    #[named]
+    #[default]
+    width: Smart<Length>,
+    /// The height available to the content. If `{auto}`, an infinite height is
+    /// assumed.
+    #[named]
+    #[default]
+    height: Smart<Length>,
     /// The virtual machine.
     vm: &mut Vm,
 ) -> SourceResult<Dict> {
-    let pod = Regions::one(Axes::splat(Abs::inf()), Axes::splat(false));
     let styles = StyleChain::new(&styles);
+    let size = Axes::new(width, height)
+        .map(|length| length.map_or(Abs::inf(), |length| length.resolve(styles)));
+    let pod = Regions::one(size, Axes::splat(false));
     let frame = content.measure(&mut vm.vt, styles, pod)?.into_frame();
     let Size { x, y } = frame.size();
     Ok(dict! { "width" => x, "height" => y })
@@ -46,14 +46,18 @@ pub use self::terms::*;
 pub use self::transform::*;
 
 use std::mem;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+use smallvec::smallvec;
 use typed_arena::Arena;
 use typst::diag::SourceResult;
 use typst::eval::Tracer;
 use typst::model::{applicable, realize, StyleVecBuilder};
 
 use crate::math::{EquationElem, LayoutMath};
-use crate::meta::DocumentElem;
+use crate::meta::{
+    Counter, CounterKey, CounterState, CounterUpdate, DocumentElem, Numbering,
+};
 use crate::prelude::*;
 use crate::shared::BehavedBuilder;
 use crate::text::{LinebreakElem, SmartQuoteElem, SpaceElem, TextElem};
@@ -70,12 +74,15 @@ pub(super) fn define(global: &mut Scope) {
     global.define("par", ParElem::func());
     global.define("parbreak", ParbreakElem::func());
     global.define("h", HElem::func());
+    global.define("tab", TabElem::func());
     global.define("box", BoxElem::func());
     global.define("block", BlockElem::func());
     global.define("list", ListElem::func());
     global.define("enum", EnumElem::func());
     global.define("terms", TermsElem::func());
     global.define("table", TableElem::func());
+    global.define("tabulate", tabulate_func());
+    global.define("stripe", stripe_func());
     global.define("stack", StackElem::func());
     global.define("grid", GridElem::func());
     global.define("columns", ColumnsElem::func());
@@ -194,6 +201,7 @@ impl Layout for Content {
             styles: StyleChain,
             regions: Regions,
         ) -> SourceResult<Fragment> {
+            LAYOUT_MISSES.fetch_add(1, Ordering::Relaxed);
             let mut locator = Locator::chained(locator);
             let mut vt = Vt { world, tracer, locator: &mut locator, introspector };
             let scratch = Scratch::default();
@@ -206,6 +214,7 @@ impl Layout for Content {
 
         tracing::info!("Layouting `Content`");
 
+        LAYOUT_REQUESTS.fetch_add(1, Ordering::Relaxed);
         let fragment = cached(
             self,
             vt.world,
@@ -221,6 +230,86 @@ impl Layout for Content {
     }
 }
 
+/// How often content was laid out, across all compilations.
+static LAYOUT_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+/// How often content layout could not be reused from the cache.
+static LAYOUT_MISSES: AtomicUsize = AtomicUsize::new(0);
+
+/// How often a run of text was shaped with a font.
+pub(crate) static SHAPE_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+/// How often shaping could not be reused from the cache.
+pub(crate) static SHAPE_MISSES: AtomicUsize = AtomicUsize::new(0);
+
+/// Statistics about the reuse of cached layouts and shaped text.
+///
+/// Layout is memoized at the granularity of the element. Between two
+/// compilations, only elements whose content, styles, regions or
+/// introspection results changed are laid out again. Within those, runs of
+/// text that were already shaped with the same font and features are reused.
+/// These statistics help to judge how well that works for a particular
+/// document.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct CacheStats {
+    /// How often content was laid out.
+    pub requests: usize,
+    /// How often the layout had to be computed instead of being reused.
+    pub misses: usize,
+    /// How often a run of text was shaped.
+    pub shape_requests: usize,
+    /// How often shaping had to run instead of being reused.
+    pub shape_misses: usize,
+}
+
+impl CacheStats {
+    /// Retrieve the statistics collected since the last reset.
+    pub fn get() -> Self {
+        Self {
+            requests: LAYOUT_REQUESTS.load(Ordering::Relaxed),
+            misses: LAYOUT_MISSES.load(Ordering::Relaxed),
+            shape_requests: SHAPE_REQUESTS.load(Ordering::Relaxed),
+            shape_misses: SHAPE_MISSES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Reset the statistics, e.g. before a new compilation.
+    pub fn reset() {
+        LAYOUT_REQUESTS.store(0, Ordering::Relaxed);
+        LAYOUT_MISSES.store(0, Ordering::Relaxed);
+        SHAPE_REQUESTS.store(0, Ordering::Relaxed);
+        SHAPE_MISSES.store(0, Ordering::Relaxed);
+    }
+
+    /// How often a cached layout was reused.
+    pub fn hits(&self) -> usize {
+        self.requests.saturating_sub(self.misses)
+    }
+
+    /// The fraction of layout requests that were served from the cache.
+    pub fn hit_rate(&self) -> f64 {
+        rate(self.hits(), self.requests)
+    }
+
+    /// How often a cached shaping result was reused.
+    pub fn shape_hits(&self) -> usize {
+        self.shape_requests.saturating_sub(self.shape_misses)
+    }
+
+    /// The fraction of shaping requests that were served from the cache.
+    pub fn shape_hit_rate(&self) -> f64 {
+        rate(self.shape_hits(), self.shape_requests)
+    }
+}
+
+/// The fraction of requests that were hits, or zero without any requests.
+fn rate(hits: usize, requests: usize) -> f64 {
+    if requests == 0 {
+        return 0.0;
+    }
+    hits as f64 / requests as f64
+}
+
 /// Realize into an element that is capable of root-level layout.
 #[tracing::instrument(skip_all)]
 fn realize_root<'a>(
@@ -360,6 +449,18 @@ impl<'a, 'v, 't> Builder<'a, 'v, 't> {
 
         if let Some(doc) = &mut self.doc {
             if doc.accept(content, styles) {
+                // A pagebreak with a numbering restarts the page counter on
+                // the next page.
+                let restart = content
+                    .to::<PagebreakElem>()
+                    .map_or(false, |pagebreak| pagebreak.numbering(styles).is_some());
+                if restart {
+                    let state = CounterState(smallvec![1]);
+                    let update =
+                        Counter::new(CounterKey::Page).update(CounterUpdate::Set(state));
+                    let stored = self.scratch.content.alloc(update);
+                    self.accept(stored, styles)?;
+                }
                 return Ok(());
             }
         }
@@ -467,17 +568,41 @@ struct DocBuilder<'a> {
     pages: StyleVecBuilder<'a, Content>,
     /// Whether to keep a following page even if it is empty.
     keep_next: bool,
+    /// Whether the next page should be cleared to an even or odd number.
+    clear_next: Option<Parity>,
+    /// The numbering set by the last numbered pagebreak, together with the
+    /// styled numbering that was active at that pagebreak.
+    numbering: Option<(Numbering, Option<Numbering>)>,
 }
 
 impl<'a> DocBuilder<'a> {
     fn accept(&mut self, content: &Content, styles: StyleChain<'a>) -> bool {
         if let Some(pagebreak) = content.to::<PagebreakElem>() {
             self.keep_next = !pagebreak.weak(styles);
+            self.clear_next = pagebreak.to(styles);
+            if let Some(numbering) = pagebreak.numbering(styles) {
+                self.numbering = Some((numbering, PageElem::numbering_in(styles)));
+            }
             return true;
         }
 
-        if content.is::<PageElem>() {
-            self.pages.push(content.clone(), styles);
+        if let Some(page) = content.to::<PageElem>() {
+            let mut page = page.clone();
+            if let Some(parity) = self.clear_next.take() {
+                page.push_clear_to(Some(parity));
+            }
+
+            // The numbering of a pagebreak stays active until a set rule
+            // changes the page numbering.
+            if let Some((numbering, outer)) = &self.numbering {
+                if PageElem::numbering_in(styles) == *outer {
+                    page.push_numbering(Some(numbering.clone()));
+                } else {
+                    self.numbering = None;
+                }
+            }
+
+            self.pages.push(page.pack(), styles);
             self.keep_next = false;
             return true;
         }
@@ -488,7 +613,12 @@ impl<'a> DocBuilder<'a> {
 
 impl Default for DocBuilder<'_> {
     fn default() -> Self {
-        Self { pages: StyleVecBuilder::new(), keep_next: true }
+        Self {
+            pages: StyleVecBuilder::new(),
+            keep_next: true,
+            clear_next: None,
+            numbering: None,
+        }
     }
 }
 
@@ -561,6 +691,7 @@ impl<'a> ParBuilder<'a> {
         } else if content.is::<SpaceElem>()
             || content.is::<TextElem>()
             || content.is::<HElem>()
+            || content.is::<TabElem>()
             || content.is::<LinebreakElem>()
             || content.is::<SmartQuoteElem>()
             || content.to::<EquationElem>().map_or(false, |elem| !elem.block(styles))
@@ -1,9 +1,11 @@
 use std::ptr;
 use std::str::FromStr;
 
+use super::place::find_placed;
 use super::{AlignElem, ColumnsElem};
-use crate::meta::{Counter, CounterKey, Numbering};
+use crate::meta::{layout_marginnotes, Counter, CounterKey, HeadingElem, Numbering};
 use crate::prelude::*;
+use crate::text::TextElem;
 
 /// Layouts its child onto one or multiple pages.
 ///
@@ -97,11 +99,21 @@ pub struct PageElem {
     ///   - `right`: The right margin.
     ///   - `bottom`: The bottom margin.
     ///   - `left`: The left margin.
+    ///   - `inside`: The margin at the inner side of the page (where the
+    ///     [binding]($func/page.binding) is).
+    ///   - `outside`: The margin at the outer side of the page (opposite to the
+    ///     [binding]($func/page.binding)).
     ///   - `x`: The horizontal margins.
     ///   - `y`: The vertical margins.
     ///   - `rest`: The margins on all sides except those for which the
     ///     dictionary explicitly sets a size.
     ///
+    /// The values for `left` and `right` are mutually exclusive with
+    /// the values for `inside` and `outside`. With `inside` and `outside`,
+    /// the margins swap sides from page to page. Which side is inside is
+    /// determined by the physical page number, so it is not affected by
+    /// updates to the page counter.
+    ///
     /// ```example
     /// #set page(
     ///  width: 3cm,
@@ -116,7 +128,30 @@ pub struct PageElem {
     /// )
     /// ```
     #[fold]
-    pub margin: Sides<Option<Smart<Rel<Length>>>>,
+    pub margin: Margin,
+
+    /// On which side the pages will be bound.
+    ///
+    /// - `{auto}`: Equivalent to `left` if the [text direction]($func/text.dir)
+    ///   is left-to-right and `right` if it is right-to-left.
+    /// - `left`: Bound on the left side.
+    /// - `right`: Bound on the right side.
+    ///
+    /// This affects the meaning of the `inside` and `outside` options for
+    /// margins.
+    ///
+    /// ```example
+    /// #set page(
+    ///   width: 3cm,
+    ///   height: 2cm,
+    ///   binding: right,
+    ///   margin: (inside: 16pt, outside: 4pt),
+    ///   fill: aqua,
+    /// )
+    ///
+    /// #lorem(6)
+    /// ```
+    pub binding: Smart<Binding>,
 
     /// How many columns the page has.
     ///
@@ -135,6 +170,68 @@ pub struct PageElem {
     #[default(NonZeroUsize::ONE)]
     pub columns: NonZeroUsize,
 
+    /// A grid onto which the baselines of text lines are snapped.
+    ///
+    /// When set to a length, the baseline of each line of text is moved down
+    /// to the next multiple of that length, measured from the top of the
+    /// page's text area. This way, the lines of facing pages and adjacent
+    /// columns sit at the same height. The top of other blocks, like headings
+    /// and figures, is snapped to the grid as well and they occupy a whole
+    /// multiple of the grid's step. Text in nested containers is snapped
+    /// relative to the container's top, so it stays on the grid as long as
+    /// the container's inset is a multiple of the step, too. Content in
+    /// [boxes]($func/box) is never snapped.
+    ///
+    /// The grid's step should usually match the distance between two lines
+    /// of body text, i.e. the line height plus the [leading]($func/par.leading).
+    /// To check the alignment, you can pass a dictionary with the `step` and
+    /// `{debug: true}` to draw the grid's lines onto the page.
+    ///
+    /// ```example
+    /// #set page(
+    ///   height: 120pt,
+    ///   columns: 2,
+    ///   baseline-grid: (step: 12pt, debug: true),
+    /// )
+    /// #set par(leading: 5pt)
+    ///
+    /// = Grid
+    /// #lorem(8)
+    ///
+    /// #text(8pt, lorem(10))
+    /// ```
+    pub baseline_grid: Option<BaselineGrid>,
+
+    /// How far the page extends beyond its trim size on each side for print
+    /// production.
+    ///
+    /// The exported page is enlarged by the bleed on each side and the page's
+    /// [fill]($func/page.fill) and [background]($func/page.background) extend
+    /// into it. In PDF export, the page's trim and bleed boxes are set
+    /// accordingly. Content that reaches beyond the page's edges, like a
+    /// full-bleed image, is not clipped at the trim line.
+    ///
+    /// ```example
+    /// #set page(
+    ///   width: 3cm,
+    ///   height: 2cm,
+    ///   bleed: 3mm,
+    ///   marks: true,
+    ///   fill: aqua,
+    /// )
+    ///
+    /// Ready for print.
+    /// ```
+    #[resolve]
+    pub bleed: Length,
+
+    /// Whether to draw crop marks at the corners of the trim box.
+    ///
+    /// The marks are placed outside of the bleed. The exported page is
+    /// enlarged to make room for them.
+    #[default(false)]
+    pub marks: bool,
+
     /// The page's background color.
     ///
     /// This instructs the printer to color the complete page with the given
@@ -230,10 +327,44 @@ pub struct PageElem {
     #[default(Ratio::new(0.3).into())]
     pub footer_descent: Rel<Length>,
 
+    /// A distinct layout for the first page of each chapter.
+    ///
+    /// A page opens a chapter if it begins with a level-one
+    /// [heading]($func/heading). Headings further down on a page do not
+    /// count, even if they are preceded by a page break. The value is a
+    /// dictionary with the following optional keys:
+    /// - `header`: The header of chapter openers, possibly `{none}`.
+    /// - `footer`: The footer of chapter openers, possibly `{none}`.
+    /// - `top`: The top margin of chapter openers.
+    ///
+    /// Keys that are not given are taken from the regular page settings.
+    ///
+    /// ```example
+    /// #set page(
+    ///   height: 120pt,
+    ///   header: [_Running header_],
+    ///   opener: (header: none, top: 48pt),
+    /// )
+    /// #show heading.where(level: 1): it => {
+    ///   pagebreak(weak: true)
+    ///   it
+    /// }
+    ///
+    /// = Introduction
+    /// #lorem(20)
+    /// ```
+    pub opener: Option<Opener>,
+
     /// Content in the page's background.
     ///
     /// This content will be placed behind the page's body. It can be
-    /// used to place a background image or a watermark.
+    /// used to place a background image or a watermark. It is clipped to the
+    /// page.
+    ///
+    /// Instead of content, you can also pass a function. It receives a
+    /// dictionary with the page's `width` and `height` and returns the content
+    /// to display. This makes it possible to size and position content
+    /// relative to the page.
     ///
     /// ```example
     /// #set page(background: rotate(24deg,
@@ -246,11 +377,22 @@ pub struct PageElem {
     /// In the year 2023, we plan to take
     /// over the world (of typesetting).
     /// ```
-    pub background: Option<Content>,
+    ///
+    /// ```example
+    /// #set page(background: page => rotate(-45deg,
+    ///   text(page.width / 4, fill: luma(220))[*DRAFT*]
+    /// ))
+    ///
+    /// = Preliminary results
+    /// These numbers are not final.
+    /// ```
+    pub background: Option<Marginal>,
 
     /// Content in the page's foreground.
     ///
-    /// This content will overlay the page's body.
+    /// This content will overlay the page's body. Like the
+    /// [background]($func/page.background), it is clipped to the page and
+    /// can be a function of the page's dimensions.
     ///
     /// ```example
     /// #set page(foreground: text(24pt)[🥸])
@@ -259,7 +401,7 @@ pub struct PageElem {
     /// "Weak Reject" because they did
     /// not understand our approach...
     /// ```
-    pub foreground: Option<Content>,
+    pub foreground: Option<Marginal>,
 
     /// The contents of the page(s).
     ///
@@ -268,6 +410,11 @@ pub struct PageElem {
     /// will be created after the body has been typeset.
     #[required]
     pub body: Content,
+
+    /// Whether the page should be aligned to an even or odd page. This is
+    /// derived from a preceding [pagebreak]($func/pagebreak).
+    #[internal]
+    pub clear_to: Option<Parity>,
 }
 
 impl PageElem {
@@ -302,12 +449,22 @@ impl PageElem {
 
         // Determine the margins.
         let default = Rel::from(0.1190 * min);
-        let margin = self
-            .margin(styles)
-            .map(|side| side.unwrap_or(default))
+        let margin = self.margin(styles);
+        let two_sided = margin.two_sided.unwrap_or(false);
+        let margin = margin
+            .sides
+            .map(|side| side.and_then(Smart::as_custom).unwrap_or(default))
             .resolve(styles)
             .relative_to(size);
 
+        // Determine the binding.
+        let binding =
+            self.binding(styles)
+                .unwrap_or_else(|| match TextElem::dir_in(styles) {
+                    Dir::LTR => Binding::Left,
+                    _ => Binding::Right,
+                });
+
         // Realize columns.
         let mut child = self.body();
         let columns = self.columns(styles);
@@ -315,14 +472,46 @@ impl PageElem {
             child = ColumnsElem::new(child).with_count(columns).pack();
         }
 
+        // Determine which pages open a chapter. This depends on the positions
+        // of headings and is thus based on the previous layout iteration.
+        let opener = self.opener(styles);
+        let opener_top = opener
+            .as_ref()
+            .and_then(|opener| opener.top)
+            .map(|top| top.resolve(styles).relative_to(size.y));
+        let openers = match &opener {
+            Some(_) => find_openers(vt, [margin.top, opener_top.unwrap_or(margin.top)]),
+            None => vec![],
+        };
+
         let area = size - margin.sum_by_axis();
         let mut regions = Regions::repeat(area, area.map(Abs::is_finite));
         regions.root = true;
 
+        // Shrink the regions of chapter openers with a larger top margin.
+        let mut backlog = vec![];
+        if let (Some(top), Some(&last)) = (opener_top, openers.iter().max()) {
+            if area.y.is_finite() && last >= number {
+                let delta = top - margin.top;
+                for i in number.get()..=last.get() {
+                    let opening = openers.iter().any(|page| page.get() == i);
+                    backlog.push(if opening { area.y - delta } else { area.y });
+                }
+
+                let first = backlog.remove(0);
+                regions.size.y = first;
+                regions.full = first;
+                regions.backlog = &backlog;
+            }
+        }
+
         // Layout the child.
         let mut fragment = child.layout(vt, styles, regions)?;
 
         let fill = self.fill(styles);
+        let grid = self.baseline_grid(styles);
+        let bleed = self.bleed(styles);
+        let marks = self.marks(styles);
         let foreground = self.foreground(styles);
         let background = self.background(styles);
         let header = self.header(styles);
@@ -331,6 +520,7 @@ impl PageElem {
             self.numbering(styles).map(|numbering| {
                 let both = match &numbering {
                     Numbering::Pattern(pattern) => pattern.pieces() >= 2,
+                    Numbering::Symbols(_) => false,
                     Numbering::Func(_) => true,
                 };
                 Counter::new(CounterKey::Page)
@@ -352,18 +542,76 @@ impl PageElem {
             // The padded width of the page's content without margins.
             let pw = frame.width();
 
+            // If two sided, left becomes inside and right becomes outside.
+            // Thus, for left-bound pages, we want to swap on even pages and
+            // for right-bound pages, we want to swap on odd pages.
+            let mut margin = margin;
+            let swapped = two_sided && binding.swap(number);
+            if swapped {
+                std::mem::swap(&mut margin.left, &mut margin.right);
+            }
+
+            // Chapter openers can have a distinct top margin and marginals.
+            let opening = openers.contains(&number);
+            let (header, footer) = match &opener {
+                Some(opener) if opening => {
+                    if let Some(top) = opener_top {
+                        margin.top = top;
+                    }
+                    (
+                        opener.header.as_ref().unwrap_or(&header),
+                        opener.footer.as_ref().unwrap_or(&footer),
+                    )
+                }
+                _ => (&header, &footer),
+            };
+
             // Realize margins.
             frame.set_size(frame.size() + margin.sum_by_axis());
             frame.translate(Point::new(margin.left, margin.top));
             frame.push(Point::zero(), numbering_meta.clone());
 
+            // Draw the baseline grid to verify alignment.
+            if let Some(grid) = grid.filter(|grid| grid.debug) {
+                draw_grid(frame, margin, grid.step.resolve(styles));
+            }
+
             // The page size with margins.
             let size = frame.size();
 
+            // Realize content that is placed relative to the page.
+            let mut placed = vec![];
+            find_placed(&mut placed, frame);
+            for elem in placed {
+                let expand = size.map(Abs::is_finite);
+                let content = elem.place(vt, styles, size, expand)?;
+                frame.push_frame(Point::zero(), content);
+            }
+
+            // Realize margin notes in the outside margin, which is opposite of
+            // the binding.
+            let outside_left = match binding {
+                Binding::Left => swapped,
+                Binding::Right => !swapped,
+            };
+            let (x, width) = if outside_left {
+                (Abs::zero(), margin.left)
+            } else {
+                (size.x - margin.right, margin.right)
+            };
+            let (top, bottom) = (margin.top, size.y - margin.bottom);
+            layout_marginnotes(vt, styles, frame, x, width, top, bottom)?;
+
+            // Resolve the overlays based on the page's dimensions.
+            let background =
+                background.as_ref().map(|m| m.resolve(vt, size)).transpose()?;
+            let foreground =
+                foreground.as_ref().map(|m| m.resolve(vt, size)).transpose()?;
+
             // Realize overlays.
             for (name, marginal) in [
-                ("header", &header),
-                ("footer", &footer),
+                ("header", header),
+                ("footer", footer),
                 ("background", &background),
                 ("foreground", &foreground),
             ] {
@@ -372,38 +620,55 @@ impl PageElem {
                 let Some(content) = marginal else { continue };
 
                 let (pos, area, align);
-                if ptr::eq(marginal, &header) {
+                if ptr::eq(marginal, header) {
                     let ascent = header_ascent.relative_to(margin.top);
                     pos = Point::with_x(margin.left);
                     area = Size::new(pw, margin.top - ascent);
                     align = Align::Bottom.into();
-                } else if ptr::eq(marginal, &footer) {
+                } else if ptr::eq(marginal, footer) {
                     let descent = footer_descent.relative_to(margin.bottom);
                     pos = Point::new(margin.left, size.y - margin.bottom + descent);
                     area = Size::new(pw, margin.bottom - descent);
                     align = Align::Top.into();
                 } else {
-                    pos = Point::zero();
-                    area = size;
+                    pos = Point::splat(-bleed);
+                    area = size + Size::splat(2.0 * bleed);
                     align = Align::CENTER_HORIZON.into();
                 };
 
                 let pod = Regions::one(area, Axes::splat(true));
-                let sub = content
+                let mut sub = content
                     .clone()
                     .styled(AlignElem::set_alignment(align))
+                    .styled(Self::set_baseline_grid(None))
                     .layout(vt, styles, pod)?
                     .into_frame();
 
-                if ptr::eq(marginal, &header) || ptr::eq(marginal, &background) {
+                // Overlays must not spill beyond the page.
+                if ptr::eq(marginal, &background) || ptr::eq(marginal, &foreground) {
+                    sub.clip();
+                }
+
+                if ptr::eq(marginal, header) || ptr::eq(marginal, &background) {
                     frame.prepend_frame(pos, sub);
                 } else {
                     frame.push_frame(pos, sub);
                 }
             }
 
+            // Extend the fill into the bleed.
             if let Some(fill) = &fill {
-                frame.fill(fill.clone());
+                let shape =
+                    Geometry::Rect(size + Size::splat(2.0 * bleed)).filled(fill.clone());
+                frame.prepend(
+                    Point::splat(-bleed),
+                    FrameItem::Shape(shape, Span::detached()),
+                );
+            }
+
+            // Enlarge the page for print production.
+            if bleed > Abs::zero() || marks {
+                enlarge(frame, bleed, marks);
             }
 
             number = number.saturating_add(1);
@@ -413,6 +678,270 @@ impl PageElem {
     }
 }
 
+/// The distinct layout of pages that open a chapter.
+#[derive(Debug, Clone, Hash)]
+pub struct Opener {
+    /// The header of chapter openers, if it differs from the regular one.
+    pub header: Option<Option<Content>>,
+    /// The footer of chapter openers, if it differs from the regular one.
+    pub footer: Option<Option<Content>>,
+    /// The top margin of chapter openers, if it differs from the regular one.
+    pub top: Option<Rel<Length>>,
+}
+
+cast! {
+    Opener,
+    self => {
+        let mut dict = Dict::new();
+        if let Some(header) = self.header {
+            dict.insert("header".into(), header.into_value());
+        }
+        if let Some(footer) = self.footer {
+            dict.insert("footer".into(), footer.into_value());
+        }
+        if let Some(top) = self.top {
+            dict.insert("top".into(), top.into_value());
+        }
+        dict.into_value()
+    },
+    mut dict: Dict => {
+        let header = dict.take("header").ok().map(Value::cast).transpose()?;
+        let footer = dict.take("footer").ok().map(Value::cast).transpose()?;
+        let top = dict.take("top").ok().map(Value::cast).transpose()?;
+        dict.finish(&["header", "footer", "top"])?;
+        Self { header, footer, top }
+    },
+}
+
+/// Find the physical numbers of the pages that begin with a level-one heading
+/// in the previous layout iteration, that is, whose heading sits at one of the
+/// given top margins.
+fn find_openers(vt: &Vt, tops: [Abs; 2]) -> Vec<NonZeroUsize> {
+    vt.introspector
+        .query(&HeadingElem::func().select())
+        .iter()
+        .filter(|elem| {
+            elem.to::<HeadingElem>()
+                .map_or(false, |heading| heading.level(StyleChain::default()).get() == 1)
+        })
+        .filter_map(|elem| {
+            let position = vt.introspector.position(elem.location()?);
+            tops.iter()
+                .any(|&top| position.point.y.approx_eq(top))
+                .then_some(position.page)
+        })
+        .collect()
+}
+
+/// Enlarge a page by its bleed and, if requested, room for crop marks around
+/// it.
+fn enlarge(frame: &mut Frame, bleed: Abs, marks: bool) {
+    // The length of crop marks and their distance from the trim box.
+    let length = Abs::pt(12.0);
+    let offset = if marks { bleed.max(Abs::pt(3.0)) } else { Abs::zero() };
+    let trim = if marks { offset + length } else { bleed };
+
+    let size = frame.size();
+    frame.set_size(size + Size::splat(2.0 * trim));
+    frame.translate(Point::splat(trim));
+    frame.push(
+        Point::zero(),
+        FrameItem::Meta(Meta::PrintBoxes { trim, bleed }, Size::zero()),
+    );
+
+    if !marks {
+        return;
+    }
+
+    let stroke = Stroke { thickness: Abs::pt(0.25), ..Stroke::default() };
+    for (x, dx) in [(trim, -1.0), (trim + size.x, 1.0)] {
+        for (y, dy) in [(trim, -1.0), (trim + size.y, 1.0)] {
+            for (start, delta) in [
+                (Point::new(x + dx * offset, y), Point::with_x(dx * length)),
+                (Point::new(x, y + dy * offset), Point::with_y(dy * length)),
+            ] {
+                let shape = Geometry::Line(delta).stroked(stroke.clone());
+                frame.push(start, FrameItem::Shape(shape, Span::detached()));
+            }
+        }
+    }
+}
+
+/// Draw the lines of a baseline grid into the text area of a page.
+fn draw_grid(frame: &mut Frame, margin: Sides<Abs>, step: Abs) {
+    if step <= Abs::zero() {
+        return;
+    }
+
+    let size = frame.size();
+    let line = Geometry::Line(Point::with_x(size.x - margin.left - margin.right));
+    let stroke = Stroke {
+        paint: Color::AQUA.into(),
+        thickness: Abs::pt(0.25),
+        ..Stroke::default()
+    };
+
+    let mut y = margin.top + step;
+    while y <= size.y - margin.bottom {
+        let shape = line.clone().stroked(stroke.clone());
+        frame.push(Point::new(margin.left, y), FrameItem::Shape(shape, Span::detached()));
+        y += step;
+    }
+}
+
+/// A grid onto which the baselines of text lines are snapped.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct BaselineGrid {
+    /// The distance between two lines of the grid.
+    pub step: Length,
+    /// Whether to draw the grid's lines onto the page.
+    pub debug: bool,
+}
+
+cast! {
+    BaselineGrid,
+    self => if self.debug {
+        dict! { "step" => self.step, "debug" => self.debug }.into_value()
+    } else {
+        self.step.into_value()
+    },
+    step: Length => Self { step, debug: false },
+    mut dict: Dict => {
+        let step = dict.take("step")?.cast()?;
+        let debug = dict.take("debug").ok().map(Value::cast).transpose()?.unwrap_or(false);
+        dict.finish(&["step", "debug"])?;
+        Self { step, debug }
+    },
+}
+
+/// Specification of the page's margins.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct Margin {
+    /// The margins for each side.
+    pub sides: Sides<Option<Smart<Rel<Length>>>>,
+    /// Whether to swap `left` and `right` to make them `inside` and `outside`
+    /// (when to swap depends on the binding).
+    pub two_sided: Option<bool>,
+}
+
+impl Margin {
+    /// Create an instance with four equal components.
+    pub fn splat(value: Option<Smart<Rel<Length>>>) -> Self {
+        Self { sides: Sides::splat(value), two_sided: None }
+    }
+}
+
+impl Fold for Margin {
+    type Output = Margin;
+
+    fn fold(self, outer: Self::Output) -> Self::Output {
+        let sides = self.sides.zip(outer.sides).map(|(inner, outer)| inner.or(outer));
+        let two_sided = self.two_sided.or(outer.two_sided);
+        Margin { sides, two_sided }
+    }
+}
+
+cast! {
+    Margin,
+    self => {
+        let mut dict = Dict::new();
+        let mut handle = |key: &str, component: Value| {
+            let value = component.into_value();
+            if value != Value::None {
+                dict.insert(key.into(), value);
+            }
+        };
+
+        handle("top", self.sides.top.into_value());
+        handle("bottom", self.sides.bottom.into_value());
+        if self.two_sided.unwrap_or(false) {
+            handle("inside", self.sides.left.into_value());
+            handle("outside", self.sides.right.into_value());
+        } else {
+            handle("left", self.sides.left.into_value());
+            handle("right", self.sides.right.into_value());
+        }
+
+        Value::Dict(dict)
+    },
+    _: AutoValue => Self::splat(Some(Smart::Auto)),
+    v: Rel<Length> => Self::splat(Some(Smart::Custom(v))),
+    mut dict: Dict => {
+        let mut take = |key| dict.take(key).ok().map(Value::cast).transpose();
+
+        let rest = take("rest")?;
+        let x = take("x")?.or(rest);
+        let y = take("y")?.or(rest);
+        let top = take("top")?.or(y);
+        let bottom = take("bottom")?.or(y);
+        let outside = take("outside")?;
+        let inside = take("inside")?;
+        let left = take("left")?;
+        let right = take("right")?;
+
+        let implicitly_two_sided = outside.is_some() || inside.is_some();
+        let implicitly_not_two_sided = left.is_some() || right.is_some();
+        if implicitly_two_sided && implicitly_not_two_sided {
+            bail!("`inside` and `outside` are mutually exclusive with `left` and `right`");
+        }
+
+        // If neither is set, the lateral margins are not affected and the
+        // previous choice of sidedness is kept.
+        let two_sided = (implicitly_two_sided || implicitly_not_two_sided)
+            .then_some(implicitly_two_sided);
+
+        dict.finish(&[
+            "left", "top", "right", "bottom", "outside", "inside", "x", "y", "rest",
+        ])?;
+
+        Margin {
+            sides: Sides {
+                left: inside.or(left).or(x),
+                top,
+                right: outside.or(right).or(x),
+                bottom,
+            },
+            two_sided,
+        }
+    }
+}
+
+/// Specification of the page's binding.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Binding {
+    /// Bound on the left, as customary in LTR languages.
+    Left,
+    /// Bound on the right, as customary in RTL languages.
+    Right,
+}
+
+impl Binding {
+    /// Whether to swap left and right margin for the page with this number.
+    fn swap(self, number: NonZeroUsize) -> bool {
+        match self {
+            // Left-bound must swap on even pages
+            // (because it is correct on the first page).
+            Self::Left => number.get() % 2 == 0,
+            // Right-bound must swap on odd pages
+            // (because it is wrong on the first page).
+            Self::Right => number.get() % 2 == 1,
+        }
+    }
+}
+
+cast! {
+    Binding,
+    self => match self {
+        Self::Left => GenAlign::Specific(Align::Left).into_value(),
+        Self::Right => GenAlign::Specific(Align::Right).into_value(),
+    },
+    v: GenAlign => match v {
+        GenAlign::Specific(Align::Left) => Self::Left,
+        GenAlign::Specific(Align::Right) => Self::Right,
+        _ => bail!("must be `left` or `right`"),
+    },
+}
+
 /// A manual page break.
 ///
 /// Must not be used inside any containers.
@@ -427,6 +956,21 @@ impl PageElem {
 /// In 1984, the first ...
 /// ```
 ///
+/// ## Restarting page numbers { #restarting }
+/// A page break can start a new section of the document with its own page
+/// numbering. When a `numbering` is given, the [page counter]($func/counter)
+/// is reset to one and the pages following the break are numbered with the
+/// given pattern. References to these pages display their number in the same
+/// way.
+///
+/// ```example
+/// >>> #set page(height: 100pt)
+/// #set page(numbering: "i")
+/// = Preface
+/// #pagebreak(to: "odd", numbering: "1")
+/// = Introduction
+/// ```
+///
 /// Display: Page Break
 /// Category: layout
 #[element]
@@ -435,6 +979,44 @@ pub struct PagebreakElem {
     /// empty.
     #[default(false)]
     pub weak: bool,
+
+    /// If given, ensures that the next page will be an even/odd page, with an
+    /// empty page in between if necessary.
+    ///
+    /// ```example
+    /// #set page(height: 30pt)
+    ///
+    /// First.
+    /// #pagebreak(to: "odd")
+    /// Third.
+    /// ```
+    pub to: Option<Parity>,
+
+    /// If given, restarts the page counter at one and numbers the following
+    /// pages with this pattern.
+    ///
+    /// The numbering stays in effect until another page break changes it or a
+    /// later [page set rule]($func/page.numbering) sets a different one.
+    pub numbering: Option<Numbering>,
+}
+
+/// Whether something should be even or odd.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Parity {
+    /// Next page will be an even page.
+    Even,
+    /// Next page will be an odd page.
+    Odd,
+}
+
+impl Parity {
+    /// Whether the given number matches the parity.
+    pub fn matches(self, number: usize) -> bool {
+        match self {
+            Self::Even => number % 2 == 0,
+            Self::Odd => number % 2 == 1,
+        }
+    }
 }
 
 /// A header, footer, foreground or background definition.
@@ -442,16 +1024,19 @@ pub struct PagebreakElem {
 pub enum Marginal {
     /// Bare content.
     Content(Content),
-    /// A closure mapping from a page number to content.
+    /// A closure mapping from the page's dimensions to content.
     Func(Func),
 }
 
 impl Marginal {
-    /// Resolve the marginal based on the page number.
-    pub fn resolve(&self, vt: &mut Vt, page: usize) -> SourceResult<Content> {
+    /// Resolve the marginal based on the page's dimensions.
+    pub fn resolve(&self, vt: &mut Vt, size: Size) -> SourceResult<Content> {
         Ok(match self {
             Self::Content(content) => content.clone(),
-            Self::Func(func) => func.call_vt(vt, [page])?.display(),
+            Self::Func(func) => {
+                let dims = dict! { "width" => size.x, "height" => size.y };
+                func.call_vt(vt, [dims])?.display()
+            }
         })
     }
 }
@@ -8,7 +8,7 @@ use typst::eval::Tracer;
 use unicode_bidi::{BidiInfo, Level as BidiLevel};
 use unicode_script::{Script, UnicodeScript};
 
-use super::{BoxElem, HElem, Sizing, Spacing};
+use super::{BoxElem, HElem, Sizing, Spacing, TabElem};
 use crate::layout::AlignElem;
 use crate::math::EquationElem;
 use crate::prelude::*;
@@ -52,6 +52,26 @@ pub struct ParElem {
     #[default(Em::new(0.65).into())]
     pub leading: Length,
 
+    /// How the height of a line, to which the `leading` is added, is
+    /// determined.
+    ///
+    /// By default, a line extends from the [top edge]($func/text.top-edge) to
+    /// the [bottom edge]($func/text.bottom-edge) of its text and the leading
+    /// is a fixed gap between the lines. In `{"normal"}` mode, a line extends
+    /// over the maximal ascent and descent of the fonts used on it instead,
+    /// similar to CSS's `line-height: normal`. This leads to more even
+    /// spacing when a line mixes different font sizes. In both modes, inline
+    /// content like formulas and boxes extends the line as needed.
+    ///
+    /// ```example
+    /// #set par(leading-mode: "normal", leading: 0.3em)
+    /// Text with a #text(2em)[large] run
+    /// in the middle of it. And more
+    /// text to fill a few lines.
+    /// ```
+    #[default(LeadingMode::Fixed)]
+    pub leading_mode: LeadingMode,
+
     /// Whether to justify text in its line.
     ///
     /// Hyphenation will be enabled for justified paragraphs if the [text
@@ -90,22 +110,180 @@ pub struct ParElem {
     #[default]
     pub linebreaks: Smart<Linebreaks>,
 
+    /// How much an inter-word space may stretch in a justified line, relative
+    /// to its natural width.
+    ///
+    /// Lines that need to stretch their spaces beyond this limit are
+    /// considered loose and are avoided by the optimized line breaker. Lower
+    /// values thus lead to more even spacing at the expense of more
+    /// hyphenation. If a loose line can't be avoided, its spaces are stretched
+    /// further nonetheless.
+    ///
+    /// ```example
+    /// #set page(width: 120pt)
+    /// #set par(justify: true)
+    /// #set text(hyphenate: false)
+    /// A narrow column tends to
+    /// produce rivers of white space.
+    ///
+    /// #set par(word-stretch: 20%)
+    /// A narrow column tends to
+    /// produce rivers of white space.
+    /// ```
+    #[default(Ratio::new(0.5))]
+    pub word_stretch: Ratio,
+
+    /// How much an inter-word space may shrink in a justified line, relative
+    /// to its natural width.
+    #[default(Ratio::new(1.0 / 3.0))]
+    pub word_shrink: Ratio,
+
+    /// How much glyphs may be scaled horizontally to justify a line.
+    ///
+    /// Slightly widening or narrowing the glyphs of a line, in addition to
+    /// its spaces, allows for a tighter and more even setting. This is also
+    /// known as _font expansion._ Values beyond a few percent will distort
+    /// the glyphs visibly. By default, glyphs are not scaled.
+    ///
+    /// ```example
+    /// #set page(width: 120pt)
+    /// #set par(justify: true)
+    /// #set text(hyphenate: false)
+    /// A narrow column tends to
+    /// produce rivers of white space.
+    ///
+    /// #set par(glyph-scale: 3%)
+    /// A narrow column tends to
+    /// produce rivers of white space.
+    /// ```
+    #[default(Ratio::zero())]
+    pub glyph_scale: Ratio,
+
     /// The indent the first line of a paragraph should have.
     ///
-    /// Only the first line of a consecutive paragraph will be indented (not
-    /// the first one in a block or on the page).
+    /// By default, only the first line of a consecutive paragraph will be
+    /// indented (not the first one in a block or on the page, or the one
+    /// after a heading, figure, or other block-level element). To indent all
+    /// paragraphs, pass a dictionary with the `amount` and `{all: true}`.
     ///
     /// By typographic convention, paragraph breaks are indicated either by some
     /// space between paragraphs or by indented first lines. Consider reducing
     /// the [paragraph spacing]($func/block.spacing) to the [`leading`] when
     /// using this property (e.g. using
     /// `[#show par: set block(spacing: 0.65em)]`).
-    pub first_line_indent: Length,
+    ///
+    /// ```example
+    /// #set par(first-line-indent: 1em)
+    /// = Introduction
+    /// Not indented.
+    ///
+    /// Indented.
+    ///
+    /// #set par(first-line-indent: (amount: 1em, all: true))
+    /// = Background
+    /// Indented, too.
+    /// ```
+    pub first_line_indent: FirstLineIndent,
 
     /// The indent all but the first line of a paragraph should have.
     #[resolve]
     pub hanging_indent: Length,
 
+    /// Whether punctuation at the edges of lines should hang into the margins.
+    ///
+    /// Glyphs like quotes, hyphens and periods are visually lighter than
+    /// letters. Shifting them partially outside of the text block makes the
+    /// paragraph's edges look straighter. This is also known as _optical
+    /// margin alignment._ It works at both edges of a line and for both
+    /// left-to-right and right-to-left text.
+    ///
+    /// When set to `{true}`, sensible default amounts are used. To override
+    /// them, pass a dictionary that maps single characters to the ratio of
+    /// their width that should hang into the margin. When disabled, only
+    /// some punctuation at the end of lines hangs into the margin, as
+    /// determined by the [text overhang]($func/text.overhang) property, which
+    /// also turns off optical alignment for text it is disabled for.
+    ///
+    /// ```example
+    /// #set page(width: 160pt)
+    /// #set par(justify: true)
+    /// "Hanging quotes and periods
+    /// straighten the edges."
+    ///
+    /// #set par(optical-alignment: true)
+    /// "Hanging quotes and periods
+    /// straighten the edges."
+    ///
+    /// #set par(optical-alignment: ("\"": 100%))
+    /// "Hanging quotes and periods
+    /// straighten the edges."
+    /// ```
+    #[default]
+    pub optical_alignment: OpticalAlignment,
+
+    /// The tab stops [tabs]($func/tab) advance to.
+    ///
+    /// A stop can be given as a length, in which case the text after the tab
+    /// starts at the stop. Alternatively, a dictionary with the keys `pos`
+    /// and `align` can be given. The alignment is one of `{"left"}`,
+    /// `{"center"}`, `{"right"}` and `{"decimal"}` and determines how the text
+    /// between the tab and the next one (or the end of the line) is aligned
+    /// at the stop. Decimal stops align the first occurrence of the
+    /// `separator` (by default `{"."}`) at the stop and behave like right
+    /// stops for text without it.
+    ///
+    /// The positions are measured from the start of the line. If there is no
+    /// stop after the current position, a tab produces no space.
+    ///
+    /// ```example
+    /// #set par(tab-stops: (
+    ///   2.5cm,
+    ///   (pos: 5cm, align: "decimal"),
+    /// ))
+    ///
+    /// Coffee #tab() small #tab() 2.50 \
+    /// Cake #tab() large #tab() 12.75 \
+    /// Water #tab() free #tab() 0
+    /// ```
+    pub tab_stops: Vec<TabStop>,
+
+    /// The minimum number of lines at the start of a paragraph that must
+    /// stay together when the paragraph is broken across pages or columns.
+    ///
+    /// A single line at the start of a paragraph that is left behind at the
+    /// bottom of a page is called an _orphan._ By default, such a line is
+    /// moved to the next page instead. If the paragraph is too short to
+    /// satisfy both this and the [`widows`]($func/par.widows) setting, it is
+    /// kept together entirely. Set both to `{1}` to allow breaking paragraphs
+    /// anywhere.
+    ///
+    /// The constraint is dropped if the lines don't fit into the next page or
+    /// column either, so that a very large paragraph is still broken.
+    ///
+    /// ```example
+    /// #set page(height: 80pt)
+    /// #set par(orphans: 3)
+    /// #lorem(10)
+    ///
+    /// #lorem(20)
+    /// ```
+    #[default(NonZeroUsize::new(2).unwrap())]
+    pub orphans: NonZeroUsize,
+
+    /// The minimum number of lines at the end of a paragraph that must stay
+    /// together when the paragraph is broken across pages or columns.
+    ///
+    /// A single line at the end of a paragraph that is carried over to the
+    /// top of the next page is called a _widow._ By default, the line before
+    /// it is carried over as well.
+    #[default(NonZeroUsize::new(2).unwrap())]
+    pub widows: NonZeroUsize,
+
+    /// The level of the heading that the paragraph forms, if any. Such a
+    /// paragraph is tagged as a heading in the document's structure.
+    #[internal]
+    pub heading: Option<NonZeroUsize>,
+
     /// The contents of the paragraph.
     #[external]
     #[required]
@@ -135,6 +313,7 @@ impl Construct for ParElem {
 impl ParElem {
     /// Layout the paragraph into a collection of lines.
     #[tracing::instrument(name = "ParElement::layout", skip_all)]
+    #[allow(clippy::too_many_arguments)]
     pub fn layout(
         &self,
         vt: &mut Vt,
@@ -142,6 +321,7 @@ impl ParElem {
         consecutive: bool,
         region: Size,
         expand: bool,
+        exclusion: Exclusion,
     ) -> SourceResult<Fragment> {
         #[comemo::memoize]
         #[allow(clippy::too_many_arguments)]
@@ -155,6 +335,7 @@ impl ParElem {
             consecutive: bool,
             region: Size,
             expand: bool,
+            exclusion: Exclusion,
         ) -> SourceResult<Fragment> {
             let mut locator = Locator::chained(locator);
             let mut vt = Vt { world, tracer, locator: &mut locator, introspector };
@@ -166,7 +347,9 @@ impl ParElem {
             // Perform BiDi analysis and then prepare paragraph layout by building a
             // representation on which we can do line breaking without layouting
             // each and every line from scratch.
-            let p = prepare(&mut vt, &children, &text, segments, spans, styles, region)?;
+            let p = prepare(
+                &mut vt, &children, &text, segments, spans, styles, region, exclusion,
+            )?;
 
             // Break the paragraph into lines.
             let lines = linebreak(&vt, &p, region.x - p.hang);
@@ -175,7 +358,7 @@ impl ParElem {
             finalize(&mut vt, &p, &lines, region, expand)
         }
 
-        let fragment = cached(
+        let mut fragment = cached(
             self,
             vt.world,
             TrackedMut::reborrow_mut(&mut vt.tracer),
@@ -185,13 +368,157 @@ impl ParElem {
             consecutive,
             region,
             expand,
+            exclusion,
         )?;
 
         vt.locator.visit_frames(&fragment);
+        fragment.tag(match Self::heading_in(styles) {
+            Some(level) => Tag::Heading(level),
+            None => Tag::Paragraph,
+        });
         Ok(fragment)
     }
 }
 
+/// Space at the sides of a paragraph's first lines that is kept free, for
+/// example for content that the text flows around.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Exclusion {
+    /// How many lines are affected.
+    pub lines: usize,
+    /// The space to keep free to the left of the lines.
+    pub left: Abs,
+    /// The space to keep free to the right of the lines.
+    pub right: Abs,
+}
+
+/// The indent of the first line of paragraphs.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FirstLineIndent {
+    /// The amount of indent.
+    pub amount: Length,
+    /// Whether to indent all paragraphs instead of just consecutive ones.
+    pub all: bool,
+}
+
+cast! {
+    FirstLineIndent,
+    self => if self.all {
+        dict! { "amount" => self.amount, "all" => self.all }.into_value()
+    } else {
+        self.amount.into_value()
+    },
+    amount: Length => Self { amount, all: false },
+    mut dict: Dict => {
+        let amount = dict.take("amount")?.cast()?;
+        let all = dict.take("all").ok().map(Value::cast).transpose()?.unwrap_or(false);
+        dict.finish(&["amount", "all"])?;
+        Self { amount, all }
+    },
+}
+
+/// Which glyphs hang into the margins of a paragraph's lines.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct OpticalAlignment {
+    /// Whether optical alignment is enabled.
+    pub enabled: bool,
+    /// Custom amounts for individual characters, relative to their width.
+    pub amounts: Vec<(char, Ratio)>,
+}
+
+impl OpticalAlignment {
+    /// How much a character should hang into the margin, relative to its
+    /// width.
+    fn amount(&self, c: char) -> f64 {
+        self.amounts
+            .iter()
+            .rev()
+            .find(|&&(k, _)| k == c)
+            .map_or_else(|| protrusion(c), |&(_, amount)| amount.get())
+    }
+}
+
+cast! {
+    OpticalAlignment,
+    self => if self.amounts.is_empty() {
+        self.enabled.into_value()
+    } else {
+        self.amounts
+            .into_iter()
+            .map(|(c, amount)| (c.into(), amount.into_value()))
+            .collect::<Dict>()
+            .into_value()
+    },
+    enabled: bool => Self { enabled, amounts: vec![] },
+    dict: Dict => Self {
+        enabled: true,
+        amounts: dict
+            .into_iter()
+            .map(|(key, value)| {
+                let mut chars = key.chars();
+                let (Some(c), None) = (chars.next(), chars.next()) else {
+                    bail!("expected a single character as key, found {key:?}");
+                };
+                Ok((c, value.cast()?))
+            })
+            .collect::<StrResult<_>>()?,
+    },
+}
+
+/// A position in a paragraph's lines that [tabs]($func/tab) advance to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TabStop {
+    /// The position of the stop, measured from the start of the line.
+    pub pos: Length,
+    /// How the text after the tab is aligned at the stop.
+    pub align: TabAlign,
+    /// The character that is aligned at the stop for decimal alignment.
+    pub separator: char,
+}
+
+cast! {
+    TabStop,
+    self => dict! {
+        "pos" => self.pos,
+        "align" => self.align,
+        "separator" => self.separator,
+    }.into_value(),
+    pos: Length => Self { pos, align: TabAlign::Left, separator: '.' },
+    mut dict: Dict => {
+        let pos = dict.take("pos")?.cast()?;
+        let align = dict.take("align").ok().map(Value::cast).transpose()?;
+        let separator = dict.take("separator").ok().map(Value::cast).transpose()?;
+        dict.finish(&["pos", "align", "separator"])?;
+        Self {
+            pos,
+            align: align.unwrap_or(TabAlign::Left),
+            separator: separator.unwrap_or('.'),
+        }
+    },
+}
+
+/// How text is aligned at a tab stop.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum TabAlign {
+    /// The text starts at the stop.
+    Left,
+    /// The text is centered at the stop.
+    Center,
+    /// The text ends at the stop.
+    Right,
+    /// The decimal separator of the text is at the stop.
+    Decimal,
+}
+
+/// How the height of a paragraph's lines is determined.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum LeadingMode {
+    /// Lines extend from the top edge to the bottom edge of their text.
+    Fixed,
+    /// Lines extend over the maximal ascent and descent of their fonts.
+    Normal,
+}
+
 /// How to determine line breaks in a paragraph.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
 pub enum Linebreaks {
@@ -261,11 +588,67 @@ struct Preparation<'a> {
     align: Align,
     /// Whether to justify the paragraph.
     justify: bool,
+    /// How much glyphs may be scaled horizontally to justify a line.
+    glyph_scale: Ratio,
+    /// Which glyphs hang into the margins.
+    optical: OpticalAlignment,
     /// The paragraph's hanging indent.
     hang: Abs,
+    /// The resolved positions of the tab stops, in ascending order.
+    tabs: Vec<(Abs, TabStop)>,
+    /// The space to keep free at the sides of the first lines.
+    exclusion: Exclusion,
 }
 
 impl<'a> Preparation<'a> {
+    /// The width available to the line with the given index.
+    fn width(&self, index: usize, width: Abs) -> Abs {
+        if index < self.exclusion.lines {
+            width - self.exclusion.left - self.exclusion.right
+        } else {
+            width
+        }
+    }
+
+    /// How far a tab at the horizontal position `x` advances, given the
+    /// items that follow it in the line.
+    fn tab(&self, x: Abs, following: &[&Item]) -> Abs {
+        let Some(&(pos, stop)) = self.tabs.iter().find(|&&(pos, _)| pos > x) else {
+            return Abs::zero();
+        };
+
+        // The text up to the next tab is aligned at the stop.
+        let run = following.iter().take_while(|item| !matches!(item, Item::Tab));
+        let before = match stop.align {
+            TabAlign::Left => Abs::zero(),
+            TabAlign::Center => run.map(|item| item.width()).sum::<Abs>() / 2.0,
+            TabAlign::Right => run.map(|item| item.width()).sum(),
+            TabAlign::Decimal => {
+                let mut width = Abs::zero();
+                for item in run {
+                    let Item::Text(shaped) = item else {
+                        width += item.width();
+                        continue;
+                    };
+
+                    match shaped.glyphs.iter().position(|g| g.c == stop.separator) {
+                        Some(i) => {
+                            width += shaped.glyphs[..i]
+                                .iter()
+                                .map(|g| g.x_advance.at(shaped.size))
+                                .sum::<Abs>();
+                            break;
+                        }
+                        None => width += shaped.width,
+                    }
+                }
+                width
+            }
+        };
+
+        (pos - before - x).max(Abs::zero())
+    }
+
     /// Find the item that contains the given `text_offset`.
     fn find(&self, text_offset: usize) -> Option<&Item<'a>> {
         let mut cursor = 0;
@@ -317,6 +700,8 @@ enum Segment<'a> {
     Text(usize),
     /// Horizontal spacing between other segments.
     Spacing(Spacing),
+    /// An advance to the next tab stop.
+    Tab,
     /// A mathematical equation.
     Equation(&'a EquationElem),
     /// A box with arbitrary content.
@@ -330,7 +715,7 @@ impl Segment<'_> {
     fn len(&self) -> usize {
         match *self {
             Self::Text(len) => len,
-            Self::Spacing(_) => SPACING_REPLACE.len_utf8(),
+            Self::Spacing(_) | Self::Tab => SPACING_REPLACE.len_utf8(),
             Self::Box(_, true) => SPACING_REPLACE.len_utf8(),
             Self::Equation(_) | Self::Box(_, _) => OBJ_REPLACE.len_utf8(),
             Self::Meta => 0,
@@ -347,6 +732,9 @@ enum Item<'a> {
     Absolute(Abs),
     /// Fractional spacing between other items.
     Fractional(Fr, Option<(&'a BoxElem, StyleChain<'a>)>),
+    /// An advance to the next tab stop, which depends on the position in the
+    /// line.
+    Tab,
     /// Layouted inline-level content.
     Frame(Frame),
     /// Metadata.
@@ -373,7 +761,9 @@ impl<'a> Item<'a> {
     fn len(&self) -> usize {
         match self {
             Self::Text(shaped) => shaped.text.len(),
-            Self::Absolute(_) | Self::Fractional(_, _) => SPACING_REPLACE.len_utf8(),
+            Self::Absolute(_) | Self::Fractional(_, _) | Self::Tab => {
+                SPACING_REPLACE.len_utf8()
+            }
             Self::Frame(_) => OBJ_REPLACE.len_utf8(),
             Self::Meta(_) => 0,
         }
@@ -385,7 +775,7 @@ impl<'a> Item<'a> {
             Self::Text(shaped) => shaped.width,
             Self::Absolute(v) => *v,
             Self::Frame(frame) => frame.width(),
-            Self::Fractional(_, _) | Self::Meta(_) => Abs::zero(),
+            Self::Fractional(_, _) | Self::Tab | Self::Meta(_) => Abs::zero(),
         }
     }
 }
@@ -513,6 +903,11 @@ impl<'a> Line<'a> {
         self.items().filter_map(Item::text).map(|s| s.shrinkability()).sum()
     }
 
+    /// How much can the line stretch or shrink by scaling its glyphs.
+    fn expandability(&self, scale: Ratio) -> Abs {
+        self.items().filter_map(Item::text).map(|s| scale.of(s.width)).sum()
+    }
+
     /// The sum of fractions in the line.
     fn fr(&self) -> Fr {
         self.items()
@@ -539,13 +934,13 @@ fn collect<'a>(
     let mut iter = children.iter().peekable();
 
     let first_line_indent = ParElem::first_line_indent_in(*styles);
-    if !first_line_indent.is_zero()
-        && consecutive
+    if !first_line_indent.amount.is_zero()
+        && (consecutive || first_line_indent.all)
         && AlignElem::alignment_in(*styles).x.resolve(*styles)
             == TextElem::dir_in(*styles).start().into()
     {
         full.push(SPACING_REPLACE);
-        segments.push((Segment::Spacing(first_line_indent.into()), *styles));
+        segments.push((Segment::Spacing(first_line_indent.amount.into()), *styles));
     }
 
     let hang = ParElem::hanging_indent_in(*styles);
@@ -580,6 +975,9 @@ fn collect<'a>(
 
             full.push(SPACING_REPLACE);
             Segment::Spacing(elem.amount())
+        } else if child.is::<TabElem>() {
+            full.push(SPACING_REPLACE);
+            Segment::Tab
         } else if let Some(elem) = child.to::<LinebreakElem>() {
             let c = if elem.justify(styles) { '\u{2028}' } else { '\n' };
             full.push(c);
@@ -600,7 +998,10 @@ fn collect<'a>(
                         elem.text().chars().next()
                     } else if child.is::<SmartQuoteElem>() {
                         Some('"')
-                    } else if child.is::<SpaceElem>() || child.is::<HElem>() {
+                    } else if child.is::<SpaceElem>()
+                        || child.is::<HElem>()
+                        || child.is::<TabElem>()
+                    {
                         Some(SPACING_REPLACE)
                     } else {
                         Some(OBJ_REPLACE)
@@ -648,6 +1049,7 @@ fn collect<'a>(
 
 /// Prepare paragraph layout by shaping the whole paragraph and layouting all
 /// contained inline-level content.
+#[allow(clippy::too_many_arguments)]
 fn prepare<'a>(
     vt: &mut Vt,
     children: &'a [Content],
@@ -656,6 +1058,7 @@ fn prepare<'a>(
     spans: SpanMapper,
     styles: StyleChain<'a>,
     region: Size,
+    exclusion: Exclusion,
 ) -> SourceResult<Preparation<'a>> {
     let bidi = BidiInfo::new(
         text,
@@ -685,6 +1088,7 @@ fn prepare<'a>(
                     items.push(Item::Fractional(v, None));
                 }
             },
+            Segment::Tab => items.push(Item::Tab),
             Segment::Equation(equation) => {
                 let pod = Regions::one(region, Axes::splat(false));
                 let mut frame = equation.layout(vt, styles, pod)?.into_frame();
@@ -720,7 +1124,18 @@ fn prepare<'a>(
         lang: shared_get(styles, children, TextElem::lang_in),
         align: AlignElem::alignment_in(styles).x.resolve(styles),
         justify: ParElem::justify_in(styles),
+        glyph_scale: ParElem::glyph_scale_in(styles),
+        optical: ParElem::optical_alignment_in(styles),
         hang: ParElem::hanging_indent_in(styles),
+        tabs: {
+            let mut tabs: Vec<_> = ParElem::tab_stops_in(styles)
+                .into_iter()
+                .map(|stop| (stop.pos.resolve(styles), stop))
+                .collect();
+            tabs.sort_by_key(|&(pos, _)| pos);
+            tabs
+        },
+        exclusion,
     })
 }
 
@@ -821,14 +1236,14 @@ fn linebreak_simple<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<Line
     let mut start = 0;
     let mut last = None;
 
-    for (end, mandatory, hyphen) in breakpoints(p) {
+    for (end, mandatory, hyphen, _) in breakpoints(p) {
         // Compute the line and its size.
         let mut attempt = line(vt, p, start..end, mandatory, hyphen);
 
         // If the line doesn't fit anymore, we push the last fitting attempt
         // into the stack and rebuild the line from the attempt's end. The
         // resulting line cannot be broken up further.
-        if !width.fits(attempt.width) {
+        if !p.width(lines.len(), width).fits(attempt.width) {
             if let Some((last_attempt, last_end)) = last.take() {
                 lines.push(last_attempt);
                 start = last_end;
@@ -839,7 +1254,7 @@ fn linebreak_simple<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<Line
         // Finish the current line if there is a mandatory line break (i.e.
         // due to "\n") or if the line doesn't fit horizontally already
         // since then no shorter line will be possible.
-        if mandatory || !width.fits(attempt.width) {
+        if mandatory || !p.width(lines.len(), width).fits(attempt.width) {
             lines.push(attempt);
             start = end;
             last = None;
@@ -881,6 +1296,7 @@ fn linebreak_optimized<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<L
         pred: usize,
         total: Cost,
         line: Line<'a>,
+        index: usize,
     }
 
     // Cost parameters.
@@ -895,11 +1311,12 @@ fn linebreak_optimized<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<L
         pred: 0,
         total: 0.0,
         line: line(vt, p, 0..0, false, false),
+        index: 0,
     }];
 
     let em = TextElem::size_in(p.styles);
 
-    for (end, mandatory, hyphen) in breakpoints(p) {
+    for (end, mandatory, hyphen, penalty) in breakpoints(p) {
         let k = table.len();
         let eof = end == p.bidi.text.len();
         let mut best: Option<Entry> = None;
@@ -912,12 +1329,17 @@ fn linebreak_optimized<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<L
 
             // Determine how much the line's spaces would need to be stretched
             // to make it the desired width.
-            let delta = width - attempt.width;
+            let delta = p.width(pred.index, width) - attempt.width;
             // Determine how much stretch are permitted.
+            let expand = if p.justify {
+                attempt.expandability(p.glyph_scale)
+            } else {
+                Abs::zero()
+            };
             let adjust = if delta >= Abs::zero() {
-                attempt.stretchability()
+                attempt.stretchability() + expand
             } else {
-                attempt.shrinkability()
+                attempt.shrinkability() + expand
             };
             // Ideally, the ratio should between -1.0 and 1.0, but sometimes a value above 1.0
             // is possible, in which case the line is underfull.
@@ -961,10 +1383,13 @@ fn linebreak_optimized<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<L
                 ratio.powi(3).abs()
             };
 
-            // Penalize hyphens.
+            // Penalize hyphens and apply custom penalties.
             if hyphen {
                 cost += HYPH_COST;
             }
+            if penalty > 0.0 {
+                cost += penalty / 100.0;
+            }
 
             // In Knuth paper, cost = (1 + 100|r|^3 + p)^2 + a,
             // where r is the ratio, p=50 is penaty, and a=3000 is consecutive penaty.
@@ -972,6 +1397,11 @@ fn linebreak_optimized<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<L
             // where p=0.5 and a=300
             cost = (0.01 + cost).powi(2);
 
+            // Negative penalties reward a break, as in the Knuth paper.
+            if penalty < 0.0 {
+                cost -= (penalty / 100.0).powi(2);
+            }
+
             // Penalize two consecutive dashes (not necessarily hyphens) extra.
             if attempt.dash && pred.line.dash {
                 cost += CONSECUTIVE_DASH_COST;
@@ -982,7 +1412,12 @@ fn linebreak_optimized<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<L
 
             // If this attempt is better than what we had before, take it!
             if best.as_ref().map_or(true, |best| best.total >= total) {
-                best = Some(Entry { pred: i, total, line: attempt });
+                best = Some(Entry {
+                    pred: i,
+                    total,
+                    line: attempt,
+                    index: pred.index + 1,
+                });
             }
         }
 
@@ -1067,6 +1502,7 @@ fn breakpoints<'a>(p: &'a Preparation<'a>) -> Breakpoints<'a> {
         suffix: 0,
         end: 0,
         mandatory: false,
+        penalty: 0.0,
     }
 }
 
@@ -1076,8 +1512,8 @@ struct Breakpoints<'a> {
     p: &'a Preparation<'a>,
     /// The inner iterator over the unicode line break opportunities.
     linebreaks: LineBreakIteratorUtf8<'a, 'a>,
-    /// Iterator over syllables of the current word.
-    syllables: Option<hypher::Syllables<'a>>,
+    /// Iterator over the byte lengths of the syllables of the current word.
+    syllables: Option<Syllables<'a>>,
     /// The current text offset.
     offset: usize,
     /// The trimmed end of the current word.
@@ -1086,15 +1522,17 @@ struct Breakpoints<'a> {
     end: usize,
     /// Whether the break after the current word is mandatory.
     mandatory: bool,
+    /// The custom penalty for the break after the current word.
+    penalty: f64,
 }
 
 impl Iterator for Breakpoints<'_> {
-    type Item = (usize, bool, bool);
+    type Item = (usize, bool, bool, f64);
 
     fn next(&mut self) -> Option<Self::Item> {
         // If we're currently in a hyphenated "word", process the next syllable.
         if let Some(syllable) = self.syllables.as_mut().and_then(Iterator::next) {
-            self.offset += syllable.len();
+            self.offset += syllable;
             if self.offset == self.suffix {
                 self.offset = self.end;
             }
@@ -1106,7 +1544,14 @@ impl Iterator for Breakpoints<'_> {
                 return self.next();
             }
 
-            return Some((self.offset, self.mandatory && !hyphen, hyphen));
+            // Skip the break after the word if it is forbidden.
+            if !hyphen && self.penalty == f64::INFINITY {
+                self.syllables = None;
+                return self.next();
+            }
+
+            let penalty = if hyphen { 0.0 } else { self.penalty };
+            return Some((self.offset, self.mandatory && !hyphen, hyphen, penalty));
         }
 
         let lb = LINEBREAK_DATA.as_borrowed();
@@ -1123,22 +1568,34 @@ impl Iterator for Breakpoints<'_> {
                         | LineBreak::NextLine
                 ) || self.end == self.p.bidi.text.len()
             });
+        self.penalty = self.penalty(self.offset).unwrap_or_default();
 
         // Hyphenate the next word.
         if self.p.hyphenate != Some(false) {
-            if let Some(lang) = self.lang(self.offset) {
-                let word = &self.p.bidi.text[self.offset..self.end];
-                let trimmed = word.trim_end_matches(|c: char| !c.is_alphabetic());
-                if !trimmed.is_empty() {
+            let word = &self.p.bidi.text[self.offset..self.end];
+            let trimmed = word.trim_end_matches(|c: char| !c.is_alphabetic());
+            if !trimmed.is_empty() {
+                let syllables = match self.exception(self.offset, trimmed) {
+                    Some(lengths) => Some(Syllables::Exception(lengths.into_iter())),
+                    None => self.lang(self.offset).map(|lang| {
+                        Syllables::Patterns(hypher::hyphenate(trimmed, lang))
+                    }),
+                };
+
+                if syllables.is_some() {
                     self.suffix = self.offset + trimmed.len();
-                    self.syllables = Some(hypher::hyphenate(trimmed, lang));
+                    self.syllables = syllables;
                     return self.next();
                 }
             }
         }
 
         self.offset = self.end;
-        Some((self.end, self.mandatory, false))
+        if self.penalty == f64::INFINITY {
+            return self.next();
+        }
+
+        Some((self.end, self.mandatory, false, self.penalty))
     }
 }
 
@@ -1154,6 +1611,29 @@ impl Breakpoints<'_> {
             .unwrap_or(false)
     }
 
+    /// The syllables of a word starting at the given offset if the word has a
+    /// hyphenation exception.
+    fn exception(&self, offset: usize, word: &str) -> Option<Vec<usize>> {
+        let shaped = self.p.find(offset)?.text()?;
+        let lang = TextElem::lang_in(shaped.styles);
+        TextElem::hyphenate_exceptions_in(shaped.styles).syllables(lang, word)
+    }
+
+    /// The custom penalty for a break after the word starting at the given
+    /// offset.
+    fn penalty(&self, offset: usize) -> Option<f64> {
+        if self.mandatory {
+            return None;
+        }
+
+        let text = self.p.bidi.text;
+        let before = text[..self.end].trim_end().rsplit(char::is_whitespace).next()?;
+        let after = text[self.end..].split(char::is_whitespace).next()?;
+        let shaped = self.p.find(offset)?.text()?;
+        let lang = TextElem::lang_in(shaped.styles);
+        TextElem::break_penalties_in(shaped.styles).get(lang, before, after)
+    }
+
     /// The text language at the given offset.
     fn lang(&self, offset: usize) -> Option<hypher::Lang> {
         let lang = self.p.lang.or_else(|| {
@@ -1166,6 +1646,23 @@ impl Breakpoints<'_> {
     }
 }
 
+/// The syllables of a word, either from hyphenation patterns or an exception.
+enum Syllables<'a> {
+    Patterns(hypher::Syllables<'a>),
+    Exception(std::vec::IntoIter<usize>),
+}
+
+impl Iterator for Syllables<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Patterns(syllables) => syllables.next().map(str::len),
+            Self::Exception(lengths) => lengths.next(),
+        }
+    }
+}
+
 /// Create a line which spans the given range.
 fn line<'a>(
     vt: &Vt,
@@ -1295,6 +1792,21 @@ fn line<'a>(
         width += item.width();
     }
 
+    // Tabs advance depending on what precedes and follows them, so lines
+    // containing them are measured item by item. Such lines are not justified
+    // since that would misalign the text at the stops.
+    let items: Vec<_> = first.iter().chain(inner).chain(&last).collect();
+    if items.iter().any(|item| matches!(item, Item::Tab)) {
+        justify = false;
+        width = Abs::zero();
+        for (i, item) in items.iter().enumerate() {
+            width += match item {
+                Item::Tab => p.tab(width, &items[i + 1..]),
+                _ => item.width(),
+            };
+        }
+    }
+
     Line {
         bidi: &p.bidi,
         trimmed: range,
@@ -1321,54 +1833,49 @@ fn finalize(
     let width = if !region.x.is_finite()
         || (!expand && lines.iter().all(|line| line.fr().is_zero()))
     {
-        p.hang + lines.iter().map(|line| line.width).max().unwrap_or_default()
+        let excluded = p.exclusion.left + p.exclusion.right;
+        p.hang
+            + lines
+                .iter()
+                .enumerate()
+                .map(|(i, line)| {
+                    if i < p.exclusion.lines {
+                        line.width + excluded
+                    } else {
+                        line.width
+                    }
+                })
+                .max()
+                .unwrap_or_default()
     } else {
         region.x
     };
 
-    // Stack the lines into one frame per region.
-    let mut frames: Vec<Frame> = lines
+    // Stack the lines into one frame per region. Orphans and widows are
+    // prevented by the flow.
+    let frames: Vec<Frame> = lines
         .iter()
-        .map(|line| commit(vt, p, line, width, region.y))
+        .enumerate()
+        .map(|(i, line)| commit(vt, p, i, line, width, region.y))
         .collect::<SourceResult<_>>()?;
 
-    // Prevent orphans.
-    let leading = ParElem::leading_in(p.styles);
-    if frames.len() >= 2 && !frames[1].is_empty() {
-        let second = frames.remove(1);
-        let first = &mut frames[0];
-        merge(first, second, leading);
-    }
-
-    // Prevent widows.
-    let len = frames.len();
-    if len >= 2 && !frames[len - 2].is_empty() {
-        let second = frames.pop().unwrap();
-        let first = frames.last_mut().unwrap();
-        merge(first, second, leading);
-    }
-
     Ok(Fragment::frames(frames))
 }
 
-/// Merge two line frames
-fn merge(first: &mut Frame, second: Frame, leading: Abs) {
-    let offset = first.height() + leading;
-    let total = offset + second.height();
-    first.push_frame(Point::with_y(offset), second);
-    first.size_mut().y = total;
-}
-
 /// Commit to a line and build its frame.
 fn commit(
     vt: &mut Vt,
     p: &Preparation,
+    index: usize,
     line: &Line,
     width: Abs,
     full: Abs,
 ) -> SourceResult<Frame> {
-    let mut remaining = width - line.width - p.hang;
+    let mut remaining = p.width(index, width) - line.width - p.hang;
     let mut offset = Abs::zero();
+    if index < p.exclusion.lines {
+        offset += p.exclusion.left;
+    }
 
     // Reorder the line from logical to visual order.
     let (reordered, starts_rtl) = reorder(line);
@@ -1376,30 +1883,40 @@ fn commit(
         offset += p.hang;
     }
 
+    // Determine how much a glyph at the given visual edge of the line hangs
+    // into the margin. Without optical alignment, only glyphs at the end of
+    // the line hang.
+    let hang = |text: &ShapedText, c: char, advance: Em, end: bool| {
+        if !TextElem::overhang_in(text.styles)
+            || (reordered.len() == 1 && text.glyphs.len() == 1)
+        {
+            return Abs::zero();
+        }
+
+        let ratio = if p.optical.enabled {
+            p.optical.amount(c)
+        } else if text.dir.is_positive() == end {
+            overhang(c)
+        } else {
+            0.0
+        };
+
+        ratio * advance.at(text.size)
+    };
+
     // Handle hanging punctuation to the left.
     if let Some(Item::Text(text)) = reordered.first() {
         if let Some(glyph) = text.glyphs.first() {
-            if !text.dir.is_positive()
-                && TextElem::overhang_in(text.styles)
-                && (reordered.len() > 1 || text.glyphs.len() > 1)
-            {
-                let amount = overhang(glyph.c) * glyph.x_advance.at(text.size);
-                offset -= amount;
-                remaining += amount;
-            }
+            let amount = hang(text, glyph.c, glyph.x_advance, false);
+            offset -= amount;
+            remaining += amount;
         }
     }
 
     // Handle hanging punctuation to the right.
     if let Some(Item::Text(text)) = reordered.last() {
         if let Some(glyph) = text.glyphs.last() {
-            if text.dir.is_positive()
-                && TextElem::overhang_in(text.styles)
-                && (reordered.len() > 1 || text.glyphs.len() > 1)
-            {
-                let amount = overhang(glyph.c) * glyph.x_advance.at(text.size);
-                remaining += amount;
-            }
+            remaining += hang(text, glyph.c, glyph.x_advance, true);
         }
     }
 
@@ -1412,8 +1929,11 @@ fn commit(
     let mut justification_ratio = 0.0;
     let mut extra_justification = Abs::zero();
 
-    let shrink = line.shrinkability();
-    let stretch = line.stretchability();
+    // Glyphs are scaled by the same ratio as the spaces are adjusted.
+    let expand = if p.justify { line.expandability(p.glyph_scale) } else { Abs::zero() };
+
+    let shrink = line.shrinkability() + expand;
+    let stretch = line.stretchability() + expand;
     if remaining < Abs::zero() && shrink > Abs::zero() {
         // Attempt to reduce the length of the line, using shrinkability.
         justification_ratio = (remaining / shrink).max(-1.0);
@@ -1438,7 +1958,8 @@ fn commit(
 
     // Build the frames and determine the height and baseline.
     let mut frames = vec![];
-    for item in reordered {
+    let start = offset;
+    for (i, item) in reordered.iter().enumerate() {
         let mut push = |offset: &mut Abs, frame: Frame| {
             let width = frame.width();
             top.set_max(frame.baseline());
@@ -1451,6 +1972,9 @@ fn commit(
             Item::Absolute(v) => {
                 offset += *v;
             }
+            Item::Tab => {
+                offset += p.tab(offset - start, &reordered[i + 1..]);
+            }
             Item::Fractional(v, elem) => {
                 let amount = v.share(fr, remaining);
                 if let Some((elem, styles)) = elem {
@@ -1464,7 +1988,14 @@ fn commit(
                 }
             }
             Item::Text(shaped) => {
-                let frame = shaped.build(vt, justification_ratio, extra_justification);
+                let mut frame =
+                    shaped.build(vt, justification_ratio, extra_justification);
+                if !expand.is_zero() {
+                    scale_glyphs(
+                        &mut frame,
+                        justification_ratio * p.glyph_scale.of(shaped.width),
+                    );
+                }
                 push(&mut offset, frame);
             }
             Item::Frame(frame) | Item::Meta(frame) => {
@@ -1492,6 +2023,16 @@ fn commit(
     Ok(output)
 }
 
+/// Scale the glyphs in a text frame horizontally such that the frame becomes
+/// `amount` wider.
+fn scale_glyphs(frame: &mut Frame, amount: Abs) {
+    let width = frame.width();
+    if width > Abs::zero() {
+        frame.transform(Transform::scale(Ratio::new(1.0 + amount / width), Ratio::one()));
+        frame.size_mut().x += amount;
+    }
+}
+
 /// Return a line's items in visual order.
 fn reorder<'a>(line: &'a Line<'a>) -> (Vec<&Item<'a>>, bool) {
     let mut reordered = vec![];
@@ -1552,3 +2093,15 @@ fn overhang(c: char) -> f64 {
         _ => 0.0,
     }
 }
+
+/// How much a character should hang into either margin with optical
+/// alignment.
+fn protrusion(c: char) -> f64 {
+    match c {
+        // Quotes.
+        '"' | '“' | '”' | '„' | '«' | '»' => 0.5,
+        '\'' | '‘' | '’' | '‚' | '‹' | '›' => 0.7,
+
+        _ => overhang(c),
+    }
+}
@@ -2,10 +2,12 @@ use crate::prelude::*;
 
 /// Place content at an absolute position.
 ///
-/// Placed content will not affect the position of other content. Place is
-/// always relative to its parent container and will be in the foreground of all
-/// other content in the container. Page margins will be respected.
-///
+/// Placed content will not affect the position of other content unless it
+/// [floats](#parameters--float). Place is relative to its parent container by
+/// default and will be in the foreground of all other content in the
+/// container. Page margins will be respected. With a [`scope`](#parameters--scope)
+/// of `{"page"}`, content is instead placed relative to the whole page,
+/// including its margins.
 ///
 /// ## Example { #example }
 /// ```example
@@ -23,7 +25,7 @@ use crate::prelude::*;
 ///
 /// Display: Place
 /// Category: layout
-#[element(Layout, Behave)]
+#[element(Layout, Behave, Locatable, Synthesize)]
 pub struct PlaceElem {
     /// Relative to which position in the parent container to place the content.
     ///
@@ -34,8 +36,71 @@ pub struct PlaceElem {
     #[default(Axes::with_x(Some(GenAlign::Start)))]
     pub alignment: Axes<Option<GenAlign>>,
 
+    /// Relative to which container the content is placed.
+    ///
+    /// ```example
+    /// #set page(height: 80pt, margin: 20pt)
+    /// #place(
+    ///   scope: "page",
+    ///   top + right,
+    ///   square(size: 10pt, fill: aqua),
+    /// )
+    /// The square sits in the page's corner.
+    /// ```
+    #[default(PlacementScope::Column)]
+    pub scope: PlacementScope,
+
+    /// Whether the placed content pushes the surrounding content away.
+    ///
+    /// A floating element must be aligned to the `{top}` or `{bottom}`. It
+    /// stays at its position, but the text of its column starts below it or
+    /// ends above it, respectively.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #place(
+    ///   top + right,
+    ///   float: true,
+    ///   rect(fill: aqua)[Callout],
+    /// )
+    /// #lorem(20)
+    /// ```
+    #[default(false)]
+    pub float: bool,
+
+    /// Whether text flows around the floating content.
+    ///
+    /// A wrapped element must be aligned to the `{left}` or `{right}` (or
+    /// `{start}` or `{end}`) without a vertical alignment. It is placed at
+    /// its position in the text and the lines of the following paragraphs
+    /// are shortened to leave room for it, including the
+    /// [clearance]($func/place.clearance). Below it, lines have their full
+    /// width again. Other blocks, like headings, are moved below it.
+    ///
+    /// ```example
+    /// #set page(height: 150pt)
+    /// #place(
+    ///   left,
+    ///   float: true,
+    ///   wrap: true,
+    ///   clearance: 8pt,
+    ///   rect(width: 40pt, height: 50pt, fill: aqua),
+    /// )
+    /// #lorem(40)
+    /// ```
+    #[default(false)]
+    pub wrap: bool,
+
+    /// The amount of space between a floating element and the surrounding
+    /// content.
+    #[resolve]
+    #[default(Em::new(1.5).into())]
+    pub clearance: Length,
+
     /// The horizontal displacement of the placed content.
     ///
+    /// The displacement is applied after the content was aligned.
+    ///
     /// ```example
     /// #set page(height: 100pt)
     /// #for i in range(16) {
@@ -46,6 +111,8 @@ pub struct PlaceElem {
     pub dx: Rel<Length>,
 
     /// The vertical displacement of the placed content.
+    ///
+    /// The displacement is applied after the content was aligned.
     pub dy: Rel<Length>,
 
     /// The content to place.
@@ -53,6 +120,16 @@ pub struct PlaceElem {
     pub body: Content,
 }
 
+impl Synthesize for PlaceElem {
+    fn synthesize(&mut self, _vt: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        self.push_alignment(self.alignment(styles));
+        self.push_scope(self.scope(styles));
+        self.push_dx(self.dx(styles));
+        self.push_dy(self.dy(styles));
+        Ok(())
+    }
+}
+
 impl Layout for PlaceElem {
     #[tracing::instrument(name = "PlaceElem::layout", skip_all)]
     fn layout(
@@ -61,26 +138,30 @@ impl Layout for PlaceElem {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
-        let out_of_flow = self.out_of_flow(styles);
-
-        // The pod is the base area of the region because for absolute
-        // placement we don't really care about the already used area.
-        let pod = {
-            let finite = regions.base().map(Abs::is_finite);
-            let expand = finite & (regions.expand | out_of_flow);
-            Regions::one(regions.base(), expand)
-        };
-
-        let child = self
-            .body()
-            .moved(Axes::new(self.dx(styles), self.dy(styles)))
-            .aligned(self.alignment(styles));
-
-        let mut frame = child.layout(vt, styles, pod)?.into_frame();
+        // Validate that a floating element is aligned to the top or bottom
+        // or, if text wraps around it, to the left or right.
+        self.float_align(styles)?;
+        self.wrap_side(styles)?;
 
         // If expansion is off, zero all sizes so that we don't take up any
         // space in our parent. Otherwise, respect the expand settings.
         let target = regions.expand.select(regions.size, Size::zero());
+
+        // Page-scoped content is laid out by the page. We only leave a
+        // marker so that the page can find it.
+        if self.scope(styles) == PlacementScope::Page {
+            let mut frame = Frame::new(target);
+            frame.meta(styles, true);
+            return Ok(Fragment::frame(frame));
+        }
+
+        // The pod is the base area of the region because for absolute
+        // placement we don't really care about the already used area.
+        let out_of_flow = self.out_of_flow(styles);
+        let finite = regions.base().map(Abs::is_finite);
+        let expand = finite & (regions.expand | out_of_flow);
+
+        let mut frame = self.place(vt, styles, regions.base(), expand)?;
         frame.resize(target, Align::LEFT_TOP);
 
         Ok(Fragment::frame(frame))
@@ -92,7 +173,87 @@ impl PlaceElem {
     /// base origin. Instead of relative to the parent's current flow/cursor
     /// position.
     pub fn out_of_flow(&self, styles: StyleChain) -> bool {
-        self.alignment(styles).y.is_some()
+        self.alignment(styles).y.is_some() || self.scope(styles) == PlacementScope::Page
+    }
+
+    /// The vertical side a floating element is attached to, if it floats.
+    pub fn float_align(&self, styles: StyleChain) -> SourceResult<Option<Align>> {
+        if !self.float(styles) || self.wrap(styles) {
+            return Ok(None);
+        }
+
+        match self.alignment(styles).y {
+            Some(GenAlign::Specific(align @ (Align::Top | Align::Bottom))) => {
+                Ok(Some(align))
+            }
+            _ => bail!(self.span(), "floating placement must be `top` or `bottom`"),
+        }
+    }
+
+    /// The horizontal side a wrapped element is attached to, if text flows
+    /// around it.
+    pub fn wrap_side(&self, styles: StyleChain) -> SourceResult<Option<Align>> {
+        if !self.float(styles) || !self.wrap(styles) {
+            return Ok(None);
+        }
+
+        let alignment = self.alignment(styles);
+        match alignment.x.map(|align| align.resolve(styles)) {
+            Some(align @ (Align::Left | Align::Right)) if alignment.y.is_none() => {
+                Ok(Some(align))
+            }
+            _ => bail!(
+                self.span(),
+                "wrapped placement must be `left` or `right` without vertical alignment"
+            ),
+        }
+    }
+
+    /// Layout the body into an area of the given size, align it within the
+    /// area and then displace it.
+    pub fn place(
+        &self,
+        vt: &mut Vt,
+        styles: StyleChain,
+        base: Size,
+        expand: Axes<bool>,
+    ) -> SourceResult<Frame> {
+        let pod = Regions::one(base, expand);
+        let child = self.body().aligned(self.alignment(styles));
+        let mut frame = child.layout(vt, styles, pod)?.into_frame();
+        let delta = Axes::new(self.dx(styles), self.dy(styles))
+            .resolve(styles)
+            .zip(base)
+            .map(|(d, s)| d.relative_to(s));
+        frame.translate(delta.to_point());
+        Ok(frame)
+    }
+}
+
+/// Relative to which container content is placed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum PlacementScope {
+    /// Place into the current column or container.
+    Column,
+    /// Place relative to the whole page, including its margins.
+    Page,
+}
+
+/// Find page-scoped placed elements in a frame.
+pub(super) fn find_placed(placed: &mut Vec<PlaceElem>, frame: &Frame) {
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => find_placed(placed, &group.frame),
+            FrameItem::Meta(Meta::Elem(content), _)
+                if !placed.iter().any(|elem| elem.0.location() == content.location()) =>
+            {
+                let Some(elem) = content.to::<PlaceElem>() else { continue };
+                if elem.scope(StyleChain::default()) == PlacementScope::Page {
+                    placed.push(elem.clone());
+                }
+            }
+            _ => {}
+        }
     }
 }
 
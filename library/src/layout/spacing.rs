@@ -62,6 +62,35 @@ impl Behave for HElem {
     }
 }
 
+/// Advance to the next tab stop in a paragraph.
+///
+/// The stops are configured with the [`tab-stops`]($func/par.tab-stops)
+/// property of paragraphs. Depending on the stop's alignment, the text up to
+/// the next tab or the end of the line starts, is centered or ends at the
+/// stop or has its decimal separator there. Spaces around a tab are removed.
+///
+/// ## Example { #example }
+/// ```example
+/// #set par(tab-stops: (
+///   (pos: 3cm, align: "right"),
+///   (pos: 4.5cm, align: "decimal"),
+/// ))
+///
+/// Apples #tab() 3 kg #tab() 4.99 \
+/// Pears #tab() 12 kg #tab() 19.5
+/// ```
+///
+/// Display: Tab
+/// Category: layout
+#[element(Behave)]
+pub struct TabElem {}
+
+impl Behave for TabElem {
+    fn behaviour(&self) -> Behaviour {
+        Behaviour::Destructive
+    }
+}
+
 /// Insert vertical spacing into a flow of blocks.
 ///
 /// The spacing can be absolute, relative, or fractional. In the last case,
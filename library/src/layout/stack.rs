@@ -32,6 +32,26 @@ pub struct StackElem {
     /// Spacing to insert between items where no explicit spacing was provided.
     pub spacing: Option<Spacing>,
 
+    /// How to distribute the space that is left over along the stacking
+    /// direction.
+    ///
+    /// By default, the items are packed together. The other options spread
+    /// the items over the full size of the available region, e.g. to fill a
+    /// page from top to bottom. This has no effect if the stack contains
+    /// [fractional spacing]($type/fraction).
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #stack(
+    ///   distribute: "space-between",
+    ///   rect(height: 20pt),
+    ///   rect(height: 20pt),
+    ///   rect(height: 20pt),
+    /// )
+    /// ```
+    #[default(Distribution::Packed)]
+    pub distribute: Distribution,
+
     /// The children to stack along the axis.
     #[variadic]
     pub children: Vec<StackChild>,
@@ -45,7 +65,12 @@ impl Layout for StackElem {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
-        let mut layouter = StackLayouter::new(self.dir(styles), regions, styles);
+        let mut layouter = StackLayouter::new(
+            self.dir(styles),
+            self.distribute(styles),
+            regions,
+            styles,
+        );
 
         // Spacing to insert before the next block.
         let spacing = self.spacing(styles);
@@ -72,6 +97,21 @@ impl Layout for StackElem {
     }
 }
 
+/// How a stack distributes the space left over in its region.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Distribution {
+    /// Pack the items together.
+    Packed,
+    /// Distribute the space evenly between the items, with no space before
+    /// the first and after the last one.
+    SpaceBetween,
+    /// Distribute the space evenly around the items, with half as much space
+    /// before the first and after the last one as between two items.
+    SpaceAround,
+    /// Distribute the space evenly between and around the items.
+    SpaceEvenly,
+}
+
 /// A child of a stack element.
 #[derive(Hash)]
 pub enum StackChild {
@@ -104,6 +144,8 @@ cast! {
 struct StackLayouter<'a> {
     /// The stacking direction.
     dir: Dir,
+    /// How to distribute the leftover space.
+    distribute: Distribution,
     /// The axis of the stacking direction.
     axis: Axis,
     /// The regions to layout children into.
@@ -137,7 +179,12 @@ enum StackItem {
 
 impl<'a> StackLayouter<'a> {
     /// Create a new stack layouter.
-    fn new(dir: Dir, mut regions: Regions<'a>, styles: StyleChain<'a>) -> Self {
+    fn new(
+        dir: Dir,
+        distribute: Distribution,
+        mut regions: Regions<'a>,
+        styles: StyleChain<'a>,
+    ) -> Self {
         let axis = dir.axis();
         let expand = regions.expand;
 
@@ -146,6 +193,7 @@ impl<'a> StackLayouter<'a> {
 
         Self {
             dir,
+            distribute,
             axis,
             regions,
             styles,
@@ -248,8 +296,34 @@ impl<'a> StackLayouter<'a> {
             size.set(self.axis, full);
         }
 
+        // Distribute the leftover space around the frames.
+        let count = self
+            .items
+            .iter()
+            .filter(|item| matches!(item, StackItem::Frame(..)))
+            .count();
+        let (mut outer, mut inner) = (Abs::zero(), Abs::zero());
+        if self.distribute != Distribution::Packed
+            && self.fr.get() == 0.0
+            && full.is_finite()
+            && count > 0
+        {
+            let free = remaining.max(Abs::zero());
+            let n = count as f64;
+            (outer, inner) = match self.distribute {
+                Distribution::SpaceBetween if count > 1 => {
+                    (Abs::zero(), free / (n - 1.0))
+                }
+                Distribution::SpaceAround => (free / (2.0 * n), free / n),
+                Distribution::SpaceEvenly => (free / (n + 1.0), free / (n + 1.0)),
+                _ => (Abs::zero(), Abs::zero()),
+            };
+            self.used.main = full;
+            size.set(self.axis, full);
+        }
+
         let mut output = Frame::new(size);
-        let mut cursor = Abs::zero();
+        let mut cursor = outer;
         let mut ruler: Align = self.dir.start().into();
 
         // Place all frames.
@@ -281,7 +355,7 @@ impl<'a> StackLayouter<'a> {
                         .position(size.get(other) - frame.size().get(other));
 
                     let pos = Gen::new(cross, main).to_point(self.axis);
-                    cursor += child;
+                    cursor += child + inner;
                     output.push_frame(pos, frame);
                 }
             }
@@ -1,8 +1,12 @@
 use typst::eval::{CastInfo, Reflect};
 
-use crate::layout::{AlignElem, GridLayouter, TrackSizings};
+use crate::layout::{
+    place_cells, AlignElem, CellElem, GridLayouter, ParElem, Sizing, TabAlign, TabElem,
+    TabStop, TrackSizings,
+};
 use crate::meta::{Figurable, LocalName};
 use crate::prelude::*;
+use crate::text::StrongElem;
 
 /// A table of items.
 ///
@@ -37,7 +41,11 @@ use crate::prelude::*;
 ///
 /// Display: Table
 /// Category: layout
-#[element(Layout, LocalName, Figurable)]
+#[element(Synthesize, Layout, LocalName, Figurable)]
+#[scope(
+    scope.define("cell", CellElem::func());
+    scope
+)]
 pub struct TableElem {
     /// Defines the column sizes. See the [grid documentation]($func/grid) for
     /// more information on track sizing.
@@ -65,11 +73,30 @@ pub struct TableElem {
     #[parse(args.named("row-gutter")?.or_else(|| gutter.clone()))]
     pub row_gutter: TrackSizings,
 
+    /// The number of leading rows that form the table's header.
+    ///
+    /// When the table breaks across pages, the header rows are repeated at the
+    /// top of each page, including their fill and stroke.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #table(
+    ///   columns: 2,
+    ///   header: 1,
+    ///   [*Name*], [*Value*],
+    ///   ..range(8).map(n => ([Item #n], [#(n * n)])).flatten(),
+    /// )
+    /// ```
+    #[default(0)]
+    pub header: usize,
+
     /// How to fill the cells.
     ///
-    /// This can be a color or a function that returns a color. The function is
-    /// passed the cell's column and row index, starting at zero. This can be
-    /// used to implement striped tables.
+    /// This can be a color, an array of colors (corresponding to each column)
+    /// or a function with the signature `(x, y) => color` that is passed the
+    /// cell's column and row index, starting at zero, and returns a color or
+    /// `{none}`. For rows with alternating fills, the [`stripe`]($func/stripe)
+    /// function creates such a function.
     ///
     /// ```example
     /// #table(
@@ -102,20 +129,100 @@ pub struct TableElem {
     ///   [A], [B], [C],
     /// )
     /// ```
-    pub align: Celled<Smart<Axes<Option<GenAlign>>>>,
+    ///
+    /// Cells aligned with `{"decimal"}` that contain a number are aligned at
+    /// their [decimal separator]($func/table.decimal-separator) with all other
+    /// such cells in the same column. Other cells are aligned to the right.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   align: (left, "decimal"),
+    ///   [*Item*], [*Price*],
+    ///   [Coffee], [2.5],
+    ///   [Cake], [12.75],
+    ///   [Water], [0],
+    ///   [Tea], [free],
+    /// )
+    /// ```
+    pub align: Celled<CellAlign>,
+
+    /// The character at which numbers in cells with `{"decimal"}`
+    /// [alignment]($func/table.align) are aligned.
+    ///
+    /// ```example
+    /// #table(
+    ///   align: "decimal",
+    ///   decimal-separator: ",",
+    ///   [3,14], [42], [-0,5],
+    /// )
+    /// ```
+    #[default('.')]
+    pub decimal_separator: char,
 
     /// How to stroke the cells.
     ///
     /// See the [line's documentation]($func/line.stroke) for more details.
     /// Strokes can be disabled by setting this to `{none}`.
     ///
-    /// _Note:_ Richer stroke customization for individual cells is not yet
-    /// implemented, but will be in the future. In the meantime, you can use
-    /// the third-party [tablex library](https://github.com/PgBiel/typst-tablex/).
-    #[resolve]
+    /// Like the `fill`, this can also be an array of strokes (corresponding to
+    /// each column) or a function that is passed the cell's column and row
+    /// index, starting at zero. For each cell, you can also specify the sides
+    /// individually with a dictionary (see the [rectangle's
+    /// documentation]($func/rect.stroke)). A side that is set to `{none}` has
+    /// no line.
+    ///
+    /// Adjacent cells may specify different strokes for the line they share.
+    /// In this case, the stroke of the cell to the right or below takes
+    /// precedence. If that cell doesn't specify the side at all, the stroke of
+    /// the other cell is used.
+    ///
+    /// Strokes from multiple set rules are combined, so that a rule only
+    /// setting the color keeps the thickness from an earlier one. This does
+    /// not apply when either rule sets a function.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 3,
+    ///   stroke: (x, y) => (
+    ///     bottom: if y == 0 { 1.5pt } else { 0.5pt },
+    ///     left: none,
+    ///     right: none,
+    ///   ),
+    ///   [*A*], [*B*], [*C*],
+    ///   [1], [2], [3],
+    ///   [4], [5], [6],
+    /// )
+    /// ```
     #[fold]
-    #[default(Some(PartialStroke::default()))]
-    pub stroke: Option<PartialStroke>,
+    #[default(Celled::Value(Sides::splat(Some(Some(PartialStroke::default())))))]
+    pub stroke: Celled<Sides<Option<Option<PartialStroke>>>>,
+
+    /// Styles for whole columns.
+    ///
+    /// This is an array with one entry per column. Each entry is either
+    /// `{none}` or a function that receives the content of a cell in the
+    /// column and returns it styled, for instance `{text.with(blue)}` or
+    /// `{align.with(right)}`. Columns without an entry are not styled.
+    ///
+    /// Styles of the cells themselves take precedence over column styles,
+    /// which in turn take precedence over the table's
+    /// [`align`]($func/table.align) setting. Column styles are independent of
+    /// the column [sizes]($func/table.columns).
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 3,
+    ///   column-styles: (
+    ///     strong,
+    ///     none,
+    ///     it => align(right, text(green, it)),
+    ///   ),
+    ///   [Apples], [Fruit], [12],
+    ///   [Carrots], [Vegetable], text(red)[-3],
+    /// )
+    /// ```
+    pub column_styles: Vec<Option<Func>>,
 
     /// How much to pad the cells's content.
     #[default(Abs::pt(5.0).into())]
@@ -126,6 +233,14 @@ pub struct TableElem {
     pub children: Vec<Content>,
 }
 
+impl Synthesize for TableElem {
+    fn synthesize(&mut self, _: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        // Show rules see the strokes combined from all set rules.
+        self.push_stroke(self.stroke(styles));
+        Ok(())
+    }
+}
+
 impl Layout for TableElem {
     #[tracing::instrument(name = "TableElem::layout", skip_all)]
     fn layout(
@@ -136,29 +251,89 @@ impl Layout for TableElem {
     ) -> SourceResult<Fragment> {
         let inset = self.inset(styles);
         let align = self.align(styles);
+        let separator = self.decimal_separator(styles);
 
         let tracks = Axes::new(self.columns(styles).0, self.rows(styles).0);
         let gutter = Axes::new(self.column_gutter(styles).0, self.row_gutter(styles).0);
         let cols = tracks.x.len().max(1);
-        let cells: Vec<_> = self
-            .children()
-            .into_iter()
-            .enumerate()
-            .map(|(i, child)| {
-                let mut child = child.padded(Sides::splat(inset));
-
-                let x = i % cols;
-                let y = i / cols;
-                if let Smart::Custom(alignment) = align.resolve(vt, x, y)? {
-                    child = child.styled(AlignElem::set_alignment(alignment));
+        let children = self.children();
+        let positions = place_cells(&children, cols, styles);
+
+        // Apply the column styles to the cells' content.
+        let column_styles = self.column_styles(styles);
+        let mut bodies = vec![];
+        for (child, (pos, _)) in children.iter().zip(&positions) {
+            let mut body =
+                child.to::<CellElem>().map_or_else(|| child.clone(), CellElem::body);
+            if let Some(Some(func)) = column_styles.get(pos.x) {
+                body = func.call_vt(vt, [body])?.display();
+            }
+            bodies.push(body);
+        }
+
+        // Resolve the alignments and measure the fractional parts of numbers
+        // in decimal-aligned cells, so that their separators can be aligned
+        // with the widest one in the column.
+        let mut aligns = vec![];
+        let mut fractions = vec![];
+        let mut widest = vec![Abs::zero(); cols];
+        for (body, (pos, _)) in bodies.iter().zip(&positions) {
+            let alignment = align.resolve(vt, pos.x, pos.y)?;
+            let mut fraction = None;
+            if alignment == CellAlign::Decimal {
+                fraction = measure_fraction(vt, body, separator, styles)?;
+                if let Some(width) = fraction {
+                    widest[pos.x].set_max(width);
+                }
+            }
+            aligns.push(alignment);
+            fractions.push(fraction);
+        }
+
+        let mut cells = vec![];
+        for (i, ((child, body), (pos, _))) in
+            children.iter().zip(bodies).zip(&positions).enumerate()
+        {
+            // Keep spanning cells intact so that the grid can detect them.
+            let spanning = child.to::<CellElem>();
+            let mut body = match spanning {
+                Some(cell) => cell.rotated(body, styles),
+                None => body,
+            };
+
+            let alignment = match aligns[i] {
+                CellAlign::Align(alignment) => alignment,
+                CellAlign::Decimal => {
+                    if let Some(width) = fractions[i] {
+                        let pad = widest[pos.x] - width;
+                        body = body.padded(Sides::new(
+                            Rel::zero(),
+                            Rel::zero(),
+                            pad.into(),
+                            Rel::zero(),
+                        ));
+                    }
+                    Smart::Custom(Axes::with_x(Some(Align::Right.into())))
                 }
+            };
+
+            // The alignment is applied within the padding so that it also
+            // affects rotated content.
+            if let Smart::Custom(alignment) = alignment {
+                body = body.styled(AlignElem::set_alignment(alignment));
+            }
+            body = body.padded(Sides::splat(inset));
 
-                Ok(child)
-            })
-            .collect::<SourceResult<_>>()?;
+            cells.push(match spanning {
+                Some(cell) => {
+                    cell.clone().with_body(body).with_rotate(Angle::zero()).pack()
+                }
+                None => body,
+            });
+        }
 
         let fill = self.fill(styles);
-        let stroke = self.stroke(styles).map(PartialStroke::unwrap_or_default);
+        let stroke = self.stroke(styles);
 
         // Prepare grid layout by unifying content and gutter tracks.
         let layouter = GridLayouter::new(
@@ -168,36 +343,84 @@ impl Layout for TableElem {
             &cells,
             regions,
             styles,
-        );
+        )
+        .repeat_header(self.header(styles))
+        .tagged(Tag::TableRow, Some(Tag::TableCell));
 
         // Measure the columns and layout the grid row-by-row.
         let mut layout = layouter.layout()?;
 
+        // Determine the position at which the cell covering a track position
+        // starts.
+        let c = layout.cols.len();
+        let origins = &layout.origins;
+        let origin =
+            |x: usize, y: usize| (origins[y * c + x] % c, origins[y * c + x] / c);
+
         // Add lines and backgrounds.
         for (frame, rows) in layout.fragment.iter_mut().zip(&layout.rows) {
-            // Render table lines.
-            if let Some(stroke) = &stroke {
-                let thickness = stroke.thickness;
-                let half = thickness / 2.0;
-
-                // Render horizontal lines.
-                for offset in points(rows.iter().map(|piece| piece.height)) {
-                    let target = Point::with_x(frame.width() + thickness);
-                    let hline = Geometry::Line(target).stroked(stroke.clone());
-                    frame.prepend(
-                        Point::new(-half, offset),
-                        FrameItem::Shape(hline, self.span()),
-                    );
+            // Resolve the strokes of all cells in this region.
+            let mut strokes = vec![];
+            for row in rows {
+                let mut resolved = vec![];
+                for x in 0..layout.cols.len() {
+                    let (x, y) = origin(x, row.y);
+                    resolved.push(stroke.resolve(vt, x, y)?.resolve(styles));
+                }
+                strokes.push(resolved);
+            }
+
+            // Render horizontal lines.
+            let ys = points(rows.iter().map(|piece| piece.height));
+            for (i, dy) in ys.enumerate() {
+                let xs = points(layout.cols.iter().copied());
+                for ((x, dx), &col) in xs.enumerate().zip(&layout.cols) {
+                    // Don't draw lines within cells spanning multiple rows.
+                    if i > 0
+                        && i < rows.len()
+                        && origin(x, rows[i - 1].y) == origin(x, rows[i].y)
+                    {
+                        continue;
+                    }
+
+                    let above = i.checked_sub(1).map(|k| strokes[k][x].bottom.clone());
+                    let below = strokes.get(i).map(|row| row[x].top.clone());
+                    if let Some(stroke) = prioritize(above, below) {
+                        let half = stroke.thickness / 2.0;
+                        let target = Point::with_x(col + stroke.thickness);
+                        let hline = Geometry::Line(target).stroked(stroke);
+                        frame.prepend(
+                            Point::new(dx - half, dy),
+                            FrameItem::Shape(hline, self.span()),
+                        );
+                    }
                 }
+            }
+
+            // Render vertical lines.
+            let xs = points(layout.cols.iter().copied());
+            for (x, dx) in xs.enumerate() {
+                let ys = points(rows.iter().map(|piece| piece.height));
+                for ((i, dy), row) in ys.enumerate().zip(rows) {
+                    // Don't draw lines within cells spanning multiple columns.
+                    if x > 0
+                        && x < layout.cols.len()
+                        && origin(x - 1, row.y) == origin(x, row.y)
+                    {
+                        continue;
+                    }
 
-                // Render vertical lines.
-                for offset in points(layout.cols.iter().copied()) {
-                    let target = Point::with_y(frame.height() + thickness);
-                    let vline = Geometry::Line(target).stroked(stroke.clone());
-                    frame.prepend(
-                        Point::new(offset, -half),
-                        FrameItem::Shape(vline, self.span()),
-                    );
+                    let before = x.checked_sub(1).map(|k| strokes[i][k].right.clone());
+                    let after = strokes[i].get(x).map(|sides| sides.left.clone());
+                    if let Some(stroke) = prioritize(before, after) {
+                        let half = stroke.thickness / 2.0;
+                        let target = Point::with_y(row.height + stroke.thickness);
+                        let vline = Geometry::Line(target).stroked(stroke);
+                        frame.prepend(
+                            Point::new(dx, dy - half),
+                            FrameItem::Shape(vline, self.span()),
+                        );
+                    }
                 }
             }
 
@@ -206,7 +429,8 @@ impl Layout for TableElem {
             for (x, &col) in layout.cols.iter().enumerate() {
                 let mut dy = Abs::zero();
                 for row in rows {
-                    if let Some(fill) = fill.resolve(vt, x, row.y)? {
+                    let (ox, oy) = origin(x, row.y);
+                    if let Some(fill) = fill.resolve(vt, ox, oy)? {
                         let pos = Point::new(dx, dy);
                         let size = Size::new(col, row.height);
                         let rect = Geometry::Rect(size).filled(fill);
@@ -218,10 +442,206 @@ impl Layout for TableElem {
             }
         }
 
+        layout.fragment.tag(Tag::Table);
         Ok(layout.fragment)
     }
 }
 
+/// Create a table from rows of data.
+///
+/// This turns a two-dimensional array, like the one returned by the
+/// [`csv`]($func/csv) function, into a [table]($func/table) with one column
+/// per entry of the longest row. The resulting table can be styled with set
+/// rules just like any other table.
+///
+/// ## Example { #example }
+/// ```example
+/// #set table(stroke: none)
+/// #tabulate(
+///   (
+///     ("Name", "Score"),
+///     ("Alice", "12"),
+///     ("Bob", "7.5"),
+///   ),
+///   header: true,
+///   numeric: true,
+///   format: (value, x, y) => if x == 0 { emph(value) } else { value },
+/// )
+/// ```
+///
+/// Display: Tabulate
+/// Category: layout
+#[func]
+pub fn tabulate(
+    /// The rows of the table. Each row is an array of cell values.
+    rows: Vec<Array>,
+    /// Whether the first row is a header. If so, its cells are shown in bold
+    /// and it is repeated when the table breaks across pages.
+    #[named]
+    #[default(false)]
+    header: bool,
+    /// Whether to right-align columns in which all values (except for the
+    /// header) are numbers.
+    #[named]
+    #[default(false)]
+    numeric: bool,
+    /// A function that formats a cell. It receives the cell's value and its
+    /// column and row index, starting at zero, and should return content. By
+    /// default, values are displayed as is.
+    #[named]
+    format: Option<Func>,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> SourceResult<Content> {
+    let cols = rows.iter().map(Array::len).max().unwrap_or(0).max(1);
+    let skip = usize::from(header);
+
+    // Determine the alignment of each column.
+    let mut align = vec![CellAlign::default(); cols];
+    if numeric {
+        for (x, align) in align.iter_mut().enumerate() {
+            let mut values =
+                rows.iter().skip(skip).filter_map(|row| row.as_slice().get(x));
+            let numbers = values.clone().all(|value| match value {
+                Value::Int(_) | Value::Float(_) => true,
+                Value::Str(s) => s.trim().parse::<f64>().is_ok(),
+                _ => false,
+            });
+
+            if numbers && values.next().is_some() {
+                *align = CellAlign::Align(Smart::Custom(Axes::with_x(Some(
+                    Align::Right.into(),
+                ))));
+            }
+        }
+    }
+
+    let mut cells = vec![];
+    for (y, row) in rows.into_iter().enumerate() {
+        let len = row.len();
+        for (x, value) in row.into_iter().enumerate() {
+            let mut cell = match &format {
+                Some(func) => {
+                    let args =
+                        Args::new(func.span(), [value, x.into_value(), y.into_value()]);
+                    func.call_vm(vm, args)?.display()
+                }
+                None => value.display(),
+            };
+
+            if header && y == 0 {
+                cell = StrongElem::new(cell).pack();
+            }
+
+            cells.push(cell);
+        }
+
+        // Fill up short rows so that the following rows stay aligned.
+        cells.extend((len..cols).map(|_| Content::empty()));
+    }
+
+    Ok(TableElem::new(cells)
+        .with_columns(TrackSizings(vec![Sizing::Auto; cols]))
+        .with_align(Celled::Array(align))
+        .with_header(skip)
+        .pack())
+}
+
+/// Measure the width of the decimal separator and the digits after it in a
+/// cell, if the cell contains a number.
+fn measure_fraction(
+    vt: &mut Vt,
+    body: &Content,
+    separator: char,
+    styles: StyleChain,
+) -> SourceResult<Option<Abs>> {
+    let text = body.plain_text();
+    let number = text.trim().replace(separator, ".").replace('−', "-");
+    if number.parse::<f64>().is_err() {
+        return Ok(None);
+    }
+
+    // Let the paragraph layout find the separator: A tab to a far away
+    // decimal stop puts the separator exactly at the stop.
+    let far = Abs::pt(10000.0);
+    let stop = TabStop {
+        pos: far.into(),
+        align: TabAlign::Decimal,
+        separator,
+    };
+    let probe =
+        (TabElem::new().pack() + body.clone()).styled(ParElem::set_tab_stops(vec![stop]));
+    let pod = Regions::one(Size::splat(Abs::inf()), Axes::splat(false));
+    let width = probe.measure(vt, styles, pod)?.into_frame().width();
+    Ok(Some((width - far).max(Abs::zero())))
+}
+
+/// Create a fill function for a table with striped rows.
+///
+/// The resulting function can be passed to the [`fill`]($func/table.fill)
+/// parameter of tables. It fills rows alternately with the `even` and `odd`
+/// fill, counting the rows from zero after the header rows. The header rows
+/// are not filled.
+///
+/// ## Example { #example }
+/// ```example
+/// #table(
+///   columns: 2,
+///   header: 1,
+///   fill: stripe(odd: luma(230), header: 1),
+///   [*Name*], [*Score*],
+///   [Alice], [12],
+///   [Bob], [7],
+///   [Carol], [9],
+/// )
+/// ```
+///
+/// Display: Stripe
+/// Category: layout
+#[func]
+pub fn stripe(
+    /// The fill of the first, third, fifth, etc. row after the header.
+    #[named]
+    even: Option<Paint>,
+    /// The fill of the second, fourth, sixth, etc. row after the header.
+    #[named]
+    odd: Option<Paint>,
+    /// The number of leading rows that are not striped.
+    #[named]
+    #[default(0)]
+    header: usize,
+    /// The call span of this function.
+    span: Span,
+) -> Func {
+    let args =
+        Args::new(span, [even.into_value(), odd.into_value(), header.into_value()]);
+    stripe_cell_func().with(args)
+}
+
+/// Determine the fill of a cell in a table with striped rows.
+///
+/// Display: Stripe Cell
+/// Category: layout
+#[func]
+fn stripe_cell(
+    /// The fill of even rows.
+    even: Option<Paint>,
+    /// The fill of odd rows.
+    odd: Option<Paint>,
+    /// The number of leading rows that are not striped.
+    header: usize,
+    /// The cell's column.
+    _x: usize,
+    /// The cell's row.
+    y: usize,
+) -> Option<Paint> {
+    match y.checked_sub(header) {
+        Some(row) if row % 2 == 0 => even,
+        Some(_) => odd,
+        None => None,
+    }
+}
+
 /// Turn an iterator extents into an iterator of offsets before, in between, and
 /// after the extents, e.g. [10mm, 5mm] -> [0mm, 10mm, 15mm].
 fn points(extents: impl IntoIterator<Item = Abs>) -> impl Iterator<Item = Abs> {
@@ -234,6 +654,49 @@ fn points(extents: impl IntoIterator<Item = Abs>) -> impl Iterator<Item = Abs> {
         })
 }
 
+/// Determine the stroke of a line shared by two cells from the strokes the
+/// cells specify for their respective sides.
+///
+/// The cell after the line (to the right or below) takes precedence, unless it
+/// doesn't specify the side at all.
+fn prioritize(
+    before: Option<Option<Option<PartialStroke<Abs>>>>,
+    after: Option<Option<Option<PartialStroke<Abs>>>>,
+) -> Option<Stroke> {
+    after
+        .flatten()
+        .or(before.flatten())
+        .flatten()
+        .map(PartialStroke::unwrap_or_default)
+}
+
+/// How to align the content of a table cell.
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub enum CellAlign {
+    /// Align the content like any other content.
+    Align(Smart<Axes<Option<GenAlign>>>),
+    /// Align numbers at their decimal separator and other content to the
+    /// right.
+    Decimal,
+}
+
+impl Default for CellAlign {
+    fn default() -> Self {
+        Self::Align(Smart::Auto)
+    }
+}
+
+cast! {
+    CellAlign,
+    self => match self {
+        Self::Align(alignment) => alignment.into_value(),
+        Self::Decimal => "decimal".into_value(),
+    },
+    /// Align numbers at their decimal separator.
+    "decimal" => Self::Decimal,
+    alignment: Smart<Axes<Option<GenAlign>>> => Self::Align(alignment),
+}
+
 /// A value that can be configured per cell.
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub enum Celled<T> {
@@ -299,6 +762,65 @@ impl<T: FromValue> FromValue for Celled<T> {
     }
 }
 
+impl Fold for Celled<Sides<Option<Option<PartialStroke>>>> {
+    type Output = Self;
+
+    fn fold(self, outer: Self::Output) -> Self::Output {
+        match (self, outer) {
+            (Self::Value(inner), Self::Value(outer)) => {
+                Self::Value(fold_sides(inner, outer))
+            }
+            (Self::Value(inner), Self::Array(outer)) => Self::Array(
+                outer
+                    .into_iter()
+                    .map(|outer| fold_sides(inner.clone(), outer))
+                    .collect(),
+            ),
+            (Self::Array(inner), Self::Value(outer)) => Self::Array(
+                inner
+                    .into_iter()
+                    .map(|inner| fold_sides(inner, outer.clone()))
+                    .collect(),
+            ),
+            (Self::Array(inner), Self::Array(outer))
+                if !inner.is_empty() && !outer.is_empty() =>
+            {
+                // Both arrays repeat, so the combination repeats after the
+                // least common multiple of their lengths.
+                let len = (1..)
+                    .map(|k| k * inner.len())
+                    .find(|len| len % outer.len() == 0)
+                    .unwrap();
+                Self::Array(
+                    (0..len)
+                        .map(|x| {
+                            fold_sides(
+                                inner[x % inner.len()].clone(),
+                                outer[x % outer.len()].clone(),
+                            )
+                        })
+                        .collect(),
+                )
+            }
+            // A function can't be combined before it is called.
+            (inner, _) => inner,
+        }
+    }
+}
+
+/// Fold the strokes of a cell's sides, keeping unspecified sides apart from
+/// sides without a line.
+fn fold_sides(
+    inner: Sides<Option<Option<PartialStroke>>>,
+    outer: Sides<Option<Option<PartialStroke>>>,
+) -> Sides<Option<Option<PartialStroke>>> {
+    inner.zip(outer).map(|(inner, outer)| match (inner, outer) {
+        (Some(Some(inner)), Some(Some(outer))) => Some(Some(inner.fold(outer))),
+        (None, outer) => outer,
+        (inner, _) => inner,
+    })
+}
+
 impl LocalName for TableElem {
     fn local_name(&self, lang: Lang, _: Option<Region>) -> &'static str {
         match lang {
@@ -323,6 +845,28 @@ impl LocalName for TableElem {
             Lang::ENGLISH | _ => "Table",
         }
     }
+
+    fn local_plural_name(&self, lang: Lang, region: Option<Region>) -> &'static str {
+        match lang {
+            Lang::BOKMÅL => "Tabeller",
+            Lang::CZECH => "Tabulky",
+            Lang::DANISH => "Tabeller",
+            Lang::DUTCH => "Tabellen",
+            Lang::FRENCH => "Tableaux",
+            Lang::GERMAN => "Tabellen",
+            Lang::ITALIAN => "Tabelle",
+            Lang::NYNORSK => "Tabellar",
+            Lang::POLISH => "Tabele",
+            Lang::PORTUGUESE => "Tabelas",
+            Lang::RUSSIAN => "Таблицы",
+            Lang::SLOVENIAN => "Tabele",
+            Lang::SPANISH => "Tablas",
+            Lang::SWEDISH => "Tabeller",
+            Lang::UKRAINIAN => "Таблиці",
+            Lang::ENGLISH => "Tables",
+            _ => self.local_name(lang, region),
+        }
+    }
 }
 
 impl Figurable for TableElem {}
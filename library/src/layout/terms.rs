@@ -1,6 +1,7 @@
 use super::{HElem, VElem};
-use crate::layout::{BlockElem, ParElem, Spacing};
+use crate::layout::{BlockElem, PadElem, ParElem, Spacing};
 use crate::prelude::*;
+use crate::text::{LinebreakElem, TextElem};
 
 /// A list of terms and their descriptions.
 ///
@@ -15,6 +16,28 @@ use crate::prelude::*;
 ///   between two adjacent letters.
 /// ```
 ///
+/// Descriptions can span multiple paragraphs and contain nested lists. All of
+/// their content is indented by the hanging indent, so that the term stands
+/// out as a label.
+///
+/// ```example
+/// / Typst: A markup-based typesetting
+///   system.
+///
+///   It is designed to be powerful
+///   and easy to learn.
+///   - Fast
+///   - Friendly
+/// ```
+///
+/// Terms are displayed in [strong]($func/strong) emphasis. To style them
+/// differently, you can use a show-set rule for `strong` within the term list.
+///
+/// ```example
+/// #show terms: set strong(delta: 0)
+/// / Term: Not bold.
+/// ```
+///
 /// ## Syntax { #syntax }
 /// This function also has dedicated syntax: Starting a line with a slash,
 /// followed by a term, a colon and a description creates a term list item.
@@ -79,6 +102,18 @@ pub struct TermsElem {
     #[default(Em::new(2.0).into())]
     pub hanging_indent: Length,
 
+    /// Whether to place each description beneath its term instead of beside
+    /// it. In this case, the separator is not used.
+    ///
+    /// ```example
+    /// #set terms(beneath: true)
+    /// / Ligature: A merged glyph.
+    /// / Kerning: A spacing adjustment
+    ///   between two adjacent letters.
+    /// ```
+    #[default(false)]
+    pub beneath: bool,
+
     /// The spacing between the items of a wide (non-tight) term list.
     ///
     /// If set to `{auto}`, uses the spacing [below blocks]($func/block.below).
@@ -118,22 +153,38 @@ impl Layout for TermsElem {
                 .unwrap_or_else(|| BlockElem::below_in(styles).amount())
         };
 
+        // The whole item is padded by the full indent. The term then hangs
+        // into the padding, like the first line of a paragraph with a hanging
+        // indent.
+        let padding: Rel<Length> = (indent + hanging_indent).into();
+        let rtl = TextElem::dir_in(styles) == Dir::RTL;
+        let beneath = self.beneath(styles);
+
         let mut seq = vec![];
         for (i, child) in self.children().into_iter().enumerate() {
             if i > 0 {
                 seq.push(VElem::new(gutter).with_weakness(1).pack());
             }
-            if !indent.is_zero() {
-                seq.push(HElem::new(indent.into()).pack());
+
+            let mut body = vec![];
+            if !hanging_indent.is_zero() {
+                body.push(HElem::new((-hanging_indent).into()).pack());
+            }
+            body.push(child.term().strong());
+            if beneath {
+                body.push(LinebreakElem::new().pack());
+            } else {
+                body.push(separator.clone());
             }
-            seq.push(child.term().strong());
-            seq.push(separator.clone());
-            seq.push(child.description());
+            body.push(child.description());
+
+            let item = PadElem::new(Content::sequence(body));
+            let item =
+                if rtl { item.with_right(padding) } else { item.with_left(padding) };
+            seq.push(item.pack());
         }
 
-        Content::sequence(seq)
-            .styled(ParElem::set_hanging_indent(hanging_indent + indent))
-            .layout(vt, styles, regions)
+        Content::sequence(seq).layout(vt, styles, regions)
     }
 }
 
@@ -56,7 +56,7 @@ impl Layout for MoveElem {
 /// Rotate content without affecting layout.
 ///
 /// Rotate an element by a given angle. The layout will act as if the element
-/// was not rotated.
+/// was not rotated, unless [`reflow`]($func/rotate.reflow) is enabled.
 ///
 /// ## Example { #example }
 /// ```example
@@ -101,6 +101,21 @@ pub struct RotateElem {
     #[default(Align::CENTER_HORIZON)]
     pub origin: Axes<Option<GenAlign>>,
 
+    /// Whether the rotation impacts the layout.
+    ///
+    /// If enabled, the rotated content occupies the bounding box of its
+    /// rotated shape instead of its original size and the `origin` has no
+    /// effect. When the content is rotated by more than 45 degrees towards
+    /// the vertical, its lines are broken according to the available height
+    /// instead of the available width.
+    ///
+    /// ```example
+    /// A #box(rotate(-90deg, reflow: true)[Up]) B \
+    /// A #box(rotate(30deg, reflow: true)[Tilted]) B
+    /// ```
+    #[default(false)]
+    pub reflow: bool,
+
     /// The content to rotate.
     #[required]
     pub body: Content,
@@ -114,18 +129,61 @@ impl Layout for RotateElem {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
+        let angle = self.angle(styles);
+        if self.reflow(styles) {
+            return Ok(Fragment::frame(reflow(
+                vt,
+                &self.body(),
+                styles,
+                regions,
+                angle,
+            )?));
+        }
+
         let pod = Regions::one(regions.base(), Axes::splat(false));
         let mut frame = self.body().layout(vt, styles, pod)?.into_frame();
         let Axes { x, y } =
             self.origin(styles).zip(frame.size()).map(|(o, s)| o.position(s));
         let ts = Transform::translate(x, y)
-            .pre_concat(Transform::rotate(self.angle(styles)))
+            .pre_concat(Transform::rotate(angle))
             .pre_concat(Transform::translate(-x, -y));
         frame.transform(ts);
         Ok(Fragment::frame(frame))
     }
 }
 
+/// Layout rotated content into the bounding box of its rotated shape.
+fn reflow(
+    vt: &mut Vt,
+    body: &Content,
+    styles: StyleChain,
+    regions: Regions,
+    angle: Angle,
+) -> SourceResult<Frame> {
+    // Mostly vertical content is laid out with the axes swapped.
+    let mut base = regions.base();
+    if angle.sin().abs() > angle.cos().abs() {
+        base = Size::new(base.y, base.x);
+    }
+
+    let pod = Regions::one(base, Axes::splat(false));
+    let mut frame = body.layout(vt, styles, pod)?.into_frame();
+
+    // Determine the bounding box of the rotated frame.
+    let rotation = Transform::rotate(angle);
+    let Size { x: w, y: h } = frame.size();
+    let corners = [Point::zero(), Point::with_x(w), Point::with_y(h), Point::new(w, h)]
+        .map(|corner| corner.transform(rotation));
+    let min = corners.iter().fold(corners[0], |a, b| a.min(*b));
+    let max = corners.iter().fold(corners[0], |a, b| a.max(*b));
+
+    let size = (max - min).to_size();
+    frame.transform(Transform::translate(-min.x, -min.y).pre_concat(rotation));
+    frame.set_size(size);
+    frame.set_baseline(size.y);
+    Ok(frame)
+}
+
 /// Scale content without affecting layout.
 ///
 /// The `scale` function allows you to scale and mirror content without
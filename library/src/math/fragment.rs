@@ -9,6 +9,7 @@ pub enum MathFragment {
     Space(Abs),
     Linebreak,
     Align,
+    Tag(Frame),
 }
 
 impl MathFragment {
@@ -110,6 +111,7 @@ impl MathFragment {
             Self::Glyph(glyph) => glyph.into_frame(),
             Self::Variant(variant) => variant.frame,
             Self::Frame(fragment) => fragment.frame,
+            Self::Tag(frame) => frame,
             _ => Frame::new(self.size()),
         }
     }
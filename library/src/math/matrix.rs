@@ -113,13 +113,48 @@ pub struct MatElem {
         rows
     )]
     pub rows: Vec<Vec<Content>>,
+
+    /// Draws augmentation lines through the matrix.
+    ///
+    /// If an integer is given, a vertical line is drawn after that many
+    /// columns. Negative integers count from the end, so `{-1}` draws a line
+    /// before the last column, as is common for augmented matrices.
+    ///
+    /// For more control, pass a dictionary with the following keys:
+    /// - `hline`: The offsets of the horizontal lines, counted in rows. Can be
+    ///   a single integer or an array of integers.
+    /// - `vline`: The offsets of the vertical lines, counted in columns. Can
+    ///   be a single integer or an array of integers.
+    /// - `stroke`: How to [stroke]($func/line.stroke) the lines. Defaults to
+    ///   the thickness of a fraction line in the text's color.
+    ///
+    /// Vertical lines span the height of the delimiters and horizontal lines
+    /// the width of the matrix. Both are centered in the gap between the
+    /// adjacent rows or columns.
+    ///
+    /// ```example
+    /// $ mat(
+    ///   1, 0, 1;
+    ///   0, 1, 2;
+    ///   augment: #(-1),
+    /// ) $
+    ///
+    /// $ mat(
+    ///   1, 0, 0, 0;
+    ///   0, 1, 0, 0;
+    ///   0, 0, 1, 0;
+    ///   augment: #(hline: 2, vline: (1, 3), stroke: blue),
+    /// ) $
+    /// ```
+    pub augment: Option<Augment>,
 }
 
 impl LayoutMath for MatElem {
     #[tracing::instrument(skip(ctx))]
     fn layout_math(&self, ctx: &mut MathContext) -> SourceResult<()> {
         let delim = self.delim(ctx.styles());
-        let frame = layout_mat_body(ctx, &self.rows())?;
+        let augment = self.augment(ctx.styles());
+        let frame = layout_mat_body(ctx, &self.rows(), augment, self.span())?;
         layout_delimiters(
             ctx,
             frame,
@@ -215,6 +250,50 @@ impl Delimiter {
     }
 }
 
+/// Augmentation lines in a matrix.
+#[derive(Debug, Default, Clone, Hash)]
+pub struct Augment {
+    /// The offsets at which to draw horizontal lines.
+    pub hline: Vec<isize>,
+    /// The offsets at which to draw vertical lines.
+    pub vline: Vec<isize>,
+    /// How to stroke the lines.
+    pub stroke: PartialStroke,
+}
+
+cast! {
+    Augment,
+    self => dict! {
+        "hline" => self.hline,
+        "vline" => self.vline,
+        "stroke" => self.stroke,
+    }.into_value(),
+    v: isize => Self { vline: vec![v], ..Default::default() },
+    mut dict: Dict => {
+        let mut take = |key| {
+            dict.take(key)
+                .ok()
+                .map(|v| v.cast::<AugmentOffsets>().map(|offsets| offsets.0))
+                .transpose()
+                .map(Option::unwrap_or_default)
+        };
+        let hline = take("hline")?;
+        let vline = take("vline")?;
+        let stroke = dict.take("stroke").ok().map(Value::cast).transpose()?;
+        dict.finish(&["hline", "vline", "stroke"])?;
+        Self { hline, vline, stroke: stroke.unwrap_or_default() }
+    },
+}
+
+/// One or multiple offsets of augmentation lines.
+struct AugmentOffsets(Vec<isize>);
+
+cast! {
+    AugmentOffsets,
+    v: isize => Self(vec![v]),
+    v: Array => Self(v.into_iter().map(Value::cast).collect::<StrResult<_>>()?),
+}
+
 /// Layout the inner contents of a vector.
 fn layout_vec_body(
     ctx: &mut MathContext,
@@ -232,7 +311,12 @@ fn layout_vec_body(
 }
 
 /// Layout the inner contents of a matrix.
-fn layout_mat_body(ctx: &mut MathContext, rows: &[Vec<Content>]) -> SourceResult<Frame> {
+fn layout_mat_body(
+    ctx: &mut MathContext,
+    rows: &[Vec<Content>],
+    augment: Option<Augment>,
+    span: Span,
+) -> SourceResult<Frame> {
     let row_gap = ROW_GAP.scaled(ctx);
     let col_gap = COL_GAP.scaled(ctx);
 
@@ -261,6 +345,7 @@ fn layout_mat_body(ctx: &mut MathContext, rows: &[Vec<Content>]) -> SourceResult
         heights.iter().map(|&(a, b)| a + b).sum::<Abs>() + row_gap * (nrows - 1) as f64,
     ));
     let mut x = Abs::zero();
+    let mut col_ends = vec![];
     for col in cols {
         let AlignmentResult { points, width: rcol } = alignments(&col);
         let mut y = Abs::zero();
@@ -273,13 +358,65 @@ fn layout_mat_body(ctx: &mut MathContext, rows: &[Vec<Content>]) -> SourceResult
             frame.push_frame(pos, cell);
             y += ascent + descent + row_gap;
         }
-        x += rcol + col_gap;
+        x += rcol;
+        col_ends.push(x);
+        x += col_gap;
     }
     frame.size_mut().x = x - col_gap;
 
+    let Some(augment) = augment else { return Ok(frame) };
+
+    let stroke = augment.stroke.resolve(ctx.styles()).unwrap_or(Stroke {
+        paint: TextElem::fill_in(ctx.styles()),
+        thickness: scaled!(ctx, fraction_rule_thickness),
+        ..Stroke::default()
+    });
+
+    // Vertical lines extend as far as the delimiters.
+    let size = frame.size();
+    let padding = VERTICAL_PADDING.of(size.y) / 2.0;
+    for offset in augment.vline {
+        let Some(i) = resolve_offset(offset, ncols) else {
+            bail!(span, "cannot draw a vertical line at offset {offset}");
+        };
+        let x = col_ends[i - 1] + col_gap / 2.0;
+        let line = Geometry::Line(Point::with_y(size.y + 2.0 * padding));
+        frame.push(
+            Point::new(x, -padding),
+            FrameItem::Shape(line.stroked(stroke.clone()), span),
+        );
+    }
+
+    let mut row_ends = vec![];
+    let mut y = Abs::zero();
+    for &(ascent, descent) in &heights {
+        y += ascent + descent;
+        row_ends.push(y);
+        y += row_gap;
+    }
+
+    for offset in augment.hline {
+        let Some(i) = resolve_offset(offset, nrows) else {
+            bail!(span, "cannot draw a horizontal line at offset {offset}");
+        };
+        let y = row_ends[i - 1] + row_gap / 2.0;
+        let line = Geometry::Line(Point::with_x(size.x));
+        frame
+            .push(Point::with_y(y), FrameItem::Shape(line.stroked(stroke.clone()), span));
+    }
+
     Ok(frame)
 }
 
+/// Resolve a possibly negative line offset into the number of rows or columns
+/// before the line. Returns `None` if the line would not be between two of
+/// them.
+fn resolve_offset(offset: isize, count: usize) -> Option<usize> {
+    let count = count as isize;
+    let i = if offset < 0 { count + offset } else { offset };
+    (0 < i && i < count).then_some(i as usize)
+}
+
 /// Layout the outer wrapper around a vector's or matrices' body.
 fn layout_delimiters(
     ctx: &mut MathContext,
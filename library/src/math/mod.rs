@@ -10,6 +10,7 @@ mod delimited;
 mod frac;
 mod fragment;
 mod matrix;
+mod numbered;
 mod op;
 mod root;
 mod row;
@@ -25,6 +26,7 @@ pub use self::cancel::*;
 pub use self::delimited::*;
 pub use self::frac::*;
 pub use self::matrix::*;
+pub use self::numbered::*;
 pub use self::op::*;
 pub use self::root::*;
 pub use self::style::*;
@@ -55,6 +57,7 @@ use crate::text::{
 pub fn module() -> Module {
     let mut math = Scope::deduplicating();
     math.define("equation", EquationElem::func());
+    math.define("numbered", NumberedElem::func());
     math.define("text", TextElem::func());
 
     // Grouping.
@@ -157,6 +160,9 @@ pub struct EquationElem {
 
     /// How to [number]($func/numbering) block-level equations.
     ///
+    /// References to an equation display its number with the same numbering,
+    /// including any parentheses. Only numbered equations can be referenced.
+    ///
     /// ```example
     /// #set math.equation(numbering: "(1)")
     ///
@@ -168,6 +174,40 @@ pub struct EquationElem {
     /// ```
     pub numbering: Option<Numbering>,
 
+    /// The horizontal alignment of the equation number.
+    ///
+    /// By default, the number is placed at the `{end}` of the line, which is
+    /// the right edge in left-to-right text and the left edge in right-to-left
+    /// text. Use `{start}` to place it on the other side.
+    ///
+    /// ```example
+    /// #set math.equation(
+    ///   numbering: "(1)",
+    ///   number-align: start,
+    /// )
+    ///
+    /// $ a^2 + b^2 = c^2 $
+    /// ```
+    #[default(HorizontalAlign(GenAlign::End))]
+    pub number_align: HorizontalAlign,
+
+    /// The minimum gap between the equation and its number.
+    ///
+    /// If the equation is too wide to leave this gap on both sides, the number
+    /// is placed below the equation instead of overlapping it.
+    ///
+    /// ```example
+    /// #set math.equation(
+    ///   numbering: "(1)",
+    ///   number-gutter: 2em,
+    /// )
+    ///
+    /// $ sum_(k=0)^n k = (n(n+1)) / 2 $
+    /// ```
+    #[resolve]
+    #[default(Em::new(0.5).into())]
+    pub number_gutter: Length,
+
     /// A supplement for the equation.
     ///
     /// For references to equations, this is added before the referenced number.
@@ -186,6 +226,11 @@ pub struct EquationElem {
     /// ```
     pub supplement: Smart<Option<Supplement>>,
 
+    /// The plural of the automatic supplement, used for references to
+    /// multiple equations.
+    #[synthesized]
+    pub plural_supplement: Option<Content>,
+
     /// The contents of the equation.
     #[required]
     pub body: Content,
@@ -200,9 +245,15 @@ impl Synthesize for EquationElem {
             Smart::Custom(Some(supplement)) => supplement.resolve(vt, [self.clone()])?,
         };
 
+        let plural = self
+            .supplement(styles)
+            .is_auto()
+            .then(|| TextElem::packed(self.local_plural_name_in(styles)));
+
         self.push_block(self.block(styles));
         self.push_numbering(self.numbering(styles));
         self.push_supplement(Smart::Custom(Some(Supplement::Content(supplement))));
+        self.push_plural_supplement(plural);
 
         Ok(())
     }
@@ -237,8 +288,6 @@ impl Layout for EquationElem {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
-        const NUMBER_GUTTER: Em = Em::new(0.5);
-
         let block = self.block(styles);
 
         // Find a math font.
@@ -256,34 +305,67 @@ impl Layout for EquationElem {
         };
 
         let mut ctx = MathContext::new(vt, styles, regions, &font, block);
-        let mut frame = ctx.layout_frame(self)?;
+        let row = ctx.layout_row(self)?;
+        let (mut frame, mut numbers) = if block && row.has_tags() {
+            row.into_tagged_frame(&ctx)
+        } else {
+            (row.into_fragment(&ctx).into_frame(), vec![])
+        };
 
         if block {
-            if let Some(numbering) = self.numbering(styles) {
+            let tagged = !numbers.is_empty();
+            if let Some(numbering) = self.numbering(styles).filter(|_| !tagged) {
                 let pod = Regions::one(regions.base(), Axes::splat(false));
                 let counter = Counter::of(Self::func())
                     .display(Some(numbering), false)
                     .layout(vt, styles, pod)?
                     .into_frame();
+                numbers.push((frame.height() / 2.0, counter));
+            }
 
-                let width = if regions.size.x.is_finite() {
-                    regions.size.x
-                } else {
-                    frame.width()
-                        + 2.0 * (counter.width() + NUMBER_GUTTER.resolve(styles))
+            if !numbers.is_empty() {
+                let gutter = self.number_gutter(styles);
+                let number_width = numbers
+                    .iter()
+                    .map(|(_, number)| number.width())
+                    .max()
+                    .unwrap_or_default();
+                let full = frame.width() + 2.0 * (number_width + gutter);
+                let width =
+                    if regions.size.x.is_finite() { regions.size.x } else { full };
+
+                let align = self.number_align(styles).0.resolve(styles);
+                let x = |number: &Frame| match align {
+                    Align::Left => Abs::zero(),
+                    _ => width - number.width(),
                 };
 
-                let height = frame.height().max(counter.height());
-                frame.resize(Size::new(width, height), Align::CENTER_HORIZON);
-
-                let x = if TextElem::dir_in(styles).is_positive() {
-                    frame.width() - counter.width()
+                if full <= width || tagged {
+                    let height = numbers
+                        .iter()
+                        .map(|(_, number)| number.height())
+                        .fold(frame.height(), Abs::max);
+                    let shift = (height - frame.height()) / 2.0;
+                    frame.resize(Size::new(width, height), Align::CENTER_HORIZON);
+                    for (center, number) in numbers {
+                        let pos = Point::new(
+                            x(&number),
+                            shift + center - number.height() / 2.0,
+                        );
+                        frame.push_frame(pos, number);
+                    }
                 } else {
-                    Abs::zero()
-                };
-                let y = (frame.height() - counter.height()) / 2.0;
-
-                frame.push_frame(Point::new(x, y), counter)
+                    // If the number does not fit next to the equation, move it
+                    // below the equation so that the two don't overlap.
+                    let (_, number) = numbers.pop().unwrap();
+                    let height = frame.height() + number.height();
+                    frame.resize(
+                        Size::new(width, height),
+                        Axes::new(Align::Center, Align::Top),
+                    );
+                    let pos = Point::new(x(&number), frame.height() - number.height());
+                    frame.push_frame(pos, number);
+                }
             }
         } else {
             let slack = ParElem::leading_in(styles) * 0.7;
@@ -304,10 +386,18 @@ impl Layout for EquationElem {
     }
 }
 
+impl EquationElem {
+    /// Whether the equation has individually numbered lines.
+    fn has_tags(&self) -> bool {
+        self.body().query_first(NumberedElem::func().select()).is_some()
+    }
+}
+
 impl Count for EquationElem {
     fn update(&self) -> Option<CounterUpdate> {
         (self.block(StyleChain::default())
-            && self.numbering(StyleChain::default()).is_some())
+            && self.numbering(StyleChain::default()).is_some()
+            && !self.has_tags())
         .then(|| CounterUpdate::Step(NonZeroUsize::ONE))
     }
 }
@@ -337,6 +427,26 @@ impl LocalName for EquationElem {
             Lang::ENGLISH | _ => "Equation",
         }
     }
+
+    fn local_plural_name(&self, lang: Lang, region: Option<Region>) -> &'static str {
+        match lang {
+            Lang::BOKMÅL => "Ligninger",
+            Lang::DANISH => "Ligninger",
+            Lang::DUTCH => "Vergelijkingen",
+            Lang::FRENCH => "Équations",
+            Lang::GERMAN => "Gleichungen",
+            Lang::ITALIAN => "Equazioni",
+            Lang::NYNORSK => "Likningar",
+            Lang::POLISH => "Równania",
+            Lang::PORTUGUESE => "Equações",
+            Lang::RUSSIAN => "Уравнения",
+            Lang::SLOVENIAN => "Enačbe",
+            Lang::SPANISH => "Ecuaciones",
+            Lang::SWEDISH => "Ekvationer",
+            Lang::ENGLISH => "Equations",
+            _ => self.local_name(lang, region),
+        }
+    }
 }
 
 impl Refable for EquationElem {
@@ -348,18 +458,27 @@ impl Refable for EquationElem {
         }
     }
 
+    fn plural_supplement(&self) -> Content {
+        self.plural_supplement().unwrap_or_else(|| Refable::supplement(self))
+    }
+
     fn counter(&self) -> Counter {
         Counter::of(Self::func())
     }
 
     fn numbering(&self) -> Option<Numbering> {
-        self.numbering(StyleChain::default())
+        self.numbering(StyleChain::default()).filter(|_| !self.has_tags())
+    }
+
+    fn ref_numbering(&self) -> Option<Numbering> {
+        // Keep the parentheses so that references read like "Equation (1)".
+        Refable::numbering(self)
     }
 }
 
 impl Outlinable for EquationElem {
     fn outline(&self, vt: &mut Vt) -> SourceResult<Option<Content>> {
-        let Some(numbering) = self.numbering(StyleChain::default()) else {
+        let Some(numbering) = Refable::numbering(self) else {
             return Ok(None);
         };
 
@@ -418,8 +537,13 @@ impl LayoutMath for Content {
         }
 
         if let Some((elem, styles)) = self.to_styled() {
-            if TextElem::font_in(ctx.styles().chain(styles))
-                != TextElem::font_in(ctx.styles())
+            // Text whose font, baseline, or features change (e.g. in a
+            // subscript) is handled by Typst's standard text layout.
+            let outer = ctx.styles();
+            let inner = outer.chain(styles);
+            if TextElem::font_in(inner) != TextElem::font_in(outer)
+                || TextElem::baseline_in(inner) != TextElem::baseline_in(outer)
+                || TextElem::features_in(inner) != TextElem::features_in(outer)
             {
                 let frame = ctx.layout_content(self)?;
                 ctx.push(FrameFragment::new(ctx, frame).with_spaced(true));
@@ -0,0 +1,108 @@
+use std::str::FromStr;
+
+use super::*;
+use crate::meta::NumberingPattern;
+
+/// Gives a single line of a block-level equation its own number.
+///
+/// Place this anywhere within a line of a multi-line equation to number that
+/// line. Lines without it stay unnumbered. As soon as an equation contains a
+/// numbered line, the equation as a whole is not numbered anymore. The line
+/// numbers continue the counter of equations and use the equation's
+/// [numbering]($func/math.equation.numbering), or `{"(1)"}` if the equation
+/// has none.
+///
+/// ## Example { #example }
+/// ```example
+/// #set math.equation(numbering: "(1)")
+///
+/// $ a &= b + c #math.numbered(<first>) \
+///     &= c + b \
+///     &= d #math.numbered() $
+///
+/// The first step in @first is
+/// trivial.
+/// ```
+///
+/// Display: Numbered Line
+/// Category: math
+#[element(Locatable, Synthesize, LayoutMath, Refable)]
+pub struct NumberedElem {
+    /// A label through which the line can be referenced.
+    #[positional]
+    pub tag: Option<Label>,
+
+    /// The numbering of the line, taken from the surrounding equation.
+    #[synthesized]
+    pub numbering: Numbering,
+
+    /// The supplement of the line, taken from the surrounding equation.
+    #[synthesized]
+    pub supplement: Content,
+}
+
+impl Synthesize for NumberedElem {
+    fn synthesize(&mut self, vt: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        let numbering = EquationElem::numbering_in(styles).unwrap_or_else(|| {
+            Numbering::Pattern(NumberingPattern::from_str("(1)").unwrap())
+        });
+
+        let supplement = match EquationElem::supplement_in(styles) {
+            Smart::Auto => TextElem::packed(
+                EquationElem::new(Content::empty()).local_name_in(styles),
+            ),
+            Smart::Custom(None) => Content::empty(),
+            Smart::Custom(Some(supplement)) => supplement.resolve(vt, [self.clone()])?,
+        };
+
+        if let Some(label) = self.tag(styles) {
+            self.0.push_field("label", label);
+        }
+
+        self.push_numbering(numbering);
+        self.push_supplement(supplement);
+        Ok(())
+    }
+}
+
+impl LayoutMath for NumberedElem {
+    #[tracing::instrument(skip(ctx))]
+    fn layout_math(&self, ctx: &mut MathContext) -> SourceResult<()> {
+        let location = self.0.location().unwrap();
+        let counter = self.counter();
+        let number = counter.at(ctx.vt, location)?.display(ctx.vt, &self.numbering())?;
+        let mut frame = ctx.layout_content(&Content::sequence([
+            counter.update(CounterUpdate::Step(NonZeroUsize::ONE)),
+            number,
+        ]))?;
+
+        // The metadata goes after the counter update so that the line's
+        // number includes its own step.
+        for meta in MetaElem::data_in(ctx.styles()) {
+            if !matches!(meta, Meta::Hide) {
+                frame.push(Point::zero(), FrameItem::Meta(meta, Size::zero()));
+            }
+        }
+
+        ctx.push(MathFragment::Tag(frame));
+        Ok(())
+    }
+}
+
+impl Refable for NumberedElem {
+    fn supplement(&self) -> Content {
+        self.supplement()
+    }
+
+    fn counter(&self) -> Counter {
+        Counter::of(EquationElem::func())
+    }
+
+    fn numbering(&self) -> Option<Numbering> {
+        Some(self.numbering())
+    }
+
+    fn ref_numbering(&self) -> Option<Numbering> {
+        Some(self.numbering())
+    }
+}
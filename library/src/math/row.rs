@@ -34,8 +34,8 @@ impl MathRow {
                     continue;
                 }
 
-                // Alignment points are resolved later.
-                MathFragment::Align => {
+                // Alignment points and tags are resolved later.
+                MathFragment::Align | MathFragment::Tag(_) => {
                     resolved.push(fragment);
                     continue;
                 }
@@ -126,11 +126,58 @@ impl MathRow {
     }
 
     pub fn into_aligned_frame(
-        mut self,
+        self,
         ctx: &MathContext,
         points: &[Abs],
         align: Align,
     ) -> Frame {
+        self.into_line_frames(ctx, points, align).0
+    }
+
+    pub fn has_tags(&self) -> bool {
+        self.iter().any(|frag| matches!(frag, MathFragment::Tag(_)))
+    }
+
+    /// Lay out the row into a frame and extract the tags of its lines. Each
+    /// tag is returned with the vertical center of its line.
+    pub fn into_tagged_frame(self, ctx: &MathContext) -> (Frame, Vec<(Abs, Frame)>) {
+        let styles = ctx.styles();
+        let align = AlignElem::alignment_in(styles).x.resolve(styles);
+
+        let mut line = 0;
+        let mut tags = vec![];
+        let mut fragments = vec![];
+        for fragment in self.0 {
+            match fragment {
+                MathFragment::Tag(frame) => tags.push((line, frame)),
+                MathFragment::Linebreak => {
+                    line += 1;
+                    fragments.push(fragment);
+                }
+                _ => fragments.push(fragment),
+            }
+        }
+
+        let (frame, centers) = Self(fragments).into_line_frames(ctx, &[], align);
+        let tags = tags
+            .into_iter()
+            .map(|(line, tag)| {
+                let center = centers.get(line).or(centers.last());
+                (center.copied().unwrap_or_default(), tag)
+            })
+            .collect();
+
+        (frame, tags)
+    }
+
+    /// Lay out the row into a frame, also returning the vertical center of
+    /// each line.
+    fn into_line_frames(
+        mut self,
+        ctx: &MathContext,
+        points: &[Abs],
+        align: Align,
+    ) -> (Frame, Vec<Abs>) {
         if self.iter().any(|frag| matches!(frag, MathFragment::Linebreak)) {
             let fragments: Vec<_> = std::mem::take(&mut self.0);
             let leading = if ctx.style.size >= MathSize::Text {
@@ -150,6 +197,7 @@ impl MathRow {
 
             let AlignmentResult { points, width } = alignments(&rows);
             let mut frame = Frame::new(Size::zero());
+            let mut centers = vec![];
 
             for (i, row) in rows.into_iter().enumerate() {
                 let sub = row.into_line_frame(&points, align);
@@ -162,13 +210,16 @@ impl MathRow {
                 if points.is_empty() {
                     pos.x = align.position(width - sub.width());
                 }
+                centers.push(size.y + sub.height() / 2.0);
                 size.y += sub.height();
                 size.x.set_max(sub.width());
                 frame.push_frame(pos, sub);
             }
-            frame
+            (frame, centers)
         } else {
-            self.into_line_frame(points, align)
+            let frame = self.into_line_frame(points, align);
+            let center = frame.height() / 2.0;
+            (frame, vec![center])
         }
     }
 
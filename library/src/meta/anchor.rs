@@ -0,0 +1,78 @@
+use crate::prelude::*;
+
+/// A named destination in the document.
+///
+/// An anchor marks its location with a stable name. In the exported PDF, the
+/// name is registered as a named destination, so external tools can link to
+/// it with a URL like `paper.pdf#results`. Within the document, you can link
+/// to an anchor by prefixing its name with a hash.
+///
+/// Anchor names may only consist of ASCII letters, digits, hyphens,
+/// underscores and dots, and must be unique within the document.
+///
+/// ## Example { #example }
+/// ```example
+/// #anchor("results")
+/// = Results
+/// We found that ...
+///
+/// #link("#results")[Back to results]
+/// ```
+///
+/// Display: Anchor
+/// Category: meta
+#[element(Locatable, Show)]
+pub struct AnchorElem {
+    /// The name of the destination.
+    #[required]
+    #[parse(
+        let Spanned { v: name, span } = args.expect::<Spanned<EcoString>>("name")?;
+        validate(&name).at(span)?;
+        name
+    )]
+    pub name: EcoString,
+}
+
+impl AnchorElem {
+    /// Find the location of the anchor with the given name.
+    pub fn resolve(vt: &Vt, name: &str) -> StrResult<Location> {
+        let mut found = vt
+            .introspector
+            .query(&Self::func().select())
+            .into_iter()
+            .filter(|elem| elem.to::<Self>().unwrap().name().as_str() == name);
+        let elem = found.next().ok_or("anchor does not exist in the document")?;
+        if found.next().is_some() {
+            bail!("anchor occurs multiple times in the document");
+        }
+        Ok(elem.location().unwrap())
+    }
+}
+
+impl Show for AnchorElem {
+    #[tracing::instrument(name = "AnchorElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+        let name = self.name();
+        if vt.introspector.init() {
+            Self::resolve(vt, &name).at(self.span())?;
+        }
+
+        Ok(MetaElem::new()
+            .pack()
+            .styled(MetaElem::set_data(vec![Meta::Anchor(name)])))
+    }
+}
+
+/// Ensure that an anchor name only consists of PDF-safe characters.
+fn validate(name: &str) -> StrResult<()> {
+    if name.is_empty() {
+        bail!("anchor name must not be empty");
+    }
+
+    let valid = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.');
+    if !name.chars().all(valid) {
+        bail!("anchor name may only contain ASCII letters, digits, `-`, `_`, and `.`");
+    }
+
+    Ok(())
+}
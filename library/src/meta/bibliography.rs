@@ -11,7 +11,7 @@ use typst::util::option_eq;
 
 use super::{LinkElem, LocalName, RefElem};
 use crate::layout::{BlockElem, GridElem, ParElem, Sizing, TrackSizings, VElem};
-use crate::meta::{FootnoteElem, HeadingElem};
+use crate::meta::{FootnoteBody, FootnoteElem, HeadingElem};
 use crate::prelude::*;
 use crate::text::TextElem;
 
@@ -28,8 +28,9 @@ use crate::text::TextElem;
 ///
 /// As soon as you add a bibliography somewhere in your document, you can start
 /// citing things with reference syntax (`[@key]`) or explicit calls to the
-/// [citation]($func/cite) function (`[#cite("key")]`). The bibliography will
-/// only show entries for works that were referenced in the document.
+/// [citation]($func/cite) function (`[#cite("key")]`). By default, the
+/// bibliography will only show entries for works that were referenced in the
+/// document.
 ///
 /// # Example
 /// ```example
@@ -76,8 +77,58 @@ pub struct BibliographyElem {
     pub title: Option<Smart<Content>>,
 
     /// The bibliography style.
+    ///
+    /// Either one of the built-in styles or a path to a
+    /// [CSL](https://citationstyles.org/) `.csl` file. CSL files are not yet
+    /// interpreted in full. Instead, the built-in style that matches the
+    /// file's citation format (author-date, numeric, note, or author) is
+    /// used.
+    #[parse(match args.named::<Spanned<EcoString>>("style")? {
+        Some(Spanned { v, span }) if v.ends_with(".csl") => {
+            let path = vm.locate(&v).at(span)?;
+            let buffer = vm.world().file(&path).at(span)?;
+            Some(csl_style(&buffer).at(span)?)
+        }
+        Some(Spanned { v, span }) => Some(Value::Str(v.into()).cast().at(span)?),
+        None => None,
+    })]
     #[default(BibliographyStyle::Ieee)]
     pub style: BibliographyStyle,
+
+    /// Whether to include all works from the bibliography files, even those
+    /// that are not cited in the document.
+    ///
+    /// Works that are not cited are listed after the cited ones in the order
+    /// of the style or, if the style orders by citation, in the order of the
+    /// files. They do not affect the numbering of citations.
+    ///
+    /// ```example
+    /// #bibliography(
+    ///   "works.bib",
+    ///   title: [Further Reading],
+    ///   full: true,
+    /// )
+    /// ```
+    #[default(false)]
+    pub full: bool,
+
+    /// The group of this bibliography.
+    ///
+    /// A document can contain multiple bibliographies as long as they belong
+    /// to different groups. Each bibliography only lists and numbers the
+    /// works cited for its group. Citations select a group through their
+    /// [`group`]($func/cite.group) parameter.
+    ///
+    /// ```example
+    /// #set cite(group: "intro")
+    /// The first chapter cites @arrgh.
+    /// #bibliography("works.bib", group: "intro")
+    ///
+    /// #set cite(group: "main")
+    /// The second one cites @netwok.
+    /// #bibliography("works.bib", group: "main")
+    /// ```
+    pub group: Option<EcoString>,
 }
 
 /// A list of bib file paths.
@@ -92,18 +143,36 @@ cast! {
 }
 
 impl BibliographyElem {
-    /// Find the document's bibliography.
-    pub fn find(introspector: Tracked<Introspector>) -> StrResult<Self> {
-        let mut iter = introspector.query(&Self::func().select()).into_iter();
+    /// Find the document's bibliography for the given group.
+    pub fn find(
+        introspector: Tracked<Introspector>,
+        group: Option<&str>,
+    ) -> StrResult<Self> {
+        let mut iter = introspector
+            .query(&Self::func().select())
+            .into_iter()
+            .map(|elem| elem.to::<Self>().unwrap().clone())
+            .filter(|elem| elem.group(StyleChain::default()).as_deref() == group);
+
         let Some(elem) = iter.next() else {
-            return Err("the document does not contain a bibliography".into());
+            return Err(match group {
+                Some(group) => eco_format!(
+                    "the document does not contain a bibliography for the group {group:?}"
+                ),
+                None => "the document does not contain a bibliography".into(),
+            });
         };
 
         if iter.next().is_some() {
-            Err("multiple bibliographies are not supported")?;
+            Err(match group {
+                Some(group) => {
+                    eco_format!("multiple bibliographies for the group {group:?}")
+                }
+                None => "multiple bibliographies without a group".into(),
+            })?;
         }
 
-        Ok(elem.to::<Self>().unwrap().clone())
+        Ok(elem)
     }
 
     /// Whether the bibliography contains the given key.
@@ -121,9 +190,10 @@ impl BibliographyElem {
         world: Tracked<dyn World + '_>,
         introspector: Tracked<Introspector>,
     ) -> Vec<(EcoString, Option<EcoString>)> {
-        Self::find(introspector)
-            .and_then(|elem| load(world, &elem.path()))
+        introspector
+            .query(&Self::func().select())
             .into_iter()
+            .flat_map(|elem| load(world, &elem.to::<Self>().unwrap().path()))
             .flatten()
             .map(|entry| {
                 let key = entry.key().into();
@@ -138,6 +208,8 @@ impl BibliographyElem {
 impl Synthesize for BibliographyElem {
     fn synthesize(&mut self, _vt: &mut Vt, styles: StyleChain) -> SourceResult<()> {
         self.push_style(self.style(styles));
+        self.push_full(self.full(styles));
+        self.push_group(self.group(styles));
         Ok(())
     }
 }
@@ -166,7 +238,8 @@ impl Show for BibliographyElem {
             return Ok(Content::sequence(seq));
         }
 
-        let works = Works::new(vt).at(self.span())?;
+        let group = self.group(StyleChain::default());
+        let works = Works::new(vt, group.as_deref()).at(self.span())?;
 
         let row_gutter = BlockElem::below_in(styles).amount();
         if works.references.iter().any(|(prefix, _)| prefix.is_some()) {
@@ -309,6 +382,18 @@ pub struct CiteElem {
     ///
     /// #bibliography("works.bib")
     /// ```
+    ///
+    /// A supplement consisting only of a page number or a page range such as
+    /// `[23--25]` is treated as a locator and automatically prefixed with
+    /// "p." or "pp." respectively. The citation style then decides where the
+    /// locator is placed.
+    ///
+    /// ```example
+    /// #set cite(style: "numerical")
+    /// See @netwok[23--25].
+    ///
+    /// #bibliography("works.bib")
+    /// ```
     #[positional]
     pub supplement: Option<Content>,
 
@@ -341,6 +426,11 @@ pub struct CiteElem {
     /// #bibliography("works.bib")
     /// ```
     pub style: Smart<CitationStyle>,
+
+    /// The group of the bibliography that contains the cited works. See the
+    /// [bibliography's documentation]($func/bibliography.group) for more
+    /// details.
+    pub group: Option<EcoString>,
 }
 
 impl Synthesize for CiteElem {
@@ -348,6 +438,7 @@ impl Synthesize for CiteElem {
         self.push_supplement(self.supplement(styles));
         self.push_brackets(self.brackets(styles));
         self.push_style(self.style(styles));
+        self.push_group(self.group(styles));
         Ok(())
     }
 }
@@ -359,14 +450,22 @@ impl Show for CiteElem {
             return Ok(Content::empty());
         }
 
-        let works = Works::new(vt).at(self.span())?;
+        let group = self.group(StyleChain::default());
+        let works = Works::new(vt, group.as_deref()).at(self.span())?;
         let location = self.0.location().unwrap();
         works
             .citations
             .get(&location)
             .cloned()
             .flatten()
-            .ok_or("bibliography does not contain this key")
+            .ok_or_else(|| match group {
+                Some(group) => {
+                    eco_format!(
+                        "bibliography for the group {group:?} does not contain this key"
+                    )
+                }
+                None => "bibliography does not contain this key".into(),
+            })
             .at(self.span())
     }
 }
@@ -411,9 +510,10 @@ struct Works {
 }
 
 impl Works {
-    /// Prepare all things need to cite a work or format a bibliography.
-    fn new(vt: &Vt) -> StrResult<Arc<Self>> {
-        let bibliography = BibliographyElem::find(vt.introspector)?;
+    /// Prepare all things need to cite a work or format the bibliography of
+    /// the given group.
+    fn new(vt: &Vt, group: Option<&str>) -> StrResult<Arc<Self>> {
+        let bibliography = BibliographyElem::find(vt.introspector, group)?;
         let citations = vt
             .introspector
             .query(&Selector::Or(eco_vec![
@@ -425,6 +525,7 @@ impl Works {
                 Some(reference) => reference.citation().unwrap(),
                 _ => elem.to::<CiteElem>().unwrap().clone(),
             })
+            .filter(|citation| citation.group(StyleChain::default()).as_deref() == group)
             .collect();
         Ok(create(vt.world, bibliography, citations))
     }
@@ -478,7 +579,7 @@ fn create(
             let location = citation.0.location().unwrap();
             let Some(cited) = cited else { return (location, None) };
 
-            let mut supplement = citation.supplement(StyleChain::default());
+            let mut supplement = citation.supplement(StyleChain::default()).map(locator);
             let brackets = citation.brackets(StyleChain::default());
             let style = citation
                 .style(StyleChain::default())
@@ -546,13 +647,21 @@ fn create(
             }
 
             if style == CitationStyle::ChicagoNotes {
-                content = FootnoteElem::new(content).pack();
+                content = FootnoteElem::new(FootnoteBody::Content(content)).pack();
             }
 
             (location, Some(content))
         })
         .collect();
 
+    // Add the works that were not cited only now, so that they don't affect
+    // the numbering of citations.
+    if bibliography.full(StyleChain::default()) {
+        for entry in entries.iter() {
+            db.push(entry);
+        }
+    }
+
     let bibliography_style: Box<dyn style::BibliographyStyle> = match style {
         BibliographyStyle::Apa => Box::new(style::Apa::new()),
         BibliographyStyle::ChicagoAuthorDate => Box::new(style::ChicagoAuthorDate::new()),
@@ -567,11 +676,13 @@ fn create(
         .map(|reference| {
             let backlink = ref_location(reference.entry);
             let prefix = reference.prefix.map(|prefix| {
-                // Format and link to first citation.
+                // Format and link to first citation, if there is one.
                 let bracketed = prefix.with_default_brackets(&*citation_style);
-                format_display_string(&bracketed, None, span)
-                    .linked(Destination::Location(ids[reference.entry.key()]))
-                    .backlinked(backlink)
+                let mut prefix = format_display_string(&bracketed, None, span);
+                if let Some(&id) = ids.get(reference.entry.key()) {
+                    prefix = prefix.linked(Destination::Location(id));
+                }
+                prefix.backlinked(backlink)
             });
 
             let mut reference = format_display_string(&reference.display, None, span);
@@ -586,6 +697,38 @@ fn create(
     Arc::new(Works { citations, references })
 }
 
+/// Determine the built-in style that best approximates a CSL style.
+fn csl_style(buffer: &[u8]) -> StrResult<BibliographyStyle> {
+    let text = std::str::from_utf8(buffer).map_err(|_| "file is not valid utf-8")?;
+    let document = roxmltree::Document::parse(text)
+        .map_err(|err| eco_format!("failed to parse CSL file ({err})"))?;
+
+    let info = |name: &str| {
+        document
+            .descendants()
+            .find(|node| node.has_tag_name(name))
+            .and_then(|node| node.text())
+            .unwrap_or_default()
+            .to_lowercase()
+    };
+
+    let format = document
+        .descendants()
+        .find_map(|node| node.attribute("citation-format"))
+        .ok_or("CSL file does not specify a citation format")?;
+
+    Ok(match format {
+        "author-date" if info("id").contains("apa") || info("title").contains("apa") => {
+            BibliographyStyle::Apa
+        }
+        "author-date" => BibliographyStyle::ChicagoAuthorDate,
+        "numeric" | "label" => BibliographyStyle::Ieee,
+        "note" => BibliographyStyle::ChicagoNotes,
+        "author" => BibliographyStyle::Mla,
+        _ => Err(eco_format!("unsupported CSL citation format: {format}"))?,
+    })
+}
+
 /// Load bibliography entries from a path.
 #[comemo::memoize]
 fn load(
@@ -654,6 +797,25 @@ fn format_biblatex_error(path: &str, src: &str, error: BibLaTeXError) -> EcoStri
     eco_format!("parsing failed at {path}:{line}: {msg}")
 }
 
+/// Turn a supplement that is just a page number or range into a locator.
+fn locator(supplement: Content) -> Content {
+    let text = supplement.plain_text();
+    let text = text.trim();
+    let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    if is_number(text) {
+        return TextElem::packed(eco_format!("p.\u{a0}{text}"));
+    }
+
+    if let Some((first, last)) = text.split_once(['-', '–']) {
+        let (first, last) = (first.trim(), last.trim());
+        if is_number(first) && is_number(last) {
+            return TextElem::packed(eco_format!("pp.\u{a0}{first}–{last}"));
+        }
+    }
+
+    supplement
+}
+
 /// Hayagriva only supports strings, but we have a content supplement. To deal
 /// with this, we pass this string to hayagriva instead of our content, find it
 /// in the output and replace it with the content.
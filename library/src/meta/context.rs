@@ -139,7 +139,6 @@ impl Show for StyleElem {
 /// dictionary with keys `width` and `height`, both of type
 /// [`length`]($type/length).
 ///
-
 /// ```example
 /// #let text = lorem(30)
 /// #layout(size => style(styles => [
@@ -173,6 +172,13 @@ impl Show for StyleElem {
 /// Note that this function will provide an infinite width or height if one of
 /// the page width or height is `auto`, respectively.
 ///
+/// The function is called anew each time the surrounding content is laid out,
+/// for instance once per region when the content breaks across pages or
+/// columns. The size it receives is determined by the container alone, so
+/// the returned content cannot feed back into it. Nesting `layout` calls
+/// more than 64 levels deep, e.g. by recursively returning another `layout`
+/// call, produces an error.
+///
 /// Display: Layout
 /// Category: meta
 #[func]
@@ -200,6 +206,16 @@ struct LayoutElem {
     /// The function to call with the outer container's (or page's) size.
     #[required]
     func: Func,
+
+    /// How many `layout` calls this one is nested in.
+    #[internal]
+    #[default(0)]
+    depth: usize,
+}
+
+impl LayoutElem {
+    /// The maximum nesting depth of `layout` calls.
+    const MAX_DEPTH: usize = 64;
 }
 
 impl Layout for LayoutElem {
@@ -213,10 +229,16 @@ impl Layout for LayoutElem {
         // Gets the current region's base size, which will be the size of the
         // outer container, or of the page if there is no such container.
         let Size { x, y } = regions.base();
+        let depth = Self::depth_in(styles) + 1;
+        if depth > Self::MAX_DEPTH {
+            bail!(self.span(), "maximum layout depth exceeded");
+        }
+
         let result = self
             .func()
             .call_vt(vt, [dict! { "width" => x, "height" => y }])?
-            .display();
+            .display()
+            .styled(Self::set_depth(depth));
         result.layout(vt, styles, regions)
     }
 }
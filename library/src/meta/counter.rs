@@ -234,6 +234,10 @@ use crate::prelude::*;
 /// - level: integer (named)
 ///   The depth at which to step the counter. Defaults to `{1}`.
 ///
+/// - by: integer (named)
+///   The amount by which to step the counter. Deeper levels are reset just
+///   like for a single step. Defaults to `{1}`.
+///
 /// - returns: content
 ///
 /// ### update()
@@ -319,11 +323,14 @@ impl Counter {
             "display" => self
                 .display(args.eat()?, args.named("both")?.unwrap_or(false))
                 .into_value(),
-            "step" => self
-                .update(CounterUpdate::Step(
-                    args.named("level")?.unwrap_or(NonZeroUsize::ONE),
-                ))
-                .into_value(),
+            "step" => {
+                let level = args.named("level")?.unwrap_or(NonZeroUsize::ONE);
+                let update = match args.named("by")? {
+                    Some(by) => CounterUpdate::StepBy(level, by),
+                    None => CounterUpdate::Step(level),
+                };
+                self.update(update).into_value()
+            }
             "update" => self.update(args.expect("value or function")?).into_value(),
             "at" => self.at(&mut vm.vt, args.expect("location")?)?.into_value(),
             "final" => self.final_(&mut vm.vt, args.expect("location")?)?.into_value(),
@@ -522,6 +529,8 @@ pub enum CounterUpdate {
     Set(CounterState),
     /// Increase the number for the given level by one.
     Step(NonZeroUsize),
+    /// Increase the number for the given level by the given amount.
+    StepBy(NonZeroUsize, usize),
     /// Apply the given function to the counter's state.
     Func(Func),
 }
@@ -554,6 +563,7 @@ impl CounterState {
         match update {
             CounterUpdate::Set(state) => *self = state,
             CounterUpdate::Step(level) => self.step(level, 1),
+            CounterUpdate::StepBy(level, by) => self.step(level, by),
             CounterUpdate::Func(func) => {
                 *self = func.call_vt(vt, self.0.iter().copied())?.cast().at(func.span())?
             }
@@ -570,9 +580,13 @@ impl CounterState {
             self.0.truncate(level);
         }
 
-        while self.0.len() < level {
+        while self.0.len() < level - 1 {
             self.0.push(1);
         }
+
+        if self.0.len() < level {
+            self.0.push(by);
+        }
     }
 
     /// Get the first number of the state.
@@ -1,3 +1,6 @@
+use typst::eval::Datetime;
+
+use super::EmbedElem;
 use crate::layout::{LayoutRoot, PageElem};
 use crate::prelude::*;
 
@@ -29,6 +32,25 @@ pub struct DocumentElem {
     /// The document's authors.
     pub author: Author,
 
+    /// The document's subject, i.e. a short description of its contents.
+    pub subject: Option<EcoString>,
+
+    /// The document's keywords.
+    pub keywords: Keywords,
+
+    /// The document's creation date.
+    ///
+    /// The date is embedded as both the creation and modification date of the
+    /// PDF. If it only contains a time, it is ignored.
+    ///
+    /// ```example
+    /// #set document(
+    ///   keywords: ("typesetting", "markup"),
+    ///   date: datetime(year: 2023, month: 5, day: 1),
+    /// )
+    /// ```
+    pub date: Option<Datetime>,
+
     /// The page runs.
     #[internal]
     #[variadic]
@@ -58,7 +80,18 @@ impl LayoutRoot for DocumentElem {
             }
 
             if let Some(page) = child.to::<PageElem>() {
-                let number = NonZeroUsize::ONE.saturating_add(pages.len());
+                let mut number = NonZeroUsize::ONE.saturating_add(pages.len());
+
+                // Insert an empty page if the run must start on an even or
+                // odd page.
+                if let Some(parity) = page.clear_to(styles) {
+                    if !parity.matches(number.get()) {
+                        let blank = page.clone().with_body(Content::empty());
+                        pages.extend(blank.layout(vt, styles, number)?);
+                        number = NonZeroUsize::ONE.saturating_add(pages.len());
+                    }
+                }
+
                 let fragment = page.layout(vt, styles, number)?;
                 pages.extend(fragment);
             } else {
@@ -66,10 +99,21 @@ impl LayoutRoot for DocumentElem {
             }
         }
 
+        // Collect the embedded files.
+        let mut attachments = vec![];
+        for elem in vt.introspector.query(&EmbedElem::func().select()) {
+            let embed = elem.to::<EmbedElem>().unwrap();
+            attachments.push(embed.attachment(vt, StyleChain::default())?);
+        }
+
         Ok(Document {
             pages,
             title: self.title(styles),
             author: self.author(styles).0,
+            subject: self.subject(styles),
+            keywords: self.keywords(styles).0,
+            date: self.date(styles),
+            attachments,
         })
     }
 }
@@ -84,3 +128,14 @@ cast! {
     v: EcoString => Self(vec![v]),
     v: Array => Self(v.into_iter().map(Value::cast).collect::<StrResult<_>>()?),
 }
+
+/// A list of keywords.
+#[derive(Debug, Default, Clone, Hash)]
+pub struct Keywords(Vec<EcoString>);
+
+cast! {
+    Keywords,
+    self => self.0.into_value(),
+    v: EcoString => Self(vec![v]),
+    v: Array => Self(v.into_iter().map(Value::cast).collect::<StrResult<_>>()?),
+}
@@ -0,0 +1,86 @@
+use std::path::Path;
+
+use typst::doc::Attachment;
+
+use crate::prelude::*;
+
+/// A file embedded into the PDF.
+///
+/// The file is attached to the exported PDF and can be opened from the
+/// attachment panel of PDF viewers. This is useful to ship the data behind a
+/// paper with the paper itself. The element itself produces no visible output.
+///
+/// Very large files are embedded as well, but produce a warning in the logs.
+///
+/// ## Example { #example }
+/// ```example
+/// #embed(
+///   "data.csv",
+///   description: "Measurements",
+///   mime: "text/csv",
+/// )
+/// ```
+///
+/// Display: Embed
+/// Category: meta
+#[element(Locatable, Synthesize, Show)]
+pub struct EmbedElem {
+    /// Path to the file to embed.
+    #[required]
+    #[parse(
+        let Spanned { v: path, span } =
+            args.expect::<Spanned<EcoString>>("path to file")?;
+        let path: EcoString = vm.locate(&path).at(span)?.to_string_lossy().into();
+        let _ = vm.world().file(Path::new(path.as_str())).at(span)?;
+        path
+    )]
+    pub path: EcoString,
+
+    /// A description of the file's contents.
+    pub description: Option<EcoString>,
+
+    /// The MIME type of the file, for example `{"text/csv"}`.
+    pub mime: Option<EcoString>,
+}
+
+impl EmbedElem {
+    /// The size in bytes above which embedding a file produces a warning.
+    pub const LARGE: usize = 16 * 1024 * 1024;
+
+    /// Load the file into an attachment.
+    pub fn attachment(&self, vt: &Vt, styles: StyleChain) -> SourceResult<Attachment> {
+        let path = self.path();
+        let data = vt.world.file(Path::new(path.as_str())).at(self.span())?;
+        if data.len() > Self::LARGE {
+            tracing::warn!("Embedding large file {path} with {} bytes", data.len());
+        }
+
+        let name = Path::new(path.as_str())
+            .file_name()
+            .map(|name| name.to_string_lossy().into())
+            .unwrap_or(path);
+
+        Ok(Attachment {
+            name,
+            data,
+            description: self.description(styles),
+            mime: self.mime(styles),
+        })
+    }
+}
+
+impl Synthesize for EmbedElem {
+    fn synthesize(&mut self, _vt: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        self.push_description(self.description(styles));
+        self.push_mime(self.mime(styles));
+        Ok(())
+    }
+}
+
+impl Show for EmbedElem {
+    #[tracing::instrument(name = "EmbedElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+        // The file is collected by the document.
+        Ok(Content::empty())
+    }
+}
@@ -0,0 +1,211 @@
+use std::str::FromStr;
+
+use super::{Count, Counter, CounterUpdate, HeadingElem, Numbering, NumberingPattern};
+use crate::layout::{HElem, VElem};
+use crate::prelude::*;
+use crate::text::SuperElem;
+
+/// An endnote.
+///
+/// Like a [footnote]($func/footnote), an endnote inserts a superscript number
+/// into the text. Instead of the bottom of the page, the note itself is listed
+/// wherever you place an [endnote listing]($func/endnotes). Notes are numbered
+/// sequentially throughout your document.
+///
+/// To refer to an existing endnote a second time, pass its label instead of
+/// content. The reference shares the number of the labelled note and does not
+/// add another entry to the listing.
+///
+/// ## Example { #example }
+/// ```example
+/// Endnotes collect remarks
+/// #endnote[Like this one.] <first>
+/// in one place. They can be
+/// cited again #endnote(<first>).
+///
+/// #endnotes()
+/// ```
+///
+/// Display: Endnote
+/// Category: meta
+#[element(Locatable, Synthesize, Count, Show)]
+pub struct EndnoteElem {
+    /// How to number endnotes.
+    ///
+    /// ```example
+    /// #set endnote(numbering: "i")
+    ///
+    /// Roman #endnote[Numerals].
+    ///
+    /// #endnotes()
+    /// ```
+    #[default(Numbering::Pattern(NumberingPattern::from_str("1").unwrap()))]
+    pub numbering: Numbering,
+
+    /// The content to put into the endnote or the label of an existing
+    /// endnote to refer to.
+    #[required]
+    pub body: EndnoteBody,
+}
+
+impl EndnoteElem {
+    /// Whether this endnote refers to another one instead of defining a note.
+    pub fn is_ref(&self) -> bool {
+        matches!(self.body(), EndnoteBody::Reference(_))
+    }
+
+    /// The location of the endnote that defines the note's content.
+    pub fn declaration_location(&self, vt: &Vt) -> StrResult<Location> {
+        match self.body() {
+            EndnoteBody::Content(_) => Ok(self.0.location().unwrap()),
+            EndnoteBody::Reference(label) => {
+                let elem = vt.introspector.query_label(&label)?;
+                match elem.to::<Self>() {
+                    Some(note) if !note.is_ref() => Ok(elem.location().unwrap()),
+                    _ => Err("label must refer to an endnote with content".into()),
+                }
+            }
+        }
+    }
+}
+
+impl Synthesize for EndnoteElem {
+    fn synthesize(&mut self, _vt: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        self.push_numbering(self.numbering(styles));
+        Ok(())
+    }
+}
+
+impl Show for EndnoteElem {
+    #[tracing::instrument(name = "EndnoteElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let loc = self.declaration_location(vt).at(self.span())?;
+        let numbering = self.numbering(styles);
+        let counter = Counter::of(Self::func());
+        let num = counter.at(vt, loc)?.display(vt, &numbering)?;
+        let sup = SuperElem::new(num).pack();
+        let hole = HElem::new(Abs::zero().into()).with_weak(true).pack();
+        Ok(hole + sup.linked(Destination::Location(loc.variant(1))))
+    }
+}
+
+impl Count for EndnoteElem {
+    fn update(&self) -> Option<CounterUpdate> {
+        (!self.is_ref()).then(|| CounterUpdate::Step(NonZeroUsize::ONE))
+    }
+}
+
+/// The body of an endnote: Either its content or a label referring to another
+/// endnote.
+#[derive(Debug, Clone, Hash)]
+pub enum EndnoteBody {
+    /// The content of the note.
+    Content(Content),
+    /// A reference to the endnote with this label.
+    Reference(Label),
+}
+
+cast! {
+    EndnoteBody,
+    self => match self {
+        Self::Content(v) => v.into_value(),
+        Self::Reference(v) => v.into_value(),
+    },
+    v: Label => Self::Reference(v),
+    v: Content => Self::Content(v),
+}
+
+/// A listing of endnotes.
+///
+/// The listing contains all endnotes since the previous listing or the start
+/// of the document. To list notes at the end of each chapter, place a
+/// listing before each top-level heading and one at the end of the document.
+///
+/// ## Example { #example }
+/// ```example
+/// #show heading.where(level: 1): it => {
+///   endnotes()
+///   it
+/// }
+///
+/// = Intro
+/// Hello #endnote[World].
+///
+/// = Outro
+/// Bye #endnote[Moon].
+///
+/// #endnotes()
+/// ```
+///
+/// Display: Endnote Listing
+/// Category: meta
+#[element(Locatable, Show)]
+pub struct EndnotesElem {
+    /// The title of the listing. Displayed as a heading that is not numbered.
+    ///
+    /// ```example
+    /// Hey #endnote[You].
+    ///
+    /// #endnotes(title: [Notes])
+    /// ```
+    pub title: Option<Content>,
+
+    /// The gap between the listing's entries.
+    #[default(Em::new(0.5).into())]
+    pub gap: Length,
+
+    /// The indent of each entry.
+    #[default(Em::new(1.0).into())]
+    pub indent: Length,
+}
+
+impl Show for EndnotesElem {
+    #[tracing::instrument(name = "EndnotesElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let loc = self.0.location().unwrap();
+
+        // Collect the notes since the previous listing.
+        let mut selector = EndnoteElem::func().select().before(loc, false);
+        let listings = Self::func().select().before(loc, false);
+        if let Some(prev) = vt.introspector.query(&listings).last() {
+            selector = selector.after(prev.location().unwrap(), false);
+        }
+
+        let counter = Counter::of(EndnoteElem::func());
+        let number_gap = Em::new(0.05);
+        let gap = self.gap(styles);
+        let mut entries = vec![];
+        for elem in vt.introspector.query(&selector) {
+            let note = elem.to::<EndnoteElem>().unwrap();
+            let EndnoteBody::Content(body) = note.body() else { continue };
+            let loc = elem.location().unwrap();
+            let numbering = note.numbering(StyleChain::default());
+            let num = counter.at(vt, loc)?.display(vt, &numbering)?;
+            let sup = SuperElem::new(num)
+                .pack()
+                .linked(Destination::Location(loc))
+                .backlinked(loc.variant(1));
+            let entry = Content::sequence([
+                HElem::new(self.indent(styles).into()).pack(),
+                sup,
+                HElem::new(number_gap.into()).with_weak(true).pack(),
+                body,
+            ]);
+            entries.push(VElem::block_around(gap.into()).pack());
+            entries.push(entry);
+        }
+
+        let mut seq = vec![];
+        if let Some(title) = self.title(styles) {
+            seq.push(
+                HeadingElem::new(title)
+                    .with_level(NonZeroUsize::ONE)
+                    .with_numbering(None)
+                    .pack(),
+            );
+        }
+
+        seq.extend(entries);
+        Ok(Content::sequence(seq))
+    }
+}
@@ -1,7 +1,8 @@
 use std::str::FromStr;
 
 use super::{
-    Count, Counter, CounterKey, CounterUpdate, LocalName, Numbering, NumberingPattern,
+    Count, Counter, CounterKey, CounterState, CounterUpdate, HeadingElem, LocalName,
+    Numbering, NumberingPattern,
 };
 use crate::layout::{BlockElem, VElem};
 use crate::meta::{Outlinable, Refable, Supplement};
@@ -134,11 +135,53 @@ pub struct FigureElem {
     /// ```
     pub supplement: Smart<Option<Supplement>>,
 
+    /// The plural of the automatic supplement, used for references to
+    /// multiple figures.
+    #[synthesized]
+    pub plural_supplement: Option<Content>,
+
     /// How to number the figure. Accepts a
     /// [numbering pattern or function]($func/numbering).
     #[default(Some(NumberingPattern::from_str("1").unwrap().into()))]
     pub numbering: Option<Numbering>,
 
+    /// The heading level at which the figure numbering restarts.
+    ///
+    /// When this is set, the figure's number consists of the numbers of the
+    /// enclosing headings up to this level, followed by the number of the
+    /// figure within that section. Figures in the second chapter are then
+    /// numbered as `2.1`, `2.2`, and so on. This also applies to references
+    /// and outlines.
+    ///
+    /// The figure's `counter` itself is not reset and continues to count
+    /// through the whole document.
+    ///
+    /// ```example
+    /// #set heading(numbering: "1.")
+    /// #set figure(numbering: "1.1", reset-at: 1)
+    ///
+    /// = Introduction
+    /// #figure(rect(), caption: [A box])
+    ///
+    /// = Results
+    /// #figure(circle(), caption: [A circle]) <circle>
+    /// See @circle.
+    /// ```
+    pub reset_at: Option<NonZeroUsize>,
+
+    /// Whether the caption is placed above (`{top}`) or below (`{bottom}`)
+    /// the figure's body.
+    ///
+    /// ```example
+    /// #figure(
+    ///   table(columns: 2, [a], [b]),
+    ///   caption: [A table with its caption on top],
+    ///   caption-pos: top,
+    /// )
+    /// ```
+    #[default(VerticalAlign(GenAlign::Specific(Align::Bottom)))]
+    pub caption_pos: VerticalAlign,
+
     /// The vertical gap between the body and caption.
     #[default(Em::new(0.65).into())]
     pub gap: Length,
@@ -148,6 +191,25 @@ pub struct FigureElem {
     #[default(true)]
     pub outlined: bool,
 
+    /// A text describing the figure for readers that can't see it.
+    ///
+    /// The text is passed on to all [images]($func/image) in the figure's body
+    /// that don't have their own `alt` text, so that exporters can make it
+    /// available to assistive technology.
+    ///
+    /// ```example
+    /// #figure(
+    ///   image("molecular.jpg", width: 60%),
+    ///   alt: "A ball-and-stick model of a molecule",
+    /// )
+    /// ```
+    pub alt: Option<EcoString>,
+
+    /// Whether to use the plain text of the caption as the figure's `alt`
+    /// text if none is given.
+    #[default(false)]
+    pub caption_alt: bool,
+
     /// Convenience field to get access to the counter for this figure.
     ///
     /// The counter only depends on the `kind`:
@@ -174,6 +236,20 @@ impl Synthesize for FigureElem {
                 .unwrap_or_else(|| FigureKind::Elem(ImageElem::func()))
         });
 
+        // Determine the plural of the automatic supplement, if the kind has
+        // a local name.
+        let plural = match (&kind, self.supplement(styles)) {
+            (FigureKind::Elem(func), Smart::Auto) => {
+                Content::new(*func).with::<dyn LocalName>().map(|c| {
+                    TextElem::packed(c.local_plural_name(
+                        TextElem::lang_in(styles),
+                        TextElem::region_in(styles),
+                    ))
+                })
+            }
+            _ => None,
+        };
+
         // Resolve the supplement.
         let supplement = match self.supplement(styles) {
             Smart::Auto => {
@@ -224,10 +300,23 @@ impl Synthesize for FigureElem {
         self.push_caption(self.caption(styles));
         self.push_kind(Smart::Custom(kind));
         self.push_supplement(Smart::Custom(Some(Supplement::Content(supplement))));
+        self.push_plural_supplement(plural);
         self.push_numbering(numbering);
+        self.push_reset_at(self.reset_at(styles));
+        self.push_caption_pos(self.caption_pos(styles));
         self.push_outlined(self.outlined(styles));
         self.push_counter(Some(counter));
 
+        // Resolve the alternative text, falling back to the caption if
+        // requested.
+        let alt = self.alt(styles).or_else(|| {
+            self.caption_alt(styles)
+                .then(|| self.caption(styles))
+                .flatten()
+                .map(|caption| caption.plain_text())
+        });
+        self.push_alt(alt);
+
         Ok(())
     }
 }
@@ -237,15 +326,28 @@ impl Show for FigureElem {
     fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
         let mut realized = self.body();
 
+        // Pass the alternative text on to the images in the body.
+        if let Some(alt) = self.alt(styles) {
+            realized = realized.styled(ImageElem::set_alt(Some(alt)));
+        }
+
+        // Let the subfigures in the body know which figure they belong to.
+        realized = realized.styled(SubfigureElem::set_parent(self.0.location()));
+
         // Build the caption, if any.
         if let Some(caption) = self.full_caption(vt)? {
-            realized += VElem::weak(self.gap(styles).into()).pack();
-            realized += caption;
+            let gap = VElem::weak(self.gap(styles).into()).pack();
+            realized = if self.caption_pos(styles).0 == Align::Top.into() {
+                caption + gap + realized
+            } else {
+                realized + gap + caption
+            };
         }
 
         // Wrap the contents in a block.
+        let tag = Tag::Figure(self.alt(styles));
         Ok(BlockElem::new()
-            .with_body(Some(realized))
+            .with_body(Some(realized.tagged(tag)))
             .pack()
             .aligned(Axes::with_x(Some(Align::Center.into()))))
     }
@@ -277,6 +379,10 @@ impl Refable for FigureElem {
         }
     }
 
+    fn plural_supplement(&self) -> Content {
+        self.plural_supplement().unwrap_or_else(|| Refable::supplement(self))
+    }
+
     fn counter(&self) -> Counter {
         self.counter().unwrap_or_else(|| Counter::of(Self::func()))
     }
@@ -284,6 +390,10 @@ impl Refable for FigureElem {
     fn numbering(&self) -> Option<Numbering> {
         self.numbering(StyleChain::default())
     }
+
+    fn numbers(&self, vt: &mut Vt, _: Location) -> SourceResult<CounterState> {
+        self.numbers(vt)
+    }
 }
 
 impl Outlinable for FigureElem {
@@ -312,8 +422,7 @@ impl FigureElem {
             self.counter(),
             self.numbering(StyleChain::default()),
         ) {
-            let loc = self.0.location().unwrap();
-            let numbers = counter.at(vt, loc)?.display(vt, &numbering)?;
+            let numbers = self.numbers_of(vt, &counter)?.display(vt, &numbering)?;
 
             if !supplement.is_empty() {
                 supplement += TextElem::packed("\u{a0}");
@@ -324,6 +433,231 @@ impl FigureElem {
 
         Ok(Some(caption))
     }
+
+    /// The numbers of the figure, taking `reset-at` into account.
+    pub fn numbers(&self, vt: &mut Vt) -> SourceResult<CounterState> {
+        let counter = self.counter().unwrap_or_else(|| Counter::of(Self::func()));
+        self.numbers_of(vt, &counter)
+    }
+
+    /// The numbers of the figure with respect to the given counter.
+    fn numbers_of(&self, vt: &mut Vt, counter: &Counter) -> SourceResult<CounterState> {
+        let loc = self.0.location().unwrap();
+        let state = counter.at(vt, loc)?;
+        let Some(level) = self.reset_at(StyleChain::default()) else {
+            return Ok(state);
+        };
+
+        // Find the last heading that starts the section we're numbering in.
+        let headings = vt
+            .introspector
+            .query(&Selector::Elem(HeadingElem::func(), None).before(loc, false));
+        let start = headings.iter().rev().find(|heading| {
+            heading
+                .to::<HeadingElem>()
+                .map_or(false, |heading| heading.level(StyleChain::default()) <= level)
+        });
+
+        // Count only the figures since the start of the section.
+        let offset = match start {
+            Some(heading) => counter.at(vt, heading.location().unwrap())?.first(),
+            None => 0,
+        };
+
+        let mut numbers = Counter::of(HeadingElem::func()).at(vt, loc)?;
+        numbers.0.resize(level.get(), 0);
+        numbers.0.push(state.first().saturating_sub(offset));
+        Ok(numbers)
+    }
+}
+
+/// A panel of a figure with its own caption.
+///
+/// Subfigures are numbered within the figure that contains them, so the
+/// panels of the fourth figure can be referenced as `Figure 4a`, `Figure 4b`,
+/// and so on. To lay out multiple panels side by side, put them into a
+/// [grid]($func/grid) within the figure.
+///
+/// ## Example { #example }
+/// ```example
+/// #figure(
+///   grid(
+///     columns: 2,
+///     gutter: 1em,
+///     [#subfigure(
+///       rect(fill: aqua),
+///       caption: [Water],
+///     ) <water>],
+///     [#subfigure(
+///       rect(fill: olive),
+///       caption: [Land],
+///     ) <land>],
+///   ),
+///   caption: [Two kinds of surfaces],
+/// )
+///
+/// @water shows water and
+/// @land shows land.
+/// ```
+///
+/// Display: Subfigure
+/// Category: meta
+#[element(Locatable, Synthesize, Show, Refable)]
+pub struct SubfigureElem {
+    /// The content of the subfigure.
+    #[required]
+    pub body: Content,
+
+    /// The subfigure's caption.
+    pub caption: Option<Content>,
+
+    /// How to number the subfigure within its figure. Accepts a
+    /// [numbering pattern or function]($func/numbering).
+    ///
+    /// In references, the pattern is appended to the number of the enclosing
+    /// figure without its prefix and suffix. If either is numbered with a
+    /// function, references show only the subfigure's own number.
+    #[default(Some(NumberingPattern::from_str("(a)").unwrap().into()))]
+    pub numbering: Option<Numbering>,
+
+    /// Whether the caption is placed above (`{top}`) or below (`{bottom}`)
+    /// the subfigure's body.
+    ///
+    /// ```example
+    /// #set subfigure(caption-pos: top)
+    /// #figure(
+    ///   grid(
+    ///     columns: 2,
+    ///     gutter: 1em,
+    ///     subfigure(rect(), caption: [Empty]),
+    ///     subfigure(rect(fill: red), caption: [Filled]),
+    ///   ),
+    ///   caption: [Rectangles],
+    /// )
+    /// ```
+    #[default(VerticalAlign(GenAlign::Specific(Align::Bottom)))]
+    pub caption_pos: VerticalAlign,
+
+    /// The vertical gap between the body and caption.
+    #[default(Em::new(0.65).into())]
+    pub gap: Length,
+
+    /// The location of the figure containing the subfigure.
+    #[internal]
+    pub parent: Option<Location>,
+
+    /// The supplement of the enclosing figure.
+    #[synthesized]
+    pub supplement: Content,
+
+    /// The numbering of the enclosing figure.
+    #[synthesized]
+    pub figure_numbering: Option<Numbering>,
+}
+
+impl SubfigureElem {
+    /// Find the figure containing this subfigure.
+    fn figure(&self, vt: &mut Vt) -> Option<FigureElem> {
+        let parent = self.parent(StyleChain::default())?;
+        vt.introspector
+            .query_first(&Selector::Location(parent))
+            .and_then(|figure| figure.to::<FigureElem>().cloned())
+    }
+
+    /// Whether references to the subfigure include the figure's number, which
+    /// is only possible if both are numbered with a pattern.
+    fn is_nested(&self) -> bool {
+        matches!(
+            (self.figure_numbering(), self.numbering(StyleChain::default())),
+            (Some(Numbering::Pattern(_)), Some(Numbering::Pattern(_)))
+        )
+    }
+
+    /// The subfigure's number within its figure.
+    fn number(&self, vt: &mut Vt) -> SourceResult<usize> {
+        let loc = self.0.location().unwrap();
+        Ok(Refable::counter(self).at(vt, loc)?.first())
+    }
+}
+
+impl Synthesize for SubfigureElem {
+    fn synthesize(&mut self, vt: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        self.push_parent(self.parent(styles));
+
+        let figure = self.figure(vt);
+        let supplement = match &figure {
+            Some(figure) => Refable::supplement(figure),
+            None => Content::empty(),
+        };
+
+        self.push_caption(self.caption(styles));
+        self.push_numbering(self.numbering(styles));
+        self.push_supplement(supplement);
+        self.push_figure_numbering(
+            figure.and_then(|figure| figure.numbering(StyleChain::default())),
+        );
+
+        Ok(())
+    }
+}
+
+impl Show for SubfigureElem {
+    #[tracing::instrument(name = "SubfigureElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let mut realized = self.body();
+
+        if let Some(mut caption) = self.caption(styles) {
+            if let Some(numbering) = self.numbering(styles) {
+                let number = self.number(vt)?;
+                let number = numbering.apply_vt(vt, &[number])?.display();
+                caption = number + TextElem::packed(" ") + caption;
+            }
+
+            let gap = VElem::weak(self.gap(styles).into()).pack();
+            realized = if self.caption_pos(styles).0 == Align::Top.into() {
+                caption + gap + realized
+            } else {
+                realized + gap + caption
+            };
+        }
+
+        Ok(BlockElem::new()
+            .with_body(Some(realized))
+            .pack()
+            .aligned(Axes::with_x(Some(Align::Center.into()))))
+    }
+}
+
+impl Refable for SubfigureElem {
+    fn supplement(&self) -> Content {
+        self.supplement()
+    }
+
+    fn counter(&self) -> Counter {
+        // Counts the subfigures of the same figure.
+        Counter::new(CounterKey::Selector(Selector::Elem(
+            Self::func(),
+            Some(dict! { "parent" => self.parent(StyleChain::default()) }),
+        )))
+    }
+
+    fn numbering(&self) -> Option<Numbering> {
+        match (self.figure_numbering(), self.numbering(StyleChain::default())?) {
+            (Some(Numbering::Pattern(figure)), Numbering::Pattern(own)) => {
+                Some(Numbering::Pattern(figure.nested(&own)))
+            }
+            (_, own) => Some(own),
+        }
+    }
+
+    fn numbers(&self, vt: &mut Vt, _: Location) -> SourceResult<CounterState> {
+        let mut numbers = match self.figure(vt) {
+            Some(figure) if self.is_nested() => figure.numbers(vt)?,
+            _ => CounterState(Default::default()),
+        };
+        numbers.0.push(self.number(vt)?);
+        Ok(numbers)
+    }
 }
 
 /// The `kind` parameter of a [`FigureElem`].
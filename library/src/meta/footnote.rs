@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use super::{Counter, Numbering, NumberingPattern};
+use super::{Count, Counter, CounterUpdate, Numbering, NumberingPattern};
 use crate::layout::{HElem, ParElem};
 use crate::prelude::*;
 use crate::text::{SuperElem, TextElem, TextSize};
@@ -28,9 +28,19 @@ use crate::visualize::LineElem;
 /// there is a space before it in the markup. To force space, you can use the
 /// string `[#" "]` or explicit [horizontal spacing]($func/h).
 ///
+/// To refer to an existing footnote a second time, pass its label instead of
+/// content. The reference shows the number of the labelled footnote and does
+/// not add another entry to the page.
+///
+/// ```example
+/// You can cite the same
+/// footnote #footnote[Once.] <once>
+/// twice #footnote(<once>).
+/// ```
+///
 /// Display: Footnote
 /// Category: meta
-#[element(Locatable, Synthesize, Show)]
+#[element(Locatable, Synthesize, Count, Show)]
 #[scope(
     scope.define("entry", FootnoteEntry::func());
     scope
@@ -39,9 +49,8 @@ pub struct FootnoteElem {
     /// How to number footnotes.
     ///
     /// By default, the footnote numbering continues throughout your document.
-    /// If you prefer per-page footnote numbering, you can reset the footnote
-    /// [counter]($func/counter) in the page [header]($func/page.header). In the
-    /// future, there might be a simpler way to achieve this.
+    /// If you prefer per-page footnote numbering, see the
+    /// [`per-page`]($func/footnote.per-page) parameter.
     ///
     /// ```example
     /// #set footnote(numbering: "*")
@@ -53,14 +62,35 @@ pub struct FootnoteElem {
     #[default(Numbering::Pattern(NumberingPattern::from_str("1").unwrap()))]
     pub numbering: Numbering,
 
-    /// The content to put into the footnote.
+    /// Whether to restart the footnote numbering on each page.
+    ///
+    /// The number of a footnote is determined by the page on which its mark
+    /// ends up, so the mark and the entry always agree, even if the entry is
+    /// moved to a later page. Note that the footnote [counter]($func/counter)
+    /// still counts throughout the document.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #set footnote(per-page: true)
+    ///
+    /// First #footnote[One] and
+    /// second #footnote[Two].
+    /// #pagebreak()
+    /// Again first #footnote[Three].
+    /// ```
+    #[default(false)]
+    pub per_page: bool,
+
+    /// The content to put into the footnote or the label of an existing
+    /// footnote to refer to.
     #[required]
-    pub body: Content,
+    pub body: FootnoteBody,
 }
 
 impl Synthesize for FootnoteElem {
     fn synthesize(&mut self, _vt: &mut Vt, styles: StyleChain) -> SourceResult<()> {
         self.push_numbering(self.numbering(styles));
+        self.push_per_page(self.per_page(styles));
         Ok(())
     }
 }
@@ -68,17 +98,93 @@ impl Synthesize for FootnoteElem {
 impl Show for FootnoteElem {
     #[tracing::instrument(name = "FootnoteElem::show", skip_all)]
     fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
-        let loc = self.0.location().unwrap();
-        let numbering = self.numbering(styles);
-        let counter = Counter::of(Self::func());
-        let num = counter.at(vt, loc)?.display(vt, &numbering)?;
+        let num = self.display_number(vt, styles)?;
         let sup = SuperElem::new(num).pack();
         let hole = HElem::new(Abs::zero().into()).with_weak(true).pack();
-        let loc = self.0.location().unwrap().variant(1);
+        let loc = self.declaration_location(vt).at(self.span())?.variant(1);
         Ok(hole + sup.linked(Destination::Location(loc)))
     }
 }
 
+impl Count for FootnoteElem {
+    fn update(&self) -> Option<CounterUpdate> {
+        (!self.is_ref()).then(|| CounterUpdate::Step(NonZeroUsize::ONE))
+    }
+}
+
+impl FootnoteElem {
+    /// Whether this footnote refers to another one instead of defining a note.
+    pub fn is_ref(&self) -> bool {
+        matches!(self.body(), FootnoteBody::Reference(_))
+    }
+
+    /// The content of the footnote, if it defines a note.
+    pub fn body_content(&self) -> Option<Content> {
+        match self.body() {
+            FootnoteBody::Content(content) => Some(content),
+            FootnoteBody::Reference(_) => None,
+        }
+    }
+
+    /// The location of the footnote that defines the note's content.
+    pub fn declaration_location(&self, vt: &Vt) -> StrResult<Location> {
+        match self.body() {
+            FootnoteBody::Content(_) => Ok(self.0.location().unwrap()),
+            FootnoteBody::Reference(label) => {
+                let elem = vt.introspector.query_label(&label)?;
+                match elem.to::<Self>() {
+                    Some(note) if !note.is_ref() => Ok(elem.location().unwrap()),
+                    _ => Err("label must refer to a footnote with content".into()),
+                }
+            }
+        }
+    }
+
+    /// Display the number of this footnote.
+    fn display_number(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let loc = self.declaration_location(vt).at(self.span())?;
+        let numbering = self.numbering(styles);
+        if !self.per_page(styles) {
+            let counter = Counter::of(Self::func());
+            return counter.at(vt, loc)?.display(vt, &numbering);
+        }
+
+        // Count the defining footnotes whose marks are on the same page.
+        let page = vt.introspector.page(loc);
+        let number = vt
+            .introspector
+            .query(&Self::func().select())
+            .iter()
+            .filter(|elem| elem.to::<Self>().map_or(false, |note| !note.is_ref()))
+            .filter_map(|elem| elem.location())
+            .filter(|&other| vt.introspector.page(other) == page)
+            .position(|other| other == loc)
+            .map_or(1, |i| i + 1);
+
+        Ok(numbering.apply_vt(vt, &[number])?.display())
+    }
+}
+
+/// The body of a footnote: Either its content or a label referring to another
+/// footnote.
+#[derive(Debug, Clone, Hash)]
+pub enum FootnoteBody {
+    /// The content of the note.
+    Content(Content),
+    /// A reference to the footnote with this label.
+    Reference(Label),
+}
+
+cast! {
+    FootnoteBody,
+    self => match self {
+        Self::Content(v) => v.into_value(),
+        Self::Reference(v) => v.into_value(),
+    },
+    v: Label => Self::Reference(v),
+    v: Content => Self::Content(v),
+}
+
 /// An entry in a footnote list.
 ///
 /// This function is not intended to be called directly. Instead, it is used
@@ -184,10 +290,8 @@ impl Show for FootnoteEntry {
     fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
         let note = self.note();
         let number_gap = Em::new(0.05);
-        let numbering = note.numbering(StyleChain::default());
-        let counter = Counter::of(FootnoteElem::func());
         let loc = note.0.location().unwrap();
-        let num = counter.at(vt, loc)?.display(vt, &numbering)?;
+        let num = note.display_number(vt, StyleChain::default())?;
         let sup = SuperElem::new(num)
             .pack()
             .linked(Destination::Location(loc))
@@ -196,7 +300,7 @@ impl Show for FootnoteEntry {
             HElem::new(self.indent(styles).into()).pack(),
             sup,
             HElem::new(number_gap.into()).with_weak(true).pack(),
-            note.body(),
+            note.body_content().unwrap_or_default(),
         ]))
     }
 }
@@ -213,5 +317,5 @@ impl Finalize for FootnoteEntry {
 
 cast! {
     FootnoteElem,
-    v: Content => v.to::<Self>().cloned().unwrap_or_else(|| Self::new(v.clone())),
+    v: Content => v.to::<Self>().cloned().unwrap_or_else(|| Self::new(FootnoteBody::Content(v.clone()))),
 }
@@ -2,7 +2,7 @@ use typst::font::FontWeight;
 use typst::util::option_eq;
 
 use super::{Counter, CounterUpdate, LocalName, Numbering, Outlinable, Refable};
-use crate::layout::{BlockElem, HElem, VElem};
+use crate::layout::{BlockElem, HElem, ParElem, VElem};
 use crate::meta::{Count, Supplement};
 use crate::prelude::*;
 use crate::text::{SpaceElem, TextElem, TextSize};
@@ -35,6 +35,28 @@ use crate::text::{SpaceElem, TextElem, TextSize};
 /// To start, ...
 /// ```
 ///
+/// ## Appendices { #appendices }
+/// To number the appendix with letters, reset the heading counter and switch
+/// the numbering and supplement at the start of the appendix. Because both are
+/// resolved for each heading individually, references to appendix sections
+/// show the letter-based number and the new supplement, even when they occur
+/// in the main body.
+///
+/// ```example
+/// #set heading(numbering: "1.")
+///
+/// = Introduction
+/// The proofs are in @proofs.
+///
+/// #counter(heading).update(0)
+/// #set heading(
+///   numbering: "A.",
+///   supplement: [Appendix],
+/// )
+///
+/// = Proofs <proofs>
+/// ```
+///
 /// ## Syntax { #syntax }
 /// Headings have dedicated syntax: They can be created by starting a line with
 /// one or multiple equals signs, followed by a space. The number of equals
@@ -43,6 +65,10 @@ use crate::text::{SpaceElem, TextElem, TextSize};
 /// Display: Heading
 /// Category: meta
 #[element(Locatable, Synthesize, Count, Show, Finalize, LocalName, Refable, Outlinable)]
+#[scope(
+    scope.define("current", heading_current_func());
+    scope
+)]
 pub struct HeadingElem {
     /// The logical nesting depth of the heading, starting from one.
     #[default(NonZeroUsize::ONE)]
@@ -60,12 +86,29 @@ pub struct HeadingElem {
     /// ```
     pub numbering: Option<Numbering>,
 
+    /// The maximum level up to which headings are numbered. When this is
+    /// `{none}`, headings of all levels are numbered.
+    ///
+    /// Headings deeper than this level still step the heading counter, so the
+    /// numbers of the remaining headings stay consistent, but they are
+    /// displayed without a number.
+    ///
+    /// ```example
+    /// #set heading(numbering: "1.1", numbering-depth: 2)
+    ///
+    /// = A section
+    /// == A subsection
+    /// === An unnumbered sub-subsection
+    /// ```
+    pub numbering_depth: Option<NonZeroUsize>,
+
     /// A supplement for the heading.
     ///
     /// For references to headings, this is added before the referenced number.
     ///
     /// If a function is specified, it is passed the referenced heading and
-    /// should return content.
+    /// should return content. The heading's `level` and `numbering` fields are
+    /// always available on it, so the supplement can depend on the level.
     ///
     /// ```example
     /// #set heading(numbering: "1.", supplement: [Chapter])
@@ -76,8 +119,24 @@ pub struct HeadingElem {
     /// in @intro[Part], it is done
     /// manually.
     /// ```
+    ///
+    /// ```example
+    /// #set heading(
+    ///   numbering: "1.1",
+    ///   supplement: it => if it.level == 1 [Chapter] else [Section],
+    /// )
+    ///
+    /// = Introduction <intro>
+    /// == Motivation <motivation>
+    /// See @intro and @motivation.
+    /// ```
     pub supplement: Smart<Option<Supplement>>,
 
+    /// The plural of the automatic supplement, used for references to
+    /// multiple headings.
+    #[synthesized]
+    pub plural_supplement: Option<Content>,
+
     /// Whether the heading should appear in the outline.
     ///
     /// ```example
@@ -93,24 +152,98 @@ pub struct HeadingElem {
     #[default(true)]
     pub outlined: bool,
 
+    /// A short version of the heading's title.
+    ///
+    /// When set, the short title is used instead of the full title in the
+    /// [outline]($func/outline). It is also available as the heading's `short`
+    /// field, for example to display it in a page header. References to the
+    /// heading are not affected and still show the supplement and number.
+    ///
+    /// ```example
+    /// #outline()
+    ///
+    /// #heading(short: [Results])[
+    ///   Results of the very long
+    ///   and detailed experiment
+    /// ]
+    /// ```
+    pub short: Option<Content>,
+
     /// The heading's title.
     #[required]
     pub body: Content,
 }
 
+/// Find the title of the section that a location is in.
+///
+/// This returns the title of the closest heading of the given level before
+/// the location. If the heading has a [short title]($func/heading.short), that
+/// one is used. Before the first such heading, the result is empty. This is
+/// typically used to display the current chapter in a page header.
+///
+/// ```example
+/// >>> #set page(width: 160pt, height: 100pt)
+/// #set page(header: locate(loc => {
+///   emph(heading.current(loc))
+/// }))
+///
+/// = Introduction
+/// #lorem(15)
+///
+/// #heading(short: [Results])[
+///   Results of the experiment
+/// ]
+/// #lorem(10)
+/// ```
+///
+/// Display: Current Heading
+/// Category: meta
+#[func]
+pub fn heading_current(
+    /// The location to find the section for. See [`query`]($func/query) for
+    /// how to obtain it.
+    location: Location,
+    /// The level of the headings to consider.
+    #[named]
+    #[default(NonZeroUsize::ONE)]
+    level: NonZeroUsize,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> Content {
+    let selector = HeadingElem::func().where_(dict! { "level" => level });
+    vm.vt
+        .introspector
+        .query_before(&selector, location)
+        .map(|elem| {
+            let heading = elem.to::<HeadingElem>().unwrap();
+            heading.short(StyleChain::default()).unwrap_or_else(|| heading.body())
+        })
+        .unwrap_or_default()
+}
+
 impl Synthesize for HeadingElem {
     fn synthesize(&mut self, vt: &mut Vt, styles: StyleChain) -> SourceResult<()> {
-        // Resolve the supplement.
+        self.push_level(self.level(styles));
+        self.push_numbering(self.numbering(styles));
+        self.push_numbering_depth(self.numbering_depth(styles));
+
+        // Resolve the supplement. This happens after the level and numbering
+        // were pushed so that a supplement function can depend on them.
         let supplement = match self.supplement(styles) {
             Smart::Auto => TextElem::packed(self.local_name_in(styles)),
             Smart::Custom(None) => Content::empty(),
             Smart::Custom(Some(supplement)) => supplement.resolve(vt, [self.clone()])?,
         };
 
-        self.push_level(self.level(styles));
-        self.push_numbering(self.numbering(styles));
+        let plural = self
+            .supplement(styles)
+            .is_auto()
+            .then(|| TextElem::packed(self.local_plural_name_in(styles)));
+
         self.push_supplement(Smart::Custom(Some(Supplement::Content(supplement))));
+        self.push_plural_supplement(plural);
         self.push_outlined(self.outlined(styles));
+        self.push_short(self.short(styles));
 
         Ok(())
     }
@@ -120,14 +253,15 @@ impl Show for HeadingElem {
     #[tracing::instrument(name = "HeadingElem::show", skip_all)]
     fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
         let mut realized = self.body();
-        if let Some(numbering) = self.numbering(styles) {
+        if let Some(numbering) = self.displayed_numbering(styles) {
             realized = Counter::of(Self::func())
                 .display(Some(numbering), false)
                 .spanned(self.span())
                 + HElem::new(Em::new(0.3).into()).with_weak(true).pack()
                 + realized;
         }
-        Ok(BlockElem::new().with_body(Some(realized)).pack())
+        let level = ParElem::set_heading(Some(self.level(styles)));
+        Ok(BlockElem::new().with_body(Some(realized.styled(level))).pack())
     }
 }
 
@@ -149,7 +283,7 @@ impl Finalize for HeadingElem {
         styles.set(TextElem::set_weight(FontWeight::BOLD));
         styles.set(BlockElem::set_above(VElem::block_around(above.into())));
         styles.set(BlockElem::set_below(VElem::block_around(below.into())));
-        styles.set(BlockElem::set_sticky(true));
+        styles.set(BlockElem::set_keep_with_next(true));
         realized.styled_with_map(styles)
     }
 }
@@ -162,6 +296,18 @@ impl Count for HeadingElem {
     }
 }
 
+impl HeadingElem {
+    /// The numbering with which this heading is displayed, if any.
+    ///
+    /// This is `None` for headings nested deeper than the numbering depth, even
+    /// though they still step the heading counter.
+    fn displayed_numbering(&self, styles: StyleChain) -> Option<Numbering> {
+        let level = self.level(styles);
+        self.numbering(styles)
+            .filter(|_| self.numbering_depth(styles).map_or(true, |depth| level <= depth))
+    }
+}
+
 cast! {
     HeadingElem,
     v: Content => v.to::<Self>().ok_or("expected heading")?.clone(),
@@ -176,12 +322,16 @@ impl Refable for HeadingElem {
         }
     }
 
+    fn plural_supplement(&self) -> Content {
+        self.plural_supplement().unwrap_or_else(|| Refable::supplement(self))
+    }
+
     fn counter(&self) -> Counter {
         Counter::of(Self::func())
     }
 
     fn numbering(&self) -> Option<Numbering> {
-        self.numbering(StyleChain::default())
+        self.displayed_numbering(StyleChain::default())
     }
 }
 
@@ -191,8 +341,9 @@ impl Outlinable for HeadingElem {
             return Ok(None);
         }
 
-        let mut content = self.body();
-        if let Some(numbering) = self.numbering(StyleChain::default()) {
+        let mut content =
+            self.short(StyleChain::default()).unwrap_or_else(|| self.body());
+        if let Some(numbering) = self.displayed_numbering(StyleChain::default()) {
             let numbers = Counter::of(Self::func())
                 .at(vt, self.0.location().unwrap())?
                 .display(vt, &numbering)?;
@@ -232,4 +383,23 @@ impl LocalName for HeadingElem {
             Lang::ENGLISH | _ => "Section",
         }
     }
+
+    fn local_plural_name(&self, lang: Lang, region: Option<Region>) -> &'static str {
+        match lang {
+            Lang::BOKMÅL => "Kapitler",
+            Lang::CZECH => "Kapitoly",
+            Lang::DUTCH => "Hoofdstukken",
+            Lang::FRENCH => "Chapitres",
+            Lang::GERMAN => "Abschnitte",
+            Lang::ITALIAN => "Sezioni",
+            Lang::POLISH => "Sekcje",
+            Lang::PORTUGUESE => "Seções",
+            Lang::RUSSIAN => "Разделы",
+            Lang::SLOVENIAN => "Poglavja",
+            Lang::SPANISH => "Secciones",
+            Lang::UKRAINIAN => "Розділи",
+            Lang::ENGLISH => "Sections",
+            _ => self.local_name(lang, region),
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use super::AnchorElem;
 use crate::prelude::*;
 use crate::text::{Hyphenate, TextElem};
 
@@ -44,6 +45,9 @@ pub struct LinkElem {
     ///   - A [location]($func/locate) resulting from a [`locate`]($func/locate)
     ///     call or [`query`]($func/query).
     ///
+    ///   - A string starting with `#` followed by the name of an
+    ///     [anchor]($func/anchor).
+    ///
     ///   - A dictionary with a `page` key of type [integer]($type/integer) and
     ///     `x` and `y` coordinates of type [length]($type/length). Pages are
     ///     counted from one, and the coordinates are relative to the page's top
@@ -77,6 +81,23 @@ pub struct LinkElem {
         _ => args.expect("body")?,
     })]
     pub body: Content,
+
+    /// A tooltip that PDF viewers show when hovering over the link.
+    ///
+    /// The tooltip is also available as a field in show rules, so you can
+    /// style links with a tooltip differently.
+    ///
+    /// ```example
+    /// #show link: it => {
+    ///   if not it.has("tooltip") { return it }
+    ///   underline(stroke: (dash: "dotted"), it)
+    /// }
+    ///
+    /// #link("https://typst.app", tooltip: "Typst's website")[
+    ///   Visit us
+    /// ]
+    /// ```
+    pub tooltip: Option<EcoString>,
 }
 
 impl LinkElem {
@@ -88,10 +109,18 @@ impl LinkElem {
 }
 
 impl Show for LinkElem {
-    #[tracing::instrument(name = "LinkElem::show", skip(self, vt))]
-    fn show(&self, vt: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+    #[tracing::instrument(name = "LinkElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
         let body = self.body();
         let dest = match self.dest() {
+            LinkTarget::Dest(Destination::Url(url)) if url.starts_with('#') => {
+                if !vt.introspector.init() {
+                    return Ok(body);
+                }
+
+                let loc = AnchorElem::resolve(vt, &url[1..]).at(self.span())?;
+                Destination::Location(loc)
+            }
             LinkTarget::Dest(dest) => dest,
             LinkTarget::Label(label) => {
                 if !vt.introspector.init() {
@@ -103,9 +132,12 @@ impl Show for LinkElem {
             }
         };
 
-        Ok(body
-            .linked(dest)
-            .styled(TextElem::set_hyphenate(Hyphenate(Smart::Custom(false)))))
+        let mut linked = body.linked(dest);
+        if let Some(tooltip) = self.tooltip(styles) {
+            linked = linked.tooltipped(tooltip);
+        }
+
+        Ok(linked.styled(TextElem::set_hyphenate(Hyphenate(Smart::Custom(false)))))
     }
 }
 
@@ -0,0 +1,149 @@
+use crate::prelude::*;
+
+/// A note in the margin of the page.
+///
+/// The note is placed into the outside margin of the page, next to the line
+/// in which it is defined. Its first baseline is aligned with the baseline of
+/// that line. If multiple notes would overlap, later ones are pushed down. If
+/// notes would extend below the bottom margin, they are shifted up instead.
+///
+/// On [two-sided]($func/page.margin) pages, the outside margin alternates
+/// between left and right. Otherwise, notes are put into the margin opposite
+/// of the [binding]($func/page.binding).
+///
+/// ## Example { #example }
+/// ```example
+/// #set page(margin: (right: 80pt))
+/// The unit of length is
+/// defined in terms of the
+/// speed of light.#marginnote[
+///   #set text(8pt)
+///   See the SI brochure.
+/// ]
+/// ```
+///
+/// Display: Margin Note
+/// Category: meta
+#[element(Locatable, Synthesize, Show)]
+pub struct MarginnoteElem {
+    /// The horizontal distance between the note and both the text area and
+    /// the edge of the page.
+    #[resolve]
+    #[default(Em::new(1.0).into())]
+    pub gap: Length,
+
+    /// The minimum vertical distance between two stacked notes.
+    #[resolve]
+    #[default(Em::new(0.5).into())]
+    pub spacing: Length,
+
+    /// The content to put into the margin.
+    #[required]
+    pub body: Content,
+}
+
+impl Synthesize for MarginnoteElem {
+    fn synthesize(&mut self, _vt: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        self.push_gap(self.gap(styles).into());
+        self.push_spacing(self.spacing(styles).into());
+        Ok(())
+    }
+}
+
+impl Show for MarginnoteElem {
+    #[tracing::instrument(name = "MarginnoteElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+        // The note itself is laid out by the page.
+        Ok(Content::empty())
+    }
+}
+
+/// Lay out the margin notes anchored in a page frame.
+///
+/// The notes are placed into the horizontal stripe of the page starting at `x`
+/// with the given `width` and are kept between the `top` and `bottom`
+/// vertical positions.
+pub fn layout_marginnotes(
+    vt: &mut Vt,
+    styles: StyleChain,
+    frame: &mut Frame,
+    x: Abs,
+    width: Abs,
+    top: Abs,
+    bottom: Abs,
+) -> SourceResult<()> {
+    let mut notes = vec![];
+    find_marginnotes(&mut notes, frame, Transform::identity());
+    if notes.is_empty() {
+        return Ok(());
+    }
+
+    // Layout the notes and determine their ideal positions.
+    notes.sort_by(|(a, _), (b, _)| a.y.cmp(&b.y));
+    let mut placed = vec![];
+    for (anchor, note) in notes {
+        let gap = note.gap(styles);
+        let spacing = note.spacing(styles);
+        let area = Size::new((width - 2.0 * gap).max(Abs::zero()), bottom - top);
+        let pod = Regions::one(area, Axes::new(true, false));
+        let content = note.body().layout(vt, styles, pod)?.into_frame();
+        let y = anchor.y - first_baseline(&content).unwrap_or_default();
+        placed.push((y, gap, spacing, content));
+    }
+
+    // Push overlapping notes down.
+    let mut cursor = top;
+    for (y, _, spacing, content) in &mut placed {
+        y.set_max(cursor);
+        cursor = *y + content.height() + *spacing;
+    }
+
+    // Shift notes that extend beyond the bottom up again.
+    let mut limit = bottom;
+    for (y, _, spacing, content) in placed.iter_mut().rev() {
+        y.set_min(limit - content.height());
+        limit = *y - *spacing;
+    }
+
+    for (y, gap, _, content) in placed {
+        frame.push_frame(Point::new(x + gap, y), content);
+    }
+
+    Ok(())
+}
+
+/// Find margin notes and their anchor positions in a frame.
+fn find_marginnotes(
+    notes: &mut Vec<(Point, MarginnoteElem)>,
+    frame: &Frame,
+    ts: Transform,
+) {
+    for (pos, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => {
+                let ts = ts
+                    .pre_concat(Transform::translate(pos.x, pos.y))
+                    .pre_concat(group.transform);
+                find_marginnotes(notes, &group.frame, ts);
+            }
+            FrameItem::Meta(Meta::Elem(content), _)
+                if !notes
+                    .iter()
+                    .any(|(_, note)| note.0.location() == content.location()) =>
+            {
+                let Some(note) = content.to::<MarginnoteElem>() else { continue };
+                notes.push((pos.transform(ts), note.clone()));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The position of the first baseline in a frame.
+fn first_baseline(frame: &Frame) -> Option<Abs> {
+    frame.items().find_map(|(pos, item)| match item {
+        FrameItem::Group(group) => first_baseline(&group.frame).map(|y| pos.y + y),
+        FrameItem::Text(_) => Some(pos.y),
+        _ => None,
+    })
+}
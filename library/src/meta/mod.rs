@@ -1,27 +1,35 @@
 //! Interaction between document parts.
 
+mod anchor;
 mod bibliography;
 mod context;
 mod counter;
 mod document;
+mod embed;
+mod endnote;
 mod figure;
 mod footnote;
 mod heading;
 mod link;
+mod marginnote;
 mod numbering;
 mod outline;
 mod query;
 mod reference;
 mod state;
 
+pub use self::anchor::*;
 pub use self::bibliography::*;
 pub use self::context::*;
 pub use self::counter::*;
 pub use self::document::*;
+pub use self::embed::*;
+pub use self::endnote::*;
 pub use self::figure::*;
 pub use self::footnote::*;
 pub use self::heading::*;
 pub use self::link::*;
+pub use self::marginnote::*;
 pub use self::numbering::*;
 pub use self::outline::*;
 pub use self::query::*;
@@ -34,12 +42,18 @@ use crate::text::TextElem;
 /// Hook up all meta definitions.
 pub(super) fn define(global: &mut Scope) {
     global.define("document", DocumentElem::func());
+    global.define("embed", EmbedElem::func());
     global.define("ref", RefElem::func());
     global.define("link", LinkElem::func());
+    global.define("anchor", AnchorElem::func());
     global.define("outline", OutlineElem::func());
     global.define("heading", HeadingElem::func());
     global.define("figure", FigureElem::func());
+    global.define("subfigure", SubfigureElem::func());
     global.define("footnote", FootnoteElem::func());
+    global.define("endnote", EndnoteElem::func());
+    global.define("endnotes", EndnotesElem::func());
+    global.define("marginnote", MarginnoteElem::func());
     global.define("cite", CiteElem::func());
     global.define("bibliography", BibliographyElem::func());
     global.define("locate", locate_func());
@@ -61,4 +75,15 @@ pub trait LocalName {
     fn local_name_in(&self, styles: StyleChain) -> &'static str {
         self.local_name(TextElem::lang_in(styles), TextElem::region_in(styles))
     }
+
+    /// Get the name for multiple elements, as in "Figures 1–3". Defaults to
+    /// the singular name for languages without a separate plural.
+    fn local_plural_name(&self, lang: Lang, region: Option<Region>) -> &'static str {
+        self.local_name(lang, region)
+    }
+
+    /// Resolve the plural local name with a style chain.
+    fn local_plural_name_in(&self, styles: StyleChain) -> &'static str {
+        self.local_plural_name(TextElem::lang_in(styles), TextElem::region_in(styles))
+    }
 }
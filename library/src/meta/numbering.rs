@@ -21,6 +21,7 @@ use crate::text::Case;
 /// #numbering("1.1)", 1, 2, 3) \
 /// #numbering("1.a.i", 1, 2) \
 /// #numbering("I – 1", 12, 2) \
+/// #numbering(("α", "β", "γ"), 5) \
 /// #numbering(
 ///   (..nums) => nums
 ///     .pos()
@@ -44,6 +45,10 @@ pub fn numbering(
     /// order of `*`, `†`, `‡`, `§`, `¶`, and `‖`. If there are more than six
     /// items, the number is represented using multiple symbols.
     ///
+    /// Zeros directly in front of a `1` pad the number with leading zeros to a
+    /// fixed width. For example, `{"001"}` displays the number five as `005`.
+    /// Other counting symbols are not padded.
+    ///
     /// **Suffixes** are all characters after the last counting symbol. They are
     /// repeated as-is at the end of any rendered number.
     ///
@@ -51,12 +56,38 @@ pub fn numbering(
     /// suffixes. They are repeated as-is at in front of their rendered
     /// equivalent of their counting symbol.
     ///
+    /// This parameter can also be an array of strings, which are used as a
+    /// custom alphabet of counting symbols. For example, `{("α", "β", "γ")}`
+    /// counts with Greek letters. Once the symbols run out, the last symbol is
+    /// repeated like for `*`. To configure this, you can instead pass a
+    /// dictionary with the `symbols` and a `wrap` mode:
+    /// - `{"repeat"}`: Repeat the symbol (`α`, `β`, `γ`, `αα`, `ββ`, ...).
+    /// - `{"alphabetic"}`: Combine symbols like letters (`α`, ..., `γ`, `αα`,
+    ///   `αβ`, ...).
+    /// - `{"cycle"}`: Start over from the first symbol.
+    ///
     /// This parameter can also be an arbitrary function that gets each number
     /// as an individual argument. When given a function, the `numbering`
     /// function just forwards the arguments to that function. While this is not
     /// particularly useful in itself, it means that you can just give arbitrary
     /// numberings to the `numbering` function without caring whether they are
     /// defined as a pattern or function.
+    ///
+    /// Since the numbers are passed individually, a function that should work
+    /// for any number of levels can collect them with an
+    /// [argument sink]($type/arguments) and turn them into an array with its
+    /// `pos` method:
+    ///
+    /// ```example
+    /// #let path(..nums) = {
+    ///   let nums = nums.pos()
+    ///   range(1, nums.len() + 1)
+    ///     .map(k => numbering("1.1.a", ..nums.slice(0, k)))
+    ///     .join(" / ")
+    /// }
+    ///
+    /// #numbering(path, 1, 2, 3)
+    /// ```
     numbering: Numbering,
     /// The numbers to apply the numbering to. Must be positive.
     ///
@@ -75,6 +106,8 @@ pub fn numbering(
 pub enum Numbering {
     /// A pattern with prefix, numbering, lower / upper case and suffix.
     Pattern(NumberingPattern),
+    /// A custom alphabet of counting symbols.
+    Symbols(NumberingSymbols),
     /// A closure mapping from an item's number to content.
     Func(Func),
 }
@@ -84,6 +117,7 @@ impl Numbering {
     pub fn apply_vm(&self, vm: &mut Vm, numbers: &[usize]) -> SourceResult<Value> {
         Ok(match self {
             Self::Pattern(pattern) => Value::Str(pattern.apply(numbers).into()),
+            Self::Symbols(symbols) => Value::Str(symbols.apply(numbers).into()),
             Self::Func(func) => {
                 let args = Args::new(func.span(), numbers.iter().copied());
                 func.call_vm(vm, args)?
@@ -95,6 +129,7 @@ impl Numbering {
     pub fn apply_vt(&self, vt: &mut Vt, numbers: &[usize]) -> SourceResult<Value> {
         Ok(match self {
             Self::Pattern(pattern) => Value::Str(pattern.apply(numbers).into()),
+            Self::Symbols(symbols) => Value::Str(symbols.apply(numbers).into()),
             Self::Func(func) => func.call_vt(vt, numbers.iter().copied())?,
         })
     }
@@ -118,24 +153,28 @@ cast! {
     Numbering,
     self => match self {
         Self::Pattern(pattern) => pattern.into_value(),
+        Self::Symbols(symbols) => symbols.into_value(),
         Self::Func(func) => func.into_value(),
     },
     v: NumberingPattern => Self::Pattern(v),
+    v: NumberingSymbols => Self::Symbols(v),
     v: Func => Self::Func(v),
 }
 
 /// How to turn a number into text.
 ///
 /// A pattern consists of a prefix, followed by one of `1`, `a`, `A`, `i`,
-/// `I`, `い`, `イ`, `א`, `가`, `ㄱ`, or `*`, and then a suffix.
+/// `I`, `い`, `イ`, `א`, `가`, `ㄱ`, or `*`, and then a suffix. Zeros directly
+/// in front of a `1` pad the number with leading zeros.
 ///
 /// Examples of valid patterns:
 /// - `1)`
 /// - `a.`
 /// - `(I)`
+/// - `A.001`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct NumberingPattern {
-    pieces: EcoVec<(EcoString, NumberingKind, Case)>,
+    pieces: EcoVec<(EcoString, NumberingKind, Case, usize)>,
     suffix: EcoString,
     trimmed: bool,
 }
@@ -146,16 +185,16 @@ impl NumberingPattern {
         let mut fmt = EcoString::new();
         let mut numbers = numbers.iter();
 
-        for (i, ((prefix, kind, case), &n)) in
+        for (i, ((prefix, kind, case, width), &n)) in
             self.pieces.iter().zip(&mut numbers).enumerate()
         {
             if i > 0 || !self.trimmed {
                 fmt.push_str(prefix);
             }
-            fmt.push_str(&kind.apply(n, *case));
+            fmt.push_str(&kind.apply(n, *case, *width));
         }
 
-        for ((prefix, kind, case), &n) in
+        for ((prefix, kind, case, width), &n) in
             self.pieces.last().into_iter().cycle().zip(numbers)
         {
             if prefix.is_empty() {
@@ -163,7 +202,7 @@ impl NumberingPattern {
             } else {
                 fmt.push_str(prefix);
             }
-            fmt.push_str(&kind.apply(n, *case));
+            fmt.push_str(&kind.apply(n, *case, *width));
         }
 
         if !self.trimmed {
@@ -176,21 +215,36 @@ impl NumberingPattern {
     /// Apply only the k-th segment of the pattern to a number.
     pub fn apply_kth(&self, k: usize, number: usize) -> EcoString {
         let mut fmt = EcoString::new();
-        if let Some((prefix, _, _)) = self.pieces.first() {
+        if let Some((prefix, _, _, _)) = self.pieces.first() {
             fmt.push_str(prefix);
         }
-        if let Some((_, kind, case)) = self
+        if let Some((_, kind, case, width)) = self
             .pieces
             .iter()
             .chain(self.pieces.last().into_iter().cycle())
             .nth(k)
         {
-            fmt.push_str(&kind.apply(number, *case));
+            fmt.push_str(&kind.apply(number, *case, *width));
         }
         fmt.push_str(&self.suffix);
         fmt
     }
 
+    /// Nest another pattern into this one, for example to number subfigures
+    /// as `1a`.
+    ///
+    /// The first prefix of the nested pattern and the suffix of this pattern
+    /// are dropped.
+    pub fn nested(&self, other: &Self) -> Self {
+        let mut pieces = self.pieces.clone();
+        for (i, (prefix, kind, case, width)) in other.pieces.iter().enumerate() {
+            let prefix = if i == 0 { EcoString::new() } else { prefix.clone() };
+            pieces.push((prefix, *kind, *case, *width));
+        }
+        let suffix = other.suffix.clone();
+        Self { pieces, suffix, trimmed: false }
+    }
+
     /// How many counting symbols this pattern has.
     pub fn pieces(&self) -> usize {
         self.pieces.len()
@@ -209,10 +263,18 @@ impl FromStr for NumberingPattern {
                 continue;
             };
 
-            let prefix = pattern[handled..i].into();
+            // Zeros in front of a decimal counting symbol determine its width.
+            let mut prefix = &pattern[handled..i];
+            let mut width = 1;
+            if kind == NumberingKind::Arabic {
+                let trimmed = prefix.trim_end_matches('0');
+                width += prefix.len() - trimmed.len();
+                prefix = trimmed;
+            }
+
             let case =
                 if c.is_uppercase() || c == '壹' { Case::Upper } else { Case::Lower };
-            pieces.push((prefix, kind, case));
+            pieces.push((prefix.into(), kind, case, width));
             handled = c.len_utf8() + i;
         }
 
@@ -229,8 +291,11 @@ cast! {
     NumberingPattern,
     self => {
         let mut pat = EcoString::new();
-        for (prefix, kind, case) in &self.pieces {
+        for (prefix, kind, case, width) in &self.pieces {
             pat.push_str(prefix);
+            for _ in 1..*width {
+                pat.push('0');
+            }
             let mut c = kind.to_char();
             if *case == Case::Upper {
                 c = c.to_ascii_uppercase();
@@ -243,6 +308,98 @@ cast! {
     v: Str => v.parse()?,
 }
 
+/// A custom alphabet of counting symbols.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct NumberingSymbols {
+    symbols: EcoVec<EcoString>,
+    wrap: SymbolWrap,
+}
+
+impl NumberingSymbols {
+    /// Apply the symbols to the given numbers.
+    pub fn apply(&self, numbers: &[usize]) -> EcoString {
+        let mut fmt = EcoString::new();
+        for (i, &n) in numbers.iter().enumerate() {
+            if i > 0 {
+                fmt.push('.');
+            }
+            fmt.push_str(&self.apply_one(n));
+        }
+        fmt
+    }
+
+    /// Apply the symbols to a single number.
+    fn apply_one(&self, n: usize) -> EcoString {
+        if n == 0 {
+            return '-'.into();
+        }
+
+        let len = self.symbols.len();
+        let symbol = &self.symbols[(n - 1) % len];
+        let mut fmt = EcoString::new();
+        match self.wrap {
+            SymbolWrap::Repeat => {
+                for _ in 0..(n - 1) / len + 1 {
+                    fmt.push_str(symbol);
+                }
+            }
+            SymbolWrap::Cycle => fmt.push_str(symbol),
+            SymbolWrap::Alphabetic => {
+                let mut n = n;
+                let mut digits = vec![];
+                while n > 0 {
+                    n -= 1;
+                    digits.push(&self.symbols[n % len]);
+                    n /= len;
+                }
+                for digit in digits.into_iter().rev() {
+                    fmt.push_str(digit);
+                }
+            }
+        }
+        fmt
+    }
+}
+
+cast! {
+    NumberingSymbols,
+    self => {
+        let symbols: Array =
+            self.symbols.into_iter().map(IntoValue::into_value).collect();
+        match self.wrap {
+            SymbolWrap::Repeat => symbols.into_value(),
+            wrap => dict! { "symbols" => symbols, "wrap" => wrap }.into_value(),
+        }
+    },
+    symbols: Vec<EcoString> => {
+        if symbols.is_empty() {
+            Err("expected at least one symbol")?;
+        }
+        Self { symbols: symbols.into_iter().collect(), wrap: SymbolWrap::Repeat }
+    },
+    mut dict: Dict => {
+        let symbols: Vec<EcoString> = dict.take("symbols")?.cast()?;
+        let wrap = dict.take("wrap").ok().map(Value::cast)
+            .transpose()?.unwrap_or(SymbolWrap::Repeat);
+        dict.finish(&["symbols", "wrap"])?;
+        if symbols.is_empty() {
+            Err("expected at least one symbol")?;
+        }
+        Self { symbols: symbols.into_iter().collect(), wrap }
+    },
+}
+
+/// What happens when a numbering runs out of custom symbols.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum SymbolWrap {
+    /// Repeat the symbol, as in `*`, `**`, `***`.
+    Repeat,
+    /// Combine symbols like the digits of spreadsheet columns.
+    Alphabetic,
+    /// Start over from the first symbol.
+    Cycle,
+}
+
 /// Different kinds of numberings.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum NumberingKind {
@@ -301,10 +458,13 @@ impl NumberingKind {
     }
 
     /// Apply the numbering to the given number.
-    pub fn apply(self, mut n: usize, case: Case) -> EcoString {
+    ///
+    /// Decimal numbers are padded with leading zeros to the given width. Other
+    /// kinds of numberings ignore the width.
+    pub fn apply(self, mut n: usize, case: Case, width: usize) -> EcoString {
         match self {
             Self::Arabic => {
-                eco_format!("{n}")
+                eco_format!("{n:0width$}")
             }
             Self::Letter => zeroless::<26>(
                 |x| match case {
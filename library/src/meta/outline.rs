@@ -110,6 +110,28 @@ pub struct OutlineElem {
     /// ```
     pub depth: Option<NonZeroUsize>,
 
+    /// The minimum level from which on elements are included in the outline.
+    ///
+    /// Together with `depth`, this can be used to create a mini-outline of a
+    /// chapter that only lists its subsections. The numbers of the included
+    /// elements are not affected by this.
+    ///
+    /// ```example
+    /// #set heading(numbering: "1.")
+    /// #outline(start-depth: 2, depth: 3)
+    ///
+    /// = Chapter
+    /// Not included.
+    ///
+    /// == Section
+    /// Included.
+    ///
+    /// === Subsection
+    /// Included.
+    /// ```
+    #[default(NonZeroUsize::ONE)]
+    pub start_depth: NonZeroUsize,
+
     /// How to indent the outline's entries.
     ///
     /// - `{none}`: No indent
@@ -127,7 +149,11 @@ pub struct OutlineElem {
     ///   return a relative length or content making up the indent. For example,
     ///   `{n => n * 2em}` would be equivalent to just specifiying `{2em}`,
     ///   while `{n => [→ ] * n}` would indent with one arrow per nesting
-    ///   level.
+    ///   level. The function is called separately for each entry, so the
+    ///   indent can follow an arbitrary ramp like `{n => 1.5em * n}`.
+    ///
+    /// Regardless of the indent, page numbers are always aligned at the end of
+    /// the line.
     ///
     /// *Migration hints:*  Specifying `{true}` (equivalent to `{auto}`) or
     /// `{false}` (equivalent to `{none}`) for this option is deprecated and
@@ -165,13 +191,29 @@ pub struct OutlineElem {
     /// Content to fill the space between the title and the page number. Can be
     /// set to `none` to disable filling.
     ///
+    /// This can also be a function that receives the level of the outlined
+    /// element (starting at 1 for top-level headings) and returns the fill
+    /// content or `{none}` for that entry.
+    ///
     /// ```example
     /// #outline(fill: line(length: 100%))
     ///
     /// = A New Beginning
     /// ```
-    #[default(Some(RepeatElem::new(TextElem::packed(".")).pack()))]
-    pub fill: Option<Content>,
+    ///
+    /// ```example
+    /// #outline(fill: level => {
+    ///   if level == 1 { line(length: 100%) }
+    ///   else { repeat[.] }
+    /// })
+    ///
+    /// = Chapter
+    /// == Section
+    /// ```
+    #[default(Some(OutlineFill::Content(
+        RepeatElem::new(TextElem::packed(".")).pack()
+    )))]
+    pub fill: Option<OutlineFill>,
 }
 
 impl Show for OutlineElem {
@@ -194,6 +236,8 @@ impl Show for OutlineElem {
 
         let indent = self.indent(styles);
         let depth = self.depth(styles).unwrap_or(NonZeroUsize::new(usize::MAX).unwrap());
+        let start_depth = self.start_depth(styles);
+        let fill = self.fill(styles);
 
         let mut ancestors: Vec<&Content> = vec![];
         let elems = vt.introspector.query(&self.target(styles).0);
@@ -203,7 +247,7 @@ impl Show for OutlineElem {
                 bail!(self.span(), "cannot outline {}", elem.func().name());
             };
 
-            if depth < outlinable.level() {
+            if depth < outlinable.level() || outlinable.level() < start_depth {
                 continue;
             }
 
@@ -238,11 +282,16 @@ impl Show for OutlineElem {
                 });
 
             // Add filler symbols between the section name and page number.
-            if let Some(filler) = self.fill(styles) {
+            let filler = match &fill {
+                Some(fill) => fill.resolve(vt, outlinable.level(), self.span())?,
+                None => None,
+            };
+
+            if let Some(filler) = filler {
                 seq.push(SpaceElem::new().pack());
                 seq.push(
                     BoxElem::new()
-                        .with_body(Some(filler.clone()))
+                        .with_body(Some(filler))
                         .with_width(Fr::one().into())
                         .pack(),
                 );
@@ -343,8 +392,7 @@ impl OutlineIndent {
 
                     if let Some(numbering) = ancestor_outlinable.numbering() {
                         let numbers = ancestor_outlinable
-                            .counter()
-                            .at(vt, ancestor.location().unwrap())?
+                            .numbers(vt, ancestor.location().unwrap())?
                             .display(vt, &numbering)?;
 
                         hidden += numbers + SpaceElem::new().pack();
@@ -399,3 +447,37 @@ cast! {
     v: Rel<Length> => Self(HElem::new(Spacing::Rel(v)).pack()),
     v: Content => Self(v),
 }
+
+/// The `fill` parameter of an [`OutlineElem`].
+#[derive(Debug, Clone)]
+pub enum OutlineFill {
+    /// The same fill for all entries.
+    Content(Content),
+    /// A function that receives an entry's level and returns its fill.
+    Func(Func),
+}
+
+impl OutlineFill {
+    /// Resolve the fill for an entry with the given level.
+    fn resolve(
+        &self,
+        vt: &mut Vt,
+        level: NonZeroUsize,
+        span: Span,
+    ) -> SourceResult<Option<Content>> {
+        Ok(match self {
+            Self::Content(content) => Some(content.clone()),
+            Self::Func(func) => func.call_vt(vt, [level])?.cast().at(span)?,
+        })
+    }
+}
+
+cast! {
+    OutlineFill,
+    self => match self {
+        Self::Content(v) => v.into_value(),
+        Self::Func(v) => v.into_value(),
+    },
+    v: Content => Self::Content(v),
+    v: Func => Self::Func(v),
+}
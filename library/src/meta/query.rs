@@ -89,6 +89,48 @@ use crate::prelude::*;
 /// })
 /// ```
 ///
+/// ## Resolving a location { #resolving-a-location }
+/// Some introspection features, like [`counter.at`]($func/counter.at) and
+/// [`state.at`]($func/state.at), need the location of a particular element.
+/// Instead of querying and picking from the resulting array, you can resolve
+/// a selector to a location directly with `query.location`. By default, the
+/// selector must match exactly one element. With `pick`, you can instead
+/// choose the `{"first"}` or `{"last"}` match.
+///
+/// ```example
+/// #let step = state("step", 0)
+/// #step.update(1)
+/// = Intro <intro>
+/// #step.update(2)
+///
+/// #locate(loc => {
+///   let intro = query.location(<intro>, loc)
+///   [At the intro: #step.at(intro)]
+/// })
+/// ```
+///
+/// ## Finding single elements { #single-elements }
+/// If you only need one element, the companion functions `query.first`,
+/// `query.last`, `query.before` and `query.after` are more efficient than
+/// picking from the full result of `query`. They return the element or `{none}`
+/// if there is no match. `query.before` finds the closest matching element
+/// before the given location and `query.after` the closest one after it.
+///
+/// Elements are ordered in document order: Elements on earlier pages come
+/// before those on later ones and elements on the same page are ordered in
+/// the order in which they were laid out. An element is never before or after
+/// itself.
+///
+/// ```example
+/// #set page(header: locate(loc => {
+///   let heading = query.before(heading, loc)
+///   if heading != none { emph(heading.body) }
+/// }))
+///
+/// = Introduction
+/// #lorem(10)
+/// ```
+///
 /// ## Migration Hints { #migration-hints }
 /// The `before` and `after` arguments have been removed in version 0.3.0. You
 /// can now use flexible selector combinator methods instead. For example,
@@ -99,6 +141,14 @@ use crate::prelude::*;
 /// Display: Query
 /// Category: meta
 #[func]
+#[scope(
+    scope.define("location", query_location_func());
+    scope.define("first", query_first_func());
+    scope.define("last", query_last_func());
+    scope.define("before", query_before_func());
+    scope.define("after", query_after_func());
+    scope
+)]
 pub fn query(
     /// Can be an element function like a `heading` or `figure`, a `{<label>}`
     /// or a more complex selector like `{heading.where(level: 1)}`.
@@ -128,6 +178,133 @@ pub fn query(
         .collect()
 }
 
+/// Find the first element that matches a selector.
+///
+/// Display: Query First
+/// Category: meta
+#[func]
+pub fn query_first(
+    /// Can be an element function like a `heading` or `figure`, a `{<label>}`
+    /// or a more complex selector like `{heading.where(level: 1)}`.
+    target: LocatableSelector,
+    /// Can be any location. See [`query`]($func/query) for why it is
+    /// required.
+    location: Location,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> Option<Content> {
+    let _ = location;
+    vm.vt
+        .introspector
+        .query_first(&target.0)
+        .map(|elem| elem.into_inner())
+}
+
+/// Find the last element that matches a selector.
+///
+/// Display: Query Last
+/// Category: meta
+#[func]
+pub fn query_last(
+    /// Can be an element function like a `heading` or `figure`, a `{<label>}`
+    /// or a more complex selector like `{heading.where(level: 1)}`.
+    target: LocatableSelector,
+    /// Can be any location. See [`query`]($func/query) for why it is
+    /// required.
+    location: Location,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> Option<Content> {
+    let _ = location;
+    vm.vt.introspector.query_last(&target.0).map(|elem| elem.into_inner())
+}
+
+/// Find the closest element before a location that matches a selector.
+///
+/// Display: Query Before
+/// Category: meta
+#[func]
+pub fn query_before(
+    /// Can be an element function like a `heading` or `figure`, a `{<label>}`
+    /// or a more complex selector like `{heading.where(level: 1)}`.
+    target: LocatableSelector,
+    /// The location before which to search.
+    location: Location,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> Option<Content> {
+    vm.vt
+        .introspector
+        .query_before(&target.0, location)
+        .map(|elem| elem.into_inner())
+}
+
+/// Find the closest element after a location that matches a selector.
+///
+/// Display: Query After
+/// Category: meta
+#[func]
+pub fn query_after(
+    /// Can be an element function like a `heading` or `figure`, a `{<label>}`
+    /// or a more complex selector like `{heading.where(level: 1)}`.
+    target: LocatableSelector,
+    /// The location after which to search.
+    location: Location,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> Option<Content> {
+    vm.vt
+        .introspector
+        .query_after(&target.0, location)
+        .map(|elem| elem.into_inner())
+}
+
+/// Resolve a selector to the location of a single element.
+///
+/// See the [query documentation]($func/query#resolving-a-location) for more
+/// details.
+///
+/// Display: Query Location
+/// Category: meta
+#[func]
+pub fn query_location(
+    /// Can be an element function like a `heading` or `figure`, a `{<label>}`
+    /// or a more complex selector like `{heading.where(level: 1)}`.
+    target: LocatableSelector,
+    /// Can be any location. See [`query`]($func/query) for why it is
+    /// required.
+    location: Location,
+    /// Which of the matching elements to resolve to.
+    #[named]
+    #[default(Pick::Unique)]
+    pick: Pick,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> StrResult<Location> {
+    let _ = location;
+    let elems = vm.vt.introspector.query(&target.0);
+    let elem = match pick {
+        Pick::Unique if elems.len() > 1 => {
+            bail!("selector matches {} elements, but must match exactly one", elems.len())
+        }
+        Pick::Unique | Pick::First => elems.first(),
+        Pick::Last => elems.last(),
+    };
+    elem.and_then(|elem| elem.location())
+        .ok_or_else(|| "selector does not match any element".into())
+}
+
+/// Which of multiple matching elements to pick.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Pick {
+    /// There must be exactly one matching element.
+    Unique,
+    /// The first matching element in the document.
+    First,
+    /// The last matching element in the document.
+    Last,
+}
+
 /// Turns a value into a selector. The following values are accepted:
 /// - An element function like a `heading` or `figure`.
 /// - A `{<label>}`.
@@ -1,4 +1,9 @@
-use super::{BibliographyElem, CiteElem, Counter, Figurable, Numbering};
+use std::str::FromStr;
+
+use super::{
+    BibliographyElem, CiteElem, Counter, CounterKey, CounterState, Figurable, LocalName,
+    Numbering, NumberingPattern,
+};
 use crate::prelude::*;
 use crate::text::TextElem;
 
@@ -84,12 +89,32 @@ use crate::text::TextElem;
 ///
 /// Display: Reference
 /// Category: meta
-#[element(Synthesize, Locatable, Show)]
+#[element(Synthesize, Locatable, Show, LocalName)]
 pub struct RefElem {
     /// The target label that should be referenced.
     #[required]
     pub target: Label,
 
+    /// Further labels to reference together with the target.
+    ///
+    /// The supplement is only shown once, in its plural form if it was
+    /// determined automatically. Runs of consecutive numbers are collapsed
+    /// into a range and other numbers are separated by commas.
+    ///
+    /// ```example
+    /// #set heading(numbering: "1.")
+    ///
+    /// = A <a>
+    /// = B <b>
+    /// = C <c>
+    /// = D <d>
+    ///
+    /// See #ref(<a>, <b>, <c>) and
+    /// #ref(<a>, <d>).
+    /// ```
+    #[variadic]
+    pub others: Vec<Label>,
+
     /// A supplement for the reference.
     ///
     /// For references to headings or figures, this is added before the
@@ -116,6 +141,41 @@ pub struct RefElem {
     /// ```
     pub supplement: Smart<Option<Supplement>>,
 
+    /// Whether to include the page number of the referenced element.
+    ///
+    /// If `{true}`, the reference is followed by "on page" in the current
+    /// [text language]($func/text.lang) and the page number of the target. If a function is specified, it is passed the supplement,
+    /// the number, and the page number as content and should return the whole
+    /// reference. Page numbers are displayed with the numbering of the page
+    /// the target is on.
+    ///
+    /// ```example
+    /// #set heading(numbering: "1.")
+    /// #set page(numbering: "1")
+    ///
+    /// = Introduction <intro>
+    /// See @intro, or rather
+    /// #ref(<intro>, page: true).
+    ///
+    /// #set ref(page: (supplement, number, page) => [
+    ///   #supplement #number (p. #page)
+    /// ])
+    /// Also see @intro.
+    /// ```
+    #[default(RefPage::Bool(false))]
+    pub page: RefPage,
+
+    /// A tooltip that PDF viewers show when hovering over the reference's
+    /// links.
+    ///
+    /// ```example
+    /// #set heading(numbering: "1.")
+    ///
+    /// = Introduction <intro>
+    /// See #ref(<intro>, tooltip: "Jump to the introduction").
+    /// ```
+    pub tooltip: Option<EcoString>,
+
     /// A synthesized citation.
     #[synthesized]
     pub citation: Option<CiteElem>,
@@ -163,53 +223,64 @@ impl Show for RefElem {
         }
 
         let elem = elem.at(span)?;
-        let refable = elem
-            .with::<dyn Refable>()
-            .ok_or_else(|| {
-                if elem.can::<dyn Figurable>() {
-                    eco_format!(
-                        "cannot reference {} directly, try putting it into a figure",
-                        elem.func().name()
-                    )
-                } else {
-                    eco_format!("cannot reference {}", elem.func().name())
-                }
-            })
-            .at(span)?;
-
-        let numbering = refable
-            .numbering()
-            .ok_or_else(|| {
-                eco_format!("cannot reference {} without numbering", elem.func().name())
-            })
-            .at(span)?;
-
-        let numbers = refable
-            .counter()
-            .at(vt, elem.location().unwrap())?
-            .display(vt, &numbering.trimmed())?;
+        let mut targets = vec![resolve(vt, &elem, span)?];
+        for label in self.others() {
+            let elem = vt.introspector.query_label(&label).at(span)?;
+            targets.push(resolve(vt, &elem, span)?);
+        }
 
+        // A reference to multiple targets reads like "Figures 1–3".
         let supplement = match self.supplement(styles) {
-            Smart::Auto => refable.supplement(),
+            Smart::Auto => {
+                let refable = elem.with::<dyn Refable>().unwrap();
+                if targets.len() > 1 {
+                    refable.plural_supplement()
+                } else {
+                    refable.supplement()
+                }
+            }
             Smart::Custom(None) => Content::empty(),
             Smart::Custom(Some(supplement)) => {
                 supplement.resolve(vt, [(*elem).clone()])?
             }
         };
 
-        let mut content = numbers;
-        if !supplement.is_empty() {
-            content = supplement + TextElem::packed("\u{a0}") + content;
-        }
+        let content = if targets.len() == 1 {
+            targets.pop().unwrap().linked()
+        } else {
+            format_targets(targets)
+        };
 
-        Ok(content.linked(Destination::Location(elem.location().unwrap())))
+        let location = elem.location().unwrap();
+        let realized = match self.page(styles) {
+            RefPage::Bool(false) => combine(supplement, content),
+            RefPage::Bool(true) => {
+                combine(supplement, content)
+                    + TextElem::packed(eco_format!(
+                        " {}\u{a0}",
+                        self.local_name_in(styles)
+                    ))
+                    + page_number(vt, location)?
+            }
+            RefPage::Func(func) => {
+                let page = page_number(vt, location)?;
+                func.call_vt(vt, [supplement, content, page])?.display()
+            }
+        };
+
+        Ok(match self.tooltip(styles) {
+            Some(tooltip) => realized.tooltipped(tooltip),
+            None => realized,
+        })
     }
 }
 
 impl RefElem {
     /// Turn the reference into a citation.
     pub fn to_citation(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<CiteElem> {
-        let mut elem = CiteElem::new(vec![self.target().0]);
+        let mut keys = vec![self.target().0];
+        keys.extend(self.others().into_iter().map(|label| label.0));
+        let mut elem = CiteElem::new(keys);
         elem.0.set_location(self.0.location().unwrap());
         elem.synthesize(vt, styles)?;
         elem.push_supplement(match self.supplement(styles) {
@@ -221,6 +292,139 @@ impl RefElem {
     }
 }
 
+impl LocalName for RefElem {
+    fn local_name(&self, lang: Lang, _: Option<Region>) -> &'static str {
+        match lang {
+            Lang::ARABIC => "في الصفحة",
+            Lang::BOKMÅL => "på side",
+            Lang::CHINESE => "页码",
+            Lang::CZECH => "na straně",
+            Lang::DANISH => "på side",
+            Lang::DUTCH => "op pagina",
+            Lang::FRENCH => "à la page",
+            Lang::GERMAN => "auf Seite",
+            Lang::ITALIAN => "a pagina",
+            Lang::NYNORSK => "på side",
+            Lang::POLISH => "na stronie",
+            Lang::PORTUGUESE => "na página",
+            Lang::RUSSIAN => "на странице",
+            Lang::SLOVENIAN => "na strani",
+            Lang::SPANISH => "en la página",
+            Lang::SWEDISH => "på sidan",
+            Lang::UKRAINIAN => "на сторінці",
+            Lang::VIETNAMESE => "ở trang",
+            Lang::ENGLISH | _ => "on page",
+        }
+    }
+}
+
+/// Prepend the supplement to the displayed numbers.
+fn combine(supplement: Content, numbers: Content) -> Content {
+    if supplement.is_empty() {
+        numbers
+    } else {
+        supplement + TextElem::packed("\u{a0}") + numbers
+    }
+}
+
+/// Display the page number of a location with the page's numbering.
+fn page_number(vt: &mut Vt, location: Location) -> SourceResult<Content> {
+    let numbering = vt
+        .introspector
+        .page_numbering(location)
+        .cast::<Option<Numbering>>()
+        .unwrap()
+        .unwrap_or_else(|| Numbering::Pattern(NumberingPattern::from_str("1").unwrap()));
+
+    Counter::new(CounterKey::Page)
+        .at(vt, location)?
+        .display(vt, &numbering)
+        .map(|page| page.linked(Destination::Location(location)))
+}
+
+/// A resolved reference target.
+struct Target {
+    /// The counter state of the target.
+    state: CounterState,
+    /// The displayed numbers.
+    numbers: Content,
+    /// The location of the target.
+    location: Location,
+}
+
+impl Target {
+    /// The displayed numbers, linked to the target.
+    fn linked(self) -> Content {
+        self.numbers.linked(Destination::Location(self.location))
+    }
+}
+
+/// Resolve the numbers and location of a referenced element.
+fn resolve(vt: &mut Vt, elem: &Content, span: Span) -> SourceResult<Target> {
+    let refable = elem
+        .with::<dyn Refable>()
+        .ok_or_else(|| {
+            if elem.can::<dyn Figurable>() {
+                eco_format!(
+                    "cannot reference {} directly, try putting it into a figure",
+                    elem.func().name()
+                )
+            } else {
+                eco_format!("cannot reference {}", elem.func().name())
+            }
+        })
+        .at(span)?;
+
+    let numbering = refable
+        .ref_numbering()
+        .ok_or_else(|| {
+            eco_format!("cannot reference {} without numbering", elem.func().name())
+        })
+        .at(span)?;
+
+    let location = elem.location().unwrap();
+    let state = refable.numbers(vt, location)?;
+    let numbers = state.display(vt, &numbering)?;
+    Ok(Target { state, numbers, location })
+}
+
+/// Format multiple reference targets, collapsing runs of consecutive numbers
+/// into ranges.
+fn format_targets(targets: Vec<Target>) -> Content {
+    let consecutive = |a: &CounterState, b: &CounterState| {
+        let (Some((x, xs)), Some((y, ys))) = (a.0.split_last(), b.0.split_last()) else {
+            return false;
+        };
+        xs == ys && x + 1 == *y
+    };
+
+    let mut runs: Vec<Vec<Target>> = vec![];
+    for target in targets {
+        match runs.last_mut() {
+            Some(run) if consecutive(&run.last().unwrap().state, &target.state) => {
+                run.push(target)
+            }
+            _ => runs.push(vec![target]),
+        }
+    }
+
+    let mut seq = vec![];
+    for (i, mut run) in runs.into_iter().enumerate() {
+        if i > 0 {
+            seq.push(TextElem::packed(", "));
+        }
+
+        let last = (run.len() > 1).then(|| run.pop().unwrap());
+        seq.push(run.swap_remove(0).linked());
+        if let Some(last) = last {
+            seq.push(TextElem::packed("–"));
+            seq.push(last.linked());
+        }
+    }
+
+    Content::sequence(seq)
+}
+
 /// Additional content for a reference.
 pub enum Supplement {
     Content(Content),
@@ -251,15 +455,51 @@ cast! {
     v: Func => Self::Func(v),
 }
 
+/// How to include the page number in a reference.
+pub enum RefPage {
+    /// Whether to append the page number.
+    Bool(bool),
+    /// A function that formats the whole reference.
+    Func(Func),
+}
+
+cast! {
+    RefPage,
+    self => match self {
+        Self::Bool(v) => v.into_value(),
+        Self::Func(v) => v.into_value(),
+    },
+    v: bool => Self::Bool(v),
+    v: Func => Self::Func(v),
+}
+
 /// Marks an element as being able to be referenced. This is used to implement
 /// the `@ref` element.
 pub trait Refable {
     /// The supplement, if not overriden by the reference.
     fn supplement(&self) -> Content;
 
+    /// The supplement for a reference to multiple elements, like "Figures" in
+    /// "Figures 1–3". Defaults to the singular supplement.
+    fn plural_supplement(&self) -> Content {
+        self.supplement()
+    }
+
     /// Returns the counter of this element.
     fn counter(&self) -> Counter;
 
     /// Returns the numbering of this element.
     fn numbering(&self) -> Option<Numbering>;
+
+    /// Returns the numbering to use when this element is referenced. By
+    /// default, this is the element's numbering without prefix and suffix.
+    fn ref_numbering(&self) -> Option<Numbering> {
+        self.numbering().map(Numbering::trimmed)
+    }
+
+    /// Returns the numbers of this element, which is located at the given
+    /// location.
+    fn numbers(&self, vt: &mut Vt, location: Location) -> SourceResult<CounterState> {
+        self.counter().at(vt, location)
+    }
 }
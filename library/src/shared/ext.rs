@@ -18,11 +18,17 @@ pub trait ContentExt {
     /// Link the content somewhere.
     fn linked(self, dest: Destination) -> Self;
 
+    /// Attach a tooltip to the links in this content.
+    fn tooltipped(self, text: EcoString) -> Self;
+
     /// Make the content linkable by `.linked(Destination::Location(loc))`.
     ///
     /// Should be used in combination with [`Location::variant`].
     fn backlinked(self, loc: Location) -> Self;
 
+    /// Mark this content as an element of the document's logical structure.
+    fn tagged(self, tag: Tag) -> Self;
+
     /// Set alignments for this content.
     fn aligned(self, aligns: Axes<Option<GenAlign>>) -> Self;
 
@@ -50,12 +56,21 @@ impl ContentExt for Content {
         self.styled(MetaElem::set_data(vec![Meta::Link(dest)]))
     }
 
+    fn tooltipped(self, text: EcoString) -> Self {
+        self.styled(MetaElem::set_data(vec![Meta::Tooltip(text)]))
+    }
+
     fn backlinked(self, loc: Location) -> Self {
         let mut backlink = Content::empty();
         backlink.set_location(loc);
         self.styled(MetaElem::set_data(vec![Meta::Elem(backlink)]))
     }
 
+    fn tagged(self, tag: Tag) -> Self {
+        let marker = |meta| MetaElem::new().pack().styled(MetaElem::set_data(vec![meta]));
+        marker(Meta::TagStart(tag)) + self + marker(Meta::TagEnd)
+    }
+
     fn aligned(self, aligns: Axes<Option<GenAlign>>) -> Self {
         self.styled(AlignElem::set_alignment(aligns))
     }
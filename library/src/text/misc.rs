@@ -1,4 +1,7 @@
-use super::TextElem;
+use typst::font::Font;
+use typst::model::{Recipe, Transform};
+
+use super::{families, report_fonts, variant, TextElem, TextSize};
 use crate::prelude::*;
 
 /// A text space.
@@ -272,11 +275,11 @@ impl Case {
 
 /// Display text in small capitals.
 ///
-/// _Note:_ This enables the OpenType `smcp` feature for the font. Not all fonts
-/// support this feature. Sometimes smallcaps are part of a dedicated font and
-/// sometimes they are not available at all. In the future, this function will
-/// support selecting a dedicated smallcaps font as well as synthesizing
-/// smallcaps from normal letters, but this is not yet implemented.
+/// This enables the OpenType `smcp` feature for the font. Not all fonts
+/// support this feature. If the font lacks it, small capitals are synthesized
+/// by setting lowercase letters as scaled-down capitals while capital letters
+/// keep their full size. Synthesis can be turned off with the `fallback`
+/// argument.
 ///
 /// ## Example { #example }
 /// ```example
@@ -295,12 +298,198 @@ impl Case {
 ///
 /// Display: Small Capitals
 /// Category: text
-#[func]
-pub fn smallcaps(
+#[element(Synthesize, Show)]
+pub struct SmallcapsElem {
+    /// Whether to synthesize small capitals if the font does not provide real
+    /// ones. When disabled, only the font's own small capitals are used.
+    ///
+    /// ```example
+    /// #smallcaps[Fallback] \
+    /// #smallcaps(fallback: false)[Real only]
+    /// ```
+    #[default(true)]
+    pub fallback: bool,
+
+    /// The size of synthesized small capitals.
+    ///
+    /// ```example
+    /// #smallcaps(size: 0.6em)[Small Capitals]
+    /// ```
+    #[default(Em::new(0.75).into())]
+    pub size: Length,
+
     /// The text to display to small capitals.
-    body: Content,
+    #[required]
+    pub body: Content,
+
+    /// Whether the small capitals are synthesized rather than taken from the
+    /// font.
+    #[synthesized]
+    pub synthesized: bool,
+}
+
+impl Synthesize for SmallcapsElem {
+    fn synthesize(&mut self, vt: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        let synthesized = self.fallback(styles) && !has_smallcaps(vt, styles);
+        self.push_fallback(self.fallback(styles));
+        self.push_size(self.size(styles));
+        self.push_synthesized(synthesized);
+        Ok(())
+    }
+}
+
+impl Show for SmallcapsElem {
+    #[tracing::instrument(name = "SmallcapsElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let body = self.body();
+        if !self.synthesized() {
+            return Ok(body.styled(TextElem::set_smallcaps(true)));
+        }
+
+        let span = self.span();
+        let func = Func::from(synthesize_smallcaps_func())
+            .with(Args::new(span, [self.size(styles)]));
+        Ok(body.styled(Recipe {
+            span,
+            selector: Some(TextElem::func().select()),
+            transform: Transform::Func(func),
+        }))
+    }
+}
+
+/// Whether the first font in the family list has real small capitals.
+fn has_smallcaps(vt: &Vt, styles: StyleChain) -> bool {
+    let world = vt.world;
+    let variant = variant(styles);
+    let Some(font) = families(styles).find_map(|family| {
+        let id = world.book().select(family.as_str(), variant)?;
+        world.font(id)
+    }) else {
+        return false;
+    };
+
+    let tag = ttf_parser::Tag::from_bytes(b"smcp");
+    font.ttf()
+        .tables()
+        .gsub
+        .map_or(false, |gsub| gsub.features.find(tag).is_some())
+}
+
+/// Synthesize small capitals by setting lowercase letters as scaled-down
+/// capitals.
+///
+/// Display: Synthesize Small Capitals
+/// Category: text
+#[func]
+fn synthesize_smallcaps(
+    /// The size of the synthesized capitals.
+    size: Length,
+    /// The text element to transform.
+    text: Content,
 ) -> Content {
-    body.styled(TextElem::set_smallcaps(true))
+    let Some(elem) = text.to::<TextElem>() else { return text };
+    let full = elem.text();
+    if !full.chars().any(char::is_lowercase) {
+        return text;
+    }
+
+    // The pieces are derived from the guarded element so that the recipe
+    // does not apply to them a second time.
+    let mut seq = vec![];
+    let mut push = |piece: &str, lower: bool| {
+        if lower {
+            let upper = text.clone().with_field("text", piece.to_uppercase());
+            seq.push(upper.styled(TextElem::set_size(TextSize(size))));
+        } else {
+            seq.push(text.clone().with_field("text", piece));
+        }
+    };
+
+    let mut start = 0;
+    let mut lower = None;
+    for (i, c) in full.char_indices() {
+        let is_lower = c.is_lowercase();
+        if matches!(lower, Some(prev) if prev != is_lower) {
+            push(&full[start..i], !is_lower);
+            start = i;
+        }
+        lower = Some(is_lower);
+    }
+
+    push(&full[start..], lower.unwrap_or(false));
+    Content::sequence(seq)
+}
+
+/// Determine the fonts that display a piece of text.
+///
+/// This is useful for debugging font fallback. The function returns a
+/// dictionary with two entries:
+///
+/// - `runs`: An array of dictionaries, one for each consecutive run of the
+///   text that is displayed by the same font. Each has the keys `text` and
+///   `font`, where `font` is the family name of the font or `{none}` if no
+///   font has glyphs for the run.
+/// - `missing`: An array of dictionaries, one for each character without a
+///   glyph. Each has the keys `char`, `codepoint` and `tried`, where `tried`
+///   lists the family names of all fonts that were consulted in order.
+///
+/// Like [`measure`]($func/measure), the function needs the active styles,
+/// which you can retrieve with the [`style`]($func/style) function.
+///
+/// ## Example { #example }
+/// ```example
+/// #set text(font: "Inria Serif")
+/// #style(styles => {
+///   let report = fonts("Latin عربي", styles)
+///   for run in report.runs [
+///     "#run.text" uses #run.font. \
+///   ]
+/// })
+/// ```
+///
+/// Display: Fonts
+/// Category: text
+#[func]
+pub fn fonts(
+    /// The text to inspect.
+    text: Str,
+    /// The styles with which to shape the text.
+    styles: Styles,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> Dict {
+    let styles = StyleChain::new(&styles);
+    let report = report_fonts(&vm.vt, &text, styles);
+    let name = |font: &Font| font.info().family.clone().into_value();
+    let tried: Array = report.consulted.iter().map(name).collect();
+
+    let mut runs = Array::new();
+    let mut missing = Array::new();
+    for (range, font) in report.runs {
+        let piece = &text[range];
+        runs.push(
+            dict! {
+                "text" => piece,
+                "font" => font.as_ref().map_or(Value::None, name),
+            }
+            .into_value(),
+        );
+
+        if font.is_none() {
+            for c in piece.chars() {
+                missing.push(
+                    dict! {
+                        "char" => c,
+                        "codepoint" => eco_format!("U+{:04X}", c as u32),
+                        "tried" => tried.clone(),
+                    }
+                    .into_value(),
+                );
+            }
+        }
+    }
+
+    dict! { "runs" => runs, "missing" => missing }
 }
 
 /// Create blind text.
@@ -14,7 +14,10 @@ pub use self::raw::*;
 pub use self::shaping::*;
 pub use self::shift::*;
 
+use std::str::FromStr;
+
 use rustybuzz::Tag;
+use typst::eval::StrPattern;
 use typst::font::{FontMetrics, FontStretch, FontStyle, FontWeight, VerticalFontMetric};
 
 use crate::layout::ParElem;
@@ -29,7 +32,8 @@ pub(super) fn define(global: &mut Scope) {
     global.define("emph", EmphElem::func());
     global.define("lower", lower_func());
     global.define("upper", upper_func());
-    global.define("smallcaps", smallcaps_func());
+    global.define("smallcaps", SmallcapsElem::func());
+    global.define("fonts", fonts_func());
     global.define("sub", SubElem::func());
     global.define("super", SuperElem::func());
     global.define("underline", UnderlineElem::func());
@@ -83,11 +87,14 @@ pub struct TextElem {
     /// contains no match. This lets Typst search through all available fonts
     /// for the most similar one that has the necessary glyphs.
     ///
-    /// _Note:_ Currently, there are no warnings when fallback is disabled and
-    /// no glyphs are found. Instead, your text shows up in the form of "tofus":
-    /// Small boxes that indicate the lack of an appropriate glyph. In the
-    /// future, you will be able to instruct Typst to issue warnings so you know
-    /// something is up.
+    /// Instead of a boolean, you can also pass an explicit, prioritized list
+    /// of fallback families. These are then tried in order after the primary
+    /// list and replace the automatic search through all available fonts.
+    ///
+    /// When no font has a glyph, your text shows up in the form of "tofus":
+    /// Small boxes that indicate the lack of an appropriate glyph. To find
+    /// out which fonts were consulted for a piece of text, use the
+    /// [`fonts`]($func/fonts) function.
     ///
     /// ```example
     /// #set text(font: "Inria Serif")
@@ -95,9 +102,12 @@ pub struct TextElem {
     ///
     /// #set text(fallback: false)
     /// هذا عربي
+    ///
+    /// #set text(fallback: ("Noto Sans Arabic",))
+    /// هذا عربي
     /// ```
-    #[default(true)]
-    pub fallback: bool,
+    #[default(FontFallback::Bool(true))]
+    pub fallback: FontFallback,
 
     /// The desired font style.
     ///
@@ -185,6 +195,18 @@ pub struct TextElem {
     /// #set text(tracking: 1.5pt)
     /// Distant text.
     /// ```
+    ///
+    /// Tracking is not applied after the last character of a line. It is
+    /// particularly useful for text in all caps, such as headings:
+    ///
+    /// ```example
+    /// #show heading: it => text(
+    ///   tracking: 0.12em,
+    ///   upper(it.body),
+    /// )
+    ///
+    /// = Introduction
+    /// ```
     #[resolve]
     pub tracking: Length,
 
@@ -338,6 +360,62 @@ pub struct TextElem {
     #[resolve]
     pub hyphenate: Hyphenate,
 
+    /// Words that should be hyphenated differently than the language's
+    /// hyphenation patterns suggest.
+    ///
+    /// Takes a dictionary that maps language codes to arrays of words. Each
+    /// word is written with hyphens at its allowed break points, e.g.
+    /// `{"data-base"}`. A word without hyphens is never hyphenated. Words are
+    /// matched case-insensitively and only in text of the given language.
+    /// Exceptions from nested set rules are merged, with inner ones taking
+    /// precedence.
+    ///
+    /// ```example
+    /// #set page(width: 60pt)
+    /// #set par(justify: true)
+    /// #set text(hyphenate-exceptions: (
+    ///   en: ("typeset-ting", "Typst"),
+    /// ))
+    ///
+    /// Typesetting with Typst
+    /// ```
+    #[fold]
+    pub hyphenate_exceptions: HyphenationExceptions,
+
+    /// Custom penalties for line breaks between specific words.
+    ///
+    /// Takes a dictionary that maps language codes to arrays of rules. Each
+    /// rule is a dictionary with a `penalty` and optionally a `before` and an
+    /// `after` pattern. These are strings or [regular expressions]($func/regex)
+    /// that must match the whole word before or after a break, respectively.
+    /// Words are delimited by spaces.
+    ///
+    /// Penalties use the same scale as TeX's: A hyphenation costs `{50}`.
+    /// Positive penalties discourage a break, negative ones encourage it. They
+    /// only affect [optimized]($func/par.linebreaks) line breaking. A penalty
+    /// of `{calc.inf}` forbids the break entirely, also for simple line
+    /// breaking. Rules from nested set rules are merged, with inner ones
+    /// taking precedence.
+    ///
+    /// To keep two words together in all cases, you can also join them with a
+    /// non-breaking space (`[~]`) or a narrow one
+    /// (`sym.space.nobreak.narrow`).
+    ///
+    /// ```example
+    /// #set page(width: 90pt)
+    /// #set text(break-penalties: (
+    ///   en: ((before: regex("\\d+"), penalty: calc.inf),),
+    ///   cs: ((before: regex("\\w"), penalty: calc.inf),),
+    /// ))
+    ///
+    /// The route was 12 km long.
+    ///
+    /// #set text(lang: "cs")
+    /// Šli jsme k řece a u mostu.
+    /// ```
+    #[fold]
+    pub break_penalties: BreakPenalties,
+
     /// Whether to apply kerning.
     ///
     /// When enabled, specific letter pairings move closer together or further
@@ -356,6 +434,24 @@ pub struct TextElem {
     #[default(true)]
     pub kerning: bool,
 
+    /// Manual adjustments of the space between specific pairs of characters.
+    ///
+    /// Takes a dictionary that maps two-character strings to the amount of
+    /// space to add between the two characters. Negative amounts move the
+    /// characters closer together. The adjustments are applied on top of the
+    /// font's kerning and the [tracking]($func/text.tracking).
+    ///
+    /// ```example
+    /// #set text(size: 25pt)
+    /// #set text(kerning-pairs: (
+    ///   "AV": -0.1em,
+    ///   "LT": -0.05em,
+    /// ))
+    /// AVALT
+    /// ```
+    #[fold]
+    pub kerning_pairs: KerningPairs,
+
     /// Whether to apply stylistic alternates.
     ///
     /// Sometimes fonts contain alternative glyphs for the same codepoint.
@@ -466,12 +562,21 @@ pub struct TextElem {
     /// - If given an array of strings, sets the features identified by the
     ///   strings to `{1}`.
     /// - If given a dictionary mapping to numbers, sets the features
-    ///   identified by the keys to the values.
+    ///   identified by the keys to the values. Instead of `{1}` and `{0}`,
+    ///   the values can also be `{true}` and `{false}` to turn a feature on or
+    ///   off.
+    ///
+    /// Features are only applied if the font supports them, so text in a
+    /// fallback font that lacks a feature is shaped as usual.
     ///
     /// ```example
     /// // Enable the `frac` feature manually.
     /// #set text(features: ("frac",))
     /// 1/2
+    ///
+    /// // Toggle features by tag.
+    /// #set text(features: (frac: false, zero: true))
+    /// 1/2 0
     /// ```
     #[fold]
     pub features: FontFeatures,
@@ -588,6 +693,33 @@ cast! {
     values: Array => Self(values.into_iter().map(|v| v.cast()).collect::<StrResult<_>>()?),
 }
 
+/// How to fall back when the primary font list has no matching glyph.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum FontFallback {
+    /// Whether to use the built-in fallback families and to search through
+    /// all available fonts.
+    Bool(bool),
+    /// An explicit, prioritized list of fallback families.
+    List(FontList),
+}
+
+impl FontFallback {
+    /// Whether to search through all available fonts as a last resort.
+    pub fn automatic(&self) -> bool {
+        matches!(self, Self::Bool(true))
+    }
+}
+
+cast! {
+    FontFallback,
+    self => match self {
+        Self::Bool(v) => v.into_value(),
+        Self::List(v) => v.into_value(),
+    },
+    v: bool => Self::Bool(v),
+    v: FontList => Self::List(v),
+}
+
 /// The size of text.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct TextSize(pub Length);
@@ -682,6 +814,202 @@ impl Resolve for Hyphenate {
     }
 }
 
+/// Words with custom hyphenation, grouped by language.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct HyphenationExceptions(pub Vec<(Lang, EcoString)>);
+
+impl HyphenationExceptions {
+    /// The byte lengths of the syllables of a word in the given language if
+    /// the word has an exception.
+    pub fn syllables(&self, lang: Lang, word: &str) -> Option<Vec<usize>> {
+        let (_, pattern) = self.0.iter().find(|(l, pattern)| {
+            *l == lang
+                && pattern
+                    .chars()
+                    .filter(|&c| c != '-')
+                    .flat_map(char::to_lowercase)
+                    .eq(word.chars().flat_map(char::to_lowercase))
+        })?;
+
+        // Map the pattern's segments onto the word to preserve its casing.
+        let mut chars = word.chars();
+        Some(
+            pattern
+                .split('-')
+                .map(|segment| {
+                    chars.by_ref().take(segment.chars().count()).map(char::len_utf8).sum()
+                })
+                .collect(),
+        )
+    }
+}
+
+cast! {
+    HyphenationExceptions,
+    self => {
+        let mut dict = Dict::new();
+        for (lang, word) in self.0 {
+            let key: Str = lang.as_str().into();
+            match dict.at_mut(&key) {
+                Ok(Value::Array(words)) => words.push(word.into_value()),
+                _ => dict.insert(key, array![word].into_value()),
+            }
+        }
+        dict.into_value()
+    },
+    values: Dict => {
+        let mut exceptions = vec![];
+        for (key, value) in values {
+            let lang = Lang::from_str(&key)?;
+            for word in value.cast::<Vec<EcoString>>()? {
+                exceptions.push((lang, word));
+            }
+        }
+        Self(exceptions)
+    },
+}
+
+impl Fold for HyphenationExceptions {
+    type Output = Self;
+
+    fn fold(mut self, outer: Self::Output) -> Self::Output {
+        self.0.extend(outer.0);
+        self
+    }
+}
+
+/// Custom penalties for line breaks between words, grouped by language.
+#[derive(Debug, Default, Clone, PartialEq, Hash)]
+pub struct BreakPenalties(pub Vec<(Lang, BreakRule)>);
+
+impl BreakPenalties {
+    /// The penalty for a break between two words in the given language if a
+    /// rule matches them.
+    pub fn get(&self, lang: Lang, before: &str, after: &str) -> Option<f64> {
+        self.0
+            .iter()
+            .find(|(l, rule)| *l == lang && rule.matches(before, after))
+            .map(|(_, rule)| rule.penalty.0)
+    }
+}
+
+cast! {
+    BreakPenalties,
+    self => {
+        let mut dict = Dict::new();
+        for (lang, rule) in self.0 {
+            let key: Str = lang.as_str().into();
+            match dict.at_mut(&key) {
+                Ok(Value::Array(rules)) => rules.push(rule.into_value()),
+                _ => dict.insert(key, array![rule].into_value()),
+            }
+        }
+        dict.into_value()
+    },
+    values: Dict => {
+        let mut penalties = vec![];
+        for (key, value) in values {
+            let lang = Lang::from_str(&key)?;
+            for rule in value.cast::<Vec<BreakRule>>()? {
+                penalties.push((lang, rule));
+            }
+        }
+        Self(penalties)
+    },
+}
+
+impl Fold for BreakPenalties {
+    type Output = Self;
+
+    fn fold(mut self, outer: Self::Output) -> Self::Output {
+        self.0.extend(outer.0);
+        self
+    }
+}
+
+/// A penalty for line breaks between matching words.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct BreakRule {
+    /// The pattern the word before the break must match.
+    pub before: Option<StrPattern>,
+    /// The pattern the word after the break must match.
+    pub after: Option<StrPattern>,
+    /// The penalty for the break.
+    pub penalty: Scalar,
+}
+
+impl BreakRule {
+    /// Whether the rule applies to a break between the two words.
+    fn matches(&self, before: &str, after: &str) -> bool {
+        self.before.as_ref().map_or(true, |pattern| pattern.matches(before))
+            && self.after.as_ref().map_or(true, |pattern| pattern.matches(after))
+    }
+}
+
+cast! {
+    BreakRule,
+    self => {
+        let mut dict = Dict::new();
+        if let Some(before) = self.before {
+            dict.insert("before".into(), before.into_value());
+        }
+        if let Some(after) = self.after {
+            dict.insert("after".into(), after.into_value());
+        }
+        dict.insert("penalty".into(), self.penalty.0.into_value());
+        dict.into_value()
+    },
+    mut dict: Dict => {
+        let before = dict.take("before").ok().map(Value::cast).transpose()?;
+        let after = dict.take("after").ok().map(Value::cast).transpose()?;
+        let penalty = Scalar(dict.take("penalty")?.cast()?);
+        dict.finish(&["before", "after", "penalty"])?;
+        Self { before, after, penalty }
+    },
+}
+
+/// Manual spacing adjustments between pairs of characters.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct KerningPairs(pub Vec<((char, char), Length)>);
+
+impl KerningPairs {
+    /// The adjustment between two characters, if any.
+    pub fn get(&self, first: char, second: char) -> Option<Length> {
+        self.0
+            .iter()
+            .find(|(pair, _)| *pair == (first, second))
+            .map(|&(_, amount)| amount)
+    }
+}
+
+cast! {
+    KerningPairs,
+    self => self.0
+        .into_iter()
+        .map(|((a, b), amount)| (eco_format!("{a}{b}").into(), amount.into_value()))
+        .collect::<Dict>()
+        .into_value(),
+    values: Dict => Self(values
+        .into_iter()
+        .map(|(k, v)| {
+            let mut chars = k.chars();
+            let (Some(a), Some(b), None) = (chars.next(), chars.next(), chars.next()) else {
+                bail!("kerning pair must consist of exactly two characters");
+            };
+            Ok(((a, b), v.cast::<Length>()?))
+        })
+        .collect::<StrResult<_>>()?),
+}
+
+impl Fold for KerningPairs {
+    type Output = Self;
+
+    fn fold(mut self, outer: Self::Output) -> Self::Output {
+        self.0.extend(outer.0);
+        self
+    }
+}
+
 /// A stylistic set in a font.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct StylisticSet(u8);
@@ -752,7 +1080,10 @@ cast! {
     values: Dict => Self(values
         .into_iter()
         .map(|(k, v)| {
-            let num = v.cast::<u32>()?;
+            let num = match v {
+                Value::Bool(on) => on as u32,
+                v => v.cast::<u32>()?,
+            };
             let tag = Tag::from_bytes_lossy(k.as_bytes());
             Ok((tag, num))
         })
@@ -1,11 +1,16 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use once_cell::sync::Lazy;
 use syntect::highlighting as synt;
+use syntect::parsing::{SyntaxDefinition, SyntaxSet};
 use typst::syntax::{self, LinkedNode};
+use typst::util::{Buffer, PathExt};
 
 use super::{
     FontFamily, FontList, Hyphenate, LinebreakElem, SmartQuoteElem, TextElem, TextSize,
 };
-use crate::layout::BlockElem;
+use crate::layout::{BlockElem, GridElem, HideElem, ParElem, Sizing, TrackSizings};
 use crate::meta::{Figurable, LocalName};
 use crate::prelude::*;
 
@@ -104,8 +109,39 @@ pub struct RawElem {
     /// This is *Typst!*
     /// ```
     /// ````
+    ///
+    /// If the language is unknown, the text is not highlighted and a warning
+    /// is logged.
     pub lang: Option<EcoString>,
 
+    /// One or multiple additional syntax definitions to load.
+    ///
+    /// The syntax definitions should be in the
+    /// [`sublime-syntax`](https://www.sublimetext.com/docs/syntax.html) file
+    /// format. They are resolved through the `lang` tag, just like the
+    /// built-in languages, by their name or file extensions. Relative paths
+    /// are resolved relative to the file containing the raw text.
+    ///
+    /// ````example
+    /// #set raw(syntaxes: "SExpressions.sublime-syntax")
+    ///
+    /// ```sexp
+    /// (defun factorial (x)
+    ///   (if (zerop x) 1
+    ///     (* x (factorial (- x 1)))))
+    /// ```
+    /// ````
+    pub syntaxes: SyntaxPaths,
+
+    /// The theme to use for syntax highlighting, as a path to a
+    /// [`tmTheme`](https://www.sublimetext.com/docs/color_schemes_tmtheme.html)
+    /// file. If set to `{none}`, the built-in theme is used.
+    ///
+    /// Only the foreground colors and font styles of the theme are applied.
+    /// Like syntax definitions, relative paths are resolved relative to the
+    /// file containing the raw text.
+    pub theme: Option<EcoString>,
+
     /// The horizontal alignment that each line in a raw block should have.
     /// This option is ignored if this is not a raw block (if specified
     /// `block: false` or single backticks were used in markup mode).
@@ -126,6 +162,90 @@ pub struct RawElem {
     /// ````
     #[default(HorizontalAlign(GenAlign::Start))]
     pub align: HorizontalAlign,
+
+    /// Whether to number the lines of a raw block.
+    ///
+    /// The numbers are right-aligned in a gutter that is as wide as the
+    /// largest number. Lines that wrap keep a single number. This option is
+    /// ignored if this is not a raw block.
+    ///
+    /// ````example
+    /// #set raw(line-numbers: true)
+    ///
+    /// ```rust
+    /// fn main() {
+    ///     println!("Hello World!");
+    /// }
+    /// ```
+    /// ````
+    #[default(false)]
+    pub line_numbers: bool,
+
+    /// The number of the first line.
+    #[default(1)]
+    pub line_start: usize,
+
+    /// Which lines to number: With a step of `{5}`, only every fifth line
+    /// is numbered, counting from the first line.
+    #[default(NonZeroUsize::ONE)]
+    pub line_step: NonZeroUsize,
+
+    /// Lines of a raw block to emphasize with a background.
+    ///
+    /// Lines are given by their number, taking `line-start` into account.
+    /// Multiple consecutive lines can be given as a string like `{"3-5"}`.
+    /// This option is ignored if this is not a raw block.
+    ///
+    /// ````example
+    /// #set raw(highlight: (1, "3-4"))
+    ///
+    /// ```rust
+    /// fn main() {
+    ///     let x = 1;
+    ///     let y = 2;
+    ///     println!("{}", x + y);
+    /// }
+    /// ```
+    /// ````
+    pub highlight: Vec<LineRange>,
+
+    /// The background of highlighted lines.
+    #[default(Color::Rgba(RgbaColor::new(0xff, 0xf2, 0xa8, 0xff)).into())]
+    pub highlight_fill: Paint,
+
+    /// Whether to mark lines of a raw block as added or removed, like in a
+    /// diff.
+    ///
+    /// - `{true}`: Each line starts with a marker: A `+` for an added line, a
+    ///   `-` for a removed line, and a space for an unchanged one. The marker
+    ///   column is removed before the text is highlighted, so that the
+    ///   indentation of the code is preserved. Lines that start with any other
+    ///   character are kept as they are.
+    /// - An array: The status of each line, given as `{"added"}`,
+    ///   `{"removed"}`, or `{"unchanged"}`. Missing lines are unchanged.
+    ///
+    /// Marked lines are tinted with the `added-fill` and `removed-fill`. This
+    /// option is ignored if this is not a raw block.
+    ///
+    /// ````example
+    /// #set raw(diff: true)
+    ///
+    /// ```python
+    ///  def greet(name):
+    /// -    print("Hello " + name)
+    /// +    print(f"Hello {name}")
+    /// ```
+    /// ````
+    #[default(Diff::None)]
+    pub diff: Diff,
+
+    /// The background of added lines in a diff.
+    #[default(Color::Rgba(RgbaColor::new(0xe6, 0xff, 0xec, 0xff)).into())]
+    pub added_fill: Paint,
+
+    /// The background of removed lines in a diff.
+    #[default(Color::Rgba(RgbaColor::new(0xff, 0xeb, 0xe9, 0xff)).into())]
+    pub removed_fill: Paint,
 }
 
 impl RawElem {
@@ -143,6 +263,102 @@ impl RawElem {
             .chain([("Typst", vec!["typ"]), ("Typst (code)", vec!["typc"])])
             .collect()
     }
+
+    /// Lay out lines individually, to number or highlight them.
+    fn layout_lines(
+        &self,
+        lines: Vec<Content>,
+        numbered: bool,
+        statuses: &[DiffStatus],
+        styles: StyleChain,
+    ) -> Content {
+        let start = self.line_start(styles);
+        let step = self.line_step(styles).get();
+        let highlight = self.highlight(styles);
+        let highlight_fill = self.highlight_fill(styles);
+        let added_fill = self.added_fill(styles);
+        let removed_fill = self.removed_fill(styles);
+
+        // The leading is resolved with the raw text's font size in the grid.
+        let leading = styles
+            .get::<Length>(ParElem::func(), "leading", None, || Em::new(0.65).into());
+        let gutter = Em::new(1.0);
+
+        // Backgrounds of neighbouring cells meet in the middle of the gutters.
+        let tint = |body: Content, fill: &Paint, left: bool, right: bool| {
+            let half = Some(Rel::from(Length::from(gutter / 2.0)));
+            let vertical = Some(Rel::from(leading / 2.0));
+            BlockElem::new()
+                .with_body(Some(body))
+                .with_fill(Some(fill.clone()))
+                .with_outset(Sides::new(
+                    half.filter(|_| left),
+                    vertical,
+                    half.filter(|_| right),
+                    vertical,
+                ))
+        };
+
+        let last = eco_format!("{}", start + lines.len().saturating_sub(1));
+        let mut cells = vec![];
+        for (i, mut line) in lines.into_iter().enumerate() {
+            let number = start + i;
+
+            // Highlighting takes precedence over diff markings.
+            let fill = if highlight.iter().any(|range| range.contains(number)) {
+                Some(&highlight_fill)
+            } else {
+                match statuses.get(i) {
+                    Some(DiffStatus::Added) => Some(&added_fill),
+                    Some(DiffStatus::Removed) => Some(&removed_fill),
+                    _ => None,
+                }
+            };
+
+            // Empty lines still take up one line.
+            if line.is_empty() {
+                line = TextElem::packed("\u{200B}");
+            }
+
+            if numbered {
+                // Numbers of lines that are skipped are hidden, so that each
+                // row is at least one line high. Shorter numbers are padded,
+                // so that all highlights in the gutter have the same width.
+                let digits = eco_format!("{number}");
+                let pad = "0".repeat(last.len() - digits.len());
+                let mut number = TextElem::packed(digits);
+                if i % step != 0 {
+                    number = HideElem::new(number).pack();
+                }
+                if !pad.is_empty() {
+                    number = HideElem::new(TextElem::packed(pad)).pack() + number;
+                }
+                if let Some(fill) = fill {
+                    number = tint(number, fill, false, true).pack();
+                }
+                cells.push(number);
+            }
+
+            if let Some(fill) = fill {
+                line = tint(line, fill, numbered, false)
+                    .with_width(Smart::Custom(Rel::one()))
+                    .pack();
+            }
+            cells.push(line);
+        }
+
+        let columns = if numbered {
+            vec![Sizing::Auto, Sizing::Fr(Fr::one())]
+        } else {
+            vec![Sizing::Fr(Fr::one())]
+        };
+
+        GridElem::new(cells)
+            .with_columns(TrackSizings(columns))
+            .with_column_gutter(TrackSizings(vec![gutter.into()]))
+            .with_row_gutter(TrackSizings(vec![leading.into()]))
+            .pack()
+    }
 }
 
 impl Synthesize for RawElem {
@@ -154,53 +370,133 @@ impl Synthesize for RawElem {
 
 impl Show for RawElem {
     #[tracing::instrument(name = "RawElem::show", skip_all)]
-    fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
-        let text = self.text();
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let (text, statuses) = match self.block(styles) {
+            true => self.diff(styles).apply(&self.text()),
+            false => (self.text(), vec![]),
+        };
         let lang = self.lang(styles).as_ref().map(|s| s.to_lowercase());
-        let foreground = THEME
+
+        let span = self.span();
+        let paths = self.syntaxes(styles).0;
+        let custom = if paths.is_empty() {
+            None
+        } else {
+            let data = paths
+                .iter()
+                .map(|path| {
+                    let path = locate(vt.world, span, path)?;
+                    vt.world.file(&path).map_err(Into::into)
+                })
+                .collect::<StrResult<Vec<_>>>()
+                .at(span)?;
+            Some(load_syntaxes(&data).at(span)?)
+        };
+        let syntaxes = custom.as_deref().unwrap_or(&SYNTAXES);
+
+        let custom = match self.theme(styles) {
+            Some(path) => {
+                let path = locate(vt.world, span, &path).at(span)?;
+                let data = vt.world.file(&path).at(span)?;
+                Some(load_theme(data).at(span)?)
+            }
+            None => None,
+        };
+        let theme = custom.as_deref().unwrap_or(&THEME);
+
+        let foreground = theme
             .settings
             .foreground
             .map(to_typst)
             .map_or(Color::BLACK, Color::from);
 
-        let mut realized = if matches!(lang.as_deref(), Some("typ" | "typst" | "typc")) {
+        // Unknown languages are not highlighted.
+        let syntax = match lang.as_deref() {
+            None | Some("typ" | "typst" | "typc") => None,
+            Some(token) => {
+                let syntax = syntaxes.find_syntax_by_token(token);
+                if syntax.is_none() {
+                    tracing::warn!("Unknown language {token} in raw text");
+                }
+                syntax
+            }
+        };
+
+        let numbered = self.block(styles) && self.line_numbers(styles);
+        let individual = numbered
+            || (self.block(styles) && !self.highlight(styles).is_empty())
+            || statuses.iter().any(|&status| status != DiffStatus::Unchanged);
+        let lines = if matches!(lang.as_deref(), Some("typ" | "typst" | "typc")) {
             let root = match lang.as_deref() {
                 Some("typc") => syntax::parse_code(&text),
                 _ => syntax::parse(&text),
             };
 
-            let mut seq = vec![];
-            let highlighter = synt::Highlighter::new(&THEME);
+            let mut lines = vec![vec![]];
+            let highlighter = synt::Highlighter::new(theme);
             highlight_themed(
                 &LinkedNode::new(&root),
                 vec![],
                 &highlighter,
                 &mut |node, style| {
-                    seq.push(styled(&text[node.range()], foreground.into(), style));
+                    // Split pieces that span multiple lines.
+                    for (i, part) in text[node.range()].split('\n').enumerate() {
+                        if i != 0 {
+                            lines.push(vec![]);
+                        }
+                        let part = part.trim_end_matches('\r');
+                        if !part.is_empty() {
+                            let piece = styled(part, foreground.into(), style);
+                            lines.last_mut().unwrap().push(piece);
+                        }
+                    }
                 },
             );
 
-            Content::sequence(seq)
-        } else if let Some(syntax) =
-            lang.and_then(|token| SYNTAXES.find_syntax_by_token(&token))
-        {
-            let mut seq = vec![];
-            let mut highlighter = syntect::easy::HighlightLines::new(syntax, &THEME);
-            for (i, line) in text.lines().enumerate() {
-                if i != 0 {
-                    seq.push(LinebreakElem::new().pack());
-                }
-
+            Some(lines.into_iter().map(Content::sequence).collect())
+        } else if let Some(syntax) = syntax {
+            let mut lines = vec![];
+            let mut highlighter = syntect::easy::HighlightLines::new(syntax, theme);
+            for line in text.lines() {
+                let mut seq = vec![];
                 for (style, piece) in
-                    highlighter.highlight_line(line, &SYNTAXES).into_iter().flatten()
+                    highlighter.highlight_line(line, syntaxes).into_iter().flatten()
                 {
                     seq.push(styled(piece, foreground.into(), style));
                 }
+                lines.push(Content::sequence(seq));
             }
 
-            Content::sequence(seq)
+            Some(lines)
+        } else if individual {
+            let lines = text.lines();
+            Some(
+                lines
+                    .map(|line| match line {
+                        "" => Content::empty(),
+                        _ => TextElem::packed(line),
+                    })
+                    .collect(),
+            )
         } else {
-            TextElem::packed(text)
+            None
+        };
+
+        let mut realized = match lines {
+            Some(lines) if individual => {
+                self.layout_lines(lines, numbered, &statuses, styles)
+            }
+            Some(lines) => {
+                let mut seq = vec![];
+                for (i, line) in lines.into_iter().enumerate() {
+                    if i != 0 {
+                        seq.push(LinebreakElem::new().pack());
+                    }
+                    seq.push(line);
+                }
+                Content::sequence(seq)
+            }
+            None => TextElem::packed(text),
         };
 
         if self.block(styles) {
@@ -248,6 +544,19 @@ impl LocalName for RawElem {
             Lang::ENGLISH | _ => "Listing",
         }
     }
+
+    fn local_plural_name(&self, lang: Lang, region: Option<Region>) -> &'static str {
+        match lang {
+            Lang::DUTCH => "Listings",
+            Lang::FRENCH => "Listes",
+            Lang::GERMAN => "Listings",
+            Lang::RUSSIAN => "Листинги",
+            Lang::SWEDISH => "Listings",
+            Lang::UKRAINIAN => "Лістинги",
+            Lang::ENGLISH => "Listings",
+            _ => self.local_name(lang, region),
+        }
+    }
 }
 
 impl Figurable for RawElem {}
@@ -258,6 +567,248 @@ impl PlainText for RawElem {
     }
 }
 
+/// A range of line numbers.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct LineRange {
+    /// The first line in the range.
+    pub start: usize,
+    /// The last line in the range.
+    pub end: usize,
+}
+
+impl LineRange {
+    /// Whether the line with the given number is in the range.
+    pub fn contains(self, line: usize) -> bool {
+        (self.start..=self.end).contains(&line)
+    }
+}
+
+cast! {
+    LineRange,
+    self => if self.start == self.end {
+        self.start.into_value()
+    } else {
+        eco_format!("{}-{}", self.start, self.end).into_value()
+    },
+    line: usize => Self { start: line, end: line },
+    string: EcoString => {
+        let parse = |s: &str| s.trim().parse::<usize>().ok();
+        let (start, end) = string
+            .split_once('-')
+            .and_then(|(start, end)| Some((parse(start)?, parse(end)?)))
+            .filter(|(start, end)| start <= end)
+            .ok_or("expected line range like \"3-5\"")?;
+        Self { start, end }
+    },
+}
+
+/// How lines of a raw block are marked as changed.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Diff {
+    /// No lines are marked.
+    None,
+    /// Lines are marked by a leading `+`, `-`, or space.
+    Markers,
+    /// The status of each line is given explicitly.
+    Lines(Vec<DiffStatus>),
+}
+
+impl Diff {
+    /// Strip the marker column from the text, if any, and determine the
+    /// status of each line.
+    fn apply(&self, text: &str) -> (EcoString, Vec<DiffStatus>) {
+        match self {
+            Self::None => (text.into(), vec![]),
+            Self::Lines(statuses) => (text.into(), statuses.clone()),
+            Self::Markers => {
+                let mut stripped = EcoString::new();
+                let mut statuses = vec![];
+                for (i, line) in text.lines().enumerate() {
+                    if i != 0 {
+                        stripped.push('\n');
+                    }
+
+                    let status = match line.as_bytes().first() {
+                        Some(b'+') => DiffStatus::Added,
+                        Some(b'-') => DiffStatus::Removed,
+                        Some(b' ') => DiffStatus::Unchanged,
+                        _ => {
+                            stripped.push_str(line);
+                            statuses.push(DiffStatus::Unchanged);
+                            continue;
+                        }
+                    };
+
+                    stripped.push_str(&line[1..]);
+                    statuses.push(status);
+                }
+                (stripped, statuses)
+            }
+        }
+    }
+}
+
+cast! {
+    Diff,
+    self => match self {
+        Self::None => false.into_value(),
+        Self::Markers => true.into_value(),
+        Self::Lines(statuses) => statuses.into_value(),
+    },
+    v: bool => if v { Self::Markers } else { Self::None },
+    statuses: Vec<DiffStatus> => Self::Lines(statuses),
+}
+
+/// Whether a line was changed in a diff.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum DiffStatus {
+    /// The line is unchanged.
+    Unchanged,
+    /// The line was added.
+    Added,
+    /// The line was removed.
+    Removed,
+}
+
+/// A list of paths to syntax definition files.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct SyntaxPaths(pub Vec<EcoString>);
+
+cast! {
+    SyntaxPaths,
+    self => self.0.into_value(),
+    path: EcoString => Self(vec![path]),
+    paths: Array => Self(paths.into_iter().map(Value::cast).collect::<StrResult<_>>()?),
+}
+
+/// Resolve a path relative to the file the raw text stems from.
+fn locate(world: Tracked<dyn World + '_>, span: Span, path: &str) -> StrResult<PathBuf> {
+    if !span.is_detached() {
+        if let Some(path) = path.strip_prefix('/') {
+            return Ok(world.root().join(path).normalize());
+        }
+
+        if let Some(dir) = world.source(span.source()).path().parent() {
+            return Ok(dir.join(path).normalize());
+        }
+    }
+
+    Err("cannot access file system from here".into())
+}
+
+/// Extend the built-in syntax set with custom syntax definitions.
+#[comemo::memoize]
+fn load_syntaxes(data: &[Buffer]) -> StrResult<Arc<SyntaxSet>> {
+    let mut builder = SYNTAXES.clone().into_builder();
+    for buffer in data {
+        let text = std::str::from_utf8(buffer)
+            .map_err(|_| "syntax definition is not valid utf-8")?;
+        let syntax = SyntaxDefinition::load_from_str(text, false, None)
+            .map_err(|err| eco_format!("failed to parse syntax definition ({err})"))?;
+        builder.add(syntax);
+    }
+    Ok(Arc::new(builder.build()))
+}
+
+/// Load a theme from a TextMate theme file.
+#[comemo::memoize]
+fn load_theme(data: Buffer) -> StrResult<Arc<synt::Theme>> {
+    let text = std::str::from_utf8(&data).map_err(|_| "theme is not valid utf-8")?;
+    let document = roxmltree::Document::parse(text)
+        .map_err(|err| eco_format!("failed to parse theme ({err})"))?;
+    let root = document
+        .root_element()
+        .children()
+        .find(|node| node.has_tag_name("dict"))
+        .ok_or("failed to parse theme (expected property list)")?;
+
+    let mut theme = synt::Theme::default();
+    for (key, value) in plist_entries(root) {
+        match key {
+            "name" => theme.name = value.text().map(Into::into),
+            "author" => theme.author = value.text().map(Into::into),
+            "settings" => {
+                for entry in value.children().filter(|node| node.is_element()) {
+                    let mut scope = None;
+                    let mut settings = None;
+                    for (key, value) in plist_entries(entry) {
+                        match key {
+                            "scope" => scope = value.text(),
+                            "settings" => settings = Some(value),
+                            _ => {}
+                        }
+                    }
+
+                    let Some(settings) = settings else { continue };
+                    let style = theme_style(settings)?;
+                    match scope {
+                        Some(scope) => theme.scopes.push(synt::ThemeItem {
+                            scope: scope.parse().map_err(|_| {
+                                eco_format!(
+                                    "failed to parse theme (invalid scope {scope})"
+                                )
+                            })?,
+                            style,
+                        }),
+                        None => {
+                            theme.settings.foreground = style.foreground;
+                            theme.settings.background = style.background;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Arc::new(theme))
+}
+
+/// The style settings of a theme entry.
+fn theme_style(settings: roxmltree::Node) -> StrResult<synt::StyleModifier> {
+    let color = |node: roxmltree::Node| {
+        let text = node.text().unwrap_or_default().trim();
+        text.parse::<RgbaColor>()
+            .map(to_syn)
+            .map_err(|_| eco_format!("failed to parse theme (invalid color {text})"))
+    };
+
+    let mut style = synt::StyleModifier::default();
+    for (key, value) in plist_entries(settings) {
+        match key {
+            "foreground" => style.foreground = Some(color(value)?),
+            "background" => style.background = Some(color(value)?),
+            "fontStyle" => {
+                let mut font_style = synt::FontStyle::empty();
+                for part in value.text().unwrap_or_default().split_whitespace() {
+                    match part {
+                        "bold" => font_style |= synt::FontStyle::BOLD,
+                        "italic" => font_style |= synt::FontStyle::ITALIC,
+                        "underline" => font_style |= synt::FontStyle::UNDERLINE,
+                        _ => {}
+                    }
+                }
+                style.font_style = Some(font_style);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(style)
+}
+
+/// The key-value pairs of a property list dictionary.
+fn plist_entries<'a, 'input>(
+    dict: roxmltree::Node<'a, 'input>,
+) -> impl Iterator<Item = (&'a str, roxmltree::Node<'a, 'input>)> {
+    let mut children = dict.children().filter(|node| node.is_element());
+    std::iter::from_fn(move || {
+        let key = children.next()?;
+        let value = children.next()?;
+        Some((key.text().unwrap_or_default(), value))
+    })
+}
+
 /// Highlight a syntax node in a theme by calling `f` with ranges and their
 /// styles.
 fn highlight_themed<F>(
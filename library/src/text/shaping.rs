@@ -1,6 +1,8 @@
 use std::borrow::Cow;
 use std::ops::Range;
 use std::str::FromStr;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 
 use az::SaturatingAs;
 use rustybuzz::{Feature, Tag, UnicodeBuffer};
@@ -8,8 +10,10 @@ use typst::font::{Font, FontStyle, FontVariant};
 use typst::util::SliceExt;
 use unicode_script::{Script, UnicodeScript};
 
-use super::{decorate, FontFamily, NumberType, NumberWidth, TextElem};
-use crate::layout::SpanMapper;
+use super::{
+    decorate, FontFallback, FontFamily, KerningPairs, NumberType, NumberWidth, TextElem,
+};
+use crate::layout::{LeadingMode, ParElem, SpanMapper, SHAPE_MISSES, SHAPE_REQUESTS};
 use crate::prelude::*;
 
 /// The result of shaping text.
@@ -145,13 +149,18 @@ impl ShapedGlyph {
         matches!(self.c, '\u{30FB}')
     }
 
-    pub fn base_adjustability(&self, gb_style: bool) -> Adjustability {
+    pub fn base_adjustability(
+        &self,
+        gb_style: bool,
+        stretch: Ratio,
+        shrink: Ratio,
+    ) -> Adjustability {
         let width = self.x_advance;
         if self.is_space() {
             Adjustability {
-                // The number for spaces is from Knuth-Plass' paper
-                stretchability: (Em::zero(), width / 2.0),
-                shrinkability: (Em::zero(), width / 3.0),
+                // The default numbers for spaces are from Knuth-Plass' paper
+                stretchability: (Em::zero(), stretch.of(width)),
+                shrinkability: (Em::zero(), shrink.of(width)),
             }
         } else if self.is_cjk_left_aligned_punctuation(gb_style) {
             Adjustability {
@@ -312,12 +321,17 @@ impl<'a> ShapedText<'a> {
 
         let top_edge = TextElem::top_edge_in(self.styles);
         let bottom_edge = TextElem::bottom_edge_in(self.styles);
+        let normal = ParElem::leading_mode_in(self.styles) == LeadingMode::Normal;
 
         // Expand top and bottom by reading the font's vertical metrics.
         let mut expand = |font: &Font| {
             let metrics = font.metrics();
             top.set_max(top_edge.resolve(self.styles, metrics));
             bottom.set_max(-bottom_edge.resolve(self.styles, metrics));
+            if normal {
+                top.set_max(metrics.ascender.at(self.size));
+                bottom.set_max(-metrics.descender.at(self.size));
+            }
         };
 
         if self.glyphs.is_empty() {
@@ -387,7 +401,30 @@ impl<'a> ShapedText<'a> {
         text_range: Range<usize>,
     ) -> ShapedText<'a> {
         let text = &self.text[text_range.start - self.base..text_range.end - self.base];
-        if let Some(glyphs) = self.slice_safe_to_break(text_range.clone()) {
+        if let Some(range) = self.slice_safe_to_break(text_range.clone()) {
+            // Like in separately shaped text, the last glyph is not followed
+            // by tracking or manual kerning.
+            let mut glyphs = Cow::Borrowed(&self.glyphs[range.clone()]);
+            let extra = match (glyphs.last(), self.glyphs.get(range.end)) {
+                (Some(last), Some(next)) => {
+                    let pairs = TextElem::kerning_pairs_in(self.styles);
+                    Em::from_length(TextElem::tracking_in(self.styles), self.size)
+                        + pair_kerning(
+                            &pairs,
+                            self.styles,
+                            self.size,
+                            self.dir,
+                            last,
+                            next,
+                        )
+                }
+                _ => Em::zero(),
+            };
+
+            if extra != Em::zero() {
+                glyphs.to_mut().last_mut().unwrap().x_advance -= extra;
+            }
+
             Self {
                 base: text_range.start,
                 text,
@@ -398,7 +435,7 @@ impl<'a> ShapedText<'a> {
                 size: self.size,
                 variant: self.variant,
                 width: glyphs.iter().map(|g| g.x_advance).sum::<Em>().at(self.size),
-                glyphs: Cow::Borrowed(glyphs),
+                glyphs,
             }
         } else {
             shape(
@@ -449,7 +486,7 @@ impl<'a> ShapedText<'a> {
 
     /// Find the subslice of glyphs that represent the given text range if both
     /// sides are safe to break.
-    fn slice_safe_to_break(&self, text_range: Range<usize>) -> Option<&[ShapedGlyph]> {
+    fn slice_safe_to_break(&self, text_range: Range<usize>) -> Option<Range<usize>> {
         let Range { mut start, mut end } = text_range;
         if !self.dir.is_positive() {
             std::mem::swap(&mut start, &mut end);
@@ -457,7 +494,7 @@ impl<'a> ShapedText<'a> {
 
         let left = self.find_safe_to_break(start, Side::Left)?;
         let right = self.find_safe_to_break(end, Side::Right)?;
-        Some(&self.glyphs[left..right])
+        Some(left..right)
     }
 
     /// Find the glyph offset matching the text index that is most towards the
@@ -521,7 +558,7 @@ struct ShapingContext<'a, 'v> {
     styles: StyleChain<'a>,
     size: Abs,
     variant: FontVariant,
-    tags: Vec<rustybuzz::Feature>,
+    tags: Vec<(Tag, u32)>,
     fallback: bool,
     dir: Dir,
 }
@@ -548,7 +585,7 @@ pub fn shape<'a>(
         styles,
         variant: variant(styles),
         tags: tags(styles),
-        fallback: TextElem::fallback_in(styles),
+        fallback: TextElem::fallback_in(styles).automatic(),
         dir,
     };
 
@@ -573,6 +610,53 @@ pub fn shape<'a>(
     }
 }
 
+/// The fonts consulted for a piece of text.
+pub struct FontReport {
+    /// Consecutive runs of the text together with the font that displays
+    /// them. Runs without a font could not be displayed by any of the
+    /// consulted fonts.
+    pub runs: Vec<(Range<usize>, Option<Font>)>,
+    /// All consulted fonts in the order they were tried.
+    pub consulted: Vec<Font>,
+}
+
+/// Determine which fonts display a piece of text.
+pub fn report_fonts(vt: &Vt, text: &str, styles: StyleChain) -> FontReport {
+    let spans = SpanMapper::new();
+    let mut ctx = ShapingContext {
+        vt,
+        spans: &spans,
+        size: TextElem::size_in(styles),
+        glyphs: vec![],
+        used: vec![],
+        styles,
+        variant: variant(styles),
+        tags: tags(styles),
+        fallback: TextElem::fallback_in(styles).automatic(),
+        dir: TextElem::dir_in(styles),
+    };
+
+    if !text.is_empty() {
+        shape_segment(&mut ctx, 0, text, families(styles));
+    }
+
+    let mut glyphs = ctx.glyphs;
+    glyphs.sort_by_key(|glyph| glyph.range.start);
+
+    let mut runs: Vec<(Range<usize>, Option<Font>)> = vec![];
+    for glyph in glyphs {
+        let font = (glyph.glyph_id != 0).then_some(glyph.font);
+        match runs.last_mut() {
+            Some((range, prev)) if *prev == font => {
+                range.end = range.end.max(glyph.range.end);
+            }
+            _ => runs.push((glyph.range, font)),
+        }
+    }
+
+    FontReport { runs, consulted: ctx.used }
+}
+
 /// Shape text with font fallback using the `families` iterator.
 fn shape_segment(
     ctx: &mut ShapingContext,
@@ -613,20 +697,16 @@ fn shape_segment(
 
     ctx.used.push(font.clone());
 
-    // Fill the buffer with our text.
-    let mut buffer = UnicodeBuffer::new();
-    buffer.push_str(text);
-    buffer.set_language(language(ctx.styles));
-    buffer.set_direction(match ctx.dir {
-        Dir::LTR => rustybuzz::Direction::LeftToRight,
-        Dir::RTL => rustybuzz::Direction::RightToLeft,
-        _ => unimplemented!("vertical text layout"),
-    });
-
     // Shape!
-    let buffer = rustybuzz::shape(font.rusty(), &ctx.tags, buffer);
-    let infos = buffer.glyph_infos();
-    let pos = buffer.glyph_positions();
+    SHAPE_REQUESTS.fetch_add(1, Ordering::Relaxed);
+    let infos = shape_run(
+        &font,
+        text,
+        &ctx.tags,
+        TextElem::lang_in(ctx.styles),
+        TextElem::region_in(ctx.styles),
+        ctx.dir,
+    );
     let ltr = ctx.dir.is_positive();
 
     // Collect the shaped glyphs, doing fallback and shaping parts again with
@@ -637,7 +717,7 @@ fn shape_segment(
         let cluster = info.cluster as usize;
 
         // Add the glyph to the shaped output.
-        if info.glyph_id != 0 {
+        if info.id != 0 {
             // Determine the text range of the glyph.
             let start = base + cluster;
             let end = base
@@ -647,21 +727,21 @@ fn shape_segment(
 
             ctx.glyphs.push(ShapedGlyph {
                 font: font.clone(),
-                glyph_id: info.glyph_id as u16,
+                glyph_id: info.id,
                 // TODO: Don't ignore y_advance.
-                x_advance: font.to_em(pos[i].x_advance),
-                x_offset: font.to_em(pos[i].x_offset),
-                y_offset: font.to_em(pos[i].y_offset),
+                x_advance: font.to_em(info.x_advance),
+                x_offset: font.to_em(info.x_offset),
+                y_offset: font.to_em(info.y_offset),
                 adjustability: Adjustability::default(),
                 range: start..end,
-                safe_to_break: !info.unsafe_to_break(),
+                safe_to_break: info.safe_to_break,
                 c: text[cluster..].chars().next().unwrap(),
                 span: ctx.spans.span_at(start),
             });
         } else {
             // First, search for the end of the tofu sequence.
             let k = i;
-            while infos.get(i + 1).map_or(false, |info| info.glyph_id == 0) {
+            while infos.get(i + 1).map_or(false, |info| info.id == 0) {
                 i += 1;
             }
 
@@ -705,6 +785,64 @@ fn shape_segment(
     ctx.used.pop();
 }
 
+/// A glyph as produced by the shaper, with its metrics in font units.
+#[derive(Debug, Clone)]
+struct RawGlyph {
+    id: u16,
+    cluster: u32,
+    x_advance: i32,
+    x_offset: i32,
+    y_offset: i32,
+    safe_to_break: bool,
+}
+
+/// Shape a piece of text with a single font.
+///
+/// This is cached across compilations, so that text runs which didn't change
+/// don't need to be shaped again, even if their paragraph did change.
+#[comemo::memoize]
+fn shape_run(
+    font: &Font,
+    text: &str,
+    tags: &[(Tag, u32)],
+    lang: Lang,
+    region: Option<Region>,
+    dir: Dir,
+) -> Arc<Vec<RawGlyph>> {
+    SHAPE_MISSES.fetch_add(1, Ordering::Relaxed);
+
+    // Fill the buffer with our text.
+    let mut buffer = UnicodeBuffer::new();
+    buffer.push_str(text);
+    buffer.set_language(language(lang, region));
+    buffer.set_direction(match dir {
+        Dir::LTR => rustybuzz::Direction::LeftToRight,
+        Dir::RTL => rustybuzz::Direction::RightToLeft,
+        _ => unimplemented!("vertical text layout"),
+    });
+
+    let features: Vec<_> = tags
+        .iter()
+        .map(|&(tag, value)| Feature::new(tag, value, ..))
+        .collect();
+    let buffer = rustybuzz::shape(font.rusty(), &features, buffer);
+    let glyphs = buffer
+        .glyph_infos()
+        .iter()
+        .zip(buffer.glyph_positions())
+        .map(|(info, pos)| RawGlyph {
+            id: info.glyph_id as u16,
+            cluster: info.cluster,
+            x_advance: pos.x_advance,
+            x_offset: pos.x_offset,
+            y_offset: pos.y_offset,
+            safe_to_break: !info.unsafe_to_break(),
+        })
+        .collect();
+
+    Arc::new(glyphs)
+}
+
 /// Shape the text with tofus from the given font.
 fn shape_tofus(ctx: &mut ShapingContext, base: usize, text: &str, font: Font) {
     let x_advance = font.advance(0).unwrap_or_default();
@@ -732,6 +870,8 @@ fn track_and_space(ctx: &mut ShapingContext) {
     let spacing =
         TextElem::spacing_in(ctx.styles).map(|abs| Em::from_length(abs, ctx.size));
 
+    let pairs = TextElem::kerning_pairs_in(ctx.styles);
+
     let mut glyphs = ctx.glyphs.iter_mut().peekable();
     while let Some(glyph) = glyphs.next() {
         // Make non-breaking space same width as normal space.
@@ -743,15 +883,33 @@ fn track_and_space(ctx: &mut ShapingContext) {
             glyph.x_advance = spacing.relative_to(glyph.x_advance);
         }
 
-        if glyphs
-            .peek()
-            .map_or(false, |next| glyph.range.start != next.range.start)
+        if let Some(next) =
+            glyphs.peek().filter(|next| glyph.range.start != next.range.start)
         {
-            glyph.x_advance += tracking;
+            let kerning =
+                pair_kerning(&pairs, ctx.styles, ctx.size, ctx.dir, glyph, next);
+            glyph.x_advance += tracking + kerning;
         }
     }
 }
 
+/// The manual kerning between a glyph and the glyph that visually follows it.
+fn pair_kerning(
+    pairs: &KerningPairs,
+    styles: StyleChain,
+    size: Abs,
+    dir: Dir,
+    glyph: &ShapedGlyph,
+    next: &ShapedGlyph,
+) -> Em {
+    let (first, second) =
+        if dir.is_positive() { (glyph.c, next.c) } else { (next.c, glyph.c) };
+    pairs
+        .get(first, second)
+        .map(|amount| Em::from_length(amount.resolve(styles), size))
+        .unwrap_or_default()
+}
+
 pub fn is_gb_style(lang: Lang, region: Option<Region>) -> bool {
     // Most CJK variants, including zh-CN, ja-JP, zh-SG, zh-MY use GB-style punctuation,
     // while zh-HK and zh-TW use alternative style. We default to use GB-style.
@@ -763,9 +921,11 @@ pub fn is_gb_style(lang: Lang, region: Option<Region>) -> bool {
 /// and CJK punctuation adjustments according to Chinese Layout Requirements.
 fn calculate_adjustability(ctx: &mut ShapingContext, lang: Lang, region: Option<Region>) {
     let gb_style = is_gb_style(lang, region);
+    let stretch = ParElem::word_stretch_in(ctx.styles);
+    let shrink = ParElem::word_shrink_in(ctx.styles);
 
     for glyph in &mut ctx.glyphs {
-        glyph.adjustability = glyph.base_adjustability(gb_style);
+        glyph.adjustability = glyph.base_adjustability(gb_style, stretch, shrink);
     }
 
     let mut glyphs = ctx.glyphs.iter_mut().peekable();
@@ -833,17 +993,22 @@ pub fn families(styles: StyleChain) -> impl Iterator<Item = FontFamily> + Clone
         "segoe ui emoji",
     ];
 
-    let tail = if TextElem::fallback_in(styles) { FALLBACKS } else { &[] };
-    TextElem::font_in(styles)
-        .into_iter()
-        .chain(tail.iter().copied().map(FontFamily::new))
+    let tail = match TextElem::fallback_in(styles) {
+        FontFallback::Bool(true) => {
+            FALLBACKS.iter().copied().map(FontFamily::new).collect()
+        }
+        FontFallback::Bool(false) => vec![],
+        FontFallback::List(list) => list.0,
+    };
+
+    TextElem::font_in(styles).into_iter().chain(tail)
 }
 
 /// Collect the tags of the OpenType features to apply.
-fn tags(styles: StyleChain) -> Vec<Feature> {
+fn tags(styles: StyleChain) -> Vec<(Tag, u32)> {
     let mut tags = vec![];
     let mut feat = |tag, value| {
-        tags.push(Feature::new(Tag::from_bytes(tag), value, ..));
+        tags.push((Tag::from_bytes(tag), value));
     };
 
     // Features that are on by default in Harfbuzz are only added if disabled.
@@ -899,18 +1064,16 @@ fn tags(styles: StyleChain) -> Vec<Feature> {
         feat(b"frac", 1);
     }
 
-    for (tag, value) in TextElem::features_in(styles).0 {
-        tags.push(Feature::new(tag, value, ..))
-    }
+    tags.extend(TextElem::features_in(styles).0);
 
     tags
 }
 
 /// Process the language and and region of a style chain into a
 /// rustybuzz-compatible BCP 47 language.
-fn language(styles: StyleChain) -> rustybuzz::Language {
-    let mut bcp: EcoString = TextElem::lang_in(styles).as_str().into();
-    if let Some(region) = TextElem::region_in(styles) {
+fn language(lang: Lang, region: Option<Region>) -> rustybuzz::Language {
+    let mut bcp: EcoString = lang.as_str().into();
+    if let Some(region) = region {
         bcp.push('-');
         bcp.push_str(region.as_str());
     }
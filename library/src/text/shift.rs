@@ -1,4 +1,7 @@
-use super::{variant, SpaceElem, TextElem, TextSize};
+use rustybuzz::{Feature, Tag, UnicodeBuffer};
+use typst::font::Font;
+
+use super::{variant, FontFeatures, SpaceElem, TextElem, TextSize};
 use crate::prelude::*;
 
 /// Set text in subscript.
@@ -14,28 +17,32 @@ use crate::prelude::*;
 /// Category: text
 #[element(Show)]
 pub struct SubElem {
-    /// Whether to prefer the dedicated subscript characters of the font.
+    /// Whether to use the typographic subscripts of the font.
     ///
-    /// If this is enabled, Typst first tries to transform the text to subscript
-    /// codepoints. If that fails, it falls back to rendering lowered and shrunk
-    /// normal letters.
+    /// - If `{true}`, Typst first tries to transform the text to subscript
+    ///   codepoints. If that fails, it falls back to rendering lowered and
+    ///   shrunk normal letters.
+    /// - If `{"features"}`, Typst prefers the font's OpenType `subs`
+    ///   feature and otherwise behaves like `{true}`.
+    /// - If `{"force"}`, the subscript is never synthesized. Typst uses the
+    ///   `subs` feature unless the codepoints are available and the font
+    ///   lacks the feature.
+    /// - If `{false}`, the subscript is always synthesized.
     ///
     /// ```example
     /// N#sub(typographic: true)[1]
     /// N#sub(typographic: false)[1]
     /// ```
-    #[default(true)]
-    pub typographic: bool,
+    #[default(ScriptMode::Codepoints)]
+    pub typographic: ScriptMode,
 
-    /// The baseline shift for synthetic subscripts. Does not apply if
-    /// `typographic` is true and the font has subscript codepoints for the
-    /// given `body`.
+    /// The baseline shift for synthetic subscripts. Does not apply if the
+    /// subscript uses the font's codepoints or features.
     #[default(Em::new(0.2).into())]
     pub baseline: Length,
 
-    /// The font size for synthetic subscripts. Does not apply if
-    /// `typographic` is true and the font has subscript codepoints for the
-    /// given `body`.
+    /// The font size for synthetic subscripts. Does not apply if the
+    /// subscript uses the font's codepoints or features.
     #[default(TextSize(Em::new(0.6).into()))]
     pub size: TextSize,
 
@@ -47,20 +54,15 @@ pub struct SubElem {
 impl Show for SubElem {
     #[tracing::instrument(name = "SubElem::show", skip_all)]
     fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
-        let body = self.body();
-        let mut transformed = None;
-        if self.typographic(styles) {
-            if let Some(text) = search_text(&body, true) {
-                if is_shapable(vt, &text, styles) {
-                    transformed = Some(TextElem::packed(text));
-                }
-            }
-        };
-
-        Ok(transformed.unwrap_or_else(|| {
-            body.styled(TextElem::set_baseline(self.baseline(styles)))
-                .styled(TextElem::set_size(self.size(styles)))
-        }))
+        Ok(show_script(
+            vt,
+            styles,
+            self.body(),
+            true,
+            self.typographic(styles),
+            self.baseline(styles),
+            self.size(styles),
+        ))
     }
 }
 
@@ -77,28 +79,32 @@ impl Show for SubElem {
 /// Category: text
 #[element(Show)]
 pub struct SuperElem {
-    /// Whether to prefer the dedicated superscript characters of the font.
+    /// Whether to use the typographic superscripts of the font.
     ///
-    /// If this is enabled, Typst first tries to transform the text to
-    /// superscript codepoints. If that fails, it falls back to rendering
-    /// raised and shrunk normal letters.
+    /// - If `{true}`, Typst first tries to transform the text to superscript
+    ///   codepoints. If that fails, it falls back to rendering raised and
+    ///   shrunk normal letters.
+    /// - If `{"features"}`, Typst prefers the font's OpenType `sups`
+    ///   feature and otherwise behaves like `{true}`.
+    /// - If `{"force"}`, the superscript is never synthesized. Typst uses the
+    ///   `sups` feature unless the codepoints are available and the font
+    ///   lacks the feature.
+    /// - If `{false}`, the superscript is always synthesized.
     ///
     /// ```example
     /// N#super(typographic: true)[1]
     /// N#super(typographic: false)[1]
     /// ```
-    #[default(true)]
-    pub typographic: bool,
+    #[default(ScriptMode::Codepoints)]
+    pub typographic: ScriptMode,
 
-    /// The baseline shift for synthetic superscripts. Does not apply if
-    /// `typographic` is true and the font has superscript codepoints for the
-    /// given `body`.
+    /// The baseline shift for synthetic superscripts. Does not apply if the
+    /// superscript uses the font's codepoints or features.
     #[default(Em::new(-0.5).into())]
     pub baseline: Length,
 
-    /// The font size for synthetic superscripts. Does not apply if
-    /// `typographic` is true and the font has superscript codepoints for the
-    /// given `body`.
+    /// The font size for synthetic superscripts. Does not apply if the
+    /// superscript uses the font's codepoints or features.
     #[default(TextSize(Em::new(0.6).into()))]
     pub size: TextSize,
 
@@ -110,37 +116,93 @@ pub struct SuperElem {
 impl Show for SuperElem {
     #[tracing::instrument(name = "SuperElem::show", skip_all)]
     fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
-        let body = self.body();
-        let mut transformed = None;
-        if self.typographic(styles) {
-            if let Some(text) = search_text(&body, false) {
-                if is_shapable(vt, &text, styles) {
-                    transformed = Some(TextElem::packed(text));
+        Ok(show_script(
+            vt,
+            styles,
+            self.body(),
+            false,
+            self.typographic(styles),
+            self.baseline(styles),
+            self.size(styles),
+        ))
+    }
+}
+
+/// How to render sub- and superscripts.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ScriptMode {
+    /// Use the font's script codepoints if possible and synthesize otherwise.
+    Codepoints,
+    /// Prefer the font's OpenType feature over the codepoints.
+    Features,
+    /// Never synthesize.
+    Force,
+    /// Always synthesize.
+    Synthesize,
+}
+
+cast! {
+    ScriptMode,
+    self => match self {
+        Self::Codepoints => true.into_value(),
+        Self::Features => "features".into_value(),
+        Self::Force => "force".into_value(),
+        Self::Synthesize => false.into_value(),
+    },
+    /// Prefer the font's OpenType feature over the codepoints.
+    "features" => Self::Features,
+    /// Never synthesize.
+    "force" => Self::Force,
+    v: bool => if v { Self::Codepoints } else { Self::Synthesize },
+}
+
+/// Realize sub- or superscript content, preferring the font's typographic
+/// glyphs as configured by `typographic`.
+fn show_script(
+    vt: &Vt,
+    styles: StyleChain,
+    body: Content,
+    sub: bool,
+    mode: ScriptMode,
+    baseline: Length,
+    size: TextSize,
+) -> Content {
+    if mode != ScriptMode::Synthesize {
+        let tag = Tag::from_bytes(if sub { b"subs" } else { b"sups" });
+        let text = search_text(&body);
+        let covered = mode != ScriptMode::Codepoints
+            && text
+                .as_ref()
+                .map_or(false, |text| has_feature_glyphs(vt, text, tag, styles));
+
+        if !covered {
+            if let Some(converted) = text.and_then(|text| convert_script(&text, sub)) {
+                if is_shapable(vt, &converted, styles) {
+                    return TextElem::packed(converted);
                 }
             }
-        };
+        }
 
-        Ok(transformed.unwrap_or_else(|| {
-            body.styled(TextElem::set_baseline(self.baseline(styles)))
-                .styled(TextElem::set_size(self.size(styles)))
-        }))
+        if covered || mode == ScriptMode::Force {
+            return body.styled(TextElem::set_features(FontFeatures(vec![(tag, 1)])));
+        }
     }
+
+    body.styled(TextElem::set_baseline(baseline))
+        .styled(TextElem::set_size(size))
 }
 
-/// Find and transform the text contained in `content` to the given script kind
-/// if and only if it only consists of `Text`, `Space`, and `Empty` leafs.
-fn search_text(content: &Content, sub: bool) -> Option<EcoString> {
+/// Find the text contained in `content` if and only if it only consists of
+/// `Text`, `Space`, and `Empty` leafs.
+fn search_text(content: &Content) -> Option<EcoString> {
     if content.is::<SpaceElem>() {
         Some(' '.into())
     } else if let Some(elem) = content.to::<TextElem>() {
-        convert_script(&elem.text(), sub)
+        Some(elem.text())
     } else if let Some(children) = content.to_sequence() {
         let mut full = EcoString::new();
         for item in children {
-            match search_text(item, sub) {
-                Some(text) => full.push_str(&text),
-                None => return None,
-            }
+            full.push_str(&search_text(item)?);
         }
         Some(full)
     } else {
@@ -148,21 +210,42 @@ fn search_text(content: &Content, sub: bool) -> Option<EcoString> {
     }
 }
 
-/// Checks whether the first retrievable family contains all code points of the
-/// given string.
-fn is_shapable(vt: &Vt, text: &str, styles: StyleChain) -> bool {
+/// The first retrievable font of the family list.
+fn first_font(vt: &Vt, styles: StyleChain) -> Option<Font> {
     let world = vt.world;
-    for family in TextElem::font_in(styles) {
-        if let Some(font) = world
+    TextElem::font_in(styles).into_iter().find_map(|family| {
+        world
             .book()
             .select(family.as_str(), variant(styles))
             .and_then(|id| world.font(id))
-        {
-            return text.chars().all(|c| font.ttf().glyph_index(c).is_some());
-        }
-    }
+    })
+}
+
+/// Checks whether the first retrievable family contains all code points of the
+/// given string.
+fn is_shapable(vt: &Vt, text: &str, styles: StyleChain) -> bool {
+    first_font(vt, styles)
+        .map_or(false, |font| text.chars().all(|c| font.ttf().glyph_index(c).is_some()))
+}
+
+/// Checks whether the OpenType feature `tag` of the first retrievable family
+/// substitutes all non-whitespace glyphs of the given string.
+fn has_feature_glyphs(vt: &Vt, text: &str, tag: Tag, styles: StyleChain) -> bool {
+    let Some(font) = first_font(vt, styles) else { return false };
+    let shape = |features: &[Feature]| {
+        let mut buffer = UnicodeBuffer::new();
+        buffer.push_str(text);
+        rustybuzz::shape(font.rusty(), features, buffer)
+    };
 
-    false
+    let plain = shape(&[]);
+    let featured = shape(&[Feature::new(tag, 1, ..)]);
+    let (plain, featured) = (plain.glyph_infos(), featured.glyph_infos());
+    plain.len() == featured.len()
+        && plain.iter().zip(featured).all(|(a, b)| {
+            text[a.cluster as usize..].starts_with(char::is_whitespace)
+                || (a.glyph_id != 0 && a.glyph_id != b.glyph_id)
+        })
 }
 
 /// Convert a string to sub- or superscript codepoints if all characters
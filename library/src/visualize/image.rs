@@ -1,7 +1,7 @@
 use std::ffi::OsStr;
 use std::path::Path;
 
-use typst::image::{Image, ImageFormat, RasterFormat, VectorFormat};
+use typst::image::{Image, ImageFormat, VectorFormat};
 
 use crate::meta::{Figurable, LocalName};
 use crate::prelude::*;
@@ -9,7 +9,9 @@ use crate::text::families;
 
 /// A raster or vector graphic.
 ///
-/// Supported formats are PNG, JPEG, GIF and SVG.
+/// Supported formats are PNG, JPEG, GIF, WebP and SVG. Raster formats are
+/// detected from the file's contents, SVGs from the file extension. Animated
+/// images show their first frame.
 ///
 /// _Note:_ Work on SVG export is ongoing and there might be visual inaccuracies
 /// in the resulting PDF. Make sure to double-check embedded SVG images. If you
@@ -52,9 +54,46 @@ pub struct ImageElem {
     /// A text describing the image.
     pub alt: Option<EcoString>,
 
+    /// The resolution of the image in dots per inch.
+    ///
+    /// If neither `width` nor `height` is given, the image's size is derived
+    /// from its pixel dimensions and this resolution (but it still shrinks to
+    /// fit into the available space). If a width or height is given, that
+    /// takes precedence. In any case, a warning is logged if the image's
+    /// resolution at its final size falls below this value. This helps to
+    /// catch low-resolution images before printing.
+    ///
+    /// Has no effect on vector graphics.
+    ///
+    /// ```example
+    /// #image("glacier.jpg", dpi: 300)
+    /// ```
+    pub dpi: Option<f64>,
+
     /// How the image should adjust itself to a given area.
     #[default(ImageFit::Cover)]
     pub fit: ImageFit,
+
+    /// Whether to mirror the image horizontally.
+    ///
+    /// ```example
+    /// #image("tiger.jpg", width: 50%, flip-x: true)
+    /// ```
+    #[default(false)]
+    pub flip_x: bool,
+
+    /// Whether to mirror the image vertically.
+    #[default(false)]
+    pub flip_y: bool,
+
+    /// Whether to display the image upright according to the orientation
+    /// stored in its EXIF metadata.
+    ///
+    /// Photos taken with phones are often stored sideways along with a note
+    /// on how to rotate them. The image's size and its fit are computed for
+    /// the upright orientation.
+    #[default(true)]
+    pub auto_rotate: bool,
 }
 
 impl Layout for ImageElem {
@@ -70,17 +109,53 @@ impl Layout for ImageElem {
         let image =
             load(vt.world, &self.path(), fallback_family, self.alt(styles)).unwrap();
         let sizing = Axes::new(self.width(styles), self.height(styles));
-        let region = sizing
+        let mut region = sizing
             .zip(regions.base())
             .map(|(s, r)| s.map(|v| v.resolve(styles).relative_to(r)))
             .unwrap_or(regions.base());
 
-        let expand = sizing.as_ref().map(Smart::is_custom) | regions.expand;
-        let region_ratio = region.x / region.y;
+        let mut expand = sizing.as_ref().map(Smart::is_custom) | regions.expand;
+
+        // Determine how the stored image must be turned and mirrored to be
+        // displayed. All sizes below are for the displayed orientation.
+        let orientation = match self.auto_rotate(styles) {
+            true => image.exif_orientation().unwrap_or(1),
+            false => 1,
+        };
+        let (mut turns, mut mirror) = match orientation {
+            2 => (0, true),
+            3 => (2, false),
+            4 => (2, true),
+            5 => (1, true),
+            6 => (1, false),
+            7 => (3, true),
+            8 => (3, false),
+            _ => (0, false),
+        };
+        mirror ^= self.flip_x(styles);
+        if self.flip_y(styles) {
+            turns = (turns + 2) % 4;
+            mirror = !mirror;
+        }
+
+        let (mut pxw, mut pxh) = (image.width() as f64, image.height() as f64);
+        if turns % 2 == 1 {
+            std::mem::swap(&mut pxw, &mut pxh);
+        }
+
+        // Derive the size from the resolution if no size is given, shrinking
+        // it to fit into the region if necessary.
+        let raster = matches!(image.format(), ImageFormat::Raster(_));
+        let dpi = self.dpi(styles).filter(|&dpi| raster && dpi > 0.0);
+        if let (Some(dpi), Axes { x: Smart::Auto, y: Smart::Auto }) = (dpi, &sizing) {
+            let natural = Size::new(Abs::inches(pxw / dpi), Abs::inches(pxh / dpi));
+            let scale = (region.x / natural.x).min(region.y / natural.y).min(1.0);
+            region = natural * scale;
+            expand = Axes::splat(true);
+        }
 
         // Find out whether the image is wider or taller than the target size.
-        let pxw = image.width() as f64;
-        let pxh = image.height() as f64;
+        let region_ratio = region.x / region.y;
         let px_ratio = pxw / pxh;
         let wide = px_ratio > region_ratio;
 
@@ -111,8 +186,24 @@ impl Layout for ImageElem {
         // First, place the image in a frame of exactly its size and then resize
         // the frame to the target size, center aligning the image in the
         // process.
+        // Warn about images whose resolution is too low at their final size.
+        if let Some(dpi) = dpi {
+            let effective = pxw / fitted.x.to_inches();
+            if effective < dpi {
+                tracing::warn!(
+                    "Image {} has {effective:.0} dpi, less than {dpi} dpi",
+                    self.path(),
+                );
+            }
+        }
+
+        let stored = if turns % 2 == 1 { Size::new(fitted.y, fitted.x) } else { fitted };
         let mut frame = Frame::new(fitted);
-        frame.push(Point::zero(), FrameItem::Image(image, fitted, self.span()));
+        frame.push(Point::zero(), FrameItem::Image(image, stored, self.span()));
+        let ts = orient(fitted, turns, mirror);
+        if !ts.is_identity() {
+            frame.transform(ts);
+        }
         frame.resize(target, Align::CENTER_HORIZON);
 
         // Create a clipping group if only part of the image should be visible.
@@ -151,10 +242,55 @@ impl LocalName for ImageElem {
             Lang::ENGLISH | _ => "Figure",
         }
     }
+
+    fn local_plural_name(&self, lang: Lang, region: Option<Region>) -> &'static str {
+        match lang {
+            Lang::BOKMÅL => "Figurer",
+            Lang::CZECH => "Obrázky",
+            Lang::DANISH => "Figurer",
+            Lang::DUTCH => "Figuren",
+            Lang::FRENCH => "Figures",
+            Lang::GERMAN => "Abbildungen",
+            Lang::ITALIAN => "Figure",
+            Lang::NYNORSK => "Figurar",
+            Lang::POLISH => "Rysunki",
+            Lang::PORTUGUESE => "Figuras",
+            Lang::RUSSIAN => "Рисунки",
+            Lang::SLOVENIAN => "Slike",
+            Lang::SPANISH => "Figuras",
+            Lang::SWEDISH => "Figurer",
+            Lang::UKRAINIAN => "Рисунки",
+            Lang::ENGLISH => "Figures",
+            _ => self.local_name(lang, region),
+        }
+    }
 }
 
 impl Figurable for ImageElem {}
 
+/// The transformation that displays an image in an area of the given size by
+/// turning it clockwise by a number of quarter turns and then mirroring it
+/// horizontally.
+fn orient(size: Size, turns: u8, mirror: bool) -> Transform {
+    let rotation = match turns {
+        1 => Transform::translate(size.x, Abs::zero())
+            .pre_concat(Transform::rotate(Angle::deg(90.0))),
+        2 => Transform::translate(size.x, size.y)
+            .pre_concat(Transform::rotate(Angle::deg(180.0))),
+        3 => Transform::translate(Abs::zero(), size.y)
+            .pre_concat(Transform::rotate(Angle::deg(270.0))),
+        _ => Transform::identity(),
+    };
+
+    if !mirror {
+        return rotation;
+    }
+
+    Transform::translate(size.x, Abs::zero())
+        .pre_concat(Transform::scale(Ratio::new(-1.0), Ratio::one()))
+        .pre_concat(rotation)
+}
+
 /// How an image should adjust itself to a given area.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
 pub enum ImageFit {
@@ -178,12 +314,12 @@ fn load(
     let full = Path::new(full);
     let buffer = world.file(full)?;
     let ext = full.extension().and_then(OsStr::to_str).unwrap_or_default();
-    let format = match ext.to_lowercase().as_str() {
-        "png" => ImageFormat::Raster(RasterFormat::Png),
-        "jpg" | "jpeg" => ImageFormat::Raster(RasterFormat::Jpg),
-        "gif" => ImageFormat::Raster(RasterFormat::Gif),
-        "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
-        _ => return Err("unknown image format".into()),
+    let format = match ImageFormat::detect(&buffer) {
+        Some(format) => format,
+        None => match ext.to_lowercase().as_str() {
+            "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
+            _ => return Err("unknown image format".into()),
+        },
     };
     Image::with_fonts(buffer, format, world, fallback_family, alt)
 }
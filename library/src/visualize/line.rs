@@ -65,6 +65,9 @@ pub struct LineElem {
     ///     - An [array]($type/array) with alternating lengths for dashes and
     ///       gaps. You can also use the string `{"dot"}` for a length equal to
     ///       the line thickness.
+    ///     - An [array]($type/array) containing such an array of dashes and
+    ///       gaps and a [length]($type/length) for the phase, which defines
+    ///       where in the pattern to start drawing.
     ///     - A [dictionary]($type/dictionary) with the keys `array` (same as
     ///       the array above), and `phase` (of type [length]($type/length)),
     ///       which defines where in the pattern to start drawing.
@@ -19,6 +19,17 @@ use PathVertex::{AllControlPoints, MirroredControlPoint, Vertex};
 /// )
 /// ```
 ///
+/// Control points are given relative to their vertex. By mirroring them, two
+/// vertices are enough for a smooth S-curve:
+///
+/// ```example
+/// #path(
+///   stroke: 2pt + teal,
+///   ((0pt, 0pt), (-30pt, 0pt)),
+///   ((60pt, 40pt), (-30pt, 0pt)),
+/// )
+/// ```
+///
 /// Display: Path
 /// Category: visualize
 #[element(Layout)]
@@ -36,6 +36,23 @@ pub struct PolygonElem {
     #[fold]
     pub stroke: Smart<Option<PartialStroke>>,
 
+    /// How much to round the polygon's corners.
+    ///
+    /// At each corner, the radius is reduced as necessary so that the rounding
+    /// takes up at most half of each adjacent edge.
+    ///
+    /// ```example
+    /// #polygon(
+    ///   fill: teal,
+    ///   radius: 6pt,
+    ///   (0pt, 30pt),
+    ///   (20pt, 0pt),
+    ///   (40pt, 30pt),
+    /// )
+    /// ```
+    #[resolve]
+    pub radius: Length,
+
     /// The vertices of the polygon. Each point is specified as an array of two
     /// [relative lengths]($type/relative-length).
     #[variadic]
@@ -78,12 +95,7 @@ impl Layout for PolygonElem {
         };
 
         // Construct a closed path given all points.
-        let mut path = Path::new();
-        path.move_to(points[0]);
-        for &point in &points[1..] {
-            path.line_to(point);
-        }
-        path.close_path();
+        let path = rounded_polygon(&points, self.radius(styles));
 
         let shape = Shape { geometry: Geometry::Path(path), stroke, fill };
         frame.push(Point::zero(), FrameItem::Shape(shape, self.span()));
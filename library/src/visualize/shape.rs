@@ -54,6 +54,11 @@ pub struct RectElem {
     ///   - `rest`: The stroke on all sides except those for which the
     ///     dictionary explicitly sets a size.
     ///
+    /// If any side of a rectangle with sharp corners is stroked with a
+    /// [gradient]($func/gradient), the sides are drawn as filled areas that
+    /// meet along the miter line through each corner. The gradient then
+    /// follows the bounding box of the stroked side.
+    ///
     /// ```example
     /// #stack(
     ///   dir: ltr,
@@ -61,6 +66,10 @@ pub struct RectElem {
     ///   rect(stroke: red),
     ///   rect(stroke: 2pt),
     ///   rect(stroke: 2pt + red),
+    ///   rect(stroke: (
+    ///     x: 4pt + gradient(red, blue),
+    ///     y: 4pt + green,
+    ///   )),
     /// )
     /// ```
     #[resolve]
@@ -71,7 +80,7 @@ pub struct RectElem {
     /// the width and height divided by two. This can be:
     ///
     /// - A relative length for a uniform corner radius.
-    /// - A dictionary: With a dictionary, the stroke for each side can be set
+    /// - A dictionary: With a dictionary, the radius for each corner can be set
     ///   individually. The dictionary can contain the following keys in order
     ///   of precedence:
     ///   - `top-left`: The top-left corner radius.
@@ -124,6 +133,32 @@ pub struct RectElem {
     #[fold]
     pub outset: Sides<Option<Rel<Length>>>,
 
+    /// A shadow to cast beneath the rectangle. This can be:
+    ///
+    /// - A color for a shadow with a small offset and blur.
+    /// - A dictionary with any of the following keys:
+    ///   - `offset`: How far to move the shadow, as an array of two
+    ///     [lengths]($type/length). Defaults to `{(2pt, 2pt)}`.
+    ///   - `blur`: The blur radius as a [length]($type/length). Defaults to
+    ///     `{4pt}`. With `{0pt}`, the shadow has sharp edges.
+    ///   - `color`: The [color]($type/color) of the shadow. Defaults to a
+    ///     translucent black.
+    ///
+    /// The shadow does not affect the layout. In PDFs, blurred shadows are
+    /// embedded as images with two pixels per point, so they become
+    /// pixelated when zooming in far.
+    ///
+    /// ```example
+    /// #rect(fill: white, shadow: gray)
+    /// #rect(
+    ///   fill: white,
+    ///   radius: 4pt,
+    ///   shadow: (offset: (0pt, 3pt), blur: 8pt),
+    /// )
+    /// ```
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
     /// The content to place into the rectangle.
     ///
     /// When this is omitted, the rectangle takes on a default size of at most
@@ -152,6 +187,7 @@ impl Layout for RectElem {
             self.inset(styles),
             self.outset(styles),
             self.radius(styles),
+            self.shadow(styles),
             self.span(),
         )
     }
@@ -232,6 +268,11 @@ pub struct SquareElem {
     #[fold]
     pub outset: Sides<Option<Rel<Length>>>,
 
+    /// A shadow to cast beneath the square. See the
+    /// [rectangle's documentation]($func/rect.shadow) for more details.
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
     /// The content to place into the square. The square expands to fit this
     /// content, keeping the 1-1 aspect ratio.
     ///
@@ -261,6 +302,7 @@ impl Layout for SquareElem {
             self.inset(styles),
             self.outset(styles),
             self.radius(styles),
+            self.shadow(styles),
             self.span(),
         )
     }
@@ -314,6 +356,11 @@ pub struct EllipseElem {
     #[fold]
     pub outset: Sides<Option<Rel<Length>>>,
 
+    /// A shadow to cast beneath the ellipse. See the
+    /// [rectangle's documentation]($func/rect.shadow) for more details.
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
     /// The content to place into the ellipse.
     ///
     /// When this is omitted, the ellipse takes on a default size of at most
@@ -342,6 +389,7 @@ impl Layout for EllipseElem {
             self.inset(styles),
             self.outset(styles),
             Corners::splat(Rel::zero()),
+            self.shadow(styles),
             self.span(),
         )
     }
@@ -422,6 +470,11 @@ pub struct CircleElem {
     #[fold]
     pub outset: Sides<Option<Rel<Length>>>,
 
+    /// A shadow to cast beneath the circle. See the
+    /// [rectangle's documentation]($func/rect.shadow) for more details.
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
     /// The content to place into the circle. The circle expands to fit this
     /// content, keeping the 1-1 aspect ratio.
     #[positional]
@@ -448,6 +501,7 @@ impl Layout for CircleElem {
             self.inset(styles),
             self.outset(styles),
             Corners::splat(Rel::zero()),
+            self.shadow(styles),
             self.span(),
         )
     }
@@ -468,6 +522,7 @@ fn layout(
     mut inset: Sides<Rel<Abs>>,
     outset: Sides<Rel<Abs>>,
     radius: Corners<Rel<Abs>>,
+    shadow: Option<Shadow<Abs>>,
     span: Span,
 ) -> SourceResult<Fragment> {
     let resolved = sizing
@@ -537,6 +592,19 @@ fn layout(
         }
     }
 
+    // Add the shadow beneath everything else.
+    if let Some(shadow) = shadow {
+        if kind.is_round() {
+            let outset = outset.relative_to(frame.size());
+            let size = frame.size() + outset.sum_by_axis();
+            let pos = Point::new(-outset.left, -outset.top) + shadow.offset.to_point();
+            let shape = ellipse(size, Some(shadow.color.into()), None);
+            frame.prepend(pos, FrameItem::Shadow(shape, shadow.blur, span));
+        } else {
+            frame.shadow(shadow, outset, radius, span);
+        }
+    }
+
     // Apply metadata.
     frame.meta(styles, false);
 
@@ -8,15 +8,17 @@ use std::sync::Arc;
 
 use ecow::EcoString;
 
-use crate::eval::{cast, dict, Dict, Value};
+use crate::eval::{cast, dict, Datetime, Dict, Value};
 use crate::font::Font;
 use crate::geom::{
-    self, rounded_rect, Abs, Align, Axes, Color, Corners, Dir, Em, Geometry, Length,
-    Numeric, Paint, Point, Rel, RgbaColor, Shape, Sides, Size, Stroke, Transform,
+    self, rounded_rect, Abs, Align, Axes, BlendMode, Color, Corners, Dir, Em, Geometry,
+    Length, Numeric, Paint, Path, Point, Ratio, Rel, RgbaColor, Shadow, Shape, Sides,
+    Size, Stroke, Transform,
 };
 use crate::image::Image;
 use crate::model::{Content, Location, MetaElem, StyleChain};
 use crate::syntax::Span;
+use crate::util::Buffer;
 
 /// A finished document with metadata and page frames.
 #[derive(Debug, Default, Clone, Hash)]
@@ -27,6 +29,27 @@ pub struct Document {
     pub title: Option<EcoString>,
     /// The document's author.
     pub author: Vec<EcoString>,
+    /// The document's subject.
+    pub subject: Option<EcoString>,
+    /// The document's keywords.
+    pub keywords: Vec<EcoString>,
+    /// The document's creation date.
+    pub date: Option<Datetime>,
+    /// Files embedded into the document.
+    pub attachments: Vec<Attachment>,
+}
+
+/// A file embedded into a document.
+#[derive(Debug, Clone, Hash)]
+pub struct Attachment {
+    /// The file's name.
+    pub name: EcoString,
+    /// The file's contents.
+    pub data: Buffer,
+    /// A description of the file.
+    pub description: Option<EcoString>,
+    /// The file's MIME type.
+    pub mime: Option<EcoString>,
 }
 
 /// A finished layout with items at fixed positions.
@@ -306,6 +329,25 @@ impl Frame {
         )
     }
 
+    /// Add a shadow of a rectangle with optional radius and outset beneath
+    /// the frame's contents.
+    pub fn shadow(
+        &mut self,
+        shadow: Shadow<Abs>,
+        outset: Sides<Rel<Abs>>,
+        radius: Corners<Rel<Abs>>,
+        span: Span,
+    ) {
+        let outset = outset.relative_to(self.size());
+        let size = self.size() + outset.sum_by_axis();
+        let pos = Point::new(-outset.left, -outset.top) + shadow.offset.to_point();
+        let radius = radius.map(|side| side.relative_to(size.x.min(size.y) / 2.0));
+        let fill = Some(shadow.color.into());
+        if let Some(shape) = rounded_rect(size, radius, fill, Sides::splat(None)).pop() {
+            self.prepend(pos, FrameItem::Shadow(shape, shadow.blur, span));
+        }
+    }
+
     /// Arbitrarily transform the contents of the frame.
     pub fn transform(&mut self, transform: Transform) {
         if !self.is_empty() {
@@ -320,6 +362,29 @@ impl Frame {
         }
     }
 
+    /// Clip the contents of a frame to the area enclosed by a path.
+    pub fn clip_to(&mut self, path: Path) {
+        if !self.is_empty() {
+            self.group(|g| {
+                g.clips = true;
+                g.clip_path = Some(path);
+            });
+        }
+    }
+
+    /// Composite the contents of a frame as a whole onto what lies beneath
+    /// it, with the given opacity and blend mode.
+    pub fn composite(&mut self, opacity: Ratio, blend_mode: BlendMode) {
+        if !self.is_empty()
+            && (opacity != Ratio::one() || blend_mode != BlendMode::Normal)
+        {
+            self.group(|g| {
+                g.opacity = opacity;
+                g.blend_mode = blend_mode;
+            });
+        }
+    }
+
     /// Wrap the frame's contents in a group and modify that group with `f`.
     fn group<F>(&mut self, f: F)
     where
@@ -408,6 +473,8 @@ pub enum FrameItem {
     Text(TextItem),
     /// A geometric shape with optional fill and stroke.
     Shape(Shape, Span),
+    /// A filled shape blurred by the given radius, used for shadows.
+    Shadow(Shape, Abs, Span),
     /// An image and its size.
     Image(Image, Size, Span),
     /// Meta information and the region it applies to.
@@ -420,6 +487,7 @@ impl Debug for FrameItem {
             Self::Group(group) => group.fmt(f),
             Self::Text(text) => write!(f, "{text:?}"),
             Self::Shape(shape, _) => write!(f, "{shape:?}"),
+            Self::Shadow(shape, blur, _) => write!(f, "Shadow({shape:?}, {blur:?})"),
             Self::Image(image, _, _) => write!(f, "{image:?}"),
             Self::Meta(meta, _) => write!(f, "{meta:?}"),
         }
@@ -435,6 +503,12 @@ pub struct GroupItem {
     pub transform: Transform,
     /// Whether the frame should be a clipping boundary.
     pub clips: bool,
+    /// The path to clip to instead of the frame's bounds, if any.
+    pub clip_path: Option<Path>,
+    /// The opacity with which the group is composited as a whole.
+    pub opacity: Ratio,
+    /// How the group's colors are combined with its backdrop.
+    pub blend_mode: BlendMode,
 }
 
 impl GroupItem {
@@ -444,6 +518,9 @@ impl GroupItem {
             frame,
             transform: Transform::identity(),
             clips: false,
+            clip_path: None,
+            opacity: Ratio::one(),
+            blend_mode: BlendMode::Normal,
         }
     }
 }
@@ -616,11 +693,24 @@ cast! {
 pub enum Meta {
     /// An internal or external link to a destination.
     Link(Destination),
+    /// A tooltip for the links in the same area.
+    Tooltip(EcoString),
+    /// A named destination at this position.
+    Anchor(EcoString),
     /// An identifiable element that produces something within the area this
     /// metadata is attached to.
     Elem(Content),
     /// The numbering of the current page.
     PageNumbering(Value),
+    /// Boxes for print production on a page that is enlarged beyond its trim
+    /// size: The trim box is inset by `trim` from each edge of the page and
+    /// the bleed box extends `bleed` beyond the trim box.
+    PrintBoxes { trim: Abs, bleed: Abs },
+    /// The start of an element of the document's logical structure. All items
+    /// up to the matching [`TagEnd`](Self::TagEnd) belong to it.
+    TagStart(Tag),
+    /// The end of the innermost open structure element.
+    TagEnd,
     /// Indicates that content should be hidden. This variant doesn't appear
     /// in the final frames as it is removed alongside the content that should
     /// be hidden.
@@ -635,13 +725,42 @@ impl Debug for Meta {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
             Self::Link(dest) => write!(f, "Link({dest:?})"),
+            Self::Tooltip(text) => write!(f, "Tooltip({text:?})"),
+            Self::Anchor(name) => write!(f, "Anchor({name:?})"),
             Self::Elem(content) => write!(f, "Elem({:?})", content.func()),
             Self::PageNumbering(value) => write!(f, "PageNumbering({value:?})"),
+            Self::PrintBoxes { trim, bleed } => {
+                write!(f, "PrintBoxes {{ trim: {trim:?}, bleed: {bleed:?} }}")
+            }
+            Self::TagStart(tag) => write!(f, "TagStart({tag:?})"),
+            Self::TagEnd => f.pad("TagEnd"),
             Self::Hide => f.pad("Hide"),
         }
     }
 }
 
+/// The kind of an element in the logical structure of a document, which
+/// makes it accessible to assistive technology.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum Tag {
+    /// A heading of the given level.
+    Heading(NonZeroUsize),
+    /// A paragraph.
+    Paragraph,
+    /// A figure with its alternative text, if any.
+    Figure(Option<EcoString>),
+    /// A bullet or numbered list.
+    List,
+    /// An item of a list.
+    ListItem,
+    /// A table.
+    Table,
+    /// A row of a table.
+    TableRow,
+    /// A cell of a table.
+    TableCell,
+}
+
 /// A link destination.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Destination {
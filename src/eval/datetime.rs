@@ -1,11 +1,17 @@
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
 use std::hash::Hash;
 
 use ecow::{eco_format, EcoString, EcoVec};
 use time::error::{Format, InvalidFormatDescription};
+use time::format_description::modifier::{MonthRepr, WeekdayRepr};
+use time::format_description::{Component, FormatItem};
 use time::{format_description, PrimitiveDateTime};
 
+use super::Duration;
+use crate::diag::StrResult;
+use crate::doc::Lang;
 use crate::eval::cast;
 use crate::util::pretty_array_like;
 
@@ -23,7 +29,14 @@ pub enum Datetime {
 
 impl Datetime {
     /// Display the date and/or time in a certain format.
-    pub fn display(&self, pattern: Option<EcoString>) -> Result<EcoString, EcoString> {
+    ///
+    /// If a language is given, the long and short names of months and
+    /// weekdays are displayed in that language, if it is supported.
+    pub fn display(
+        &self,
+        pattern: Option<EcoString>,
+        lang: Option<Lang>,
+    ) -> Result<EcoString, EcoString> {
         let pattern = pattern.as_ref().map(EcoString::as_str).unwrap_or(match self {
             Datetime::Date(_) => "[year]-[month]-[day]",
             Datetime::Time(_) => "[hour]:[minute]:[second]",
@@ -33,14 +46,85 @@ impl Datetime {
         let format = format_description::parse(pattern)
             .map_err(format_time_invalid_format_description_error)?;
 
-        let formatted_result = match self {
-            Datetime::Date(date) => date.format(&format),
-            Datetime::Time(time) => time.format(&format),
-            Datetime::Datetime(datetime) => datetime.format(&format),
+        let mut formatted = EcoString::new();
+        for item in &format {
+            if let Some(name) = lang.and_then(|lang| self.localized(item, lang)) {
+                formatted.push_str(&name);
+                continue;
+            }
+
+            let part = match self {
+                Datetime::Date(date) => date.format(item),
+                Datetime::Time(time) => time.format(item),
+                Datetime::Datetime(datetime) => datetime.format(item),
+            };
+
+            formatted.push_str(&part.map_err(format_time_format_error)?);
         }
-        .map(EcoString::from);
 
-        formatted_result.map_err(format_time_format_error)
+        Ok(formatted)
+    }
+
+    /// The localized month or weekday name for a format item, if it is one.
+    fn localized(&self, item: &FormatItem, lang: Lang) -> Option<EcoString> {
+        let (names, index, short) = match item {
+            FormatItem::Component(Component::Month(month)) => {
+                let short = match month.repr {
+                    MonthRepr::Long => false,
+                    MonthRepr::Short => true,
+                    _ => return None,
+                };
+                (month_names(lang)?, self.month()? - 1, short)
+            }
+            FormatItem::Component(Component::Weekday(weekday)) => {
+                let short = match weekday.repr {
+                    WeekdayRepr::Long => false,
+                    WeekdayRepr::Short => true,
+                    _ => return None,
+                };
+                (weekday_names(lang)?, self.weekday()? - 1, short)
+            }
+            _ => return None,
+        };
+
+        let (long, abbreviated) = names[usize::from(index)];
+        Some(if short { abbreviated } else { long }.into())
+    }
+
+    /// Add a duration to the datetime.
+    ///
+    /// Dates can only be shifted by whole days. Times wrap around at
+    /// midnight.
+    pub fn checked_add(self, duration: Duration) -> StrResult<Self> {
+        let shift: time::Duration = duration.into();
+        Ok(match self {
+            Datetime::Date(date) => {
+                if !duration.is_whole_days() {
+                    return Err("cannot shift a date by a fraction of a day".into());
+                }
+                Datetime::Date(date.checked_add(shift).ok_or("date is out of range")?)
+            }
+            Datetime::Time(time) => Datetime::Time(time + shift),
+            Datetime::Datetime(datetime) => Datetime::Datetime(
+                datetime.checked_add(shift).ok_or("datetime is out of range")?,
+            ),
+        })
+    }
+
+    /// Subtract a duration from the datetime.
+    pub fn checked_sub(self, duration: Duration) -> StrResult<Self> {
+        self.checked_add(duration.checked_neg().ok_or("duration is too large")?)
+    }
+
+    /// The duration between two datetimes of the same kind.
+    pub fn since(self, other: Self) -> StrResult<Duration> {
+        Ok(match (self, other) {
+            (Datetime::Date(a), Datetime::Date(b)) => a - b,
+            (Datetime::Time(a), Datetime::Time(b)) => a - b,
+            (Datetime::Datetime(a), Datetime::Datetime(b)) => a - b,
+            _ => return Err("cannot subtract datetimes of different kinds".into()),
+        }
+        .into())
     }
 
     /// Return the year of the datetime, if existing.
@@ -136,6 +220,17 @@ impl Datetime {
     }
 }
 
+impl PartialOrd for Datetime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Datetime::Date(a), Datetime::Date(b)) => a.partial_cmp(b),
+            (Datetime::Time(a), Datetime::Time(b)) => a.partial_cmp(b),
+            (Datetime::Datetime(a), Datetime::Datetime(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
 impl Debug for Datetime {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let year = self.year().map(|y| eco_format!("year: {y}"));
@@ -157,6 +252,183 @@ cast! {
     type Datetime: "datetime",
 }
 
+/// The long and short names of the months in a language, starting with
+/// January.
+fn month_names(lang: Lang) -> Option<[(&'static str, &'static str); 12]> {
+    Some(match lang.as_str() {
+        "en" => [
+            ("January", "Jan"),
+            ("February", "Feb"),
+            ("March", "Mar"),
+            ("April", "Apr"),
+            ("May", "May"),
+            ("June", "Jun"),
+            ("July", "Jul"),
+            ("August", "Aug"),
+            ("September", "Sep"),
+            ("October", "Oct"),
+            ("November", "Nov"),
+            ("December", "Dec"),
+        ],
+        "de" => [
+            ("Januar", "Jan."),
+            ("Februar", "Feb."),
+            ("März", "März"),
+            ("April", "Apr."),
+            ("Mai", "Mai"),
+            ("Juni", "Juni"),
+            ("Juli", "Juli"),
+            ("August", "Aug."),
+            ("September", "Sept."),
+            ("Oktober", "Okt."),
+            ("November", "Nov."),
+            ("Dezember", "Dez."),
+        ],
+        "fr" => [
+            ("janvier", "janv."),
+            ("février", "févr."),
+            ("mars", "mars"),
+            ("avril", "avr."),
+            ("mai", "mai"),
+            ("juin", "juin"),
+            ("juillet", "juil."),
+            ("août", "août"),
+            ("septembre", "sept."),
+            ("octobre", "oct."),
+            ("novembre", "nov."),
+            ("décembre", "déc."),
+        ],
+        "es" => [
+            ("enero", "ene"),
+            ("febrero", "feb"),
+            ("marzo", "mar"),
+            ("abril", "abr"),
+            ("mayo", "may"),
+            ("junio", "jun"),
+            ("julio", "jul"),
+            ("agosto", "ago"),
+            ("septiembre", "sept"),
+            ("octubre", "oct"),
+            ("noviembre", "nov"),
+            ("diciembre", "dic"),
+        ],
+        "it" => [
+            ("gennaio", "gen"),
+            ("febbraio", "feb"),
+            ("marzo", "mar"),
+            ("aprile", "apr"),
+            ("maggio", "mag"),
+            ("giugno", "giu"),
+            ("luglio", "lug"),
+            ("agosto", "ago"),
+            ("settembre", "set"),
+            ("ottobre", "ott"),
+            ("novembre", "nov"),
+            ("dicembre", "dic"),
+        ],
+        "nl" => [
+            ("januari", "jan."),
+            ("februari", "feb."),
+            ("maart", "mrt."),
+            ("april", "apr."),
+            ("mei", "mei"),
+            ("juni", "jun."),
+            ("juli", "jul."),
+            ("augustus", "aug."),
+            ("september", "sep."),
+            ("oktober", "okt."),
+            ("november", "nov."),
+            ("december", "dec."),
+        ],
+        "pt" => [
+            ("janeiro", "jan."),
+            ("fevereiro", "fev."),
+            ("março", "mar."),
+            ("abril", "abr."),
+            ("maio", "mai."),
+            ("junho", "jun."),
+            ("julho", "jul."),
+            ("agosto", "ago."),
+            ("setembro", "set."),
+            ("outubro", "out."),
+            ("novembro", "nov."),
+            ("dezembro", "dez."),
+        ],
+        _ => return None,
+    })
+}
+
+/// The long and short names of the weekdays in a language, starting with
+/// Monday.
+fn weekday_names(lang: Lang) -> Option<[(&'static str, &'static str); 7]> {
+    Some(match lang.as_str() {
+        "en" => [
+            ("Monday", "Mon"),
+            ("Tuesday", "Tue"),
+            ("Wednesday", "Wed"),
+            ("Thursday", "Thu"),
+            ("Friday", "Fri"),
+            ("Saturday", "Sat"),
+            ("Sunday", "Sun"),
+        ],
+        "de" => [
+            ("Montag", "Mo."),
+            ("Dienstag", "Di."),
+            ("Mittwoch", "Mi."),
+            ("Donnerstag", "Do."),
+            ("Freitag", "Fr."),
+            ("Samstag", "Sa."),
+            ("Sonntag", "So."),
+        ],
+        "fr" => [
+            ("lundi", "lun."),
+            ("mardi", "mar."),
+            ("mercredi", "mer."),
+            ("jeudi", "jeu."),
+            ("vendredi", "ven."),
+            ("samedi", "sam."),
+            ("dimanche", "dim."),
+        ],
+        "es" => [
+            ("lunes", "lun"),
+            ("martes", "mar"),
+            ("miércoles", "mié"),
+            ("jueves", "jue"),
+            ("viernes", "vie"),
+            ("sábado", "sáb"),
+            ("domingo", "dom"),
+        ],
+        "it" => [
+            ("lunedì", "lun"),
+            ("martedì", "mar"),
+            ("mercoledì", "mer"),
+            ("giovedì", "gio"),
+            ("venerdì", "ven"),
+            ("sabato", "sab"),
+            ("domenica", "dom"),
+        ],
+        "nl" => [
+            ("maandag", "ma"),
+            ("dinsdag", "di"),
+            ("woensdag", "wo"),
+            ("donderdag", "do"),
+            ("vrijdag", "vr"),
+            ("zaterdag", "za"),
+            ("zondag", "zo"),
+        ],
+        "pt" => [
+            ("segunda-feira", "seg."),
+            ("terça-feira", "ter."),
+            ("quarta-feira", "qua."),
+            ("quinta-feira", "qui."),
+            ("sexta-feira", "sex."),
+            ("sábado", "sáb."),
+            ("domingo", "dom."),
+        ],
+        _ => return None,
+    })
+}
+
 /// Format the `Format` error of the time crate in an appropriate way.
 fn format_time_format_error(error: Format) -> EcoString {
     match error {
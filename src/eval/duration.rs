@@ -0,0 +1,110 @@
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+
+use ecow::{eco_format, EcoVec};
+
+use crate::eval::cast;
+use crate::util::pretty_array_like;
+
+/// A span of time with a precision of seconds.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Duration(time::Duration);
+
+impl Duration {
+    /// Create a duration from a number of seconds.
+    pub fn from_seconds(seconds: i64) -> Self {
+        Self(time::Duration::seconds(seconds))
+    }
+
+    /// Whether the duration is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Whether the duration only consists of whole days.
+    pub fn is_whole_days(&self) -> bool {
+        self.0.whole_seconds() % 86400 == 0 && self.0.subsec_nanoseconds() == 0
+    }
+
+    /// The duration in seconds.
+    pub fn seconds(&self) -> f64 {
+        self.0.as_seconds_f64()
+    }
+
+    /// The duration in minutes.
+    pub fn minutes(&self) -> f64 {
+        self.seconds() / 60.0
+    }
+
+    /// The duration in hours.
+    pub fn hours(&self) -> f64 {
+        self.seconds() / 3600.0
+    }
+
+    /// The duration in days.
+    pub fn days(&self) -> f64 {
+        self.seconds() / 86400.0
+    }
+
+    /// The duration in weeks.
+    pub fn weeks(&self) -> f64 {
+        self.seconds() / 604800.0
+    }
+
+    /// The negated duration, if it is representable.
+    pub fn checked_neg(self) -> Option<Self> {
+        time::Duration::ZERO.checked_sub(self.0).map(Self)
+    }
+
+    /// The sum of two durations, if it is representable.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// The difference of two durations, if it is representable.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+}
+
+impl From<time::Duration> for Duration {
+    fn from(duration: time::Duration) -> Self {
+        Self(duration)
+    }
+}
+
+impl From<Duration> for time::Duration {
+    fn from(duration: Duration) -> Self {
+        duration.0
+    }
+}
+
+impl Debug for Duration {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut rest = self.0.whole_seconds();
+        let mut components = EcoVec::new();
+        for (unit, seconds) in [
+            ("weeks", 604800),
+            ("days", 86400),
+            ("hours", 3600),
+            ("minutes", 60),
+            ("seconds", 1),
+        ] {
+            let count = rest / seconds;
+            if count != 0 {
+                components.push(eco_format!("{unit}: {count}"));
+                rest -= count * seconds;
+            }
+        }
+
+        if components.is_empty() {
+            components.push("seconds: 0".into());
+        }
+
+        write!(f, "duration{}", &pretty_array_like(&components, false))
+    }
+}
+
+cast! {
+    type Duration: "duration",
+}
@@ -4,7 +4,7 @@ use ecow::EcoString;
 
 use super::{Args, IntoValue, Str, Value, Vm};
 use crate::diag::{At, SourceResult};
-use crate::eval::Datetime;
+use crate::eval::{Datetime, Duration};
 use crate::model::{Location, Selector};
 use crate::syntax::Span;
 
@@ -187,7 +187,9 @@ pub fn call(
             } else if let Some(&datetime) = dynamic.downcast::<Datetime>() {
                 match method {
                     "display" => {
-                        datetime.display(args.eat()?).at(args.span)?.into_value()
+                        let pattern = args.eat()?;
+                        let lang = args.named("lang")?;
+                        datetime.display(pattern, lang).at(args.span)?.into_value()
                     }
                     "year" => datetime.year().into_value(),
                     "month" => datetime.month().into_value(),
@@ -198,6 +200,15 @@ pub fn call(
                     "second" => datetime.second().into_value(),
                     _ => return missing(),
                 }
+            } else if let Some(&duration) = dynamic.downcast::<Duration>() {
+                match method {
+                    "seconds" => duration.seconds().into_value(),
+                    "minutes" => duration.minutes().into_value(),
+                    "hours" => duration.hours().into_value(),
+                    "days" => duration.days().into_value(),
+                    "weeks" => duration.weeks().into_value(),
+                    _ => return missing(),
+                }
             } else {
                 return (vm.items.library_method)(vm, &dynamic, method, args, span);
             }
@@ -368,6 +379,23 @@ pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
             ("update", true),
         ],
         "state" => &[("display", true), ("at", true), ("final", true), ("update", true)],
+        "datetime" => &[
+            ("display", true),
+            ("year", false),
+            ("month", false),
+            ("weekday", false),
+            ("day", false),
+            ("hour", false),
+            ("minute", false),
+            ("second", false),
+        ],
+        "duration" => &[
+            ("seconds", false),
+            ("minutes", false),
+            ("hours", false),
+            ("days", false),
+            ("weeks", false),
+        ],
         _ => &[],
     }
 }
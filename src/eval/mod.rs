@@ -15,6 +15,7 @@ mod value;
 mod args;
 mod auto;
 mod datetime;
+mod duration;
 mod func;
 mod int;
 mod methods;
@@ -43,13 +44,14 @@ pub use self::cast::{
 };
 pub use self::datetime::Datetime;
 pub use self::dict::{dict, Dict};
+pub use self::duration::Duration;
 pub use self::func::{Func, FuncInfo, NativeFunc, Param, ParamInfo};
 pub use self::library::{set_lang_items, LangItems, Library};
 pub use self::methods::methods_on;
 pub use self::module::Module;
 pub use self::none::NoneValue;
 pub use self::scope::{Scope, Scopes};
-pub use self::str::{format_str, Regex, Str};
+pub use self::str::{format_str, Regex, Str, StrPattern};
 pub use self::symbol::Symbol;
 pub use self::value::{Dynamic, Type, Value};
 
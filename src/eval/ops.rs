@@ -5,7 +5,7 @@ use std::fmt::Debug;
 
 use ecow::eco_format;
 
-use super::{format_str, Regex, Value};
+use super::{format_str, Datetime, Duration, Regex, Value};
 use crate::diag::StrResult;
 use crate::geom::{Axes, Axis, GenAlign, Length, Numeric, PartialStroke, Rel, Smart};
 use Value::*;
@@ -61,6 +61,10 @@ pub fn neg(value: Value) -> StrResult<Value> {
         Ratio(v) => Ratio(-v),
         Relative(v) => Relative(-v),
         Fraction(v) => Fraction(-v),
+        Dyn(v) if v.is::<Duration>() => {
+            let duration = *v.downcast::<Duration>().unwrap();
+            Value::dynamic(duration.checked_neg().ok_or("duration is too large")?)
+        }
         v => mismatch!("cannot apply '-' to {}", v),
     })
 }
@@ -128,6 +132,26 @@ pub fn add(lhs: Value, rhs: Value) -> StrResult<Value> {
                 }));
             };
 
+            if let (Some(&a), Some(&b)) =
+                (a.downcast::<Datetime>(), b.downcast::<Duration>())
+            {
+                return Ok(Value::dynamic(a.checked_add(b)?));
+            }
+
+            if let (Some(&a), Some(&b)) =
+                (a.downcast::<Duration>(), b.downcast::<Datetime>())
+            {
+                return Ok(Value::dynamic(b.checked_add(a)?));
+            }
+
+            if let (Some(&a), Some(&b)) =
+                (a.downcast::<Duration>(), b.downcast::<Duration>())
+            {
+                return Ok(Value::dynamic(
+                    a.checked_add(b).ok_or("duration is too large")?,
+                ));
+            }
+
             mismatch!("cannot add {} and {}", a, b);
         }
 
@@ -159,6 +183,30 @@ pub fn sub(lhs: Value, rhs: Value) -> StrResult<Value> {
 
         (Fraction(a), Fraction(b)) => Fraction(a - b),
 
+        (Dyn(a), Dyn(b)) => {
+            if let (Some(&a), Some(&b)) =
+                (a.downcast::<Datetime>(), b.downcast::<Duration>())
+            {
+                return Ok(Value::dynamic(a.checked_sub(b)?));
+            }
+
+            if let (Some(&a), Some(&b)) =
+                (a.downcast::<Datetime>(), b.downcast::<Datetime>())
+            {
+                return Ok(Value::dynamic(a.since(b)?));
+            }
+
+            if let (Some(&a), Some(&b)) =
+                (a.downcast::<Duration>(), b.downcast::<Duration>())
+            {
+                return Ok(Value::dynamic(
+                    a.checked_sub(b).ok_or("duration is too large")?,
+                ));
+            }
+
+            mismatch!("cannot subtract {1} from {0}", a, b);
+        }
+
         (a, b) => mismatch!("cannot subtract {1} from {0}", a, b),
     })
 }
@@ -380,6 +428,20 @@ pub fn compare(lhs: &Value, rhs: &Value) -> StrResult<Ordering> {
         (Relative(a), Relative(b)) => try_cmp_values(a, b)?,
         (Fraction(a), Fraction(b)) => a.cmp(b),
         (Str(a), Str(b)) => a.cmp(b),
+        (Dyn(a), Dyn(b)) => {
+            if let (Some(a), Some(b)) =
+                (a.downcast::<Datetime>(), b.downcast::<Datetime>())
+            {
+                a.partial_cmp(b)
+                    .ok_or("cannot compare datetimes of different kinds")?
+            } else if let (Some(a), Some(b)) =
+                (a.downcast::<Duration>(), b.downcast::<Duration>())
+            {
+                a.cmp(b)
+            } else {
+                mismatch!("cannot compare {} and {}", lhs, rhs);
+            }
+        }
 
         // Some technically different things should be comparable.
         (Int(a), Float(b)) => try_cmp_values(&(*a as f64), b)?,
@@ -571,7 +571,7 @@ cast! {
 }
 
 /// A pattern which can be searched for in a string.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Hash)]
 pub enum StrPattern {
     /// Just a string.
     Str(Str),
@@ -579,8 +579,24 @@ pub enum StrPattern {
     Regex(Regex),
 }
 
+impl StrPattern {
+    /// Whether the pattern matches the whole text.
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            Self::Str(pat) => pat.as_str() == text,
+            Self::Regex(re) => {
+                re.find(text).map_or(false, |m| m.range() == (0..text.len()))
+            }
+        }
+    }
+}
+
 cast! {
     StrPattern,
+    self => match self {
+        Self::Str(v) => v.into_value(),
+        Self::Regex(v) => v.into_value(),
+    },
     text: Str => Self::Str(text),
     regex: Regex => Self::Regex(regex),
 }
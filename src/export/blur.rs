@@ -0,0 +1,101 @@
+//! Blurring of shapes for shadows.
+//!
+//! Neither PDF nor tiny-skia can blur, so both exporters rasterize the
+//! shape and blur the pixels. The raster exporter does so at its own
+//! resolution. The PDF exporter embeds the result as an image at a fixed
+//! resolution of [`PDF_PIXEL_PER_PT`], so blurred shadows in PDFs become
+//! pixelated when zooming in far.
+
+use tiny_skia as sk;
+
+use super::render;
+use crate::geom::{Abs, Color, Paint, Shape, Size};
+
+/// The resolution at which the PDF exporter rasterizes blurred shapes. Since
+/// a blurred shape has no sharp edges, a low resolution suffices at normal
+/// zoom levels and keeps files small.
+pub const PDF_PIXEL_PER_PT: f32 = 2.0;
+
+/// Rasterize a filled shape at the given number of pixels per point and blur
+/// it by the given radius.
+///
+/// Returns the resulting pixel buffer and the padding that was added around
+/// the shape's bounding box to make room for the blur.
+pub fn blur_shape(
+    shape: &Shape,
+    blur: Abs,
+    pixel_per_pt: f32,
+) -> Option<(sk::Pixmap, Abs)> {
+    // As in CSS, the blur radius is twice the standard deviation of the
+    // gaussian. Beyond three standard deviations, the blur is invisible.
+    let sigma = blur.to_f32() / 2.0 * pixel_per_pt;
+    let pad = blur * 1.5;
+    let size = shape.geometry.bbox_size() + Size::splat(2.0 * pad);
+    let pxw = (pixel_per_pt * size.x.to_f32()).ceil().max(1.0) as u32;
+    let pxh = (pixel_per_pt * size.y.to_f32()).ceil().max(1.0) as u32;
+
+    let mut pixmap = sk::Pixmap::new(pxw, pxh)?;
+    let ts = sk::Transform::from_scale(pixel_per_pt, pixel_per_pt)
+        .pre_translate(pad.to_f32(), pad.to_f32());
+    render::render_shape(&mut pixmap, ts, None, shape)?;
+
+    // Blur only the coverage and then recolor the pixels.
+    let mut alpha: Vec<f32> =
+        pixmap.pixels().iter().map(|pixel| pixel.alpha() as f32).collect();
+    gaussian_blur(&mut alpha, pxw as usize, pxh as usize, sigma);
+
+    let c = match shape.fill {
+        Some(Paint::Solid(color)) => color.to_rgba(),
+        _ => Color::BLACK.to_rgba(),
+    };
+
+    for (pixel, a) in pixmap.pixels_mut().iter_mut().zip(alpha) {
+        let a = a.round().clamp(0.0, 255.0) as u8;
+        *pixel = sk::ColorU8::from_rgba(c.r, c.g, c.b, a).premultiply();
+    }
+
+    Some((pixmap, pad))
+}
+
+/// Approximate a gaussian blur with the given standard deviation by three
+/// successive box blurs.
+fn gaussian_blur(data: &mut [f32], width: usize, height: usize, sigma: f32) {
+    let size = (4.0 * sigma * sigma + 1.0).sqrt();
+    let radius = ((size - 1.0) / 2.0).round() as usize;
+    if radius == 0 {
+        return;
+    }
+
+    let mut buf = vec![0.0; data.len()];
+    for _ in 0..3 {
+        box_blur(data, &mut buf, height, width, radius, |y, x| y * width + x);
+        box_blur(&buf, data, width, height, radius, |x, y| y * width + x);
+    }
+}
+
+/// Blur each of the `lines` of `len` values in `src` with a box of the given
+/// radius and write the result into `dst`. Values outside of a line count as
+/// zero.
+fn box_blur(
+    src: &[f32],
+    dst: &mut [f32],
+    lines: usize,
+    len: usize,
+    radius: usize,
+    index: impl Fn(usize, usize) -> usize,
+) {
+    let norm = 1.0 / (2 * radius + 1) as f32;
+    for line in 0..lines {
+        let at = |i| src[index(line, i)];
+        let mut sum: f32 = (0..radius.min(len)).map(at).sum();
+        for i in 0..len {
+            if i + radius < len {
+                sum += at(i + radius);
+            }
+            dst[index(line, i)] = sum * norm;
+            if i >= radius {
+                sum -= at(i - radius);
+            }
+        }
+    }
+}
@@ -1,7 +1,8 @@
 //! Exporting into external formats.
 
+mod blur;
 mod pdf;
 mod render;
 
-pub use self::pdf::pdf;
+pub use self::pdf::{pdf, pdf_with_options, FontEmbedding, PdfOptions, PdfStandard};
 pub use self::render::render;
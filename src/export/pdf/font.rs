@@ -1,12 +1,15 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use ecow::{eco_format, EcoString};
 use pdf_writer::types::{CidFontType, FontFlags, SystemInfo, UnicodeCmap};
-use pdf_writer::{Filter, Finish, Name, Rect, Str};
+use pdf_writer::writers::FontDescriptor;
+use pdf_writer::{Filter, Finish, Name, PdfWriter, Rect, Ref, Str};
 use ttf_parser::{name_id, GlyphId, Tag};
 use unicode_general_category::GeneralCategory;
 
-use super::{deflate, EmExt, PdfContext, RefExt};
+use super::{deflate, EmExt, FontEmbedding, PdfContext, RefExt};
+use crate::doc::{Frame, FrameItem};
+use crate::font::Font;
 use crate::util::SliceExt;
 
 const CMAP_NAME: Name = Name(b"Custom");
@@ -16,26 +19,139 @@ const SYSTEM_INFO: SystemInfo = SystemInfo {
     supplement: 0,
 };
 
+/// Determine the fonts that are not embedded if the options ask for it.
+///
+/// Viewers can only substitute a font if the text refers to its glyphs by
+/// character code instead of by glyph id. Thus, a font is only left
+/// unembedded if each of its glyphs in the document stands for a single
+/// character of the WinAnsi encoding. Such fonts are written as simple
+/// TrueType fonts. All other fonts are embedded anyway.
+#[tracing::instrument(skip_all)]
+pub fn select_unembedded(ctx: &mut PdfContext, frames: &[Frame]) {
+    if ctx.options.embedding() != FontEmbedding::None {
+        return;
+    }
+
+    let mut usable = HashMap::new();
+    for frame in frames {
+        collect_usable(frame, &mut usable);
+    }
+
+    for (font, usable) in usable {
+        let postscript_name = font
+            .find_name(name_id::POST_SCRIPT_NAME)
+            .unwrap_or_else(|| "unknown".to_string());
+
+        if usable {
+            tracing::warn!(
+                "Not embedding font {postscript_name}, its appearance depends on the viewer"
+            );
+            ctx.unembedded.insert(font);
+        } else {
+            tracing::warn!(
+                "Embedding font {postscript_name} anyway, as some of its glyphs \
+                 can't be referred to by character code"
+            );
+        }
+    }
+}
+
+/// Record for each font in the frame whether all its glyphs have a WinAnsi
+/// character code.
+fn collect_usable(frame: &Frame, usable: &mut HashMap<Font, bool>) {
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => collect_usable(&group.frame, usable),
+            FrameItem::Text(text) => {
+                let entry = usable.entry(text.font.clone()).or_insert(true);
+                *entry &= text.glyphs.iter().all(|glyph| {
+                    winansi_code(&text.font, glyph.id, &text.text[glyph.range()])
+                        .is_some()
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The WinAnsi character code of a glyph, if it stands for a single
+/// character that the encoding covers and is the font's default glyph for
+/// that character.
+pub fn winansi_code(font: &Font, glyph: u16, text: &str) -> Option<u8> {
+    let mut chars = text.chars();
+    let c = chars.next()?;
+    if chars.next().is_some() || font.ttf().glyph_index(c) != Some(GlyphId(glyph)) {
+        return None;
+    }
+
+    Some(match c {
+        ' '..='~' | '\u{a0}'..='\u{ff}' => c as u8,
+        '€' => 0x80,
+        '‚' => 0x82,
+        'ƒ' => 0x83,
+        '„' => 0x84,
+        '…' => 0x85,
+        '†' => 0x86,
+        '‡' => 0x87,
+        'ˆ' => 0x88,
+        '‰' => 0x89,
+        'Š' => 0x8A,
+        '‹' => 0x8B,
+        'Œ' => 0x8C,
+        'Ž' => 0x8E,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '˜' => 0x98,
+        '™' => 0x99,
+        'š' => 0x9A,
+        '›' => 0x9B,
+        'œ' => 0x9C,
+        'ž' => 0x9E,
+        'Ÿ' => 0x9F,
+        _ => return None,
+    })
+}
+
 /// Embed all used fonts into the PDF.
 #[tracing::instrument(skip_all)]
 pub fn write_fonts(ctx: &mut PdfContext) {
     for font in ctx.font_map.items() {
         let type0_ref = ctx.alloc.bump();
-        let cid_ref = ctx.alloc.bump();
         let descriptor_ref = ctx.alloc.bump();
+        ctx.font_refs.push(type0_ref);
+
+        if ctx.unembedded.contains(font) {
+            write_unembedded(ctx, font, type0_ref, descriptor_ref);
+            continue;
+        }
+
+        let cid_ref = ctx.alloc.bump();
         let cmap_ref = ctx.alloc.bump();
         let data_ref = ctx.alloc.bump();
-        ctx.font_refs.push(type0_ref);
 
         let glyph_set = ctx.glyph_sets.get_mut(font).unwrap();
-        let metrics = font.metrics();
         let ttf = font.ttf();
 
         let postscript_name = font
             .find_name(name_id::POST_SCRIPT_NAME)
             .unwrap_or_else(|| "unknown".to_string());
 
-        let base_font = eco_format!("ABCDEF+{}", postscript_name);
+        // Subsetted fonts are marked with a tag in their name. Fonts that
+        // can't be left unembedded are subsetted.
+        let embedding = match ctx.options.embedding() {
+            FontEmbedding::Full if font.index() > 0 => FontEmbedding::Subset,
+            FontEmbedding::None => FontEmbedding::Subset,
+            embedding => embedding,
+        };
+        let base_font = match embedding {
+            FontEmbedding::Subset => eco_format!("ABCDEF+{}", postscript_name),
+            _ => postscript_name.as_str().into(),
+        };
         let base_font = Name(base_font.as_bytes());
 
         // Write the base font object referencing the CID font.
@@ -92,39 +208,9 @@ pub fn write_fonts(ctx: &mut PdfContext) {
         width_writer.finish();
         cid.finish();
 
-        let mut flags = FontFlags::empty();
-        flags.set(FontFlags::SERIF, postscript_name.contains("Serif"));
-        flags.set(FontFlags::FIXED_PITCH, ttf.is_monospaced());
-        flags.set(FontFlags::ITALIC, ttf.is_italic());
-        flags.insert(FontFlags::SYMBOLIC);
-        flags.insert(FontFlags::SMALL_CAP);
-
-        let global_bbox = ttf.global_bounding_box();
-        let bbox = Rect::new(
-            font.to_em(global_bbox.x_min).to_font_units(),
-            font.to_em(global_bbox.y_min).to_font_units(),
-            font.to_em(global_bbox.x_max).to_font_units(),
-            font.to_em(global_bbox.y_max).to_font_units(),
-        );
-
-        let italic_angle = ttf.italic_angle().unwrap_or(0.0);
-        let ascender = metrics.ascender.to_font_units();
-        let descender = metrics.descender.to_font_units();
-        let cap_height = metrics.cap_height.to_font_units();
-        let stem_v = 10.0 + 0.244 * (f32::from(ttf.weight().to_number()) - 50.0);
-
-        // Write the font descriptor (contains metrics about the font).
-        let mut font_descriptor = ctx.writer.font_descriptor(descriptor_ref);
-        font_descriptor
-            .name(base_font)
-            .flags(flags)
-            .bbox(bbox)
-            .italic_angle(italic_angle)
-            .ascent(ascender)
-            .descent(descender)
-            .cap_height(cap_height)
-            .stem_v(stem_v);
-
+        // Write the font descriptor referencing the font's bytes.
+        let mut font_descriptor =
+            write_descriptor(&mut ctx.writer, font, descriptor_ref, base_font, true);
         match subtype {
             CidFontType::Type0 => font_descriptor.font_file3(data_ref),
             CidFontType::Type2 => font_descriptor.font_file2(data_ref),
@@ -139,26 +225,128 @@ pub fn write_fonts(ctx: &mut PdfContext) {
 
         // Subset and write the font's bytes.
         let data = font.data();
-        let subsetted = {
-            let glyphs: Vec<_> = glyph_set.keys().copied().collect();
-            let profile = subsetter::Profile::pdf(&glyphs);
-            subsetter::subset(data, font.index(), profile)
+        let subsetted = match embedding {
+            FontEmbedding::Subset => {
+                let glyphs: Vec<_> = glyph_set.keys().copied().collect();
+                let profile = subsetter::Profile::pdf(&glyphs);
+                subsetter::subset(data, font.index(), profile).ok()
+            }
+            _ => None,
         };
 
         // Compress and write the font's bytes.
+        let subsetted_ok = subsetted.is_some();
         let data = subsetted.as_deref().unwrap_or(data);
         let data = deflate(data);
+        tracing::info!("Embedding font {postscript_name} with {} bytes", data.len());
         let mut stream = ctx.writer.stream(data_ref, &data);
         stream.filter(Filter::FlateDecode);
 
+        // A subsetted CFF font is stored as a bare CFF table, a complete one
+        // as an OpenType font.
         if subtype == CidFontType::Type0 {
-            stream.pair(Name(b"Subtype"), Name(b"CIDFontType0C"));
+            let format = if subsetted_ok { "CIDFontType0C" } else { "OpenType" };
+            stream.pair(Name(b"Subtype"), Name(format.as_bytes()));
         }
 
         stream.finish();
     }
 }
 
+/// Write a font that is not embedded as a simple TrueType font with the
+/// WinAnsi encoding, so that viewers can substitute it.
+fn write_unembedded(
+    ctx: &mut PdfContext,
+    font: &Font,
+    font_ref: Ref,
+    descriptor_ref: Ref,
+) {
+    let postscript_name = font
+        .find_name(name_id::POST_SCRIPT_NAME)
+        .unwrap_or_else(|| "unknown".to_string());
+    let base_font = Name(postscript_name.as_bytes());
+
+    // Extract the widths of the used character codes.
+    let mut widths = [0.0; 256];
+    let (mut first, mut last) = (u8::MAX, u8::MIN);
+    for (&g, text) in &ctx.glyph_sets[font] {
+        let Some(code) = winansi_code(font, g, text) else { continue };
+        let x = font.ttf().glyph_hor_advance(GlyphId(g)).unwrap_or(0);
+        widths[usize::from(code)] = font.to_em(x).to_font_units();
+        first = first.min(code);
+        last = last.max(code);
+    }
+
+    if first > last {
+        (first, last) = (b' ', b' ');
+    }
+
+    let mut dict = ctx.writer.indirect(font_ref).dict();
+    dict.pair(Name(b"Type"), Name(b"Font"));
+    dict.pair(Name(b"Subtype"), Name(b"TrueType"));
+    dict.pair(Name(b"BaseFont"), base_font);
+    dict.pair(Name(b"Encoding"), Name(b"WinAnsiEncoding"));
+    dict.pair(Name(b"FirstChar"), i32::from(first));
+    dict.pair(Name(b"LastChar"), i32::from(last));
+    dict.insert(Name(b"Widths"))
+        .array()
+        .items(widths[usize::from(first)..=usize::from(last)].iter().copied());
+    dict.pair(Name(b"FontDescriptor"), descriptor_ref);
+    dict.finish();
+
+    write_descriptor(&mut ctx.writer, font, descriptor_ref, base_font, false).finish();
+}
+
+/// Write a font descriptor, which contains metrics about the font.
+fn write_descriptor<'a>(
+    writer: &'a mut PdfWriter,
+    font: &Font,
+    id: Ref,
+    base_font: Name,
+    symbolic: bool,
+) -> FontDescriptor<'a> {
+    let metrics = font.metrics();
+    let ttf = font.ttf();
+    let serif = base_font.0.windows(5).any(|part| part == b"Serif");
+
+    let mut flags = FontFlags::empty();
+    flags.set(FontFlags::SERIF, serif);
+    flags.set(FontFlags::FIXED_PITCH, ttf.is_monospaced());
+    flags.set(FontFlags::ITALIC, ttf.is_italic());
+    if symbolic {
+        flags.insert(FontFlags::SYMBOLIC);
+        flags.insert(FontFlags::SMALL_CAP);
+    } else {
+        flags.insert(FontFlags::NON_SYMBOLIC);
+    }
+
+    let global_bbox = ttf.global_bounding_box();
+    let bbox = Rect::new(
+        font.to_em(global_bbox.x_min).to_font_units(),
+        font.to_em(global_bbox.y_min).to_font_units(),
+        font.to_em(global_bbox.x_max).to_font_units(),
+        font.to_em(global_bbox.y_max).to_font_units(),
+    );
+
+    let italic_angle = ttf.italic_angle().unwrap_or(0.0);
+    let ascender = metrics.ascender.to_font_units();
+    let descender = metrics.descender.to_font_units();
+    let cap_height = metrics.cap_height.to_font_units();
+    let stem_v = 10.0 + 0.244 * (f32::from(ttf.weight().to_number()) - 50.0);
+
+    let mut font_descriptor = writer.font_descriptor(id);
+    font_descriptor
+        .name(base_font)
+        .flags(flags)
+        .bbox(bbox)
+        .italic_angle(italic_angle)
+        .ascent(ascender)
+        .descent(descender)
+        .cap_height(cap_height)
+        .stem_v(stem_v);
+    font_descriptor
+}
+
 /// Create a /ToUnicode CMap.
 fn create_cmap(
     ttf: &ttf_parser::Face,
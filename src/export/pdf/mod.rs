@@ -4,35 +4,134 @@ mod font;
 mod image;
 mod outline;
 mod page;
+mod pdfa;
+mod tags;
 
 use std::cmp::Eq;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
 
 use ecow::EcoString;
 use pdf_writer::types::Direction;
-use pdf_writer::{Finish, Name, PdfWriter, Ref, TextStr};
+use pdf_writer::{Filter, Finish, Name, PdfWriter, Ref, Str, TextStr};
 use xmp_writer::{LangId, RenditionClass, XmpWriter};
 
-use self::page::Page;
+use self::page::{Form, Page};
+use self::tags::StructTree;
+use crate::diag::StrResult;
 use crate::doc::{Document, Lang};
+use crate::eval::Datetime;
 use crate::font::Font;
-use crate::geom::{Abs, Dir, Em};
+use crate::geom::{Abs, BlendMode, Dir, Em, Point, Ratio};
 use crate::image::Image;
 use crate::model::Introspector;
 
 /// Export a document into a PDF file.
 ///
+/// The file is tagged with the document's headings, paragraphs, figures,
+/// lists and tables so that assistive technology can follow its structure.
+///
 /// Returns the raw bytes making up the PDF file.
 #[tracing::instrument(skip_all)]
 pub fn pdf(document: &Document) -> Vec<u8> {
-    let mut ctx = PdfContext::new(document);
+    write(document, &PdfOptions::default())
+}
+
+/// Export a document into a PDF file with custom settings.
+///
+/// Returns the raw bytes making up the PDF file or an error if the document
+/// uses features that the requested PDF standard forbids.
+#[tracing::instrument(skip_all)]
+pub fn pdf_with_options(document: &Document, options: &PdfOptions) -> StrResult<Vec<u8>> {
+    if options.standard == PdfStandard::A2b {
+        pdfa::check(document)?;
+    }
+
+    Ok(write(document, options))
+}
+
+/// Write the PDF file.
+fn write(document: &Document, options: &PdfOptions) -> Vec<u8> {
+    let mut ctx = PdfContext::new(document, options);
+    font::select_unembedded(&mut ctx, &document.pages);
     page::construct_pages(&mut ctx, &document.pages);
     font::write_fonts(&mut ctx);
     image::write_images(&mut ctx);
+    page::write_graphics_states(&mut ctx);
+    page::write_forms(&mut ctx);
     page::write_page_tree(&mut ctx);
     write_catalog(&mut ctx);
-    ctx.writer.finish()
+
+    let mut buf = ctx.writer.finish();
+    if options.standard == PdfStandard::A2b {
+        pdfa::write_file_id(&mut buf);
+    }
+    buf
+}
+
+/// Settings for PDF export.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct PdfOptions {
+    /// The one-based, inclusive range of pages to export. If this is `None`,
+    /// all pages are exported. Either way, counters and references are
+    /// resolved based on the full document.
+    pub pages: Option<RangeInclusive<usize>>,
+    /// Whether to keep links to pages that are not exported. Such links keep
+    /// their clickable area, but don't lead anywhere. By default, they are
+    /// dropped.
+    pub keep_dangling_links: bool,
+    /// How fonts are embedded into the PDF. PDF/A always embeds the complete
+    /// fonts.
+    pub font_embedding: FontEmbedding,
+    /// The standard the PDF conforms to.
+    pub standard: PdfStandard,
+}
+
+/// How fonts are embedded into a PDF.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FontEmbedding {
+    /// Embed only the glyphs that are used in the document, including the
+    /// components of composite glyphs.
+    #[default]
+    Subset,
+    /// Embed the complete fonts. Font collections are still subsetted because
+    /// a PDF can only embed a single font from them.
+    Full,
+    /// Don't embed fonts whose glyphs all stand for single characters of the
+    /// WinAnsi encoding (roughly Latin-1). Viewers substitute them with the
+    /// same or similar installed fonts. This keeps files small, but their
+    /// appearance depends on the viewer's system. Other fonts are subsetted
+    /// since a substitute can't be addressed by glyph id.
+    None,
+}
+
+/// A standard that an exported PDF conforms to.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PdfStandard {
+    /// Plain PDF 1.7.
+    #[default]
+    V1_7,
+    /// PDF/A-2b for long-term archival. This embeds an sRGB output intent and
+    /// the PDF/A identification into the metadata, embeds all fonts
+    /// completely, and rejects documents with attached files or CMYK colors.
+    A2b,
+}
+
+impl PdfOptions {
+    /// Whether the page with the given one-based number is exported.
+    pub fn exports(&self, page: NonZeroUsize) -> bool {
+        self.pages.as_ref().map_or(true, |range| range.contains(&page.get()))
+    }
+
+    /// How fonts are actually embedded, taking the standard into account.
+    fn embedding(&self) -> FontEmbedding {
+        match self.standard {
+            PdfStandard::V1_7 => self.font_embedding,
+            PdfStandard::A2b => FontEmbedding::Full,
+        }
+    }
 }
 
 /// Identifies the color space definitions.
@@ -42,17 +141,27 @@ const D65_GRAY: Name<'static> = Name(b"d65gray");
 /// Context for exporting a whole PDF document.
 pub struct PdfContext<'a> {
     document: &'a Document,
+    options: &'a PdfOptions,
     introspector: Introspector,
     writer: PdfWriter,
     pages: Vec<Page>,
+    /// The form XObjects of transparency groups, written after the pages.
+    forms: Vec<Form>,
     page_heights: Vec<f32>,
     alloc: Ref,
     page_tree_ref: Ref,
     font_refs: Vec<Ref>,
     image_refs: Vec<Ref>,
+    gradient_refs: Vec<Ref>,
+    form_refs: Vec<Ref>,
+    ext_gs_refs: Vec<Ref>,
     page_refs: Vec<Ref>,
+    /// For each page of the document, its index among the exported pages.
+    page_indices: Vec<Option<usize>>,
     font_map: Remapper<Font>,
     image_map: Remapper<Image>,
+    /// The opacities and blend modes of transparency groups.
+    ext_gs_map: Remapper<(Ratio, BlendMode)>,
     /// For each font a mapping from used glyphs to their text representation.
     /// May contain multiple chars in case of ligatures or similar things. The
     /// same glyph can have a different text representation within one document,
@@ -60,30 +169,53 @@ pub struct PdfContext<'a> {
     /// PDF's /ToUnicode map for glyphs that don't have an entry in the font's
     /// cmap. This is important for copy-paste and searching.
     glyph_sets: HashMap<Font, BTreeMap<u16, EcoString>>,
+    /// The fonts that are written as simple fonts without embedding them.
+    unembedded: HashSet<Font>,
     languages: HashMap<Lang, usize>,
+    /// Named destinations with their page and position in the PDF coordinate
+    /// system.
+    anchors: Vec<(EcoString, Ref, Point)>,
+    /// The logical structure of the document.
+    tree: StructTree,
 }
 
 impl<'a> PdfContext<'a> {
-    fn new(document: &'a Document) -> Self {
+    fn new(document: &'a Document, options: &'a PdfOptions) -> Self {
         let mut alloc = Ref::new(1);
         let page_tree_ref = alloc.bump();
         Self {
             document,
+            options,
             introspector: Introspector::new(&document.pages),
             writer: PdfWriter::new(),
             pages: vec![],
+            forms: vec![],
             page_heights: vec![],
             alloc,
             page_tree_ref,
             page_refs: vec![],
+            page_indices: vec![],
             font_refs: vec![],
             image_refs: vec![],
+            gradient_refs: vec![],
+            form_refs: vec![],
+            ext_gs_refs: vec![],
             font_map: Remapper::new(),
             image_map: Remapper::new(),
+            ext_gs_map: Remapper::new(),
             glyph_sets: HashMap::new(),
+            unembedded: HashSet::new(),
             languages: HashMap::new(),
+            anchors: vec![],
+            tree: StructTree::default(),
         }
     }
+
+    /// The index among the exported pages of the document page with the
+    /// given one-based number, if it is exported.
+    fn page_index(&self, page: NonZeroUsize) -> Option<usize> {
+        self.page_indices.get(page.get() - 1).copied().flatten()
+    }
 }
 
 /// Write the document catalog.
@@ -114,40 +246,203 @@ fn write_catalog(ctx: &mut PdfContext) {
 
     let authors = &ctx.document.author;
     if !authors.is_empty() {
-        info.author(TextStr(&authors.join(", ")));
-        xmp.creator(authors.iter().map(|s| s.as_str()));
+        let joined = authors.join(", ");
+        info.author(TextStr(&joined));
+        // PDF/A requires the metadata to match the author entry exactly.
+        if ctx.options.standard == PdfStandard::A2b {
+            xmp.creator([joined.as_str()]);
+        } else {
+            xmp.creator(authors.iter().map(|s| s.as_str()));
+        }
+    }
+
+    if let Some(subject) = &ctx.document.subject {
+        info.subject(TextStr(subject));
+        xmp.description([(None, subject.as_str())]);
+    }
+
+    let keywords = &ctx.document.keywords;
+    if !keywords.is_empty() {
+        let joined = keywords.join(", ");
+        info.keywords(TextStr(&joined));
+        xmp.pdf_keywords(&joined);
     }
+
+    if let Some(date) = ctx.document.date {
+        if let Some(date) = pdf_date(date) {
+            info.creation_date(date);
+            info.modified_date(date);
+        }
+        if let Some(date) = xmp_date(date) {
+            xmp.create_date(date);
+            xmp.modify_date(date);
+        }
+    }
+
     info.creator(TextStr("Typst"));
     info.finish();
     xmp.creator_tool("Typst");
-    xmp.num_pages(ctx.document.pages.len() as u32);
+    xmp.num_pages(ctx.page_refs.len() as u32);
     xmp.format("application/pdf");
     xmp.language(ctx.languages.keys().map(|lang| LangId(lang.as_str())));
     xmp.rendition_class(RenditionClass::Proof);
     xmp.pdf_version("1.7");
 
-    let xmp_buf = xmp.finish(None);
+    let mut xmp_buf = xmp.finish(None);
+    if ctx.options.standard == PdfStandard::A2b {
+        pdfa::identify(&mut xmp_buf);
+    }
+
     let meta_ref = ctx.alloc.bump();
     let mut meta_stream = ctx.writer.stream(meta_ref, xmp_buf.as_bytes());
     meta_stream.pair(Name(b"Type"), Name(b"Metadata"));
     meta_stream.pair(Name(b"Subtype"), Name(b"XML"));
     meta_stream.finish();
 
+    // Write the embedded files.
+    let attachments = write_attachments(ctx);
+
+    // Write the structure tree.
+    let struct_tree_ref = tags::write_struct_tree(ctx);
+
+    // Write the profile of the output intent.
+    let profile_ref =
+        (ctx.options.standard == PdfStandard::A2b).then(|| pdfa::write_profile(ctx));
+
     // Write the document catalog.
     let mut catalog = ctx.writer.catalog(ctx.alloc.bump());
     catalog.pages(ctx.page_tree_ref);
     catalog.viewer_preferences().direction(dir);
     catalog.pair(Name(b"Metadata"), meta_ref);
 
+    let mut anchors = std::mem::take(&mut ctx.anchors);
+    anchors.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+    anchors.dedup_by(|(a, ..), (b, ..)| a == b);
+
+    if !attachments.is_empty() || !anchors.is_empty() {
+        let mut names = catalog.insert(Name(b"Names")).dict();
+
+        if !anchors.is_empty() {
+            let mut tree = names.insert(Name(b"Dests")).dict();
+            let mut array = tree.insert(Name(b"Names")).array();
+            for (name, page_ref, point) in &anchors {
+                array.item(Str(name.as_bytes()));
+                array
+                    .push()
+                    .start::<pdf_writer::writers::Destination>()
+                    .page(*page_ref)
+                    .xyz(point.x.to_f32(), point.y.to_f32(), None);
+            }
+        }
+
+        if !attachments.is_empty() {
+            let mut tree = names.insert(Name(b"EmbeddedFiles")).dict();
+            let mut array = tree.insert(Name(b"Names")).array();
+            for (name, spec_ref) in &attachments {
+                array.item(TextStr(name));
+                array.item(*spec_ref);
+            }
+        }
+    }
+
     if let Some(outline_root_id) = outline_root_id {
         catalog.outlines(outline_root_id);
     }
 
+    if let Some(struct_tree_ref) = struct_tree_ref {
+        catalog.pair(Name(b"StructTreeRoot"), struct_tree_ref);
+        catalog.insert(Name(b"MarkInfo")).dict().pair(Name(b"Marked"), true);
+    }
+
+    if let Some(profile_ref) = profile_ref {
+        let mut intents = catalog.insert(Name(b"OutputIntents")).array();
+        pdfa::write_output_intent(&mut intents, profile_ref);
+    }
+
     if let Some(lang) = lang {
         catalog.lang(TextStr(lang.as_str()));
     }
 }
 
+/// Write the document's embedded files and return their names and file
+/// specifications, sorted by name.
+fn write_attachments(ctx: &mut PdfContext) -> Vec<(EcoString, Ref)> {
+    let mut attachments = vec![];
+    let document = ctx.document;
+    for attachment in &document.attachments {
+        let file_ref = ctx.alloc.bump();
+        let spec_ref = ctx.alloc.bump();
+
+        let data = deflate(&attachment.data);
+        let mut stream = ctx.writer.stream(file_ref, &data);
+        stream.filter(Filter::FlateDecode);
+        stream.pair(Name(b"Type"), Name(b"EmbeddedFile"));
+        if let Some(mime) = &attachment.mime {
+            stream.pair(Name(b"Subtype"), Name(mime.as_bytes()));
+        }
+        stream
+            .insert(Name(b"Params"))
+            .dict()
+            .pair(Name(b"Size"), attachment.data.len() as i32);
+        stream.finish();
+
+        let mut spec = ctx.writer.indirect(spec_ref).dict();
+        spec.pair(Name(b"Type"), Name(b"Filespec"));
+        spec.pair(Name(b"F"), Str(attachment.name.as_bytes()));
+        spec.pair(Name(b"UF"), TextStr(&attachment.name));
+        if let Some(description) = &attachment.description {
+            spec.pair(Name(b"Desc"), TextStr(description));
+        }
+        spec.insert(Name(b"EF"))
+            .dict()
+            .pair(Name(b"F"), file_ref)
+            .pair(Name(b"UF"), file_ref);
+        spec.finish();
+
+        attachments.push((attachment.name.clone(), spec_ref));
+    }
+
+    // The keys of a name tree must be sorted.
+    attachments.sort_by(|(a, _), (b, _)| a.cmp(b));
+    attachments
+}
+
+/// Convert a datetime to a PDF date, if it has a date component.
+fn pdf_date(datetime: Datetime) -> Option<pdf_writer::Date> {
+    let year = datetime.year().filter(|&year| year >= 0)? as u16;
+    let mut date = pdf_writer::Date::new(year);
+    if let Some(month) = datetime.month() {
+        date = date.month(month);
+    }
+    if let Some(day) = datetime.day() {
+        date = date.day(day);
+    }
+    if let Some(hour) = datetime.hour() {
+        date = date.hour(hour);
+    }
+    if let Some(minute) = datetime.minute() {
+        date = date.minute(minute);
+    }
+    if let Some(second) = datetime.second() {
+        date = date.second(second);
+    }
+    Some(date)
+}
+
+/// Convert a datetime to an XMP date, if it has a date component.
+fn xmp_date(datetime: Datetime) -> Option<xmp_writer::DateTime> {
+    let year = datetime.year().filter(|&year| year >= 0)? as u16;
+    Some(xmp_writer::DateTime {
+        year,
+        month: datetime.month(),
+        day: datetime.day(),
+        hour: datetime.hour(),
+        minute: datetime.minute(),
+        second: datetime.second(),
+        timezone: None,
+    })
+}
+
 /// Compress data with the DEFLATE algorithm.
 #[tracing::instrument(skip_all)]
 fn deflate(data: &[u8]) -> Vec<u8> {
@@ -233,3 +528,155 @@ impl RefExt for Ref {
         prev
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doc::{Attachment, Frame, FrameItem, Meta, Tag};
+    use crate::geom::{
+        Angle, CmykColor, Color, Geometry, Gradient, LinearGradient, Paint, Point, Shape,
+        Size, Stroke,
+    };
+    use crate::image::{ImageFormat, RasterFormat};
+    use crate::syntax::Span;
+    use crate::util::Buffer;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_pdf_page_range() {
+        let page = Frame::new(Size::new(Abs::pt(100.0), Abs::pt(100.0)));
+        let document = Document { pages: vec![page; 4], ..Default::default() };
+
+        let has_count = |options: &PdfOptions, n: usize| {
+            let pdf = pdf_with_options(&document, options).unwrap();
+            String::from_utf8_lossy(&pdf).contains(&format!("/Count {n}"))
+        };
+
+        assert!(has_count(&PdfOptions::default(), 4));
+        assert!(has_count(&PdfOptions { pages: Some(2..=3), ..Default::default() }, 2));
+        assert!(has_count(&PdfOptions { pages: Some(4..=9), ..Default::default() }, 1));
+    }
+
+    #[test]
+    fn test_pdf_shared_images() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/files/graph.png");
+        let data = std::fs::read(path).unwrap();
+        let format = ImageFormat::Raster(RasterFormat::Png);
+        let size = Size::new(Abs::pt(100.0), Abs::pt(30.0));
+        let image = |alt: Option<&str>| {
+            let image = Image::new(data.clone().into(), format, alt.map(Into::into));
+            FrameItem::Image(image.unwrap(), size, Span::detached())
+        };
+
+        let xobjects = |alts: &[Option<&str>]| {
+            let mut page = Frame::new(Size::new(Abs::pt(200.0), Abs::pt(200.0)));
+            for &alt in alts {
+                page.push(Point::zero(), image(alt));
+            }
+            let document = Document { pages: vec![page; 2], ..Default::default() };
+            let pdf = pdf(&document);
+            String::from_utf8_lossy(&pdf).matches("/Subtype /Image").count()
+        };
+
+        assert_eq!(xobjects(&[None, Some("A graph")]), xobjects(&[None]));
+    }
+
+    #[test]
+    fn test_pdf_a2b() {
+        let page = Frame::new(Size::new(Abs::pt(100.0), Abs::pt(100.0)));
+        let document = Document {
+            pages: vec![page.clone()],
+            title: Some("Archive".into()),
+            author: vec!["A".into(), "B".into()],
+            ..Default::default()
+        };
+
+        let options = PdfOptions { standard: PdfStandard::A2b, ..Default::default() };
+        let pdf = pdf_with_options(&document, &options).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/S /GTS_PDFA1"));
+        assert!(text.contains("pdfaid:part=\"2\" pdfaid:conformance=\"B\""));
+        assert!(text.contains("<rdf:li>A, B</rdf:li>"));
+        assert!(text.contains("/ID ["));
+
+        let attached = Document {
+            pages: vec![page.clone()],
+            attachments: vec![Attachment {
+                name: "data.csv".into(),
+                data: Buffer::from(vec![]),
+                description: None,
+            }],
+            ..Default::default()
+        };
+        assert!(pdf_with_options(&attached, &options).is_err());
+        assert!(pdf_with_options(&attached, &PdfOptions::default()).is_ok());
+
+        let mut cmyk = page;
+        let fill = Paint::Solid(Color::Cmyk(CmykColor::new(0, 0, 0, 255)));
+        let shape = Geometry::Rect(Size::splat(Abs::pt(10.0))).filled(fill);
+        cmyk.push(Point::zero(), FrameItem::Shape(shape, Span::detached()));
+        let document = Document { pages: vec![cmyk], ..Default::default() };
+        assert!(pdf_with_options(&document, &options).is_err());
+    }
+
+    #[test]
+    fn test_pdf_gradient_stroke_per_shape() {
+        let gradient = Gradient::Linear(Arc::new(LinearGradient {
+            stops: vec![(Color::BLACK, Ratio::zero()), (Color::WHITE, Ratio::one())],
+            angle: Angle::zero(),
+        }));
+        let stroke = Stroke {
+            paint: Paint::Gradient(gradient),
+            ..Default::default()
+        };
+
+        let mut page = Frame::new(Size::new(Abs::pt(200.0), Abs::pt(200.0)));
+        for size in [Size::splat(Abs::pt(20.0)), Size::splat(Abs::pt(100.0))] {
+            let shape = Shape {
+                geometry: Geometry::Rect(size),
+                fill: None,
+                stroke: Some(stroke.clone()),
+            };
+            page.push(Point::zero(), FrameItem::Shape(shape, Span::detached()));
+        }
+
+        // Each shape needs a pattern fitted to its own bounding box.
+        let document = Document { pages: vec![page], ..Default::default() };
+        let pdf = pdf(&document);
+        assert_eq!(String::from_utf8_lossy(&pdf).matches("/PatternType 2").count(), 2);
+    }
+
+    #[test]
+    fn test_pdf_struct_tree() {
+        let level = NonZeroUsize::new(2).unwrap();
+        let alt = Some("A square".into());
+        let square =
+            Geometry::Rect(Size::splat(Abs::pt(10.0))).filled(Color::BLACK.into());
+
+        let mut page = Frame::new(Size::new(Abs::pt(100.0), Abs::pt(100.0)));
+        for item in [
+            FrameItem::Meta(Meta::TagStart(Tag::Heading(level)), Size::zero()),
+            FrameItem::Shape(square.clone(), Span::detached()),
+            FrameItem::Meta(Meta::TagEnd, Size::zero()),
+            FrameItem::Meta(Meta::TagStart(Tag::Figure(alt)), Size::zero()),
+            FrameItem::Meta(Meta::TagStart(Tag::Paragraph), Size::zero()),
+            FrameItem::Shape(square, Span::detached()),
+            FrameItem::Meta(Meta::TagEnd, Size::zero()),
+            FrameItem::Meta(Meta::TagEnd, Size::zero()),
+        ] {
+            page.push(Point::zero(), item);
+        }
+
+        let document = Document { pages: vec![page], ..Default::default() };
+        let pdf = pdf(&document);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Type /StructTreeRoot"));
+        assert!(text.contains("/Marked true"));
+        assert!(text.contains("/StructParents 0"));
+        assert!(text.contains("/S /H2"));
+        assert!(text.contains("/S /Figure"));
+        assert!(text.contains("/Alt (A square)"));
+        assert!(text.contains("/S /P"));
+        assert_eq!(text.matches("/Type /MCR").count(), 2);
+    }
+}
@@ -11,6 +11,12 @@ use crate::model::Content;
 pub fn write_outline(ctx: &mut PdfContext) -> Option<Ref> {
     let mut tree: Vec<HeadingNode> = vec![];
     for heading in ctx.introspector.query(&item!(heading_func).select()) {
+        // Skip headings on pages that aren't exported.
+        let loc = heading.location().unwrap();
+        if ctx.page_index(ctx.introspector.page(loc)).is_none() {
+            continue;
+        }
+
         let leaf = HeadingNode::leaf((*heading).clone());
 
         let mut children = &mut tree;
@@ -100,8 +106,8 @@ fn write_outline_item(
 
     let loc = node.element.location().unwrap();
     let pos = ctx.introspector.position(loc);
-    let index = pos.page.get() - 1;
-    if let Some(&height) = ctx.page_heights.get(index) {
+    if let Some(index) = ctx.page_index(pos.page) {
+        let height = ctx.page_heights[index];
         let y = (pos.point.y - Abs::pt(10.0)).max(Abs::zero());
         outline.dest().page(ctx.page_refs[index]).xyz(
             pos.point.x.to_f32(),
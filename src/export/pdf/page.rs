@@ -1,24 +1,37 @@
-use ecow::eco_format;
+use std::collections::BTreeSet;
+use std::num::NonZeroUsize;
+
+use ecow::{eco_format, EcoString};
 use pdf_writer::types::{
-    ActionType, AnnotationType, ColorSpaceOperand, LineCapStyle, LineJoinStyle,
+    ActionType, AnnotationType, BlendMode as PdfBlendMode, ColorSpaceOperand,
+    FunctionShadingType, LineCapStyle, LineJoinStyle,
 };
 use pdf_writer::writers::ColorSpace;
-use pdf_writer::{Content, Filter, Finish, Name, Rect, Ref, Str};
+use pdf_writer::{Content, Dict, Filter, Finish, Name, Rect, Ref, Str, TextStr};
 
+use super::font::winansi_code;
 use super::{deflate, AbsExt, EmExt, PdfContext, RefExt, D65_GRAY, SRGB};
 use crate::doc::{Destination, Frame, FrameItem, GroupItem, Meta, TextItem};
+use crate::export::blur::{blur_shape, PDF_PIXEL_PER_PT};
 use crate::font::Font;
 use crate::geom::{
-    self, Abs, Color, Em, Geometry, LineCap, LineJoin, Numeric, Paint, Point, Ratio,
-    Shape, Size, Stroke, Transform,
+    self, Abs, BlendMode, Color, Em, Geometry, Gradient, LineCap, LineJoin, Numeric,
+    Paint, Point, Ratio, Shape, Size, Stroke, Transform,
 };
-use crate::image::Image;
+use crate::image::{Image, ImageFormat, RasterFormat};
 
 /// Construct page objects.
 #[tracing::instrument(skip_all)]
 pub fn construct_pages(ctx: &mut PdfContext, frames: &[Frame]) {
-    for frame in frames {
-        construct_page(ctx, frame);
+    for (i, frame) in frames.iter().enumerate() {
+        let number = NonZeroUsize::new(1 + i).unwrap();
+        if ctx.options.exports(number) {
+            ctx.page_indices.push(Some(ctx.page_refs.len()));
+            construct_page(ctx, frame);
+        } else {
+            ctx.page_indices.push(None);
+            ctx.tree.skip(frame);
+        }
     }
 }
 
@@ -28,6 +41,7 @@ pub fn construct_page(ctx: &mut PdfContext, frame: &Frame) {
     let page_ref = ctx.alloc.bump();
     ctx.page_refs.push(page_ref);
     ctx.page_heights.push(frame.height().to_f32());
+    ctx.tree.start_page();
 
     let mut ctx = PageContext {
         parent: ctx,
@@ -35,8 +49,13 @@ pub fn construct_page(ctx: &mut PdfContext, frame: &Frame) {
         content: Content::new(),
         state: State::default(),
         saves: vec![],
+        size: frame.size(),
         bottom: 0.0,
         links: vec![],
+        tooltips: vec![],
+        resources: Resources::default(),
+        marked: false,
+        tagging: true,
     };
 
     let size = frame.size();
@@ -54,22 +73,33 @@ pub fn construct_page(ctx: &mut PdfContext, frame: &Frame) {
 
     // Encode the page into the content stream.
     write_frame(&mut ctx, frame);
+    ctx.unmark();
 
     let page = Page {
         size,
         content: ctx.content,
         id: ctx.page_ref,
         links: ctx.links,
+        tooltips: ctx.tooltips,
+        boxes: print_boxes(frame),
     };
 
     ctx.parent.pages.push(page);
 }
 
+/// Find the print production boxes of a page.
+fn print_boxes(frame: &Frame) -> Option<(Abs, Abs)> {
+    frame.items().find_map(|(_, item)| match item {
+        FrameItem::Meta(Meta::PrintBoxes { trim, bleed }, _) => Some((*trim, *bleed)),
+        _ => None,
+    })
+}
+
 /// Write the page tree.
 #[tracing::instrument(skip_all)]
 pub fn write_page_tree(ctx: &mut PdfContext) {
-    for page in std::mem::take(&mut ctx.pages).into_iter() {
-        write_page(ctx, page);
+    for (i, page) in std::mem::take(&mut ctx.pages).into_iter().enumerate() {
+        write_page(ctx, i, page);
     }
 
     let mut pages = ctx.writer.pages(ctx.page_tree_ref);
@@ -97,14 +127,83 @@ pub fn write_page_tree(ctx: &mut PdfContext) {
         images.pair(Name(name.as_bytes()), image_ref);
     }
 
+    for (i, &form_ref) in ctx.form_refs.iter().enumerate() {
+        let name = eco_format!("Fm{}", i);
+        images.pair(Name(name.as_bytes()), form_ref);
+    }
+
     images.finish();
+
+    let mut states = resources.ext_g_states();
+    for (gs_ref, gs) in ctx.ext_gs_map.pdf_indices(&ctx.ext_gs_refs) {
+        let name = eco_format!("Gs{}", gs);
+        states.pair(Name(name.as_bytes()), gs_ref);
+    }
+
+    states.finish();
+
+    let mut patterns = resources.patterns();
+    for (i, &pattern_ref) in ctx.gradient_refs.iter().enumerate() {
+        let name = eco_format!("Gr{}", i);
+        patterns.pair(Name(name.as_bytes()), pattern_ref);
+    }
+
+    patterns.finish();
     resources.finish();
     pages.finish();
 }
 
+/// Write the form XObjects of transparency groups with the resources they
+/// use.
+#[tracing::instrument(skip_all)]
+pub fn write_forms(ctx: &mut PdfContext) {
+    for form in std::mem::take(&mut ctx.forms) {
+        let mut xobject = ctx.writer.form_xobject(form.id, &form.content);
+        xobject.filter(Filter::FlateDecode);
+        xobject.bbox(form.bbox);
+        xobject.group().transparency().isolated(true);
+
+        let used = &form.resources;
+        let mut resources = xobject.resources();
+        let mut spaces = resources.color_spaces();
+        spaces.insert(SRGB).start::<ColorSpace>().srgb();
+        spaces.insert(D65_GRAY).start::<ColorSpace>().d65_gray();
+        spaces.finish();
+
+        let mut fonts = resources.fonts();
+        insert_named(&mut fonts, "F", &used.fonts, &ctx.font_refs);
+        fonts.finish();
+
+        let mut x_objects = resources.x_objects();
+        insert_named(&mut x_objects, "Im", &used.images, &ctx.image_refs);
+        insert_named(&mut x_objects, "Fm", &used.forms, &ctx.form_refs);
+        x_objects.finish();
+
+        let mut states = resources.ext_g_states();
+        insert_named(&mut states, "Gs", &used.ext_gs, &ctx.ext_gs_refs);
+        states.finish();
+
+        let mut patterns = resources.patterns();
+        insert_named(&mut patterns, "Gr", &used.gradients, &ctx.gradient_refs);
+        patterns.finish();
+
+        resources.finish();
+        xobject.finish();
+    }
+}
+
+/// Add the resources with the given indices to a resource dictionary under
+/// their names.
+fn insert_named(dict: &mut Dict, prefix: &str, indices: &BTreeSet<usize>, refs: &[Ref]) {
+    for &i in indices {
+        let name = eco_format!("{prefix}{i}");
+        dict.pair(Name(name.as_bytes()), refs[i]);
+    }
+}
+
 /// Write a page tree node.
 #[tracing::instrument(skip_all)]
-fn write_page(ctx: &mut PdfContext, page: Page) {
+fn write_page(ctx: &mut PdfContext, index: usize, page: Page) {
     let content_id = ctx.alloc.bump();
 
     let mut page_writer = ctx.writer.page(page.id);
@@ -113,27 +212,48 @@ fn write_page(ctx: &mut PdfContext, page: Page) {
     let w = page.size.x.to_f32();
     let h = page.size.y.to_f32();
     page_writer.media_box(Rect::new(0.0, 0.0, w, h));
+    if let Some((trim, bleed)) = page.boxes {
+        let inset =
+            |d: Abs| Rect::new(d.to_f32(), d.to_f32(), w - d.to_f32(), h - d.to_f32());
+        page_writer.trim_box(inset(trim));
+        page_writer.bleed_box(inset(trim - bleed));
+    }
     page_writer.contents(content_id);
+    if !ctx.tree.is_empty() {
+        page_writer.pair(Name(b"StructParents"), index as i32);
+    }
 
     let mut annotations = page_writer.annotations();
     for (dest, rect) in page.links {
+        // Links to pages that aren't exported are dropped unless they should
+        // be kept dangling.
+        let pos = match &dest {
+            Destination::Url(_) => None,
+            Destination::Position(pos) => Some(*pos),
+            Destination::Location(loc) => Some(ctx.introspector.position(*loc)),
+        };
+        let index = pos.and_then(|pos| ctx.page_index(pos.page));
+        if pos.is_some() && index.is_none() && !ctx.options.keep_dangling_links {
+            continue;
+        }
+
         let mut annotation = annotations.push();
         annotation.subtype(AnnotationType::Link).rect(rect);
         annotation.border(0.0, 0.0, 0.0, None);
 
-        let pos = match dest {
-            Destination::Url(uri) => {
-                annotation
-                    .action()
-                    .action_type(ActionType::Uri)
-                    .uri(Str(uri.as_bytes()));
-                continue;
-            }
-            Destination::Position(pos) => pos,
-            Destination::Location(loc) => ctx.introspector.position(loc),
-        };
+        if let Some((text, _)) = page.tooltips.iter().find(|(_, area)| *area == rect) {
+            annotation.contents(TextStr(text));
+        }
+
+        if let Destination::Url(uri) = &dest {
+            annotation
+                .action()
+                .action_type(ActionType::Uri)
+                .uri(Str(uri.as_bytes()));
+            continue;
+        }
 
-        let index = pos.page.get() - 1;
+        let (Some(pos), Some(index)) = (pos, index) else { continue };
         let y = (pos.point.y - Abs::pt(10.0)).max(Abs::zero());
         if let Some(&height) = ctx.page_heights.get(index) {
             annotation
@@ -153,6 +273,36 @@ fn write_page(ctx: &mut PdfContext, page: Page) {
     ctx.writer.stream(content_id, &data).filter(Filter::FlateDecode);
 }
 
+/// Write the graphics states for the opacities and blend modes of
+/// transparency groups.
+#[tracing::instrument(skip_all)]
+pub fn write_graphics_states(ctx: &mut PdfContext) {
+    for &(opacity, blend_mode) in ctx.ext_gs_map.items() {
+        let gs_ref = ctx.alloc.bump();
+        ctx.ext_gs_refs.push(gs_ref);
+
+        let opacity = opacity.get() as f32;
+        ctx.writer
+            .ext_graphics(gs_ref)
+            .non_stroking_alpha(opacity)
+            .stroking_alpha(opacity)
+            .blend_mode(match blend_mode {
+                BlendMode::Normal => PdfBlendMode::Normal,
+                BlendMode::Multiply => PdfBlendMode::Multiply,
+                BlendMode::Screen => PdfBlendMode::Screen,
+                BlendMode::Overlay => PdfBlendMode::Overlay,
+                BlendMode::Darken => PdfBlendMode::Darken,
+                BlendMode::Lighten => PdfBlendMode::Lighten,
+                BlendMode::ColorDodge => PdfBlendMode::ColorDodge,
+                BlendMode::ColorBurn => PdfBlendMode::ColorBurn,
+                BlendMode::HardLight => PdfBlendMode::HardLight,
+                BlendMode::SoftLight => PdfBlendMode::SoftLight,
+                BlendMode::Difference => PdfBlendMode::Difference,
+                BlendMode::Exclusion => PdfBlendMode::Exclusion,
+            });
+    }
+}
+
 /// Data for an exported page.
 pub struct Page {
     /// The indirect object id of the page.
@@ -163,6 +313,33 @@ pub struct Page {
     pub content: Content,
     /// Links in the PDF coordinate system.
     pub links: Vec<(Destination, Rect)>,
+    /// Tooltips for the links with the same area.
+    pub tooltips: Vec<(EcoString, Rect)>,
+    /// The insets of the trim and bleed boxes, if the page has any.
+    pub boxes: Option<(Abs, Abs)>,
+}
+
+/// A form XObject for a transparency group. It is written once all resources
+/// have been allocated.
+pub struct Form {
+    /// The indirect object id of the form.
+    pub id: Ref,
+    /// The form's compressed content stream.
+    pub content: Vec<u8>,
+    /// The form's bounding box.
+    pub bbox: Rect,
+    /// The resources the content stream uses.
+    pub resources: Resources,
+}
+
+/// The resources used by a content stream, by their indices.
+#[derive(Debug, Default)]
+pub struct Resources {
+    fonts: BTreeSet<usize>,
+    images: BTreeSet<usize>,
+    forms: BTreeSet<usize>,
+    ext_gs: BTreeSet<usize>,
+    gradients: BTreeSet<usize>,
 }
 
 /// An exporter for the contents of a single PDF page.
@@ -172,8 +349,17 @@ struct PageContext<'a, 'b> {
     content: Content,
     state: State,
     saves: Vec<State>,
+    size: Size,
     bottom: f32,
     links: Vec<(Destination, Rect)>,
+    tooltips: Vec<(EcoString, Rect)>,
+    /// The resources used by the content stream being written.
+    resources: Resources,
+    /// Whether a marked-content sequence is open.
+    marked: bool,
+    /// Whether drawn content is marked for the structure tree. This is off
+    /// within form XObjects, which are marked as a whole.
+    tagging: bool,
 }
 
 /// A simulated graphics state used to deduplicate graphics state changes and
@@ -181,6 +367,9 @@ struct PageContext<'a, 'b> {
 #[derive(Debug, Default, Clone)]
 struct State {
     transform: Transform,
+    /// Maps from the page's coordinate system into the one of the form
+    /// XObject that is currently being written, if any.
+    form: Transform,
     font: Option<(Font, Abs)>,
     fill: Option<Paint>,
     fill_space: Option<Name<'static>>,
@@ -190,15 +379,44 @@ struct State {
 
 impl PageContext<'_, '_> {
     fn save_state(&mut self) {
+        self.unmark();
         self.saves.push(self.state.clone());
         self.content.save_state();
     }
 
     fn restore_state(&mut self) {
+        self.unmark();
         self.content.restore_state();
         self.state = self.saves.pop().expect("missing state save");
     }
 
+    /// Mark the content that is drawn next as belonging to the innermost
+    /// open structure element, or as an artifact outside of any.
+    fn mark(&mut self) {
+        if self.marked || !self.tagging {
+            return;
+        }
+
+        if self.parent.tree.is_open() {
+            let (mcid, role) = self.parent.tree.mark();
+            let mut marked =
+                self.content.begin_marked_content_with_properties(Name(role));
+            marked.properties().pair(Name(b"MCID"), mcid);
+        } else {
+            self.content.begin_marked_content(Name(b"Artifact"));
+        }
+
+        self.marked = true;
+    }
+
+    /// End the current marked-content sequence, if any.
+    fn unmark(&mut self) {
+        if self.marked {
+            self.content.end_marked_content();
+            self.marked = false;
+        }
+    }
+
     fn transform(&mut self, transform: Transform) {
         let Transform { sx, ky, kx, sy, tx, ty } = transform;
         self.state.transform = self.state.transform.pre_concat(transform);
@@ -215,31 +433,41 @@ impl PageContext<'_, '_> {
     fn set_font(&mut self, font: &Font, size: Abs) {
         if self.state.font.as_ref().map(|(f, s)| (f, *s)) != Some((font, size)) {
             self.parent.font_map.insert(font.clone());
-            let name = eco_format!("F{}", self.parent.font_map.map(font.clone()));
+            let index = self.parent.font_map.map(font.clone());
+            self.resources.fonts.insert(index);
+            let name = eco_format!("F{}", index);
             self.content.set_font(Name(name.as_bytes()), size.to_f32());
             self.state.font = Some((font.clone(), size));
         }
     }
 
-    fn set_fill(&mut self, fill: &Paint) {
+    fn set_fill(&mut self, fill: &Paint, pos: Point, size: Size) {
         if self.state.fill.as_ref() != Some(fill) {
             let f = |c| c as f32 / 255.0;
-            let Paint::Solid(color) = fill;
-            match color {
-                Color::Luma(c) => {
+            match fill {
+                Paint::Solid(Color::Luma(c)) => {
                     self.set_fill_color_space(D65_GRAY);
                     self.content.set_fill_gray(f(c.0));
                 }
-                Color::Rgba(c) => {
+                Paint::Solid(Color::Rgba(c)) => {
                     self.set_fill_color_space(SRGB);
                     self.content.set_fill_color([f(c.r), f(c.g), f(c.b)]);
                 }
-                Color::Cmyk(c) => {
+                Paint::Solid(Color::Cmyk(c)) => {
                     self.reset_fill_color_space();
                     self.content.set_fill_cmyk(f(c.c), f(c.m), f(c.y), f(c.k));
                 }
+                Paint::Gradient(gradient) => {
+                    let name = self.write_gradient(gradient, pos, size);
+                    self.reset_fill_color_space();
+                    self.content.set_fill_color_space(ColorSpaceOperand::Pattern);
+                    self.content.set_fill_pattern(None, Name(name.as_bytes()));
+                }
             }
-            self.state.fill = Some(fill.clone());
+
+            // A gradient's pattern depends on the bounding box, so it can't
+            // be reused.
+            self.state.fill = matches!(fill, Paint::Solid(_)).then(|| fill.clone());
         }
     }
 
@@ -254,7 +482,7 @@ impl PageContext<'_, '_> {
         self.state.fill_space = None;
     }
 
-    fn set_stroke(&mut self, stroke: &Stroke) {
+    fn set_stroke(&mut self, stroke: &Stroke, pos: Point, size: Size) {
         if self.state.stroke.as_ref() != Some(stroke) {
             let Stroke {
                 paint,
@@ -266,20 +494,25 @@ impl PageContext<'_, '_> {
             } = stroke;
 
             let f = |c| c as f32 / 255.0;
-            let Paint::Solid(color) = paint;
-            match color {
-                Color::Luma(c) => {
+            match paint {
+                Paint::Solid(Color::Luma(c)) => {
                     self.set_stroke_color_space(D65_GRAY);
                     self.content.set_stroke_gray(f(c.0));
                 }
-                Color::Rgba(c) => {
+                Paint::Solid(Color::Rgba(c)) => {
                     self.set_stroke_color_space(SRGB);
                     self.content.set_stroke_color([f(c.r), f(c.g), f(c.b)]);
                 }
-                Color::Cmyk(c) => {
+                Paint::Solid(Color::Cmyk(c)) => {
                     self.reset_stroke_color_space();
                     self.content.set_stroke_cmyk(f(c.c), f(c.m), f(c.y), f(c.k));
                 }
+                Paint::Gradient(gradient) => {
+                    let name = self.write_gradient(gradient, pos, size);
+                    self.reset_stroke_color_space();
+                    self.content.set_stroke_color_space(ColorSpaceOperand::Pattern);
+                    self.content.set_stroke_pattern(None, Name(name.as_bytes()));
+                }
             }
 
             self.content.set_line_width(thickness.to_f32());
@@ -302,8 +535,66 @@ impl PageContext<'_, '_> {
             if self.state.stroke.as_ref().map(|s| &s.miter_limit) != Some(miter_limit) {
                 self.content.set_miter_limit(miter_limit.0 as f32);
             }
-            self.state.stroke = Some(stroke.clone());
+
+            // Like for fills, a gradient stroke's pattern depends on the
+            // bounding box of the stroked shape.
+            self.state.stroke = matches!(paint, Paint::Solid(_)).then(|| stroke.clone());
+        }
+    }
+
+    /// Write a shading pattern for a gradient that paints the bounding box
+    /// at `pos` with the given `size` and return the pattern's name.
+    fn write_gradient(
+        &mut self,
+        gradient: &Gradient,
+        pos: Point,
+        size: Size,
+    ) -> EcoString {
+        let function_ref = write_gradient_function(self.parent, gradient.stops());
+        let pattern_ref = self.parent.alloc.bump();
+        let index = self.parent.gradient_refs.len();
+        self.parent.gradient_refs.push(pattern_ref);
+        self.resources.gradients.insert(index);
+
+        let mut pattern = self.parent.writer.shading_pattern(pattern_ref);
+        let mut shading = pattern.function_shading();
+        shading.color_space().srgb();
+        shading.function(function_ref);
+        shading.extend([true, true]);
+        match gradient {
+            Gradient::Linear(linear) => {
+                let (start, end) = linear.line(size);
+                shading.shading_type(FunctionShadingType::Axial);
+                shading.coords([start.x, start.y, end.x, end.y].map(Abs::to_f32));
+            }
+            Gradient::Radial(radial) => {
+                let (center, radius) = radial.circle(size);
+                let (x, y) = (center.x.to_f32(), center.y.to_f32());
+                shading.shading_type(FunctionShadingType::Radial);
+                shading.coords([x, y, 0.0, x, y, radius.to_f32()]);
+            }
         }
+        shading.finish();
+
+        // Patterns live in the default coordinate system of the page or form,
+        // so they need to be moved to the bounding box in the current
+        // coordinate system.
+        let ts = self
+            .state
+            .form
+            .pre_concat(self.state.transform)
+            .pre_concat(Transform::translate(pos.x, pos.y));
+        let Transform { sx, ky, kx, sy, tx, ty } = ts;
+        pattern.matrix([
+            sx.get() as _,
+            ky.get() as _,
+            kx.get() as _,
+            sy.get() as _,
+            tx.to_f32(),
+            ty.to_f32(),
+        ]);
+
+        eco_format!("Gr{}", index)
     }
 
     fn set_stroke_color_space(&mut self, space: Name<'static>) {
@@ -327,12 +618,24 @@ fn write_frame(ctx: &mut PageContext, frame: &Frame) {
             FrameItem::Group(group) => write_group(ctx, pos, group),
             FrameItem::Text(text) => write_text(ctx, x, y, text),
             FrameItem::Shape(shape, _) => write_shape(ctx, x, y, shape),
+            FrameItem::Shadow(shape, blur, _) => write_shadow(ctx, x, y, shape, *blur),
             FrameItem::Image(image, size, _) => write_image(ctx, x, y, image, *size),
             FrameItem::Meta(meta, size) => match meta {
                 Meta::Link(dest) => write_link(ctx, pos, dest, *size),
+                Meta::Tooltip(text) => write_tooltip(ctx, pos, text, *size),
+                Meta::Anchor(name) => write_anchor(ctx, pos, name),
                 Meta::Elem(_) => {}
                 Meta::Hide => {}
                 Meta::PageNumbering(_) => {}
+                Meta::PrintBoxes { .. } => {}
+                Meta::TagStart(tag) => {
+                    ctx.unmark();
+                    ctx.parent.tree.start(tag.clone());
+                }
+                Meta::TagEnd => {
+                    ctx.unmark();
+                    ctx.parent.tree.end();
+                }
             },
         }
     }
@@ -346,21 +649,77 @@ fn write_group(ctx: &mut PageContext, pos: Point, group: &GroupItem) {
     ctx.transform(translation.pre_concat(group.transform));
 
     if group.clips {
-        let size = group.frame.size();
-        let w = size.x.to_f32();
-        let h = size.y.to_f32();
-        ctx.content.move_to(0.0, 0.0);
-        ctx.content.line_to(w, 0.0);
-        ctx.content.line_to(w, h);
-        ctx.content.line_to(0.0, h);
+        if let Some(path) = &group.clip_path {
+            write_path(ctx, 0.0, 0.0, path);
+        } else {
+            let size = group.frame.size();
+            let w = size.x.to_f32();
+            let h = size.y.to_f32();
+            ctx.content.move_to(0.0, 0.0);
+            ctx.content.line_to(w, 0.0);
+            ctx.content.line_to(w, h);
+            ctx.content.line_to(0.0, h);
+        }
         ctx.content.clip_nonzero();
         ctx.content.end_path();
     }
 
-    write_frame(ctx, &group.frame);
+    if group.opacity != Ratio::one() || group.blend_mode != BlendMode::Normal {
+        write_transparency_group(ctx, group);
+    } else {
+        write_frame(ctx, &group.frame);
+    }
+
     ctx.restore_state();
 }
 
+/// Encode a group that is composited as a whole into a transparency group.
+fn write_transparency_group(ctx: &mut PageContext, group: &GroupItem) {
+    let Some(inverse) = ctx.state.transform.invert() else { return };
+
+    // The group's contents go into a form XObject, which starts out with the
+    // graphics state in effect where it is painted.
+    let outer = std::mem::replace(&mut ctx.content, Content::new());
+    let outer_resources = std::mem::take(&mut ctx.resources);
+    let state = ctx.state.clone();
+    ctx.state.form = inverse;
+    let tagging = std::mem::replace(&mut ctx.tagging, false);
+    write_frame(ctx, &group.frame);
+    let data = std::mem::replace(&mut ctx.content, outer).finish();
+    let resources = std::mem::replace(&mut ctx.resources, outer_resources);
+    ctx.state = state;
+    ctx.tagging = tagging;
+
+    // The form may cover the whole page, but no more.
+    let (w, h) = (ctx.size.x, ctx.size.y);
+    let corners = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)]
+        .map(|(x, y)| Point::new(w * x, h * y).transform(inverse));
+    let min = corners.iter().fold(corners[0], |min, &p| min.min(p));
+    let max = corners.iter().fold(corners[0], |max, &p| max.max(p));
+
+    // The form is written once the refs of its resources are known.
+    let form_ref = ctx.parent.alloc.bump();
+    let form_index = ctx.parent.form_refs.len();
+    let form = eco_format!("Fm{}", form_index);
+    ctx.parent.form_refs.push(form_ref);
+    ctx.parent.forms.push(Form {
+        id: form_ref,
+        content: deflate(&data),
+        bbox: Rect::new(min.x.to_f32(), min.y.to_f32(), max.x.to_f32(), max.y.to_f32()),
+        resources,
+    });
+    ctx.resources.forms.insert(form_index);
+
+    let key = (group.opacity, group.blend_mode);
+    ctx.parent.ext_gs_map.insert(key);
+    let gs_index = ctx.parent.ext_gs_map.map(key);
+    ctx.resources.ext_gs.insert(gs_index);
+    let gs = eco_format!("Gs{}", gs_index);
+    ctx.mark();
+    ctx.content.set_parameters(Name(gs.as_bytes()));
+    ctx.content.x_object(Name(form.as_bytes()));
+}
+
 /// Encode a text run into the content stream.
 fn write_text(ctx: &mut PageContext, x: f32, y: f32, text: &TextItem) {
     *ctx.parent.languages.entry(text.lang).or_insert(0) += text.glyphs.len();
@@ -371,7 +730,13 @@ fn write_text(ctx: &mut PageContext, x: f32, y: f32, text: &TextItem) {
         glyph_set.entry(g.id).or_insert_with(|| segment.into());
     }
 
-    ctx.set_fill(&text.fill);
+    let size = Size::new(text.width(), text.size);
+    ctx.mark();
+    ctx.set_fill(
+        &text.fill,
+        Point::new(Abs::pt(x as f64), Abs::pt(y as f64) - text.size),
+        size,
+    );
     ctx.set_font(&text.font, text.size);
     ctx.content.begin_text();
 
@@ -382,6 +747,7 @@ fn write_text(ctx: &mut PageContext, x: f32, y: f32, text: &TextItem) {
     let mut items = positioned.items();
     let mut adjustment = Em::zero();
     let mut encoded = vec![];
+    let unembedded = ctx.parent.unembedded.contains(&text.font);
 
     // Write the glyphs with kerning adjustments.
     for glyph in &text.glyphs {
@@ -397,8 +763,14 @@ fn write_text(ctx: &mut PageContext, x: f32, y: f32, text: &TextItem) {
             adjustment = Em::zero();
         }
 
-        encoded.push((glyph.id >> 8) as u8);
-        encoded.push((glyph.id & 0xff) as u8);
+        if unembedded {
+            // Unembedded fonts only contain glyphs with a character code.
+            let segment = &text.text[glyph.range()];
+            encoded.extend(winansi_code(&text.font, glyph.id, segment));
+        } else {
+            encoded.push((glyph.id >> 8) as u8);
+            encoded.push((glyph.id & 0xff) as u8);
+        }
 
         if let Some(advance) = text.font.advance(glyph.id) {
             adjustment += glyph.x_advance - advance;
@@ -430,12 +802,15 @@ fn write_shape(ctx: &mut PageContext, x: f32, y: f32, shape: &Shape) {
         return;
     }
 
+    let pos = Point::new(Abs::pt(x as f64), Abs::pt(y as f64));
+    let size = shape.geometry.bbox_size();
+    ctx.mark();
     if let Some(fill) = &shape.fill {
-        ctx.set_fill(fill);
+        ctx.set_fill(fill, pos, size);
     }
 
     if let Some(stroke) = stroke {
-        ctx.set_stroke(stroke);
+        ctx.set_stroke(stroke, pos, size);
     }
 
     match shape.geometry {
@@ -465,6 +840,53 @@ fn write_shape(ctx: &mut PageContext, x: f32, y: f32, shape: &Shape) {
     };
 }
 
+/// Write a function that interpolates between the colors of a gradient's
+/// stops and return its reference.
+fn write_gradient_function(ctx: &mut PdfContext, stops: &[(Color, Ratio)]) -> Ref {
+    let rgb = |color: Color| {
+        let c = color.to_rgba();
+        [c.r, c.g, c.b].map(|v| v as f32 / 255.0)
+    };
+
+    // Extend the first and last color to the ends of the function's domain.
+    let mut stops = stops.to_vec();
+    if let Some(&(color, offset)) = stops.first() {
+        if offset > Ratio::zero() {
+            stops.insert(0, (color, Ratio::zero()));
+        }
+    }
+    if let Some(&(color, offset)) = stops.last() {
+        if offset < Ratio::one() {
+            stops.push((color, Ratio::one()));
+        }
+    }
+
+    let mut functions = vec![];
+    for window in stops.windows(2) {
+        let function_ref = ctx.alloc.bump();
+        ctx.writer
+            .exponential_function(function_ref)
+            .domain([0.0, 1.0])
+            .c0(rgb(window[0].0))
+            .c1(rgb(window[1].0))
+            .n(1.0);
+        functions.push(function_ref);
+    }
+
+    let bounds = stops[1..stops.len() - 1]
+        .iter()
+        .map(|(_, offset)| offset.get() as f32);
+    let function_ref = ctx.alloc.bump();
+    ctx.writer
+        .stitching_function(function_ref)
+        .domain([0.0, 1.0])
+        .functions(functions.iter().copied())
+        .bounds(bounds)
+        .encode(functions.iter().flat_map(|_| [0.0, 1.0]));
+
+    function_ref
+}
+
 /// Encode a bezier path into the content stream.
 fn write_path(ctx: &mut PageContext, x: f32, y: f32, path: &geom::Path) {
     for elem in &path.0 {
@@ -488,12 +910,42 @@ fn write_path(ctx: &mut PageContext, x: f32, y: f32, path: &geom::Path) {
     }
 }
 
+/// Encode a blurred shape into the content stream.
+fn write_shadow(ctx: &mut PageContext, x: f32, y: f32, shape: &Shape, blur: Abs) {
+    if blur <= Abs::zero() {
+        write_shape(ctx, x, y, shape);
+        return;
+    }
+
+    // PDF has no blur filter, so the blurred shape is embedded as a raster
+    // image at a fixed resolution.
+    let Some((pixmap, pad)) = blur_shape(shape, blur, PDF_PIXEL_PER_PT) else {
+        return;
+    };
+
+    let Ok(data) = pixmap.encode_png() else { return };
+    let format = ImageFormat::Raster(RasterFormat::Png);
+    let Ok(image) = Image::new(data.into(), format, None) else { return };
+    let size = Size::new(
+        Abs::pt((pixmap.width() as f32 / PDF_PIXEL_PER_PT) as f64),
+        Abs::pt((pixmap.height() as f32 / PDF_PIXEL_PER_PT) as f64),
+    );
+
+    let pad = pad.to_f32();
+    write_image(ctx, x - pad, y - pad, &image, size);
+}
+
 /// Encode a vector or raster image into the content stream.
 fn write_image(ctx: &mut PageContext, x: f32, y: f32, image: &Image, size: Size) {
-    ctx.parent.image_map.insert(image.clone());
-    let name = eco_format!("Im{}", ctx.parent.image_map.map(image.clone()));
+    // Images that only differ in their alt text share one XObject.
+    let shared = image.without_alt();
+    ctx.parent.image_map.insert(shared.clone());
+    let index = ctx.parent.image_map.map(shared);
+    ctx.resources.images.insert(index);
+    let name = eco_format!("Im{}", index);
     let w = size.x.to_f32();
     let h = size.y.to_f32();
+    ctx.mark();
     ctx.content.save_state();
     ctx.content.transform([w, 0.0, 0.0, -h, x, y + h]);
 
@@ -516,12 +968,30 @@ fn write_image(ctx: &mut PageContext, x: f32, y: f32, image: &Image, size: Size)
 
 /// Save a link for later writing in the annotations dictionary.
 fn write_link(ctx: &mut PageContext, pos: Point, dest: &Destination, size: Size) {
+    let rect = transformed_rect(ctx, pos, size);
+    ctx.links.push((dest.clone(), rect));
+}
+
+/// Save a tooltip for later writing in the annotations dictionary.
+fn write_tooltip(ctx: &mut PageContext, pos: Point, text: &EcoString, size: Size) {
+    let rect = transformed_rect(ctx, pos, size);
+    ctx.tooltips.push((text.clone(), rect));
+}
+
+/// Save a named destination for later writing in the catalog.
+fn write_anchor(ctx: &mut PageContext, pos: Point, name: &EcoString) {
+    let point = pos.transform(ctx.state.transform);
+    ctx.parent.anchors.push((name.clone(), ctx.page_ref, point));
+}
+
+/// Compute the bounding box of an area in the PDF coordinate system.
+fn transformed_rect(ctx: &PageContext, pos: Point, size: Size) -> Rect {
     let mut min_x = Abs::inf();
     let mut min_y = Abs::inf();
     let mut max_x = -Abs::inf();
     let mut max_y = -Abs::inf();
 
-    // Compute the bounding box of the transformed link.
+    // Compute the bounding box of the transformed area.
     for point in [
         pos,
         pos + Point::with_x(size.x),
@@ -539,9 +1009,7 @@ fn write_link(ctx: &mut PageContext, pos: Point, dest: &Destination, size: Size)
     let x2 = max_x.to_f32();
     let y1 = max_y.to_f32();
     let y2 = min_y.to_f32();
-    let rect = Rect::new(x1, y1, x2, y2);
-
-    ctx.links.push((dest.clone(), rect));
+    Rect::new(x1, y1, x2, y2)
 }
 
 impl From<&LineCap> for LineCapStyle {
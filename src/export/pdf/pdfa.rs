@@ -0,0 +1,107 @@
+use pdf_writer::{Array, Filter, Finish, Name, Ref, TextStr};
+
+use super::{deflate, PdfContext};
+use crate::diag::{bail, StrResult};
+use crate::doc::{Document, Frame, FrameItem};
+use crate::geom::{Color, Paint, Shape};
+use crate::util::hash128;
+
+/// The sRGB profile that the output intent refers to.
+const SRGB_PROFILE: &[u8] = include_bytes!("../../../assets/data/sRGB-IEC61966-2.1.icc");
+
+/// The name of the output condition described by the profile.
+const SRGB_CONDITION: &str = "sRGB IEC61966-2.1";
+
+/// Check that the document only uses features that PDF/A-2b permits.
+pub fn check(document: &Document) -> StrResult<()> {
+    if !document.attachments.is_empty() {
+        bail!("PDF/A-2b does not allow attaching files, export without PDF/A instead");
+    }
+
+    for frame in &document.pages {
+        check_frame(frame)?;
+    }
+
+    Ok(())
+}
+
+/// Check the paints used in a frame and its subframes.
+fn check_frame(frame: &Frame) -> StrResult<()> {
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => check_frame(&group.frame)?,
+            FrameItem::Text(text) => check_paint(&text.fill)?,
+            FrameItem::Shape(shape, _) | FrameItem::Shadow(shape, ..) => {
+                check_shape(shape)?
+            }
+            FrameItem::Image(..) | FrameItem::Meta(..) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Check the fill and stroke of a shape.
+fn check_shape(shape: &Shape) -> StrResult<()> {
+    if let Some(fill) = &shape.fill {
+        check_paint(fill)?;
+    }
+    if let Some(stroke) = &shape.stroke {
+        check_paint(&stroke.paint)?;
+    }
+    Ok(())
+}
+
+/// Check that a paint fits the document's sRGB output intent.
+fn check_paint(paint: &Paint) -> StrResult<()> {
+    if let Paint::Solid(Color::Cmyk(_)) = paint {
+        bail!("PDF/A-2b does not allow CMYK colors, use `rgb` or `luma` instead");
+    }
+    Ok(())
+}
+
+/// Write the sRGB profile for the output intent and return its reference.
+pub fn write_profile(ctx: &mut PdfContext) -> Ref {
+    let profile_ref = ctx.alloc.bump();
+    let compressed = deflate(SRGB_PROFILE);
+    let mut stream = ctx.writer.icc_profile(profile_ref, &compressed);
+    stream.filter(Filter::FlateDecode);
+    stream.n(3);
+    stream.finish();
+    profile_ref
+}
+
+/// Write the output intent into the given array of the catalog.
+pub fn write_output_intent(intents: &mut Array, profile_ref: Ref) {
+    let mut intent = intents.push().dict();
+    intent.pair(Name(b"Type"), Name(b"OutputIntent"));
+    intent.pair(Name(b"S"), Name(b"GTS_PDFA1"));
+    intent.pair(Name(b"OutputConditionIdentifier"), TextStr(SRGB_CONDITION));
+    intent.pair(Name(b"Info"), TextStr(SRGB_CONDITION));
+    intent.pair(Name(b"DestOutputProfile"), profile_ref);
+}
+
+/// Add the PDF/A identification schema to an XMP packet.
+pub fn identify(xmp: &mut String) {
+    let schema = "<rdf:Description rdf:about=\"\" \
+                  xmlns:pdfaid=\"http://www.aiim.org/pdfa/ns/id/\" \
+                  pdfaid:part=\"2\" pdfaid:conformance=\"B\"/>";
+    if let Some(end) = xmp.rfind("</rdf:RDF>") {
+        xmp.insert_str(end, schema);
+    }
+}
+
+/// Add a file identifier to the trailer of a finished PDF.
+///
+/// The trailer follows the cross-reference table, so inserting into it
+/// doesn't move any objects.
+pub fn write_file_id(buf: &mut Vec<u8>) {
+    const TRAILER: &[u8] = b"trailer\n<<";
+    let Some(start) = buf.windows(TRAILER.len()).rposition(|w| w == TRAILER) else {
+        return;
+    };
+
+    let id = format!("{:032x}", hash128(buf.as_slice()));
+    let entry = format!("\n  /ID [<{id}> <{id}>]");
+    let at = start + TRAILER.len();
+    buf.splice(at..at, entry.into_bytes());
+}
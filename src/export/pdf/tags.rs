@@ -0,0 +1,198 @@
+use pdf_writer::{Finish, Name, Ref, TextStr};
+
+use super::PdfContext;
+use crate::doc::{Frame, FrameItem, Meta, Tag};
+
+/// The logical structure of a document, collected while writing its pages.
+pub struct StructTree {
+    /// The structure elements. The first one is the document itself.
+    elems: Vec<StructElem>,
+    /// The indices of the open elements, innermost last.
+    stack: Vec<usize>,
+    /// For each exported page, the element each marked-content id on it
+    /// belongs to.
+    parents: Vec<Vec<usize>>,
+}
+
+/// An element of the structure tree.
+struct StructElem {
+    /// The element's tag, or `None` for the document.
+    tag: Option<Tag>,
+    /// The index of the parent element.
+    parent: usize,
+    /// The element's children in reading order.
+    kids: Vec<Kid>,
+}
+
+/// A child of a structure element.
+enum Kid {
+    /// Another structure element, by index.
+    Elem(usize),
+    /// A marked-content sequence on the exported page with the given index.
+    Content { page: usize, mcid: i32 },
+}
+
+impl StructTree {
+    /// Whether the document has no structure elements.
+    pub fn is_empty(&self) -> bool {
+        self.elems.len() == 1
+    }
+
+    /// Whether drawn content belongs to a structure element. Other content
+    /// is an artifact.
+    pub fn is_open(&self) -> bool {
+        !self.stack.is_empty()
+    }
+
+    /// Start a new element in the innermost open one.
+    pub fn start(&mut self, tag: Tag) {
+        // Paragraphs and headings can't nest, so a paragraph within another
+        // one, like in a box, just continues the outer one.
+        let current = self.current();
+        let is_text = |tag: &Tag| matches!(tag, Tag::Paragraph | Tag::Heading(_));
+        if is_text(&tag) && self.elems[current].tag.as_ref().map_or(false, is_text) {
+            self.stack.push(current);
+            return;
+        }
+
+        let index = self.elems.len();
+        self.elems
+            .push(StructElem { tag: Some(tag), parent: current, kids: vec![] });
+        self.elems[current].kids.push(Kid::Elem(index));
+        self.stack.push(index);
+    }
+
+    /// Close the innermost open element.
+    pub fn end(&mut self) {
+        self.stack.pop();
+    }
+
+    /// Start collecting the marked content of the next exported page.
+    pub fn start_page(&mut self) {
+        self.parents.push(vec![]);
+    }
+
+    /// Open or close the elements in a page that isn't exported.
+    pub fn skip(&mut self, frame: &Frame) {
+        for (_, item) in frame.items() {
+            match item {
+                FrameItem::Group(group) => self.skip(&group.frame),
+                FrameItem::Meta(Meta::TagStart(tag), _) => self.start(tag.clone()),
+                FrameItem::Meta(Meta::TagEnd, _) => self.end(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Allocate a marked-content id on the current page for the innermost
+    /// open element and return it along with the element's role.
+    pub fn mark(&mut self) -> (i32, &'static [u8]) {
+        let current = self.current();
+        let page = self.parents.len() - 1;
+        let parents = &mut self.parents[page];
+        let mcid = parents.len() as i32;
+        parents.push(current);
+
+        let elem = &mut self.elems[current];
+        elem.kids.push(Kid::Content { page, mcid });
+        (mcid, role(elem.tag.as_ref()))
+    }
+
+    /// The index of the innermost open element.
+    fn current(&self) -> usize {
+        self.stack.last().copied().unwrap_or(0)
+    }
+}
+
+impl Default for StructTree {
+    fn default() -> Self {
+        let document = StructElem { tag: None, parent: 0, kids: vec![] };
+        Self {
+            elems: vec![document],
+            stack: vec![],
+            parents: vec![],
+        }
+    }
+}
+
+/// The standard structure type of an element.
+fn role(tag: Option<&Tag>) -> &'static [u8] {
+    match tag {
+        None => b"Document",
+        Some(Tag::Heading(level)) => match level.get() {
+            1 => b"H1",
+            2 => b"H2",
+            3 => b"H3",
+            4 => b"H4",
+            5 => b"H5",
+            _ => b"H6",
+        },
+        Some(Tag::Paragraph) => b"P",
+        Some(Tag::Figure(_)) => b"Figure",
+        Some(Tag::List) => b"L",
+        Some(Tag::ListItem) => b"LI",
+        Some(Tag::Table) => b"Table",
+        Some(Tag::TableRow) => b"TR",
+        Some(Tag::TableCell) => b"TD",
+    }
+}
+
+/// Write the structure tree and return the reference of its root, if the
+/// document has any structure.
+pub fn write_struct_tree(ctx: &mut PdfContext) -> Option<Ref> {
+    let tree = std::mem::take(&mut ctx.tree);
+    if tree.is_empty() {
+        return None;
+    }
+
+    let root_ref = ctx.alloc.bump();
+    let refs: Vec<_> = tree.elems.iter().map(|_| ctx.alloc.bump()).collect();
+
+    for (i, elem) in tree.elems.iter().enumerate() {
+        let mut dict = ctx.writer.indirect(refs[i]).dict();
+        dict.pair(Name(b"Type"), Name(b"StructElem"));
+        dict.pair(Name(b"S"), Name(role(elem.tag.as_ref())));
+        dict.pair(Name(b"P"), if i == 0 { root_ref } else { refs[elem.parent] });
+        if let Some(Tag::Figure(Some(alt))) = &elem.tag {
+            dict.pair(Name(b"Alt"), TextStr(alt));
+        }
+
+        let mut kids = dict.insert(Name(b"K")).array();
+        for kid in &elem.kids {
+            match *kid {
+                Kid::Elem(k) => {
+                    kids.item(refs[k]);
+                }
+                Kid::Content { page, mcid } => {
+                    kids.push()
+                        .dict()
+                        .pair(Name(b"Type"), Name(b"MCR"))
+                        .pair(Name(b"Pg"), ctx.page_refs[page])
+                        .pair(Name(b"MCID"), mcid);
+                }
+            }
+        }
+
+        kids.finish();
+        dict.finish();
+    }
+
+    // The parent tree maps from each page's marked content back to the
+    // elements it belongs to.
+    let mut root = ctx.writer.indirect(root_ref).dict();
+    root.pair(Name(b"Type"), Name(b"StructTreeRoot"));
+    root.pair(Name(b"K"), refs[0]);
+    let mut parent_tree = root.insert(Name(b"ParentTree")).dict();
+    let mut nums = parent_tree.insert(Name(b"Nums")).array();
+    for (page, parents) in tree.parents.iter().enumerate() {
+        nums.item(page as i32);
+        nums.push().array().items(parents.iter().map(|&p| refs[p]));
+    }
+
+    nums.finish();
+    parent_tree.finish();
+    root.pair(Name(b"ParentTreeNextKey"), tree.parents.len() as i32);
+    root.finish();
+
+    Some(root_ref)
+}
@@ -10,10 +10,11 @@ use tiny_skia as sk;
 use ttf_parser::{GlyphId, OutlineBuilder};
 use usvg::{NodeExt, TreeParsing};
 
+use super::blur::blur_shape;
 use crate::doc::{Frame, FrameItem, GroupItem, Meta, TextItem};
 use crate::geom::{
-    self, Abs, Color, Geometry, LineCap, LineJoin, Paint, PathItem, Shape, Size, Stroke,
-    Transform,
+    self, Abs, BlendMode, Color, Geometry, Gradient, LineCap, LineJoin, Paint, PathItem,
+    Point, Ratio, Shape, Size, Stroke, Transform,
 };
 use crate::image::{DecodedImage, Image};
 
@@ -57,13 +58,21 @@ fn render_frame(
             FrameItem::Shape(shape, _) => {
                 render_shape(canvas, ts, mask, shape);
             }
+            FrameItem::Shadow(shape, blur, _) => {
+                render_shadow(canvas, ts, mask, shape, *blur);
+            }
             FrameItem::Image(image, size, _) => {
                 render_image(canvas, ts, mask, image, *size);
             }
             FrameItem::Meta(meta, _) => match meta {
                 Meta::Link(_) => {}
+                Meta::Tooltip(_) => {}
+                Meta::Anchor(_) => {}
                 Meta::Elem(_) => {}
                 Meta::PageNumbering(_) => {}
+                Meta::PrintBoxes { .. } => {}
+                Meta::TagStart(_) => {}
+                Meta::TagEnd => {}
                 Meta::Hide => {}
             },
         }
@@ -82,19 +91,26 @@ fn render_group(
     let mut mask = mask;
     let storage;
     if group.clips {
-        let size = group.frame.size();
-        let w = size.x.to_f32();
-        let h = size.y.to_f32();
-        if let Some(path) = sk::Rect::from_xywh(0.0, 0.0, w, h)
-            .map(sk::PathBuilder::from_rect)
-            .and_then(|path| path.transform(ts))
-        {
+        // Arbitrary clip paths are anti-aliased, while rectangular bounds are
+        // kept crisp.
+        let anti_alias = group.clip_path.is_some();
+        let path = match &group.clip_path {
+            Some(path) => convert_path(path),
+            None => {
+                let size = group.frame.size();
+                let w = size.x.to_f32();
+                let h = size.y.to_f32();
+                sk::Rect::from_xywh(0.0, 0.0, w, h).map(sk::PathBuilder::from_rect)
+            }
+        };
+
+        if let Some(path) = path.and_then(|path| path.transform(ts)) {
             if let Some(mask) = mask {
                 let mut mask = mask.clone();
                 mask.intersect_path(
                     &path,
                     sk::FillRule::default(),
-                    false,
+                    anti_alias,
                     sk::Transform::default(),
                 );
                 storage = mask;
@@ -110,7 +126,7 @@ fn render_group(
                 mask.fill_path(
                     &path,
                     sk::FillRule::default(),
-                    false,
+                    anti_alias,
                     sk::Transform::default(),
                 );
                 storage = mask;
@@ -120,6 +136,23 @@ fn render_group(
         }
     }
 
+    // Composite the group as a whole, so that its overlapping parts don't
+    // shine through each other.
+    if group.opacity != Ratio::one() || group.blend_mode != BlendMode::Normal {
+        let Some(mut layer) = sk::Pixmap::new(canvas.width(), canvas.height()) else {
+            return;
+        };
+
+        render_frame(&mut layer, ts, mask, &group.frame);
+        let paint = sk::PixmapPaint {
+            opacity: group.opacity.get() as f32,
+            blend_mode: group.blend_mode.into(),
+            quality: sk::FilterQuality::Nearest,
+        };
+        canvas.draw_pixmap(0, 0, layer.as_ref(), &paint, sk::Transform::identity(), None);
+        return;
+    }
+
     render_frame(canvas, ts, mask, &group.frame);
 }
 
@@ -136,9 +169,21 @@ fn render_text(
         let offset = x + glyph.x_offset.at(text.size).to_f32();
         let ts = ts.pre_translate(offset, 0.0);
 
+        // Each glyph is filled with a single color. For gradients, it is
+        // sampled at the glyph's center.
+        let color = match &text.fill {
+            Paint::Solid(color) => *color,
+            Paint::Gradient(gradient) => {
+                let advance = glyph.x_advance.at(text.size);
+                let center =
+                    Point::new(Abs::pt(offset as f64) + advance / 2.0, text.size / 2.0);
+                gradient.sample_at(center, Size::new(text.width(), text.size))
+            }
+        };
+
         render_svg_glyph(canvas, ts, mask, text, id)
             .or_else(|| render_bitmap_glyph(canvas, ts, mask, text, id))
-            .or_else(|| render_outline_glyph(canvas, ts, mask, text, id));
+            .or_else(|| render_outline_glyph(canvas, ts, mask, text, id, color));
 
         x += glyph.x_advance.at(text.size).to_f32();
     }
@@ -272,6 +317,7 @@ fn render_outline_glyph(
     mask: Option<&sk::Mask>,
     text: &TextItem,
     id: GlyphId,
+    color: Color,
 ) -> Option<()> {
     let ppem = text.size.to_f32() * ts.sy;
 
@@ -285,7 +331,7 @@ fn render_outline_glyph(
             builder.0.finish()?
         };
 
-        let paint = (&text.fill).into();
+        let paint = to_sk_paint(&Paint::Solid(color), Size::zero());
         let rule = sk::FillRule::default();
 
         // Flip vertically because font design coordinate
@@ -308,7 +354,6 @@ fn render_outline_glyph(
         let mw = bitmap.width;
         let mh = bitmap.height;
 
-        let Paint::Solid(color) = text.fill;
         let c = color.to_rgba();
 
         // Pad the pixmap with 1 pixel in each dimension so that we do
@@ -348,7 +393,6 @@ fn render_outline_glyph(
         let bottom = top + mh;
 
         // Premultiply the text color.
-        let Paint::Solid(color) = text.fill;
         let c = color.to_rgba();
         let color = sk::ColorU8::from_rgba(c.r, c.g, c.b, 255).premultiply().get();
 
@@ -378,7 +422,7 @@ fn render_outline_glyph(
 }
 
 /// Render a geometrical shape into the canvas.
-fn render_shape(
+pub(super) fn render_shape(
     canvas: &mut sk::Pixmap,
     ts: sk::Transform,
     mask: Option<&sk::Mask>,
@@ -399,8 +443,9 @@ fn render_shape(
         Geometry::Path(ref path) => convert_path(path)?,
     };
 
+    let bbox = shape.geometry.bbox_size();
     if let Some(fill) = &shape.fill {
-        let mut paint: sk::Paint = fill.into();
+        let mut paint = to_sk_paint(fill, bbox);
         if matches!(shape.geometry, Geometry::Rect(_)) {
             paint.anti_alias = false;
         }
@@ -433,7 +478,7 @@ fn render_shape(
 
                 sk::StrokeDash::new(dash_array, pattern.phase.to_f32())
             });
-            let paint = paint.into();
+            let paint = to_sk_paint(paint, bbox);
             let stroke = sk::Stroke {
                 width,
                 line_cap: line_cap.into(),
@@ -448,6 +493,28 @@ fn render_shape(
     Some(())
 }
 
+/// Render a blurred shape into the canvas.
+fn render_shadow(
+    canvas: &mut sk::Pixmap,
+    ts: sk::Transform,
+    mask: Option<&sk::Mask>,
+    shape: &Shape,
+    blur: Abs,
+) -> Option<()> {
+    if blur <= Abs::zero() {
+        return render_shape(canvas, ts, mask, shape);
+    }
+
+    let scale = ts.sx.hypot(ts.ky).max(ts.kx.hypot(ts.sy));
+    let (pixmap, pad) = blur_shape(shape, blur, scale)?;
+    let ts = ts
+        .pre_translate(-pad.to_f32(), -pad.to_f32())
+        .pre_scale(1.0 / scale, 1.0 / scale);
+    canvas.draw_pixmap(0, 0, pixmap.as_ref(), &sk::PixmapPaint::default(), ts, mask);
+
+    Some(())
+}
+
 /// Convert a Typst path into a tiny-skia path.
 fn convert_path(path: &geom::Path) -> Option<sk::Path> {
     let mut builder = sk::PathBuilder::new();
@@ -555,14 +622,74 @@ impl From<Transform> for sk::Transform {
     }
 }
 
-impl From<&Paint> for sk::Paint<'static> {
-    fn from(paint: &Paint) -> Self {
-        let mut sk_paint = sk::Paint::default();
-        let Paint::Solid(color) = *paint;
-        sk_paint.set_color(color.into());
-        sk_paint.anti_alias = true;
-        sk_paint
+impl From<BlendMode> for sk::BlendMode {
+    fn from(mode: BlendMode) -> Self {
+        match mode {
+            BlendMode::Normal => sk::BlendMode::SourceOver,
+            BlendMode::Multiply => sk::BlendMode::Multiply,
+            BlendMode::Screen => sk::BlendMode::Screen,
+            BlendMode::Overlay => sk::BlendMode::Overlay,
+            BlendMode::Darken => sk::BlendMode::Darken,
+            BlendMode::Lighten => sk::BlendMode::Lighten,
+            BlendMode::ColorDodge => sk::BlendMode::ColorDodge,
+            BlendMode::ColorBurn => sk::BlendMode::ColorBurn,
+            BlendMode::HardLight => sk::BlendMode::HardLight,
+            BlendMode::SoftLight => sk::BlendMode::SoftLight,
+            BlendMode::Difference => sk::BlendMode::Difference,
+            BlendMode::Exclusion => sk::BlendMode::Exclusion,
+        }
+    }
+}
+
+/// Convert a Typst paint into a tiny-skia paint. Gradients are laid out in a
+/// bounding box of the given size.
+fn to_sk_paint(paint: &Paint, size: Size) -> sk::Paint<'static> {
+    let mut sk_paint = sk::Paint::default();
+    match paint {
+        Paint::Solid(color) => sk_paint.set_color((*color).into()),
+        Paint::Gradient(gradient) => {
+            let point = |p: Point| sk::Point::from_xy(p.x.to_f32(), p.y.to_f32());
+            let stops: Vec<_> = gradient
+                .stops()
+                .iter()
+                .map(|&(color, offset)| {
+                    sk::GradientStop::new(offset.get() as f32, color.into())
+                })
+                .collect();
+
+            let shader = match gradient {
+                Gradient::Linear(linear) => {
+                    let (start, end) = linear.line(size);
+                    sk::LinearGradient::new(
+                        point(start),
+                        point(end),
+                        stops,
+                        sk::SpreadMode::Pad,
+                        sk::Transform::identity(),
+                    )
+                }
+                Gradient::Radial(radial) => {
+                    let (center, radius) = radial.circle(size);
+                    sk::RadialGradient::new(
+                        point(center),
+                        point(center),
+                        radius.to_f32(),
+                        stops,
+                        sk::SpreadMode::Pad,
+                        sk::Transform::identity(),
+                    )
+                }
+            };
+
+            // Degenerate gradients can't be represented as shaders.
+            match shader {
+                Some(shader) => sk_paint.shader = shader,
+                None => sk_paint.set_color(gradient.sample(0.0).into()),
+            }
+        }
     }
+    sk_paint.anti_alias = true;
+    sk_paint
 }
 
 impl From<Color> for sk::Color {
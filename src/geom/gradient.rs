@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use super::*;
+
+/// A color gradient.
+///
+/// Gradients are defined relative to the bounding box of the shape or text
+/// they paint.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub enum Gradient {
+    /// A gradient along a straight line.
+    Linear(Arc<LinearGradient>),
+    /// A gradient radiating outwards from a center point.
+    Radial(Arc<RadialGradient>),
+}
+
+impl Gradient {
+    /// The gradient's color stops with their offsets, in ascending order.
+    pub fn stops(&self) -> &[(Color, Ratio)] {
+        match self {
+            Self::Linear(linear) => &linear.stops,
+            Self::Radial(radial) => &radial.stops,
+        }
+    }
+
+    /// Sample the gradient's color at an offset between `0.0` and `1.0`.
+    ///
+    /// Offsets before the first or after the last stop yield the color of
+    /// that stop.
+    pub fn sample(&self, t: f64) -> Color {
+        let stops = self.stops();
+        let Some(&(first, start)) = stops.first() else {
+            return Color::BLACK;
+        };
+
+        if t <= start.get() {
+            return first;
+        }
+
+        for window in stops.windows(2) {
+            let (a, from) = window[0];
+            let (b, to) = window[1];
+            if t <= to.get() {
+                let span = to.get() - from.get();
+                let t = if span > 0.0 { (t - from.get()) / span } else { 1.0 };
+                return mix(a, b, t);
+            }
+        }
+
+        stops[stops.len() - 1].0
+    }
+
+    /// Sample the gradient's color at a point within a bounding box of the
+    /// given size.
+    pub fn sample_at(&self, point: Point, size: Size) -> Color {
+        let t = match self {
+            Self::Linear(linear) => {
+                let (start, end) = linear.line(size);
+                let d = end - start;
+                let p = point - start;
+                let length = d.x.to_raw().powi(2) + d.y.to_raw().powi(2);
+                if length > 0.0 {
+                    (p.x.to_raw() * d.x.to_raw() + p.y.to_raw() * d.y.to_raw()) / length
+                } else {
+                    0.0
+                }
+            }
+            Self::Radial(radial) => {
+                let (center, radius) = radial.circle(size);
+                let d = point - center;
+                let distance = d.x.to_raw().hypot(d.y.to_raw());
+                if radius > Abs::zero() {
+                    distance / radius.to_raw()
+                } else {
+                    0.0
+                }
+            }
+        };
+
+        self.sample(t)
+    }
+}
+
+impl Debug for Gradient {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Linear(linear) => linear.fmt(f),
+            Self::Radial(radial) => radial.fmt(f),
+        }
+    }
+}
+
+cast! {
+    type Gradient: "gradient",
+}
+
+/// A gradient along a straight line through the center of the bounding box.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct LinearGradient {
+    /// The color stops with their offsets along the gradient line.
+    pub stops: Vec<(Color, Ratio)>,
+    /// The direction of the gradient line. At `0deg`, it runs from left to
+    /// right. Angles increase clockwise.
+    pub angle: Angle,
+}
+
+impl LinearGradient {
+    /// The start and end point of the gradient line for a bounding box of
+    /// the given size.
+    ///
+    /// The line is chosen such that the corners of the bounding box receive
+    /// the colors of the first and last stop.
+    pub fn line(&self, size: Size) -> (Point, Point) {
+        let (sin, cos) = self.angle.to_rad().sin_cos();
+        let length = size.x * cos.abs() + size.y * sin.abs();
+        let center = Point::new(size.x / 2.0, size.y / 2.0);
+        let delta = Point::new(length * cos / 2.0, length * sin / 2.0);
+        (center - delta, center + delta)
+    }
+}
+
+impl Debug for LinearGradient {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "gradient.linear(")?;
+        for (color, offset) in &self.stops {
+            write!(f, "({color:?}, {offset:?}), ")?;
+        }
+        write!(f, "angle: {:?})", self.angle)
+    }
+}
+
+/// A gradient radiating outwards from a center point.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct RadialGradient {
+    /// The color stops with their offsets from the center.
+    pub stops: Vec<(Color, Ratio)>,
+    /// The center point, relative to the bounding box.
+    pub center: Axes<Ratio>,
+    /// The radius of the gradient, relative to the larger side of the
+    /// bounding box.
+    pub radius: Ratio,
+}
+
+impl RadialGradient {
+    /// The center point and the radius of the gradient for a bounding box
+    /// of the given size.
+    pub fn circle(&self, size: Size) -> (Point, Abs) {
+        let center = Point::new(self.center.x.of(size.x), self.center.y.of(size.y));
+        (center, self.radius.of(size.x.max(size.y)))
+    }
+}
+
+impl Debug for RadialGradient {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "gradient.radial(")?;
+        for (color, offset) in &self.stops {
+            write!(f, "({color:?}, {offset:?}), ")?;
+        }
+        write!(
+            f,
+            "center: ({:?}, {:?}), radius: {:?})",
+            self.center.x, self.center.y, self.radius
+        )
+    }
+}
+
+/// Interpolate linearly between two colors in RGBA space.
+fn mix(a: Color, b: Color, t: f64) -> Color {
+    let (a, b) = (a.to_rgba(), b.to_rgba());
+    let lerp = |x: u8, y: u8| (x as f64 + (y as f64 - x as f64) * t).round() as u8;
+    RgbaColor::new(lerp(a.r, b.r), lerp(a.g, b.g), lerp(a.b, b.b), lerp(a.a, b.a)).into()
+}
@@ -12,6 +12,7 @@ mod dir;
 mod ellipse;
 mod em;
 mod fr;
+mod gradient;
 mod length;
 mod paint;
 mod path;
@@ -20,6 +21,7 @@ mod ratio;
 mod rel;
 mod rounded;
 mod scalar;
+mod shadow;
 mod shape;
 mod sides;
 mod size;
@@ -37,14 +39,16 @@ pub use self::dir::Dir;
 pub use self::ellipse::ellipse;
 pub use self::em::Em;
 pub use self::fr::Fr;
+pub use self::gradient::{Gradient, LinearGradient, RadialGradient};
 pub use self::length::Length;
-pub use self::paint::Paint;
+pub use self::paint::{BlendMode, Paint};
 pub use self::path::{Path, PathItem};
 pub use self::point::Point;
 pub use self::ratio::Ratio;
 pub use self::rel::Rel;
-pub use self::rounded::rounded_rect;
+pub use self::rounded::{rounded_polygon, rounded_rect};
 pub use self::scalar::Scalar;
+pub use self::shadow::Shadow;
 pub use self::shape::{Geometry, Shape};
 pub use self::sides::{Side, Sides};
 pub use self::size::Size;
@@ -1,3 +1,5 @@
+use crate::eval::Cast;
+
 use super::*;
 
 /// How a fill or stroke should be painted.
@@ -5,6 +7,8 @@ use super::*;
 pub enum Paint {
     /// A solid color.
     Solid(Color),
+    /// A color gradient.
+    Gradient(Gradient),
 }
 
 impl<T: Into<Color>> From<T> for Paint {
@@ -17,6 +21,7 @@ impl Debug for Paint {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
             Self::Solid(color) => color.fmt(f),
+            Self::Gradient(gradient) => gradient.fmt(f),
         }
     }
 }
@@ -25,6 +30,41 @@ cast! {
     Paint,
     self => match self {
         Self::Solid(color) => Value::Color(color),
+        Self::Gradient(gradient) => Value::dynamic(gradient),
     },
     color: Color => Self::Solid(color),
+    gradient: Gradient => Self::Gradient(gradient),
+}
+
+/// How the colors of layered content are combined with what lies beneath it.
+///
+/// Only the separable blend modes are available, which all export formats
+/// support in the same way.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum BlendMode {
+    /// Paint the content over its backdrop.
+    #[default]
+    Normal,
+    /// Multiply the colors, which always darkens.
+    Multiply,
+    /// Multiply the inverted colors, which always lightens.
+    Screen,
+    /// Multiply or screen depending on the backdrop color.
+    Overlay,
+    /// Keep the darker of the two colors.
+    Darken,
+    /// Keep the lighter of the two colors.
+    Lighten,
+    /// Brighten the backdrop to reflect the content.
+    ColorDodge,
+    /// Darken the backdrop to reflect the content.
+    ColorBurn,
+    /// Multiply or screen depending on the content color.
+    HardLight,
+    /// Darken or lighten depending on the content color.
+    SoftLight,
+    /// Subtract the darker from the lighter color.
+    Difference,
+    /// Like difference, but with lower contrast.
+    Exclusion,
 }
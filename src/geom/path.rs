@@ -51,4 +51,40 @@ impl Path {
     pub fn close_path(&mut self) {
         self.0.push(PathItem::ClosePath);
     }
+
+    /// Apply a transformation to all points of the path.
+    pub fn transform(&self, ts: Transform) -> Self {
+        Self(
+            self.0
+                .iter()
+                .map(|item| match *item {
+                    PathItem::MoveTo(p) => PathItem::MoveTo(p.transform(ts)),
+                    PathItem::LineTo(p) => PathItem::LineTo(p.transform(ts)),
+                    PathItem::CubicTo(p1, p2, p3) => PathItem::CubicTo(
+                        p1.transform(ts),
+                        p2.transform(ts),
+                        p3.transform(ts),
+                    ),
+                    PathItem::ClosePath => PathItem::ClosePath,
+                })
+                .collect(),
+        )
+    }
+
+    /// The size of the area spanned by the origin and the path's points,
+    /// including control points.
+    pub fn bbox_size(&self) -> Size {
+        let mut size = Size::zero();
+        let mut extend = |p: &Point| size = size.max(p.to_size());
+        for item in &self.0 {
+            match item {
+                PathItem::MoveTo(p) | PathItem::LineTo(p) => extend(p),
+                PathItem::CubicTo(p1, p2, p3) => {
+                    [p1, p2, p3].into_iter().for_each(&mut extend)
+                }
+                PathItem::ClosePath => {}
+            }
+        }
+        size
+    }
 }
@@ -17,6 +17,16 @@ pub fn rounded_rect(
     }
 
     if !stroke.is_uniform() {
+        // Gradients are painted as filled areas so that they follow the
+        // side. All other strokes keep their own caps and joins.
+        if radius.iter().copied().all(Abs::is_zero)
+            && stroke.iter().flatten().all(|s| s.dash_pattern.is_none())
+            && stroke.iter().flatten().any(|s| matches!(s.paint, Paint::Gradient(_)))
+        {
+            res.extend(mitered_segments(size, &stroke));
+            return res;
+        }
+
         for (path, stroke) in stroke_segments(size, radius, stroke) {
             if stroke.is_some() {
                 res.push(Shape { geometry: Geometry::Path(path), fill: None, stroke });
@@ -27,6 +37,77 @@ pub fn rounded_rect(
     res
 }
 
+/// Produce a closed path through the given points whose corners are rounded
+/// with the given radius.
+///
+/// At each vertex, the radius shrinks as necessary so that the rounding takes
+/// up at most half of each adjacent edge.
+pub fn rounded_polygon(points: &[Point], radius: Abs) -> Path {
+    let mut path = Path::new();
+    let len = points.len();
+    for (i, &vertex) in points.iter().enumerate() {
+        let prev = points[(i + len - 1) % len];
+        let next = points[(i + 1) % len];
+        let arc = corner_arc(prev, vertex, next, radius);
+        let start = arc.map_or(vertex, |[start, ..]| start);
+        if i == 0 {
+            path.move_to(start);
+        } else {
+            path.line_to(start);
+        }
+
+        // The arc is split at its midpoint so that each half spans at most a
+        // quarter circle, which a single cubic approximates well.
+        if let Some([start, mid, end, center]) = arc {
+            for [_, c1, c2, p] in
+                [bezier_arc(start, center, mid), bezier_arc(mid, center, end)]
+            {
+                path.cubic_to(c1, c2, p);
+            }
+        }
+    }
+
+    path.close_path();
+    path
+}
+
+/// Determine the start, middle, end and center point of the arc rounding the
+/// corner at `vertex`, if it should be rounded at all.
+fn corner_arc(
+    prev: Point,
+    vertex: Point,
+    next: Point,
+    radius: Abs,
+) -> Option<[Point; 4]> {
+    let (a, b) = ((prev - vertex).hypot(), (next - vertex).hypot());
+    if radius <= Abs::zero() || a.is_zero() || b.is_zero() {
+        return None;
+    }
+
+    // Straight and fully folded corners can't be rounded.
+    let u1 = (prev - vertex) / a.to_raw();
+    let u2 = (next - vertex) / b.to_raw();
+    let cos = u1.x.to_raw() * u2.x.to_raw() + u1.y.to_raw() * u2.y.to_raw();
+    if cos.abs() > 1.0 - 1e-9 {
+        return None;
+    }
+
+    // The distance from the vertex to the points where the arc touches the
+    // edges, shrunk to at most half of the shorter edge.
+    let half = cos.acos() / 2.0;
+    let distance = (radius / half.tan()).min(a.min(b) / 2.0);
+    let radius = distance * half.tan();
+
+    let unit = (u1 + u2) / (u1 + u2).hypot().to_raw();
+    let center = vertex + unit * (radius / half.sin()).to_raw();
+    Some([
+        vertex + u1 * distance.to_raw(),
+        center - unit * radius.to_raw(),
+        vertex + u2 * distance.to_raw(),
+        center,
+    ])
+}
+
 /// Output the shape of the rectangle as a path or primitive rectangle,
 /// depending on whether it is rounded.
 fn fill_geometry(size: Size, radius: Corners<Abs>) -> Geometry {
@@ -82,6 +163,70 @@ fn stroke_segments(
     res
 }
 
+/// Output the areas covered by the strokes of a rectangle with sharp corners
+/// as filled shapes.
+///
+/// Neighboring sides with different strokes meet at the miter line through
+/// their corner, so that each side's paint ends exactly on the diagonal.
+fn mitered_segments(size: Size, stroke: &Sides<Option<Stroke>>) -> Vec<Shape> {
+    let half = |side: Side| {
+        stroke
+            .get_ref(side)
+            .as_ref()
+            .map_or(Abs::zero(), |s| s.thickness / 2.0)
+    };
+
+    // The outer and inner ends of the miter line through a corner.
+    let miter = |corner: Corner| {
+        let (x, dx) = match corner {
+            Corner::TopLeft | Corner::BottomLeft => (Abs::zero(), -half(Side::Left)),
+            Corner::TopRight | Corner::BottomRight => (size.x, half(Side::Right)),
+        };
+        let (y, dy) = match corner {
+            Corner::TopLeft | Corner::TopRight => (Abs::zero(), -half(Side::Top)),
+            Corner::BottomLeft | Corner::BottomRight => (size.y, half(Side::Bottom)),
+        };
+        (Point::new(x + dx, y + dy), Point::new(x - dx, y - dy))
+    };
+
+    // Start at a side whose stroke differs from its predecessor, so that no
+    // run of equally stroked sides wraps around.
+    let mut side = Side::Top;
+    while stroke.get_ref(side) == stroke.get_ref(side.next_ccw()) {
+        side = side.next_cw();
+    }
+
+    let mut res = vec![];
+    let mut run = vec![];
+    for _ in 0..4 {
+        run.push(side);
+        let next = side.next_cw();
+        if stroke.get_ref(side) != stroke.get_ref(next) {
+            if let Some(stroke) = stroke.get_ref(side) {
+                let mut path = Path::new();
+                path.move_to(miter(run[0].start_corner()).0);
+                for side in &run {
+                    path.line_to(miter(side.end_corner()).0);
+                }
+                for side in run.iter().rev() {
+                    path.line_to(miter(side.end_corner()).1);
+                }
+                path.line_to(miter(run[0].start_corner()).1);
+                path.close_path();
+                res.push(Shape {
+                    geometry: Geometry::Path(path),
+                    fill: Some(stroke.paint.clone()),
+                    stroke: None,
+                });
+            }
+            run.clear();
+        }
+        side = next;
+    }
+
+    res
+}
+
 /// Draws one side of the rounded rectangle. Will always draw the left arc. The
 /// right arc will be drawn halfway if and only if there is no connection.
 fn draw_side(
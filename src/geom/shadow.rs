@@ -0,0 +1,70 @@
+use super::*;
+
+/// A soft shadow cast by a shape or block.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct Shadow<T = Length> {
+    /// How far the shadow is moved away from the element.
+    pub offset: Axes<T>,
+    /// The blur radius. A radius of zero yields a hard shadow.
+    pub blur: T,
+    /// The color of the shadow.
+    pub color: Color,
+}
+
+impl Shadow {
+    /// Create a shadow with the given color and a small offset and blur.
+    pub fn new(color: Color) -> Self {
+        Self {
+            offset: Axes::splat(Abs::pt(2.0).into()),
+            blur: Abs::pt(4.0).into(),
+            color,
+        }
+    }
+}
+
+impl Default for Shadow {
+    fn default() -> Self {
+        Self::new(RgbaColor::new(0, 0, 0, 128).into())
+    }
+}
+
+impl<T: Debug> Debug for Shadow<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "(offset: ({:?}, {:?}), blur: {:?}, color: {:?})",
+            self.offset.x, self.offset.y, self.blur, self.color
+        )
+    }
+}
+
+impl Resolve for Shadow {
+    type Output = Shadow<Abs>;
+
+    fn resolve(self, styles: StyleChain) -> Self::Output {
+        Shadow {
+            offset: self.offset.resolve(styles),
+            blur: self.blur.resolve(styles),
+            color: self.color,
+        }
+    }
+}
+
+cast! {
+    type Shadow: "shadow",
+    color: Color => Self::new(color),
+    mut dict: Dict => {
+        let mut shadow = Self::default();
+        if let Some(offset) = dict.take("offset").ok() {
+            shadow.offset = offset.cast()?;
+        }
+        if let Some(blur) = dict.take("blur").ok() {
+            shadow.blur = blur.cast()?;
+        }
+        if let Some(color) = dict.take("color").ok() {
+            shadow.color = color.cast()?;
+        }
+        dict.finish(&["offset", "blur", "color"])?;
+        shadow
+    },
+}
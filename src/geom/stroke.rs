@@ -186,7 +186,7 @@ impl Resolve for PartialStroke {
     }
 }
 
-impl Fold for PartialStroke<Abs> {
+impl<T> Fold for PartialStroke<T> {
     type Output = Self;
 
     fn fold(self, outer: Self::Output) -> Self::Output {
@@ -211,6 +211,10 @@ cast! {
         paint: Smart::Custom(color.into()),
         ..Default::default()
     },
+    gradient: Gradient => Self {
+        paint: Smart::Custom(Paint::Gradient(gradient)),
+        ..Default::default()
+    },
     mut dict: Dict => {
         fn take<T: FromValue>(dict: &mut Dict, key: &str) -> StrResult<Smart<T>> {
             Ok(dict.take(key).ok().map(T::from_value)
@@ -334,7 +338,13 @@ cast! {
     "densely-dash-dotted" => vec![Abs::pt(3.0).into(), Abs::pt(1.0).into(), DashLength::LineWidth, Abs::pt(1.0).into()].into(),
     "loosely-dash-dotted" => vec![Abs::pt(3.0).into(), Abs::pt(4.0).into(), DashLength::LineWidth, Abs::pt(4.0).into()].into(),
 
-    array: Vec<DashLength> => Self { array, phase: Length::zero() },
+    array: Array => match array.as_slice() {
+        [Value::Array(dashes), phase] => Self {
+            array: Value::Array(dashes.clone()).cast()?,
+            phase: phase.clone().cast()?,
+        },
+        _ => Self { array: Value::Array(array).cast()?, phase: Length::zero() },
+    },
     mut dict: Dict => {
         let array: Vec<DashLength> = dict.take("array")?.cast()?;
         let phase = dict.take("phase").ok().map(Value::cast)
@@ -11,6 +11,7 @@ use ecow::{EcoString, EcoVec};
 use image::codecs::gif::GifDecoder;
 use image::codecs::jpeg::JpegDecoder;
 use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
 use image::io::Limits;
 use image::{ImageDecoder, ImageResult};
 use usvg::{TreeParsing, TreeTextToPath};
@@ -124,7 +125,39 @@ impl Image {
         self.0.alt.as_deref()
     }
 
+    /// The EXIF orientation of the image, from 1 to 8, if it has one.
+    ///
+    /// Orientations other than 1 mean that the pixels are stored rotated
+    /// and/or mirrored with respect to how the image should be displayed.
+    pub fn exif_orientation(&self) -> Option<u8> {
+        match self.format() {
+            ImageFormat::Raster(RasterFormat::Jpg) => jpeg_orientation(self.data()),
+            _ => None,
+        }
+    }
+
+    /// The same image without a describing text.
+    ///
+    /// Images that only differ in their description look the same, so
+    /// exporters can use this to store them only once.
+    pub fn without_alt(&self) -> Self {
+        if self.0.alt.is_none() {
+            return self.clone();
+        }
+
+        Self(Arc::new(Prehashed::new(Repr {
+            data: self.0.data.clone(),
+            format: self.0.format,
+            size: self.0.size,
+            loader: self.0.loader.clone(),
+            alt: None,
+        })))
+    }
+
     /// The decoded version of the image.
+    ///
+    /// Decoding is memoized by the image's data, so all uses of the same
+    /// image share one decoded buffer.
     pub fn decoded(&self) -> Arc<DecodedImage> {
         match self.format() {
             ImageFormat::Raster(format) => decode_raster(self.data(), format),
@@ -156,6 +189,27 @@ pub enum ImageFormat {
     Vector(VectorFormat),
 }
 
+impl ImageFormat {
+    /// Detect the format of an image from its magic bytes.
+    ///
+    /// SVGs have no magic bytes and are thus not detected.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        let raster = if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            RasterFormat::Png
+        } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            RasterFormat::Jpg
+        } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+            RasterFormat::Gif
+        } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP".as_slice())
+        {
+            RasterFormat::Webp
+        } else {
+            return None;
+        };
+        Some(Self::Raster(raster))
+    }
+}
+
 /// A raster graphics format.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum RasterFormat {
@@ -165,6 +219,8 @@ pub enum RasterFormat {
     Jpg,
     /// Raster format that is typically used for short animated clips.
     Gif,
+    /// Raster format of the web with lossy and lossless compression.
+    Webp,
 }
 
 /// A vector graphics format.
@@ -180,6 +236,7 @@ impl From<RasterFormat> for image::ImageFormat {
             RasterFormat::Png => image::ImageFormat::Png,
             RasterFormat::Jpg => image::ImageFormat::Jpeg,
             RasterFormat::Gif => image::ImageFormat::Gif,
+            RasterFormat::Webp => image::ImageFormat::WebP,
         }
     }
 }
@@ -250,12 +307,78 @@ fn decode_raster(data: &Buffer, format: RasterFormat) -> StrResult<Arc<DecodedIm
         RasterFormat::Jpg => decode_with(JpegDecoder::new(cursor)),
         RasterFormat::Png => decode_with(PngDecoder::new(cursor)),
         RasterFormat::Gif => decode_with(GifDecoder::new(cursor)),
+        RasterFormat::Webp => {
+            if is_animated_webp(data) {
+                tracing::warn!("Only the first frame of animated WebP images is shown");
+            }
+            decode_with(WebPDecoder::new(cursor))
+        }
     }
     .map_err(format_image_error)?;
 
     Ok(Arc::new(DecodedImage::Raster(dynamic, icc, format)))
 }
 
+/// Read the EXIF orientation from the APP1 segment of a JPEG.
+fn jpeg_orientation(data: &[u8]) -> Option<u8> {
+    let u16_be = |i: usize| data.get(i..i + 2).map(|b| u16::from_be_bytes([b[0], b[1]]));
+
+    // Walk the segments until the start of the scan.
+    let mut i = 2;
+    while data.get(i) == Some(&0xFF) {
+        let marker = *data.get(i + 1)?;
+        if marker == 0xDA {
+            break;
+        }
+
+        let len = u16_be(i + 2)? as usize;
+        let payload = data.get(i + 4..i + 2 + len)?;
+        if marker == 0xE1 && payload.starts_with(b"Exif\0\0") {
+            return tiff_orientation(&payload[6..]);
+        }
+
+        i += 2 + len;
+    }
+
+    None
+}
+
+/// Read the orientation tag from the first IFD of TIFF-structured EXIF data.
+fn tiff_orientation(tiff: &[u8]) -> Option<u8> {
+    let big = match tiff.get(..2)? {
+        b"MM" => true,
+        b"II" => false,
+        _ => return None,
+    };
+
+    let u16_at = |i: usize| {
+        let b: [u8; 2] = tiff.get(i..i + 2)?.try_into().ok()?;
+        Some(if big { u16::from_be_bytes(b) } else { u16::from_le_bytes(b) })
+    };
+    let u32_at = |i: usize| {
+        let b: [u8; 4] = tiff.get(i..i + 4)?.try_into().ok()?;
+        Some(if big { u32::from_be_bytes(b) } else { u32::from_le_bytes(b) })
+    };
+
+    // Each IFD entry consists of a tag, a type, a count and the value.
+    let ifd = u32_at(4)? as usize;
+    let count = u16_at(ifd)? as usize;
+    (0..count).find_map(|k| {
+        let entry = ifd + 2 + 12 * k;
+        if u16_at(entry)? != 0x0112 {
+            return None;
+        }
+        let value = u16_at(entry + 8)?;
+        (1..=8).contains(&value).then_some(value as u8)
+    })
+}
+
+/// Whether WebP data has the animation flag of the extended file format set.
+fn is_animated_webp(data: &[u8]) -> bool {
+    data.get(12..16) == Some(b"VP8X".as_slice())
+        && data.get(20).map_or(false, |flags| flags & 0x02 != 0)
+}
+
 /// Decode an SVG image.
 #[comemo::memoize]
 fn decode_svg(
@@ -411,7 +534,7 @@ impl SvgFontLoader for WorldLoader<'_> {
 }
 
 /// Loads fonts for an SVG from a prepared list.
-#[derive(Default, Hash)]
+#[derive(Default, Clone, Hash)]
 struct PreparedLoader {
     families: BTreeMap<EcoString, EcoVec<Font>>,
     fallback_family_cased: Option<String>,
@@ -231,6 +231,20 @@ impl Introspector {
         self.elems.get(location).map(|(elem, _)| elem)
     }
 
+    /// Whether the selector can only be resolved by a full query or was
+    /// already queried, such that the cached result can be reused.
+    ///
+    /// Element selectors are also resolved by a full query. They are
+    /// typically queried repeatedly, for example for the current section in
+    /// each page header, so that a cached list with binary search beats
+    /// scanning all elements each time.
+    fn needs_list(&self, selector: &Selector) -> bool {
+        matches!(
+            selector,
+            Selector::Elem(..) | Selector::Before { .. } | Selector::After { .. }
+        ) || self.queries.borrow().contains_key(&crate::util::hash128(selector))
+    }
+
     /// Get the index of this element among all.
     fn index(&self, elem: &Content) -> usize {
         self.elems
@@ -309,10 +323,70 @@ impl Introspector {
     pub fn query_first(&self, selector: &Selector) -> Option<Prehashed<Content>> {
         match selector {
             Selector::Location(location) => self.get(location).cloned(),
-            _ => self.query(selector).first().cloned(),
+            _ if self.needs_list(selector) => self.query(selector).first().cloned(),
+            _ => self.all().find(|elem| selector.matches(elem)).cloned(),
         }
     }
 
+    /// Query for the last element that matches the selector.
+    pub fn query_last(&self, selector: &Selector) -> Option<Prehashed<Content>> {
+        match selector {
+            Selector::Location(location) => self.get(location).cloned(),
+            _ if self.needs_list(selector) => self.query(selector).last().cloned(),
+            _ => self
+                .elems
+                .values()
+                .rev()
+                .map(|(elem, _)| elem)
+                .find(|elem| selector.matches(elem))
+                .cloned(),
+        }
+    }
+
+    /// Query for the last element that matches the selector and comes
+    /// strictly before the location in document order.
+    pub fn query_before(
+        &self,
+        selector: &Selector,
+        location: Location,
+    ) -> Option<Prehashed<Content>> {
+        let end = self.elems.get_index_of(&location)?;
+        if self.needs_list(selector) {
+            let list = self.query(selector);
+            let split = list.partition_point(|elem| self.index(elem) < end);
+            return list[..split].last().cloned();
+        }
+
+        (0..end)
+            .rev()
+            .filter_map(|i| self.elems.get_index(i))
+            .map(|(_, (elem, _))| elem)
+            .find(|elem| selector.matches(elem))
+            .cloned()
+    }
+
+    /// Query for the first element that matches the selector and comes
+    /// strictly after the location in document order.
+    pub fn query_after(
+        &self,
+        selector: &Selector,
+        location: Location,
+    ) -> Option<Prehashed<Content>> {
+        let start = self.elems.get_index_of(&location)?;
+        if self.needs_list(selector) {
+            let list = self.query(selector);
+            let split = list.partition_point(|elem| self.index(elem) <= start);
+            return list[split..].first().cloned();
+        }
+
+        self.elems
+            .values()
+            .skip(start + 1)
+            .map(|(elem, _)| elem)
+            .find(|elem| selector.matches(elem))
+            .cloned()
+    }
+
     /// Query for a unique element with the label.
     pub fn query_label(&self, label: &Label) -> StrResult<Prehashed<Content>> {
         let mut found = None;
@@ -191,3 +191,52 @@
 ---
 // Error: 26-36 failed to format datetime in the requested format
 #datetime.today().display("[hour]")
+
+---
+// Test datetime arithmetic and comparison.
+#let date = datetime(year: 2023, month: 3, day: 10)
+#test((date - duration(days: 30)).display(), "2023-02-08")
+#test((date + duration(weeks: 1)).display(), "2023-03-17")
+#test((duration(days: 1) + date).display(), "2023-03-11")
+#test((date - datetime(year: 2023, month: 1, day: 1)).days(), 68)
+#test(date < datetime(year: 2023, month: 3, day: 11), true)
+#test(date == datetime(year: 2023, month: 3, day: 10), true)
+
+#let time = datetime(hour: 23, minute: 30, second: 0)
+#test((time + duration(hours: 1)).display(), "00:30:00")
+#test((time - datetime(hour: 22, minute: 0, second: 0)).minutes(), 90)
+
+#let full = datetime(year: 2023, month: 12, day: 31, hour: 23, minute: 0, second: 0)
+#test((full + duration(hours: 2)).display(), "2024-01-01 01:00:00")
+
+// Test durations.
+#test(duration(hours: 36).days(), 1.5)
+#test(duration(minutes: 90).hours(), 1.5)
+#test(duration(days: 14).weeks(), 2.0)
+#test((-duration(seconds: 30)).seconds(), -30.0)
+#test(duration(hours: 1) - duration(minutes: 30), duration(minutes: 30))
+#test(duration(days: 1) > duration(hours: 23), true)
+#test(repr(duration(days: 8, seconds: 5)), "duration(weeks: 1, days: 1, seconds: 5)")
+#test(repr(duration()), "duration(seconds: 0)")
+
+// Test localized names.
+#test(date.display("[weekday], [day]. [month repr:long]", lang: "de"), "Freitag, 10. März")
+#test(date.display("[month repr:short] [day]", lang: "fr"), "mars 10")
+#test(datetime(year: 2023, month: 6, day: 1).display("[month repr:short]", lang: "fr"), "juin")
+#test(datetime(year: 2023, month: 7, day: 1).display("[month repr:short]", lang: "fr"), "juil.")
+#test(date.display("[weekday repr:short]", lang: "de"), "Fr.")
+#test(date.display("[weekday repr:short] [month repr:short]"), "Fri Mar")
+#test(date.display("[month repr:long]", lang: "zh"), "March")
+#test(date.display("[month repr:long]"), "March")
+
+---
+// Error: 3-62 cannot shift a date by a fraction of a day
+#(datetime(year: 2023, month: 1, day: 1) + duration(hours: 1))
+
+---
+// Error: 3-83 cannot compare datetimes of different kinds
+#(datetime(year: 2023, month: 1, day: 1) < datetime(hour: 1, minute: 0, second: 0))
+
+---
+// Error: 3-83 cannot subtract datetimes of different kinds
+#(datetime(year: 2023, month: 1, day: 1) - datetime(hour: 1, minute: 0, second: 0))
@@ -46,3 +46,12 @@
 ---
 // Error: 8-20 cannot add two vertical alignments
 #align(top + bottom, [A])
+
+---
+// Test centering a title on its page.
+// Ref: false
+#set page(height: 120pt)
+#block(height: 100%, width: 100%, align(center + horizon)[
+  #text(16pt)[*Title*] \
+  An Author
+])
@@ -0,0 +1,29 @@
+// Test keeping blocks together and with their successors.
+
+---
+// Ref: false
+#set page(height: 60pt)
+#set block(spacing: 0pt)
+#rect(height: 30pt)
+#block(keep-together: true)[
+  #locate(loc => test(loc.page(), 2))
+  A \ B
+]
+
+---
+// Ref: false
+#set page(height: 60pt)
+#set block(spacing: 0pt)
+#rect(height: 30pt)
+#block(keep-with-next: true)[
+  #locate(loc => test(loc.page(), 2))
+  Note
+]
+#block(breakable: false)[A \ B]
+
+---
+// Test that blocks larger than a page are still broken.
+// Ref: false
+#set page(height: 60pt)
+#lorem(5)
+#block(keep-together: true, lorem(40))
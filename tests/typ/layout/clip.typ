@@ -40,3 +40,25 @@ First!
   But, soft! what light through yonder window breaks? It is the east, and Juliet
   is the sun.
 ]
+
+---
+// Ref: false
+// Test clipping to shapes.
+#box(width: 20pt, height: 20pt, clip: circle(radius: 10pt), rect(width: 100%, height: 100%, fill: red))
+#box(clip: polygon((10pt, 0pt), (20pt, 20pt), (0pt, 20pt)), rect(width: 20pt, height: 20pt, fill: red))
+#block(width: 30pt, clip: path((0pt, 0pt), (30pt, 0pt), (15pt, 30pt), closed: true))[
+  #lorem(10)
+]
+#box(clip: rect(radius: 5pt, width: 20pt, height: 20pt), square(size: 30pt, fill: red))
+
+// Clipping to a shape doesn't change the container's size.
+#style(styles => {
+  let body = square(size: 30pt)
+  let plain = measure(box(body), styles)
+  test(measure(box(clip: circle(radius: 10pt), body), styles), plain)
+  test(measure(block(clip: polygon((0pt, 0pt), (5pt, 0pt), (0pt, 5pt)), body), styles), plain)
+})
+
+---
+// Error: 2-37 clip content must contain a shape
+#box(clip: "yes", square(fill: red))
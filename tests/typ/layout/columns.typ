@@ -103,3 +103,23 @@ This is a normal page. Very normal.
 // Test a page with zero columns.
 // Error: 49-50 number must be positive
 #set page(height: auto, width: 7.05cm, columns: 0)
+
+---
+// Test balancing the last region.
+// Ref: false
+#set page(width: 120pt, height: 200pt, margin: 10pt)
+#set columns(balance: true)
+#columns(2)[
+  #lorem(20)
+  #locate(loc => test(loc.position().x > 62pt, true))
+]
+
+---
+// Test full-width footnotes in page-level columns. The fractional spacing
+// moves the end of the note to the right edge of the page, beyond the first
+// column.
+// Ref: false
+#set page(height: 120pt, columns: 2)
+#lorem(8) #footnote[Note #h(1fr) #locate(loc => test(loc.position().x > 400pt, true))]
+#colbreak()
+#lorem(4)
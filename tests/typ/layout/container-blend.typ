@@ -0,0 +1,29 @@
+// Test opacity and blend modes of containers.
+
+---
+// Ref: false
+#box(opacity: 50%)[
+  #circle(fill: red)
+  #place(top + left, dx: 15pt, circle(fill: red))
+]
+#rect(fill: yellow)[
+  #box(blend-mode: "multiply", rect(fill: aqua))
+  #box(blend-mode: "difference", opacity: 80%, rect(fill: gradient(red, blue)))
+]
+#block(opacity: 0%, fill: red)[Invisible]
+#block(opacity: 200%, blend-mode: "screen")[Fully opaque]
+
+// Compositing doesn't change the layout.
+#test(box(blend-mode: "multiply").blend-mode, "multiply")
+#test(block(opacity: 50%).opacity, 50%)
+#style(styles => {
+  let body = rect(fill: aqua)
+  test(
+    measure(box(opacity: 50%, blend-mode: "difference", body), styles),
+    measure(box(body), styles),
+  )
+})
+
+---
+// Error: 18-23 expected "normal", "multiply", "screen", "overlay", "darken", "lighten", "color-dodge", "color-burn", "hard-light", "soft-light", "difference", or "exclusion"
+#box(blend-mode: "hue")[A]
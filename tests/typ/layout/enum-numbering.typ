@@ -46,6 +46,24 @@
 + E
 + F
 
+---
+// Test continuing enumerations.
+// Ref: false
++ One
++ Two
+  + Nested
+  + Nested
+
+An interruption.
+
+#set enum(start: auto)
++ Three
+  + Nested again
+4. Four
+
+#enum(start: 1)[Restart]
+#enum[Two again]
+
 ---
 // Error: 22-24 invalid numbering pattern
 #set enum(numbering: "")
@@ -28,3 +28,64 @@ This is the start and it goes on.
 // All three lines go to the next page.
 #set text(olive)
 #lorem(10)
+
+---
+// Test that the first two lines of a paragraph stay together.
+// Ref: false
+#set page(width: 100pt, height: 42pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 5pt)
+#set block(spacing: 5pt)
+A \ B
+
+#locate(loc => test(loc.page(), 2))
+C \ D \ E \ F
+
+---
+// Test that a single line may be left behind without orphan control.
+// Ref: false
+#set page(width: 100pt, height: 42pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 5pt, orphans: 1)
+#set block(spacing: 5pt)
+A \ B
+
+#locate(loc => test(loc.page(), 1))
+C \ #locate(loc => test(loc.page(), 2))D \ E \ F
+
+---
+// Test that the last two lines of a paragraph stay together.
+// Ref: false
+#set page(width: 100pt, height: 42pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 5pt)
+C \ D \ #locate(loc => test(loc.page(), 2))E \ F
+
+---
+// Test that a single line may be moved on without widow control.
+// Ref: false
+#set page(width: 100pt, height: 42pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 5pt, widows: 1)
+C \ D \ #locate(loc => test(loc.page(), 1))E \ #locate(loc => test(loc.page(), 2))F
+
+---
+// Test configurable orphans.
+// Ref: false
+#set page(width: 100pt, height: 42pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 5pt, orphans: 3)
+#set block(spacing: 5pt)
+A
+
+#locate(loc => test(loc.page(), 2))
+C \ D \ E \ F \ G
+
+---
+// Test that a paragraph larger than a page is still broken.
+// Ref: false
+#set page(width: 100pt, height: 27pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 5pt, orphans: 10, widows: 10)
+#locate(loc => test(loc.page(), 1))
+C \ D \ #locate(loc => test(loc.page(), 2))E \ F \ G
@@ -10,3 +10,37 @@
   [Hello there, my friends! Hi!],
   [Hello there, my friends! Hi! What is going on right now?],
 )
+
+---
+// Test columns that fit their content up to a maximum.
+// Ref: false
+#set page(width: 200pt)
+#let t = table(
+  columns: (auto, (max: 80pt)),
+  [Source], link("https://example.com/a/very/long/path/to/the/dataset.csv"),
+  [Short], [Fits],
+)
+#t
+
+// The link column is capped, the first column includes its inset.
+#style(styles => {
+  let width(body) = measure(body, styles).width
+  test(width(t) <= width[Source] + 10pt + 80pt + 0.01pt, true)
+})
+
+---
+// Test that columns with a maximum wrap their content.
+// Ref: false
+#set table(inset: 0pt, stroke: none)
+#style(styles => {
+  let width(body) = measure(body, styles).width
+  test(width(grid(columns: ((max: 50pt),), lorem(10))) <= 50pt, true)
+  test(width(grid(columns: ((max: 50pt),), [Hi])), width[Hi])
+  test(width(grid(columns: ((max: 20%),), [Hi])), width[Hi])
+  test(width(box(width: (max: 40pt), lorem(10))) <= 40pt, true)
+  test(width(box(width: (max: 40pt))[Hi]), width[Hi])
+})
+
+---
+// Error: 16-39 unexpected key "min", valid keys are "max"
+#grid(columns: ((max: 1pt, min: 2pt),))
@@ -0,0 +1,152 @@
+// Test cells spanning multiple columns and rows.
+
+---
+// Test a 2x2 merge in the middle of a larger grid. The merged cell spans two
+// rows and the gutter between them, so the grid is as tall as two text rows,
+// the merged cell and the two remaining gutters.
+// Ref: false
+#let g = grid(
+  columns: (auto, 1fr, 1fr, auto),
+  gutter: 3pt,
+  [A], [B], [C], [D],
+  [E], grid.cell(colspan: 2, rowspan: 2, rect(width: 100%, height: 40pt)), [F],
+  [G], [H],
+  [I], [J], [K], [L],
+)
+#g
+
+#style(styles => {
+  let height(body) = measure(block(width: 100pt, body), styles).height
+  test(calc.abs((height(g) - 2 * height[A] - 46pt) / 1pt) < 0.01, true)
+})
+
+---
+// Test that a spanning cell grows the auto columns and rows it spans.
+// Ref: false
+#let t = table(
+  columns: 3,
+  fill: (x, y) => if x == 0 { luma(230) },
+  [A], table.cell(colspan: 2)[A very long cell spanning two columns],
+  table.cell(rowspan: 3)[B \ C \ D \ E \ F], [1], [2],
+  [3], [4],
+  [5], [6],
+)
+#t
+
+#style(styles => {
+  let size = measure(t, styles)
+  let long = measure([A very long cell spanning two columns], styles)
+  let tall = measure([B \ C \ D \ E \ F], styles)
+  test(size.width + 0.01pt >= long.width + 10pt, true)
+  test(size.height > tall.height + 20pt, true)
+})
+
+---
+// Test that a column span is cut off at the end of the row and at cells
+// spanning from above.
+// Ref: false
+#let t = table(
+  columns: 3,
+  table.cell(rowspan: 2)[A], [B], [C],
+  table.cell(colspan: 5)[D], [E],
+  [F], [G], [H],
+)
+#t
+
+#style(styles => test(
+  measure(t, styles),
+  measure(table(
+    columns: 3,
+    table.cell(rowspan: 2)[A], [B], [C],
+    table.cell(colspan: 2)[D], [E],
+    [F], [G], [H],
+  ), styles),
+))
+
+---
+// Test a cell spanning rows across a page break.
+// Ref: false
+#set page(width: 100pt, height: 80pt)
+#table(
+  columns: 2,
+  table.cell(rowspan: 4)[#lorem(20) #locate(loc => test(loc.page() > 1, true))], [1],
+  [2],
+  [3],
+  [4],
+)
+
+---
+// Test that a spanning cell is placed in the order of its first row.
+// Ref: false
+#let c = counter("cells")
+#grid(
+  columns: 2,
+  c.step(), grid.cell(rowspan: 2, locate(loc => {
+    c.step()
+    test(c.at(loc), (1,))
+  })),
+  locate(loc => {
+    c.step()
+    test(c.at(loc), (2,))
+  }),
+)
+
+---
+// Test the size of spanning cells.
+// Ref: false
+#let r(w, h) = rect(width: w, height: h, inset: 0pt)
+#style(styles => {
+  let size = measure(grid(
+    columns: 2,
+    grid.cell(rowspan: 2, r(10pt, 30pt)), r(10pt, 5pt),
+    r(10pt, 5pt),
+  ), styles)
+  test(size.height, 30pt)
+
+  let size = measure(grid(
+    columns: 2,
+    grid.cell(colspan: 2, r(40pt, 5pt)),
+    r(10pt, 5pt), r(10pt, 5pt),
+  ), styles)
+  test(size.width, 40pt)
+})
+
+---
+// Test a cell spanning rows in a repeated header. Each row is 10pt high, so
+// the first page fits the header and six body rows and each further page fits
+// the repeated header and six more.
+// Ref: false
+#set page(height: 80pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#table(
+  columns: 2,
+  inset: 0pt,
+  header: 2,
+  table.cell(rowspan: 2)[*Name* #locate(loc => test(counter(page).final(loc), (3,)))],
+  [*A*],
+  [*B*],
+  ..range(28).map(str),
+)
+
+---
+// Test a cell spanning a fractional row. The fractional row takes the 60pt
+// left by the two 10pt auto rows.
+// Ref: false
+#set page(height: 80pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#grid(
+  columns: (20pt, 20pt),
+  rows: (auto, 1fr, auto),
+  grid.cell(rowspan: 2, layout(size => {
+    test(calc.abs((size.height - 70pt) / 1pt) < 0.01, true)
+    rect(width: 100%, height: 100%)
+  })),
+  [A],
+  [B],
+  [C], [D],
+)
+
+---
+// The cell function is scoped to grids and tables.
+// Error: 2-6 unknown variable: cell
+#cell(colspan: 2)[A]
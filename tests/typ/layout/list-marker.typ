@@ -23,6 +23,23 @@
     - E
 - F
 
+---
+// Test cycling through markers with a function.
+// Ref: false
+#set list(marker: depth => ([•], [‣], [--]).at(calc.rem(depth, 3)))
+- A
+  - B
+    - C
+      - D
+
+---
+// Test that markers are mirrored in right-to-left text.
+// Ref: false
+#set text(dir: rtl)
+#set list(marker: depth => if depth == 0 [→] else [←])
+- A
+  - B
+
 ---
 // Test that bare hyphen doesn't lead to cycles and crashes.
 #set list(marker: [-])
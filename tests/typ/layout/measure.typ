@@ -0,0 +1,35 @@
+// Test measuring content.
+
+---
+// Ref: false
+#style(styles => {
+  let size = measure(rect(width: 20pt, height: 10pt), styles)
+  test(size, (width: 20pt, height: 10pt))
+})
+
+---
+// Test that the measurement respects the active styles.
+// Ref: false
+#style(styles => {
+  let small = measure([Hello], styles).width
+  set text(20pt)
+  style(styles => test(measure([Hello], styles).width > small, true))
+})
+
+---
+// Test measuring with a limited width.
+// Ref: false
+#style(styles => {
+  let body = lorem(20)
+  let free = measure(body, styles)
+  let narrow = measure(body, styles, width: 100pt)
+  test(narrow.width <= 100pt, true)
+  test(narrow.height > free.height, true)
+  test(measure(body, styles, width: auto), free)
+  let sized = measure(block(height: 100%), styles, height: 40pt)
+  test(sized.height, 40pt)
+})
+
+---
+// Error: 45-48 expected length or auto, found ratio
+#style(styles => measure([], styles, width: 50%))
@@ -0,0 +1,39 @@
+// Test the baseline grid.
+
+---
+// Test that the baselines of all lines sit on the grid. With a margin of one
+// step, the grid's lines are at multiples of the step from the page's top.
+// Ref: false
+#set page(
+  width: 180pt,
+  height: 160pt,
+  margin: 12pt,
+  columns: 2,
+  baseline-grid: (step: 12pt, debug: true),
+)
+#set par(leading: 5pt)
+#let on-grid = locate(loc => {
+  let rem = calc.rem(loc.position().y / 1pt, 12)
+  test(rem < 0.01 or rem > 11.99, true)
+})
+
+= Grid
+#lorem(12) #on-grid
+
+#text(8pt, lorem(12)) #on-grid
+
+#block(fill: aqua, inset: 0pt)[Block]
+#lorem(8) #on-grid
+
+---
+// Test that blocks occupy a whole multiple of the grid's step.
+// Ref: false
+#set page(baseline-grid: 12pt)
+#style(styles => {
+  let size = measure(block[A \ B], styles)
+  test(calc.abs(size.height / 1pt - 24) < 0.01, true)
+})
+
+---
+// Error: 26-47 unexpected key "size", valid keys are "step" and "debug"
+#set page(baseline-grid: (step: 12pt, size: 1))
@@ -18,3 +18,30 @@
 
 // Ensure that specific margins override general margins.
 #[#set page(margin: (rest: 0pt, left: 20pt)); Overridden]
+
+---
+// Test inside and outside margins.
+// Ref: false
+#set page(width: 100pt, height: 40pt, margin: (inside: 30pt, outside: 10pt, y: 10pt))
+#locate(loc => test(loc.position().x, 30pt))
+#pagebreak()
+#locate(loc => test(loc.position().x, 10pt))
+#pagebreak()
+
+// The sides are determined by the physical page number.
+#counter(page).update(1)
+#locate(loc => test(loc.position().x, 30pt))
+
+---
+// Test a right-bound document.
+// Ref: false
+#set page(binding: right, margin: (inside: 30pt, outside: 10pt))
+#locate(loc => test(loc.position().x, 10pt))
+
+---
+// Error: 19-44 `inside` and `outside` are mutually exclusive with `left` and `right`
+#set page(margin: (left: 1cm, outside: 2cm))
+
+---
+// Error: 20-23 must be `left` or `right`
+#set page(binding: top)
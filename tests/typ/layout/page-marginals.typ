@@ -22,3 +22,35 @@ answer it.
 
 #set page(header: none, height: auto, margin: (top: 15pt, bottom: 25pt))
 The END.
+
+---
+// Test a distinct layout for chapter openers.
+// Ref: false
+#set page(height: 100pt, margin: (top: 20pt, rest: 10pt), opener: (top: 50pt, header: none))
+#show heading.where(level: 1): it => pagebreak(weak: true) + it
+
+= One <one>
+#lorem(5)
+== Section <section>
+= Two <two>
+
+#locate(loc => {
+  let y(label) = query(label, loc).first().location().position().y
+  test(y(<one>), 50pt)
+  test(y(<two>), 50pt)
+  test(y(<section>) > 50pt, true)
+})
+
+---
+// Error: 19-30 unexpected key "left", valid keys are "header", "footer", and "top"
+#set page(opener: (left: 1pt))
+
+---
+// Test overlays that depend on the page's dimensions.
+// Ref: false
+#set page(width: 120pt, height: 80pt, background: page => {
+  test(page, (width: 120pt, height: 80pt))
+  rotate(-45deg, text(page.width / 4, fill: luma(220))[*DRAFT*])
+})
+#set page(foreground: page => place(bottom + right, square(size: page.height / 8)))
+Draft.
@@ -40,3 +40,11 @@
   h(1em)
   place(left, rect(width: 80pt, stroke: blue))
 })
+
+---
+// Test that bleed and crop marks enlarge the page.
+// Ref: false
+#set page(width: 100pt, height: 50pt, margin: 10pt, bleed: 5pt, fill: aqua)
+#locate(loc => test(loc.position().x, 15pt))
+#set page(marks: true)
+#locate(loc => test(loc.position().x, 27pt))
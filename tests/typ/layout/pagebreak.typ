@@ -43,3 +43,26 @@ First
 #page[Second]
 #pagebreak(weak: true)
 #page[Third]
+
+---
+// Test clearing to odd pages and restarting the page numbering.
+// Ref: false
+#set page(height: 60pt, numbering: "i")
+= Preface <preface>
+#pagebreak(to: "odd", numbering: "1")
+= Intro <intro>
+#pagebreak()
+= Body <body>
+#pagebreak(to: "even")
+= End <end>
+
+#locate(loc => {
+  let at(label) = {
+    let loc = query(label, loc).first().location()
+    (loc.page(), counter(page).at(loc).first(), loc.page-numbering())
+  }
+  test(at(<preface>), (1, 1, "i"))
+  test(at(<intro>), (3, 1, "1"))
+  test(at(<body>), (4, 2, "1"))
+  test(at(<end>), (6, 4, "1"))
+})
@@ -49,3 +49,14 @@ Welcome \ here. Does this work well?
 #set text(dir: rtl)
 لآن وقد أظلم الليل وبدأت النجوم
 تنضخ وجه الطبيعة التي أعْيَتْ من طول ما انبعثت في النهار
+
+---
+// Test indenting all paragraphs.
+// Ref: false
+#set par(first-line-indent: (amount: 12pt, all: true))
+= Heading
+The first paragraph is indented, too.
+
+---
+// Error: 29-53 unexpected key "al", valid keys are "amount" and "all"
+#set par(first-line-indent: (amount: 12pt, al: true))
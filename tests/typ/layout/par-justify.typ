@@ -30,4 +30,22 @@ D E F #linebreak(justify: true)
 // Test that the last line can be shrunk
 #set page(width: 155pt)
 #set par(justify: true)
-This text can be fitted in one line.
\ No newline at end of file
+This text can be fitted in one line.
+---
+// Test limiting the stretch of spaces and scaling glyphs.
+// Ref: false
+#set page(width: 100pt)
+#set par(justify: true)
+#set text(hyphenate: false)
+#let body = [A narrow column tends to produce rivers of white space.]
+#body
+
+#set par(word-stretch: 20%, word-shrink: 10%)
+#body
+
+#set par(glyph-scale: 3%)
+#body
+
+---
+// Error: 24-27 expected ratio, found length
+#set par(word-stretch: 1pt)
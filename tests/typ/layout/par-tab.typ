@@ -0,0 +1,53 @@
+// Test tab stops.
+
+---
+// Test a table-like layout with tab stops. The text after the first tab starts
+// at the first stop.
+// Ref: false
+#set page(width: 180pt, margin: 10pt)
+#set par(tab-stops: (
+  60pt,
+  (pos: 110pt, align: "center"),
+  (pos: 160pt, align: "decimal"),
+))
+#let at-stop = locate(loc => {
+  test(calc.abs((loc.position().x - 70pt) / 1pt) < 0.01, true)
+})
+
+Coffee #tab()#at-stop small #tab() 2.50 \
+Cake #tab()#at-stop large #tab() 12.75 \
+Water #tab()#at-stop free #tab() 0
+
+---
+// Test that text is aligned at the stops.
+// Ref: false
+#let near(a, b) = calc.abs((a - b) / 1pt) < 0.01
+#style(styles => {
+  let width(stop, body) = measure({
+    set par(tab-stops: (stop,))
+    body
+  }, styles).width
+
+  let b = measure([B], styles).width
+  let frac = measure([.25], styles).width
+  test(near(width(50pt, [A#tab()B]), 50pt + b), true)
+  test(near(width((pos: 50pt, align: "right"), [A#tab()B]), 50pt), true)
+  test(near(width((pos: 50pt, align: "center"), [A#tab()B]), 50pt + b / 2), true)
+  test(near(width((pos: 50pt, align: "decimal"), [A#tab()1.25]), 50pt + frac), true)
+  test(near(width((pos: 50pt, align: "decimal"), [A#tab()B]), 50pt), true)
+  test(near(width(
+    (pos: 50pt, align: "decimal", separator: ","),
+    [A#tab()1,25],
+  ), 50pt + measure([,25], styles).width), true)
+})
+
+---
+// Test that a tab without a following stop produces no space.
+// Ref: false
+#style(styles => {
+  test(measure([A#tab()B], styles).width, measure([AB], styles).width)
+})
+
+---
+// Error: 21-25 expected length or dictionary, found integer
+#set par(tab-stops: (1,))
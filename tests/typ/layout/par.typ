@@ -34,3 +34,17 @@ fn main() {}
 - List
 
 Paragraph
+
+---
+// Test that lines extend over the font's ascent and descent in normal
+// leading mode.
+// Ref: false
+#style(styles => {
+  let fixed = measure(par(leading: 0pt)[A \ B], styles)
+  let normal = measure(par(leading: 0pt, leading-mode: "normal")[A \ B], styles)
+  test(fixed.height < normal.height, true)
+})
+
+---
+// Error: 24-31 expected "fixed" or "normal"
+#set par(leading-mode: "tight")
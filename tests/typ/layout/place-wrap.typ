@@ -0,0 +1,46 @@
+// Test text wrapping around placed elements.
+
+---
+// Test wrapping on both sides. Lines next to the left rectangle start to the
+// right of it and its clearance, lines next to the right one end before it.
+// Ref: false
+#set page(width: 160pt, height: 200pt, margin: 10pt)
+#place(left, float: true, wrap: true, clearance: 6pt, rect(width: 40pt, height: 45pt))
+#locate(loc => test(calc.abs((loc.position().x - 56pt) / 1pt) < 0.01, true))
+#lorem(30)
+
+#place(end, float: true, wrap: true, rect(width: 30pt, height: 30pt))
+Short #h(1fr) #locate(loc => test(loc.position().x <= 120pt, true)) \
+#lorem(20)
+
+---
+// Test that lines next to a wrapped element are shortened.
+// Ref: false
+#let body = lorem(20)
+#let wrapped = [
+  #place(left, float: true, wrap: true, rect(width: 50pt, height: 20pt))
+  #body
+]
+#style(styles => {
+  let plain = measure(block(width: 120pt, body), styles)
+  let narrow = measure(block(width: 120pt, wrapped), styles)
+  test(narrow.height > plain.height, true)
+  test(narrow.width <= 120pt, true)
+})
+
+---
+// Test that blocks are moved below wrapped elements.
+// Ref: false
+#set page(height: 200pt, margin: 10pt)
+#place(right, float: true, wrap: true, rect(width: 20pt, height: 60pt))
+A
+= Heading <heading>
+
+#locate(loc => {
+  let pos = query(<heading>, loc).first().location().position()
+  test(pos.y >= 70pt, true)
+})
+
+---
+// Error: 2-45 wrapped placement must be `left` or `right` without vertical alignment
+#place(top, float: true, wrap: true, rect())
@@ -33,3 +33,22 @@ First
 #place(bottom + right)[Placed]
 
 Second
+
+---
+// Test page-scoped placement and floating elements.
+// Ref: false
+#set page(height: 100pt, margin: 20pt)
+#place(scope: "page", top + left, dx: 5pt, dy: 5pt)[= Note <note>]
+#place(top, float: true, clearance: 10pt, rect(height: 15pt))
+= Body <body>
+
+#locate(loc => {
+  let pos(label) = query(label, loc).first().location().position()
+  test(pos(<note>).x, 5pt)
+  test(pos(<note>).y, 5pt)
+  test(pos(<body>).y, 45pt)
+})
+
+---
+// Error: 2-23 floating placement must be `top` or `bottom`
+#place(float: true)[A]
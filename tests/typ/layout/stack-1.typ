@@ -50,3 +50,17 @@
   align(left, [B]),
   [C],
 )
+
+---
+// Test distributing the leftover space.
+// Ref: false
+#set page(width: 100pt, height: 100pt)
+#let items = range(3).map(_ => rect(width: 20pt, height: 10pt))
+#stack(dir: ltr, distribute: "space-between", ..items)
+#stack(dir: ltr, distribute: "space-around", ..items)
+#stack(dir: ltr, distribute: "space-evenly", ..items)
+#block(height: 40pt, stack(distribute: "space-between", ..items))
+
+---
+// Error: 20-26 expected "packed", "space-between", "space-around", or "space-evenly"
+#stack(distribute: "wide")
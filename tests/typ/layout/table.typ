@@ -34,5 +34,262 @@
 #table()
 
 ---
-// Error: 14-19 expected color, none, array, or function, found string
+// Error: 14-19 expected color, gradient, none, array, or function, found string
 #table(fill: "hey")
+
+---
+// Test that header rows are repeated on each page without being counted twice.
+// Ref: false
+#set page(height: 80pt)
+#let c = counter("header")
+#table(
+  columns: 2,
+  header: 1,
+  [Key #c.step()], [Value],
+  ..range(10).map(n => ([#n], [#(n * n)])).flatten(),
+)
+
+#locate(loc => {
+  test(counter(page).at(loc).first() > 1, true)
+  test(c.at(loc), (1,))
+})
+
+---
+// Test a repeated header in a grid with gutters. The header and its trailing
+// gutter take 28pt, leaving room for two body rows per page.
+// Ref: false
+#set page(height: 60pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#grid(
+  columns: 2,
+  gutter: 4pt,
+  header: 2,
+  [A #locate(loc => test(counter(page).final(loc), (3,)))], [B],
+  [C], [D],
+  ..range(12).map(str),
+)
+
+---
+// Test per-cell strokes with a function.
+// Ref: false
+#show table: it => {
+  test(type(it.stroke), "function")
+  test((it.stroke)(0, 1).top, 2pt)
+  it
+}
+#table(
+  columns: 3,
+  stroke: (x, y) => (
+    top: if y == 1 { 2pt } else { none },
+    left: none,
+    right: none,
+  ),
+  [*A*], [*B*], [*C*],
+  [1], [2], [3],
+)
+
+---
+// Test per-column strokes with an array and per-side dictionaries. Sides
+// without a stroke are left out, unspecified ones get the default.
+// Ref: false
+#show table: it => {
+  test(it.stroke.at(0), table(stroke: red).stroke)
+  test(it.stroke.at(1).left, table(stroke: 1pt + blue).stroke)
+  test("bottom" in it.stroke.at(1), false)
+  it
+}
+#table(
+  columns: 2,
+  stroke: (red, (left: 1pt + blue, bottom: none)),
+  [A], [B],
+  [C], [D],
+)
+
+---
+// Test that strokes from multiple set rules are combined. A side that a set
+// rule removes keeps an inner stroke, but not the removed thickness.
+// Ref: false
+#set table(stroke: 2pt)
+#[
+  #show table: it => {
+    test(it.stroke, table(stroke: 2pt + red).stroke)
+    it
+  }
+  #table(stroke: red, [A])
+]
+
+#set table(stroke: (left: none))
+#show table: it => {
+  test(it.stroke, table(stroke: (
+    (left: blue, rest: 2pt + blue),
+    (left: green, rest: 2pt + green),
+  )).stroke)
+  it
+}
+#table(stroke: (blue, green), columns: 2, [A], [B])
+
+---
+// Error: 38-44 expected length, color, gradient, dictionary, stroke, none, array, or function, found string
+#table(columns: 2, [A], [B], stroke: "blue")
+
+---
+// Test building a table from CSV data.
+// Ref: false
+#let t = tabulate(csv("/zoo.csv"), header: true)
+#test(t.columns, (auto,) * 4)
+#test(t.children.len(), 16)
+#test(t.header, 1)
+#style(styles => test(
+  measure(t.children.first(), styles),
+  measure(strong[Name], styles),
+))
+#t
+
+---
+// Test numeric alignment and formatting of data cells.
+// Ref: false
+#let t = tabulate(
+  (("Item", "Price"), ("Apple", "1.5"), ("Pear", 2), ("Melon",)),
+  header: true,
+  numeric: true,
+  format: (value, x, y) => if y > 0 and x == 1 [#value €] else [#value],
+)
+#test(t.align, (auto, right))
+#test(t.children.len(), 8)
+#style(styles => test(measure(t.children.at(3), styles), measure([1.5 €], styles)))
+#t
+
+---
+// Error: 11-17 expected array, found integer
+#tabulate((1, 2))
+
+---
+// Test decimal alignment.
+// Ref: false
+#table(
+  columns: 3,
+  align: (left, "decimal", "decimal"),
+  [*Item*], [*Price*], [*Change*],
+  [Coffee], [2.5], [−0.25],
+  [Cake], [12.75], [+1],
+  [Water], [0], [n/a],
+)
+
+// The price column is as wide as its longest integer and fractional parts.
+#style(styles => {
+  let width(body) = measure(body, styles).width
+  let price = table(align: "decimal", inset: 0pt, stroke: none, [2.5], [12.75], [0])
+  test(calc.abs((width(price) - width[12.75]) / 1pt) < 0.1, true)
+})
+
+---
+// Test that the decimal separators are aligned.
+// Ref: false
+#set table(inset: 0pt, stroke: none)
+#style(styles => {
+  let near(a, b) = calc.abs((a - b) / 1pt) < 0.1
+  let width(body) = measure(body, styles).width
+  test(near(width(table(align: "decimal", [10.5], [1.25])), width[10.25]), true)
+  test(near(
+    width(table(align: "decimal", decimal-separator: ",", [10,5], [1,25])),
+    width[10,25],
+  ), true)
+  test(width(table(align: "decimal", [A], [10])), width[10])
+})
+
+---
+// Test rotated cells.
+// Ref: false
+#table(
+  columns: 4,
+  align: bottom + center,
+  [], table.cell(rotate: -90deg)[*First*], table.cell(rotate: -90deg)[*Second*],
+  table.cell(rotate: 90deg)[*Third*],
+  [A], [1], [2], [3],
+)
+
+// Rotated cells make their row taller and their column narrower.
+#test(table.cell(rotate: -90deg)[*First*].rotate, -90deg)
+#style(styles => {
+  let plain = measure(table(columns: 2, [*Second*], [A]), styles)
+  let rotated = measure(table(columns: 2, table.cell(rotate: -90deg)[*Second*], [A]), styles)
+  test(rotated.height > plain.height and rotated.width < plain.width, true)
+})
+
+---
+// Test that rotated cells size their row.
+// Ref: false
+#set table(inset: 0pt, stroke: none)
+#style(styles => {
+  let near(a, b) = calc.abs((a - b) / 1pt) < 0.01
+  let r = rect(width: 30pt, height: 10pt)
+  let size = measure(table(columns: 2, table.cell(rotate: 90deg, r), [A]), styles)
+  test(near(size.height, 30pt), true)
+  let size = measure(grid(grid.cell(rotate: -90deg, r)), styles)
+  test(near(size.width, 10pt) and near(size.height, 30pt), true)
+})
+
+---
+// Test striped rows.
+// Ref: false
+#table(
+  columns: 2,
+  header: 1,
+  fill: stripe(even: luma(240), odd: luma(220), header: 1),
+  [*A*], [*B*],
+  [1], [2],
+  [3], [4],
+  [5], [6],
+)
+
+---
+// Test the stripe fill function.
+// Ref: false
+#let f = stripe(odd: red, header: 1)
+#test(f(0, 0), none)
+#test(f(1, 1), none)
+#test(f(0, 2), red)
+#test(f(1, 4), red)
+#let g = stripe(even: blue, odd: green)
+#test(g(0, 0), blue)
+#test(g(2, 1), green)
+#test(stripe()(0, 0), none)
+
+---
+// Test striping a single row and an empty table.
+// Ref: false
+#table(fill: stripe(even: aqua), [Single])
+#table(fill: stripe(even: aqua, header: 1), [Header])
+#table(fill: stripe(even: aqua))
+
+---
+// Error: 25-28 expected color or gradient, found string
+#stripe(odd: red, even: "x")
+
+---
+// Test column styles.
+// Ref: false
+#table(
+  columns: (auto, 1fr, auto),
+  align: center,
+  column-styles: (strong, none, it => align(right, text(blue, it))),
+  [Apples], [Fruit], [12],
+  [Carrots], [Vegetable], text(red)[-3],
+  table.cell(colspan: 2)[Total], [9],
+)
+
+---
+// Test that column styles are applied to each cell of their column only.
+// Ref: false
+#set table(stroke: none)
+#style(styles => {
+  let width(body) = measure(body, styles).width
+  test(
+    width(table(columns: 2, column-styles: (none, _ => [Wide content]), [A], [B])),
+    width(table(columns: 2, [A], [Wide content])),
+  )
+})
+
+---
+// Error: 23-27 expected function or none, found integer
+#table(column-styles: (1,))
@@ -42,6 +42,30 @@
 / BB: Two letters
 / CCC: Three letters
 
+---
+// Test multiple paragraphs and nesting.
+// Ref: false
+#set text(8pt)
+/ Fruit: A tasty, edible thing.
+
+  Grows on trees.
+  / Apple: Red or green.
+  / Banana: Yellow.
+/ Veggie: Healthy.
+
+---
+// Test descriptions beneath terms.
+// Ref: false
+#set terms(beneath: true, indent: 5pt)
+/ Ligature: A merged glyph.
+/ Kerning: A spacing adjustment.
+
+---
+// Test right-to-left term list.
+// Ref: false
+#set text(lang: "ar", font: ("PT Sans", "Noto Sans Arabic"))
+/ مصطلح: وصف طويل بما فيه الكفاية ليمتد على عدة أسطر.
+
 ---
 / Term:
 Not in list
@@ -47,3 +47,17 @@ nor #xetex!
 #box(scale(r, x: 50%, y: 200%, origin: left + top))
 #box(scale(r, x: 50%, origin: center))
 #box(scale(r, x: 50%, y: 200%, origin: right + bottom))
+
+---
+// Test that reflowing rotations affect the layout.
+// Ref: false
+#style(styles => {
+  let near(a, b) = calc.abs((a - b) / 1pt) < 0.01
+  let r = rect(width: 20pt, height: 10pt)
+  let size = measure(rotate(90deg, reflow: true, r), styles)
+  test(near(size.width, 10pt) and near(size.height, 20pt), true)
+  let size = measure(rotate(45deg, reflow: true, square(size: 10pt)), styles)
+  test(near(size.width, calc.sqrt(2) * 10pt), true)
+  let size = measure(rotate(90deg, r), styles)
+  test(size.width, 20pt)
+})
@@ -63,3 +63,17 @@ $ mat(B, A B) $
 $ mat(B, A B, dots) $
 $ mat(B, A B, dots;) $
 $ mat(#1, #(foo: "bar")) $
+
+---
+// Test augmented matrices.
+// Ref: false
+$ mat(1, 0, 1; 0, 1, 2; augment: #(-1)) $
+$ mat(1, 0, 0; 0, 1, 0; 0, 0, 1; augment: #(hline: (1, -1), vline: 2, stroke: red)) $
+
+---
+// Error: 3-31 cannot draw a vertical line at offset 2
+$ mat(1, 2; 3, 4; augment: #2) $
+
+---
+// Error: 29-40 unexpected key "hlines", valid keys are "hline", "vline", and "stroke"
+$ mat(1, 2; 3, 4; augment: #(hlines: 1)) $
@@ -9,3 +9,46 @@ $ phi.alt := (1 + sqrt(5)) / 2 $ <ratio>
 
 With @ratio, we get
 $ F_n = round(1 / sqrt(5) phi.alt^n) $ <fib>
+
+---
+// Test number alignment, gutter and overly wide equations.
+// Ref: false
+#set page(width: 150pt)
+#set math.equation(numbering: "(1)", number-align: start, number-gutter: 1em)
+$ a + b = c $
+$ a + b + c + d + e + f + g + h + i + j = k $
+
+#set text(dir: rtl)
+#set math.equation(number-align: end)
+$ a + b = c $
+
+---
+// Error: 52-55 alignment must be horizontal
+#set math.equation(numbering: "(1)", number-align: top)
+
+---
+// Test individually numbered lines.
+// Ref: false
+#set math.equation(numbering: "(1)")
+$ a = b $ <before>
+$ a &= b + c #math.numbered(<line1>) \
+    &= c + b \
+    &= d #math.numbered(<line3>) $
+$ e = f $ <after>
+
+#locate(loc => {
+  let at(label) = counter(math.equation).at(query(label, loc).first().location())
+  test(at(<before>), (1,))
+  test(at(<line1>), (2,))
+  test(at(<line3>), (3,))
+  test(at(<after>), (4,))
+})
+
+See @line1, @line3 and @after.
+
+---
+#set math.equation(numbering: "(1)")
+$ a &= b #math.numbered() \
+    &= c $ <whole>
+// Error: 1-7 cannot reference equation without numbering
+@whole
@@ -0,0 +1,20 @@
+// Test named destinations.
+
+---
+// Ref: false
+#anchor("results")
+= Results
+#link("#results")[Back to results]
+
+---
+// Error: 9-20 anchor name may only contain ASCII letters, digits, `-`, `_`, and `.`
+#anchor("not valid")
+
+---
+// Error: 2-27 anchor does not exist in the document
+#link("#nowhere")[Nowhere]
+
+---
+// Error: 2-18 anchor occurs multiple times in the document
+#anchor("twice")
+#anchor("twice")
@@ -0,0 +1,14 @@
+// Test bibliography styles from CSL files.
+// Ref: false
+
+---
+See @arrgh and @netwok.
+#bibliography("/works.bib", style: "/numeric.csl")
+#locate(loc => {
+  let bib = query(bibliography, loc).first()
+  test(bib.style, "ieee")
+})
+
+---
+// Error: 36-47 file not found (searched at /nope.csl)
+#bibliography("/works.bib", style: "/nope.csl")
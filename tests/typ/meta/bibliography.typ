@@ -36,3 +36,38 @@ And quark! @quark
 = Multiple Bibs
 Now we have multiple bibliographies containing #cite("glacier-melt", "keshav2007read")
 #bibliography(("/works.bib", "/works_too.bib"))
+
+---
+// Test listing uncited works.
+// Ref: false
+#set page(width: 200pt)
+See @netwok.
+#bibliography("/works.bib", full: true)
+
+---
+// Test multiple bibliographies with separate numbering.
+// Ref: false
+#set page(width: 200pt)
+#set cite(group: "first")
+@arrgh and @netwok
+#bibliography("/works.bib", group: "first")
+
+#set cite(group: "second")
+@distress
+#bibliography("/works.bib", group: "second")
+
+---
+#set cite(group: "second")
+// Error: 1-7 bibliography for the group "second" does not contain this key
+@arrgh
+#bibliography("/works.bib", group: "first")
+#bibliography("/works_too.bib", group: "second")
+
+---
+// Test page locators in numeric and author-date styles.
+// Ref: false
+#set page(width: 200pt)
+@netwok[23] and @arrgh[23--25] and @distress[chapter 2]
+
+#cite("netwok", "arrgh", style: "chicago-author-date")[7-9]
+#bibliography("/works.bib")
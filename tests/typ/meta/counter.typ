@@ -50,3 +50,35 @@ At Beta, it was #locate(loc => {
 #figure(caption: [Four 'C's], kind: image, supplement: "Figure")[_CCCC!_]
 #counter(figure.where(kind: image)).update(n => n + 3)
 #figure(caption: [Four 'D's], kind: image, supplement: "Figure")[_DDDD!_]
+
+---
+// Ref: false
+// Final values, also of counters that are never used.
+#locate(loc => test(counter("unused").final(loc), (0,)))
+#locate(loc => test(counter(figure).final(loc), (2,)))
+
+#figure([A], caption: [First])
+#figure([B], caption: [Second])
+
+---
+// Ref: false
+// Step by an arbitrary amount.
+#let c = counter("skip")
+#c.step(by: 10)
+#locate(loc => test(c.at(loc), (10,)))
+#c.step(level: 2, by: 3)
+#locate(loc => test(c.at(loc), (10, 3)))
+#c.step(by: 2)
+#locate(loc => test(c.at(loc), (12,)))
+
+---
+// Ref: false
+// Skip figure numbers.
+#figure([A], caption: [A], kind: "thing", supplement: [Thing]) <a>
+#counter(figure.where(kind: "thing")).step(by: 3)
+#figure([B], caption: [B], kind: "thing", supplement: [Thing]) <b>
+
+#locate(loc => {
+  let b = query(<b>, loc).first()
+  test(b.counter.at(b.location()), (5,))
+})
@@ -10,6 +10,18 @@ What's up?
 // Ref: false
 #set document(author: ("A", "B"))
 
+---
+// Ref: false
+#set document(
+  subject: "Testing",
+  keywords: ("typst", "pdf"),
+  date: datetime(year: 2023, month: 5, day: 1),
+)
+
+---
+// Error: 21-24 expected datetime or none, found integer
+#set document(date: 123)
+
 ---
 // This, too.
 // Error: 23-29 expected string, found integer
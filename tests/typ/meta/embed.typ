@@ -0,0 +1,14 @@
+// Test embedded files.
+
+---
+// Ref: false
+#embed("/zoo.csv", description: "Animals", mime: "text/csv")
+
+---
+// Ref: false
+#set embed(description: "Greeting")
+#embed("/hello.txt")
+
+---
+// Error: 8-18 file not found (searched at typ/meta/nope.csv)
+#embed("nope.csv")
@@ -0,0 +1,45 @@
+// Test endnotes.
+// Ref: false
+
+---
+// Test collection and shared numbers.
+A #endnote[One] <one>
+B #endnote[Two]
+C #endnote(<one>)
+
+#endnotes(title: [Notes])
+#locate(loc => {
+  let notes = query(endnote, loc)
+  test(notes.len(), 3)
+  test(counter(endnote).at(notes.last().location()), (2,))
+})
+
+---
+// Test per-chapter listings.
+#show heading.where(level: 1): it => {
+  endnotes()
+  it
+}
+
+= Intro
+Hello #endnote[World].
+
+= Outro
+Bye #endnote[Moon].
+
+#endnotes()
+
+---
+// A reference before the definition gets the same number.
+See #endnote(<later>).
+Then #endnote[Defined] <later>.
+#locate(loc => {
+  let notes = query(endnote, loc)
+  test(counter(endnote).at(notes.first().location()), (0,))
+  test(counter(endnote).at(notes.last().location()), (1,))
+})
+#endnotes()
+
+---
+// Error: 2-17 label does not exist in the document
+#endnote(<nope>)
@@ -100,3 +100,155 @@ We can clearly see that @fig-cylinder and
 #show figure: set block(breakable: true)
 
 #figure(table[a][b][c][d][e], caption: [A table])
+
+---
+// Ref: false
+// Number figures per chapter. The numbering records the numbers it receives
+// from the outline, the captions and the references, in this order.
+#let seen = state("seen", ())
+#set heading(numbering: "1.")
+#set figure(numbering: (..nums) => seen.update(it => it + (nums.pos(),)), reset-at: 1)
+
+#outline(target: figure)
+
+#figure([Z], caption: [Before]) <z>
+
+= Introduction
+#figure([A], caption: [First]) <a>
+
+= Results
+#figure([B], caption: [Second]) <b>
+#figure([C], caption: [Third]) <c>
+
+See @z, @a, @b, and @c.
+
+#locate(loc => test(seen.final(loc), ((0, 1), (1, 1), (2, 1), (2, 2)) * 3))
+
+---
+// Ref: false
+// Subfigures are numbered within their figure.
+#figure([Other], caption: [Another figure])
+
+#figure(
+  grid(
+    columns: 2,
+    [#subfigure([A], caption: [First]) <sub-a>],
+    [#subfigure([B], caption: [Second]) <sub-b>],
+  ),
+  caption: [A figure with panels],
+) <parent>
+
+See @parent, @sub-a, and @sub-b.
+
+#locate(loc => {
+  let parent = query(<parent>, loc).first()
+  let sub = query(<sub-b>, loc).first()
+  test(counter(figure.where(kind: image)).at(parent.location()), (2,))
+  test(sub.supplement, [Figure])
+  test(sub.at("figure-numbering"), "1")
+  test(sub.numbering, "(a)")
+})
+
+---
+// Ref: false
+// Subfigures numbered with a function use only their own number. The
+// numbering records the numbers from the captions and references.
+#let seen = state("seen", ())
+#set subfigure(numbering: n => seen.update(it => it + (n,)))
+#figure([Other], caption: [Another figure])
+
+#figure(
+  grid(
+    columns: 2,
+    [#subfigure([A], caption: [First]) <sub-a>],
+    [#subfigure([B], caption: [Second]) <sub-b>],
+  ),
+  caption: [A figure with panels],
+)
+
+See @sub-b and @sub-a.
+
+#locate(loc => test(seen.final(loc), (1, 2, 2, 1)))
+
+---
+// Subfigures only belong to the figure containing them.
+// Ref: false
+#figure(
+  subfigure([A], caption: [Inside]),
+  caption: [A figure with a panel],
+)
+
+#subfigure([B], caption: [Outside]) <outside>
+
+See @outside.
+
+#locate(loc => {
+  let outside = query(<outside>, loc).first()
+  test(outside.at("figure-numbering"), none)
+})
+
+---
+// Ref: false
+// Subfigures outside of figures count on their own.
+#let seen = state("seen", ())
+#set subfigure(numbering: n => seen.update(it => it + (n,)))
+#figure(
+  grid(
+    columns: 2,
+    subfigure([A], caption: [Inside]),
+    subfigure([B], caption: [Also inside]),
+  ),
+  caption: [A figure with panels],
+)
+
+#subfigure([C], caption: [Outside]) <outside>
+See @outside.
+
+#locate(loc => test(seen.final(loc), (1, 2, 1, 1)))
+
+---
+// Test caption placement of subfigures. The figure starts at the top margin,
+// so the captions' baselines are above 88pt and the bodies' below.
+// Ref: false
+#set subfigure(caption-pos: top, gap: 1em)
+#let above = locate(loc => test(loc.position().y < 88pt, true))
+#let below = locate(loc => test(loc.position().y > 88pt, true))
+#figure(
+  grid(
+    columns: 2,
+    subfigure([Body #below], caption: [Above #above]),
+    subfigure([Body #below], caption: [Also above #above]),
+  ),
+  caption: [Panels],
+)
+
+---
+// Test caption placement above the body. As above, the caption's baseline is
+// above 88pt and the table's below.
+// Ref: false
+#set figure(caption-pos: top)
+#figure(
+  table(columns: 2, [a #locate(loc => test(loc.position().y > 88pt, true))], [b], [c], [d]),
+  caption: [Caption above the table #locate(loc => test(loc.position().y < 88pt, true))],
+)
+
+#set figure(caption-pos: bottom, gap: 1em)
+#figure(rect(), caption: [Caption below the rectangle])
+
+---
+// Error: 40-44 alignment must be vertical
+#figure([], caption: [A], caption-pos: left)
+
+---
+// Test alternative text for figures.
+// Ref: false
+#set figure(caption-alt: true)
+#figure(rect(), caption: [A *bold* box]) <a>
+#figure(rect(), alt: "Explicit", caption: [Ignored]) <b>
+#figure(rect()) <c>
+
+#locate(loc => {
+  test(query(<a>, loc).first().alt, "A bold box")
+  test(query(<b>, loc).first().alt, "Explicit")
+  test(query(<c>, loc).first().alt, none)
+})
@@ -0,0 +1,24 @@
+// Test per-page footnote numbering.
+// Ref: false
+
+---
+#set page(height: 120pt)
+#set footnote(per-page: true)
+
+A #footnote[One]
+B #footnote[Two]
+#pagebreak()
+C #footnote[One again]
+
+---
+// The counter still counts throughout the document.
+#set page(height: 120pt)
+#set footnote(per-page: true)
+
+A #footnote[One]
+#pagebreak()
+B #footnote[Two] <b>
+#locate(loc => {
+  let b = query(<b>, loc).first()
+  test(counter(footnote).at(b.location()), (2,))
+})
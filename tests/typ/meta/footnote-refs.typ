@@ -0,0 +1,38 @@
+// Test references to existing footnotes.
+// Ref: false
+
+---
+A #footnote[One] <one>
+B #footnote[Two]
+C #footnote(<one>)
+#locate(loc => {
+  let notes = query(footnote, loc)
+  test(notes.len(), 3)
+  test(counter(footnote).at(notes.last().location()), (2,))
+})
+
+---
+// A reference before its definition.
+#footnote(<later>)
+#footnote[Defined] <later>
+#locate(loc => {
+  let notes = query(footnote, loc)
+  test(counter(footnote).at(notes.first().location()), (0,))
+  test(counter(footnote).at(notes.last().location()), (1,))
+})
+
+---
+// References also work with per-page numbering.
+#set footnote(per-page: true)
+A #footnote[One]
+B #footnote[Two] <two>
+C #footnote(<two>)
+
+---
+// Error: 2-21 label does not exist in the document
+#footnote(<missing>)
+
+---
+= Heading <hi>
+// Error: 2-16 label must refer to a footnote with content
+#footnote(<hi>)
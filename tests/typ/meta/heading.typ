@@ -51,3 +51,87 @@ multiline.
 =
 Not in heading
 =Nope
+
+---
+// Ref: false
+// Headings beyond the numbering depth still step the counter.
+#set heading(numbering: "1.1", numbering-depth: 2)
+= Alpha
+== Beta
+=== Gamma <gamma>
+== Delta <delta>
+
+#locate(loc => {
+  let gamma = query(<gamma>, loc).first()
+  test(counter(heading).at(gamma.location()), (1, 1, 1))
+  let delta = query(<delta>, loc).first()
+  test(counter(heading).at(delta.location()), (1, 2))
+})
+
+---
+#set heading(numbering: "1.1", numbering-depth: 1)
+= Alpha
+== Beta <beta>
+
+// Error: 1-6 cannot reference heading without numbering
+@beta
+
+---
+// Ref: false
+// The short title is available as a field.
+#heading(short: [Short])[A very long title] <long>
+
+#locate(loc => {
+  let elem = query(<long>, loc).first()
+  test(elem.short, [Short])
+  test(elem.body, [A very long title])
+})
+
+---
+// Ref: false
+// Switch to lettered numbering for the appendix.
+#set heading(numbering: "1.")
+= Introduction
+= Methods
+
+#counter(heading).update(0)
+#set heading(numbering: "A.", supplement: [Appendix])
+= Proofs <proofs>
+
+#locate(loc => {
+  let elem = query(<proofs>, loc).first()
+  test(elem.supplement, [Appendix])
+  test(numbering(elem.numbering, ..counter(heading).at(elem.location())), "A.")
+})
+
+---
+// Ref: false
+// The supplement function can depend on the level.
+#set heading(
+  numbering: "1.1",
+  supplement: it => if it.level == 1 [Chapter] else [Section],
+)
+
+= Introduction <intro>
+== Motivation <motivation>
+
+#locate(loc => {
+  test(query(<intro>, loc).first().supplement, [Chapter])
+  test(query(<motivation>, loc).first().supplement, [Section])
+})
+
+---
+// Test finding the current section.
+// Ref: false
+#locate(loc => test(heading.current(loc), []))
+= Introduction
+== Motivation
+#locate(loc => {
+  test(heading.current(loc), [Introduction])
+  test(heading.current(loc, level: 2), [Motivation])
+})
+#heading(short: [Results])[Results of the experiment]
+#locate(loc => {
+  test(heading.current(loc), [Results])
+  test(heading.current(loc, level: 2), [Motivation])
+})
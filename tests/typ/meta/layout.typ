@@ -0,0 +1,22 @@
+// Test the `layout` function.
+
+---
+// Ref: false
+// Content can depend on the available width.
+#let adaptive = layout(size => {
+  test(size.width > 100pt, size.width == 200pt)
+})
+#box(width: 200pt, adaptive)
+#box(width: 50pt, adaptive)
+
+---
+// Ref: false
+// The size of the page is reported without its margins.
+#set page(height: 80pt, margin: 10pt)
+#layout(size => test(size.height, 60pt))
+
+---
+// Ref: false
+// Error: 19-31 maximum layout depth exceeded
+#let deep(size) = layout(deep)
+#layout(deep)
@@ -75,3 +75,12 @@ Text <hey>
 Text <hey>
 // Error: 2-20 label occurs multiple times in the document
 #link(<hey>)[Nope.]
+
+---
+// Test tooltips.
+// Ref: false
+#show link: it => {
+  test(it.tooltip, "Typst")
+  it
+}
+#link("https://typst.app", tooltip: "Typst")[A link that wraps across lines]
@@ -0,0 +1,31 @@
+// Test margin notes.
+
+---
+// Test stacking and outside margins on two-sided pages.
+// Ref: false
+#set page(width: 200pt, height: 100pt, margin: (inside: 20pt, outside: 60pt, y: 20pt))
+#set marginnote(gap: 5pt)
+A #marginnote[= One <one>] #marginnote[= Two <two>]
+#pagebreak()
+B #marginnote[= Three <three>]
+
+#locate(loc => {
+  let pos(label) = query(label, loc).first().location().position()
+  test(pos(<one>).x, 145pt)
+  test(pos(<two>).x, 145pt)
+  test(pos(<two>).y > pos(<one>).y, true)
+  test(pos(<three>).page, 2)
+  test(pos(<three>).x, 5pt)
+})
+
+---
+// Test that notes at the bottom of the page are shifted up.
+// Ref: false
+#set page(width: 200pt, height: 100pt, margin: (right: 60pt, rest: 20pt))
+#v(1fr)
+A #marginnote[= One <one> \ More \ Lines]
+
+#locate(loc => {
+  let pos = query(<one>, loc).first().location().position()
+  test(pos.y < 60pt, true)
+})
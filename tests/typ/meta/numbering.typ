@@ -85,3 +85,40 @@
 ---
 // Error: 17-19 number must be at least zero
 #numbering("1", -1)
+
+---
+// Ref: false
+// Custom counting symbols.
+#let greek = ("α", "β", "γ")
+#test(numbering(greek, 2), "β")
+#test(numbering(greek, 5), "ββ")
+#test(numbering(greek, 1, 3), "α.γ")
+#test(numbering((symbols: greek, wrap: "alphabetic"), 5), "αβ")
+#test(numbering((symbols: greek, wrap: "cycle"), 5), "β")
+#test(numbering((symbols: greek, wrap: "repeat"), 7), "ααα")
+
+---
+// Error: 12-14 expected at least one symbol
+#numbering((), 1)
+
+---
+// Ref: false
+// Zero padding.
+#test(numbering("001", 5), "005")
+#test(numbering("001", 1234), "1234")
+#test(numbering("A.001", 2, 7), "B.007")
+#test(numbering("01.1", 3, 4, 5), "03.4.5")
+#test(numbering("0a", 1), "0a")
+
+---
+// Ref: false
+// Numbering functions can collect all numbers into an array.
+#let path(..nums) = {
+  let nums = nums.pos()
+  range(1, nums.len() + 1)
+    .map(k => numbering("1.1.a", ..nums.slice(0, k)))
+    .join(" / ")
+}
+
+#test(numbering(path, 1), "1")
+#test(numbering(path, 1, 2, 3), "1 / 1.2 / 1.2.c")
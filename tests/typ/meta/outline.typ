@@ -34,3 +34,56 @@ Ok ...
 
 = #text(blue)[Zusammen]fassung
 #lorem(10)
+
+---
+// Ref: false
+// Outline figures of a specific kind instead of headings.
+#outline(
+  title: [List of Tables],
+  target: figure.where(kind: table),
+)
+
+#figure(
+  table(columns: 2, [a], [b]),
+  caption: [A table],
+)
+
+#figure(
+  rect(),
+  caption: [Not a table],
+  kind: image,
+)
+
+#locate(loc => test(query(figure.where(kind: table), loc).len(), 1))
+
+// Check the entries: Only the table's caption is listed, once.
+#let entries = counter("entries")
+#[
+  #show "A table": it => entries.step() + it
+  #show "Not a table": it => panic("image listed in outline of tables")
+  #outline(title: none, target: figure.where(kind: table))
+]
+
+#locate(loc => test(entries.final(loc), (1,)))
+
+---
+// Ref: false
+// An outline without matching entries.
+#outline(title: none, start-depth: 3)
+
+= Chapter
+== Section
+
+---
+// Ref: false
+// Fill depending on the level.
+#outline(fill: level => if level == 1 { line(length: 100%) })
+
+= Chapter
+== Section
+
+---
+// Error: 2-31 expected content or none, found integer
+#outline(fill: level => level)
+
+= Chapter
@@ -0,0 +1,25 @@
+// Test resolving selectors to locations.
+
+---
+// Ref: false
+#let s = state("s", 0)
+= One <one>
+#s.update(1)
+= Two
+#s.update(2)
+
+#locate(loc => {
+  test(s.at(query.location(<one>, loc)), 0)
+  test(s.at(query.location(heading, loc, pick: "last")), 1)
+  test(query.location(heading, loc, pick: "first"), query.location(<one>, loc))
+})
+
+---
+= One
+= Two
+// Error: 30-44 selector matches 2 elements, but must match exactly one
+#locate(loc => query.location(heading, loc))
+
+---
+// Error: 30-43 selector does not match any element
+#locate(loc => query.location(<nope>, loc))
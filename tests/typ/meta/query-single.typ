@@ -0,0 +1,19 @@
+// Test queries for single elements.
+
+---
+// Ref: false
+= One
+#locate(loc => {
+  test(query.before(heading, loc).body, [One])
+  test(query.after(heading, loc).body, [Two])
+  test(query.first(heading, loc).body, [One])
+  test(query.last(heading, loc).body, [Three])
+  test(query.before(heading.where(level: 2), loc), none)
+  test(query.last(figure, loc), none)
+
+  let one = query.first(heading, loc)
+  test(query.before(heading, one.location()), none)
+  test(query.after(heading, one.location()).body, [Two])
+})
+= Two
+= Three
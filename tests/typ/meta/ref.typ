@@ -46,3 +46,82 @@ $ A = 1 $ <eq2>
 
 #set ref(supplement: none)
 @fig1, @fig2, @eq1, @eq2
+
+---
+// Test equation references and outlines.
+// Ref: false
+#set math.equation(numbering: "(1)")
+$ a = b $ <eq3>
+See @eq3.
+#outline(title: none, target: math.equation)
+
+---
+// Test that equation references keep the parentheses of the numbering.
+// Ref: false
+#set math.equation(numbering: "(1)")
+$ a = b $ <eq5>
+#locate(loc => style(styles => {
+  test(measure(ref(<eq5>), styles), measure([Equation~(1)], styles))
+  test(measure(ref(<eq5>, supplement: none), styles), measure([(1)], styles))
+}))
+
+---
+$ a = b $ <eq4>
+// Error: 1-5 cannot reference equation without numbering
+@eq4
+
+---
+// Test references to multiple targets.
+// Ref: false
+#set heading(numbering: "1.")
+#set figure(numbering: "1")
+= A <a>
+= B <b>
+= C <c>
+#figure([X], caption: [X]) <x>
+#figure([Y], caption: [Y]) <y>
+#figure([Z], caption: [Z]) <z>
+
+#locate(loc => style(styles => {
+  test(measure(ref(<a>, <b>, <c>), styles), measure([Sections~1–3], styles))
+  test(measure(ref(<x>, <z>), styles), measure([Figures~1, 3], styles))
+  test(measure(ref(<x>, <y>, <z>), styles), measure([Figures~1–3], styles))
+  test(measure(ref(<a>), styles), measure([Section~1], styles))
+  test(
+    measure(ref(<a>, <c>, supplement: [Part]), styles),
+    measure([Part~1, 3], styles),
+  )
+}))
+
+---
+= A <h>
+#set heading(numbering: "1.")
+= B <i>
+// Error: 2-15 cannot reference heading without numbering
+#ref(<i>, <h>)
+
+---
+// Test references with page numbers.
+// Ref: false
+#set heading(numbering: "1.")
+#set page(numbering: "i")
+= Intro <intro>
+#pagebreak()
+#ref(<intro>, page: true)
+#ref(<intro>, page: (supplement, number, page) => [
+  #supplement #number on p. #page
+])
+
+---
+// Test that the page suffix follows the text language.
+// Ref: false
+#set text(lang: "de")
+#set heading(numbering: "1.")
+#set page(numbering: "1")
+= Einleitung <einleitung>
+#locate(loc => style(styles => {
+  test(
+    measure(ref(<einleitung>, page: true), styles),
+    measure([Abschnitt~1 auf Seite~1], styles),
+  )
+}))
@@ -18,3 +18,32 @@ Aب😀🏞سمB
 
 // Tofus are rendered with the first font.
 A🐈ዲሞB
+
+---
+// Test inspecting the consulted fonts.
+// Ref: false
+#set text(font: "Inria Serif", fallback: false)
+#style(styles => {
+  let report = fonts("Aب", styles)
+  test(report.runs, (
+    (text: "A", font: "Inria Serif"),
+    (text: "ب", font: none),
+  ))
+  test(report.missing, (
+    (char: "ب", codepoint: "U+0628", tried: ("Inria Serif",)),
+  ))
+})
+
+---
+// Test an explicit fallback list.
+// Ref: false
+#set text(font: "Inria Serif", fallback: ("Noto Sans Arabic",))
+#style(styles => {
+  let report = fonts("Aب", styles)
+  test(report.runs.map(run => run.font), ("Inria Serif", "Noto Sans Arabic"))
+  test(report.missing, ())
+})
+
+---
+// Error: 21-23 expected boolean, string, or array, found integer
+#set text(fallback: 12)
@@ -61,3 +61,20 @@ fi vs. #text(features: (liga: 0))[No fi]
 ---
 // Error: 21-35 expected string, found boolean
 #set text(features: ("tag", false))
+
+---
+// Test toggling features with booleans.
+// Ref: false
+#set text(features: (frac: true, zero: true))
+#set text(features: (frac: false))
+1/2 0
+
+---
+// Test which small capitals are used.
+// Ref: false
+#let synthesized = state("synthesized", ())
+#show smallcaps: it => synthesized.update(s => s + (it.synthesized,)) + it
+#smallcaps[Real]
+#text(font: "IBM Plex Sans", smallcaps[Synthesized])
+#text(font: "IBM Plex Sans", smallcaps(fallback: false)[Missing])
+#locate(loc => test(synthesized.final(loc), (false, true, false)))
@@ -49,3 +49,17 @@ It's a #emph[Tree]beard.
 #set page(width: 60pt)
 #set text(hyphenate: true)
 #h(6pt) networks, the rest.
+
+---
+// Test hyphenation exceptions.
+// Ref: false
+#set page(width: 60pt)
+#set text(hyphenate: true, hyphenate-exceptions: (en: ("net-works", "Typst")))
+#h(6pt) NETWORKS, the rest.
+
+#set text(hyphenate-exceptions: (de: ("Bar-an-kauf",)))
+#text(lang: "de")[Barankauf]
+
+---
+// Error: 33-52 expected two or three letter language code (ISO 639-1/2/3)
+#set text(hyphenate-exceptions: (english: ("a-b",)))
@@ -49,3 +49,23 @@ Second part
 ทีวีตรวจทานนอร์ทแฟรีเลคเชอร์โกลด์อัลบัมเชอร์รี่เย้วสโตร์กฤษณ์เคลมเยอบีร่าพ่อค้าบลูเบอร์รี่สหัสวรรษโฮปแคนูโยโย่จูนสตรอว์เบอร์รีซื่อบื้อเยนแบ็กโฮเป็นไงโดนัททอมสเตริโอแคนูวิทย์แดรี่โดนัทวิทย์แอปพริคอทเซอร์ไพรส์ไฮบริดกิฟท์อินเตอร์โซนเซอร์วิสเทียมทานโคโยตี้ม็อบเที่ยงคืนบุญคุณ
 
 
+
+---
+// Test forbidding breaks with custom break penalties.
+// Ref: false
+#let rules = (en: ((before: regex("\\d+"), after: "km", penalty: calc.inf),))
+#style(styles => {
+  let free = measure(block(width: 5pt)[5 km], styles)
+  let kept = measure(block(width: 5pt, text(break-penalties: rules)[5 km]), styles)
+  let narrow = measure(block(width: 5pt)[5#sym.space.nobreak.narrow;km], styles)
+  test(kept.height < free.height, true)
+  test(narrow.height, kept.height)
+})
+
+#set text(break-penalties: (en: ((after: "km", penalty: 1000),)))
+#set par(linebreaks: "optimized")
+The distance was exactly 5 km.
+
+---
+// Error: 28-58 unexpected key "pen", valid keys are "before", "after", and "penalty"
+#set text(break-penalties: (en: ((after: "km", pen: 5),)))
@@ -21,3 +21,20 @@
 #set align(end)
 #set text(dir: rtl)
 :
+
+---
+// Test optical margin alignment at both edges.
+// Ref: false
+#set page(width: 130pt, margin: 15pt)
+#set par(justify: true, optical-alignment: true)
+"Quotes at the start and end of lines hang into the margin, too."
+
+#set par(optical-alignment: ("\"": 100%, ".": 0%))
+"Overridden amounts apply to quotes and periods."
+
+#set text(lang: "he", font: ("PT Sans", "Noto Serif Hebrew"))
+בנייה נכונה של משפטים ארוכים דורשת ידע בשפה. אז בואו נדבר על מזג האוויר.
+
+---
+// Error: 29-40 expected a single character as key, found "ab"
+#set par(optical-alignment: ("ab": 50%))
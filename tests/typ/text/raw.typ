@@ -53,6 +53,90 @@ The keyword ```rust let```.
 #show raw: set text(font: "Roboto")
 `Roboto`
 
+---
+// Line numbers.
+// Ref: false
+#set page(width: 120pt)
+#set raw(line-numbers: true, line-start: 8, line-step: 2)
+```rust
+fn main() {
+    println!("A line that is long enough to wrap");
+
+}
+```
+
+---
+// Highlighted lines.
+// Ref: false
+#set page(width: 120pt)
+#set raw(highlight: (1, "3-4"), highlight-fill: luma(230))
+```rust
+fn main() {
+
+    println!("A line that is long enough to wrap");
+}
+```
+
+---
+// Error: 21-29 expected line range like "3-5"
+#set raw(highlight: ("5-3",))
+
+---
+// Diff markers.
+// Ref: false
+#set page(width: 150pt)
+#set raw(diff: true, line-numbers: true)
+```python
+ def greet(name):
+-    print("Hello " + name)
++    print(f"Hello {name}")
+
+ greet("World")
+```
+
+---
+// Explicit diff statuses.
+// Ref: false
+#set raw(diff: ("unchanged", "removed", "added"))
+```rust
+fn main() {
+    let x = 1;
+    let x = 2;
+}
+```
+
+---
+// Error: 16-36 expected "unchanged", "added", or "removed"
+#set raw(diff: ("added", "changed"))
+
+---
+// Custom syntax and theme.
+// Ref: false
+#set raw(syntaxes: "/SExpressions.sublime-syntax", theme: "/halcyon.tmTheme")
+```sexp
+; Compute the factorial.
+(defun factorial (x)
+  (if (zerop x) 1 (* x (factorial (- x 1)))))
+```
+
+---
+// Unknown languages are not highlighted.
+// Ref: false
+```nonexistent
+Hello
+```
+
+---
+// Paths are resolved when the raw text is shown.
+#set raw(theme: "/missing.tmTheme")
+// Error: 1-4 file not found (searched at /missing.tmTheme)
+`x`
+
+---
+#set raw(theme: "/data.xml")
+// Error: 1-4 failed to parse theme (expected property list)
+`x`
+
 ---
 // Unterminated.
 // Error: 1-2:1 unclosed raw text
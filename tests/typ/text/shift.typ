@@ -17,3 +17,27 @@ n#super[1], n#sub[2], ... n#super[N]
 #underline[The claim#super[\[4\]]] has been disputed. \
 The claim#super[#underline[\[4\]]] has been disputed. \
 It really has been#super(box(text(baseline: 0pt, underline[\[4\]]))) \
+
+---
+// Test the typographic modes with a font that has `sups` and `subs`.
+// Ref: false
+#set text(font: "IBM Plex Sans")
+#set sub(typographic: "features")
+H#sub[2]O, x#super[st], x#super(typographic: "force")[abc],
+x#sub(typographic: false, baseline: 0.3em, size: 0.5em)[i]
+
+#test(super(typographic: "force")[1].typographic, "force")
+#test(sub(typographic: true)[1].typographic, true)
+#style(styles => test(
+  measure(super(typographic: "force")[2], styles),
+  measure(super(typographic: "features")[2], styles),
+))
+
+---
+// Test sub- and superscripts in math.
+// Ref: false
+$x#super[2] + y#sub(typographic: "features")[i] + z#super(typographic: false)[n]$
+
+---
+// Error: 25-29 expected "features", "force", or boolean
+#set sub(typographic: "no")
@@ -29,3 +29,24 @@ My text has spaces.
 // Test word spacing relative to the font's space width.
 #set text(spacing: 50% + 1pt)
 This is tight.
+
+---
+// Test that tracking is not applied after the last glyph of a line.
+// Ref: false
+#set page(width: 80pt)
+#set par(justify: true)
+#set text(tracking: 0.2em)
+#show heading: it => upper(it.body)
+= Tracked heading
+Tracked text that spans a few lines.
+
+---
+// Test manual kerning pairs.
+// Ref: false
+#set text(kerning-pairs: ("AV": -0.1em))
+#set text(kerning-pairs: ("LT": -0.05em))
+AVALT
+
+---
+// Error: 26-38 kerning pair must consist of exactly two characters
+#set text(kerning-pairs: ("AVA": 1pt))
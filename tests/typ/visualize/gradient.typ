@@ -0,0 +1,43 @@
+// Test gradient paints.
+// Ref: false
+
+---
+// Test filling shapes and text with gradients.
+#set page(width: 120pt)
+#rect(width: 100%, fill: gradient(red, yellow, blue))
+#stack(
+  dir: ltr,
+  spacing: 5pt,
+  circle(radius: 15pt, fill: gradient.radial(white, blue)),
+  square(size: 30pt, fill: gradient.linear(teal, purple, angle: 45deg)),
+  ellipse(width: 40pt, stroke: 2pt + gradient(red, blue)),
+)
+#text(fill: gradient(red, blue))[Gradient text]
+
+#let g = gradient(red, yellow, blue)
+#test(rect(fill: g).fill, g)
+#test(text(fill: g)[A].fill, g)
+#style(styles => test(
+  measure(text(fill: g)[Gradient text], styles),
+  measure([Gradient text], styles),
+))
+
+---
+// Test stop distribution.
+#test(gradient(red, blue), gradient((red, 0%), (blue, 100%)))
+#test(gradient(red, green, blue), gradient(red, (green, 50%), (blue, 100%)))
+#test(gradient.linear(red, blue), gradient(red, blue))
+#test(repr(gradient(red, blue, angle: 90deg)), "gradient.linear((rgb(\"#ff4136\"), 0%), (rgb(\"#0074d9\"), 100%), angle: 90deg)")
+#test(type(gradient.radial(red, blue)), "gradient")
+
+---
+// Error: 10-15 a gradient must have at least two stops
+#gradient(red)
+
+---
+// Error: 23-34 offsets must be in ascending order
+#gradient((red, 50%), (blue, 20%))
+
+---
+// Error: 16-28 offset must be between 0% and 100%
+#gradient(red, (blue, 150%))
@@ -49,6 +49,32 @@ A #box(image("/tiger.jpg", height: 1cm, width: 80%)) B
 // Test advanced SVG features.
 #image("/pattern.svg")
 
+---
+// Test WebP, detected from the magic bytes.
+// Ref: false
+#image("/pixel.webp", width: 10pt)
+
+---
+// Test flipping and EXIF orientation.
+// Ref: false
+#style(styles => {
+  let size(..args) = measure(image(..args, width: 67pt), styles)
+  test(size("/tiger.jpg", flip-x: true, flip-y: true), size("/tiger.jpg"))
+  test(size("/tiger-rotated.jpg").height, 102.4pt)
+  test(size("/tiger-rotated.jpg", auto-rotate: false).height, 43.837890625pt)
+})
+
+---
+// Test size derived from the resolution.
+// Ref: false
+#style(styles => {
+  let size = measure(image("/graph.png", dpi: 144), styles)
+  test(calc.round(size.width / 1pt, digits: 1), 595.5)
+  test(calc.round(size.height / 1pt, digits: 1), 170.5)
+  let size = measure(image("/graph.png", width: 100pt, dpi: 144), styles)
+  test(size.width, 100pt)
+})
+
 ---
 // Error: 8-29 file not found (searched at typ/visualize/path/does/not/exist)
 #image("path/does/not/exist")
@@ -39,6 +39,15 @@
   ),
 )
 
+---
+// Test a smooth S-curve with mirrored control points.
+// Ref: false
+#path(
+  stroke: 2pt + teal,
+  ((0pt, 0pt), (-30pt, 0pt)),
+  ((60pt, 40pt), (-30pt, 0pt)),
+)
+
 ---
 // Error: 7-9 path vertex must have 1, 2, or 3 points
 #path(())
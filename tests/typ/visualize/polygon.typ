@@ -30,3 +30,25 @@
 ---
 // Error: 10-17 point array must contain exactly two entries
 #polygon((50pt,))
+
+---
+// Ref: false
+// Test rounded corners.
+#set polygon(fill: blue, radius: 4pt)
+#polygon()
+#polygon((0pt, 0pt), (10pt, 0pt))
+#polygon((0pt, 0pt), (10pt, 0pt), (20pt, 0pt), (10pt, 10pt))
+
+// The radius is clamped at short edges.
+#polygon(radius: 100pt, (5pt, 0pt), (0pt, 10pt), (10pt, 10pt))
+
+// Rounding does not change the polygon's size.
+#style(styles => {
+  let star = polygon(
+    radius: 2pt,
+    (10pt, 0pt), (13pt, 7pt), (20pt, 7pt), (14pt, 12pt),
+    (16pt, 20pt), (10pt, 15pt), (4pt, 20pt), (6pt, 12pt),
+    (0pt, 7pt), (7pt, 7pt),
+  )
+  test(measure(star, styles), (width: 20pt, height: 20pt))
+})
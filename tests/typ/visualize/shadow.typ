@@ -0,0 +1,38 @@
+// Test shadows of shapes and blocks.
+// Ref: false
+
+---
+#set page(width: 120pt)
+#rect(fill: white, shadow: gray)
+#rect(fill: white, radius: 5pt, shadow: (offset: (0pt, 3pt), blur: 6pt))
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  circle(radius: 12pt, fill: white, shadow: (blur: 0pt, color: blue)),
+  ellipse(width: 30pt, height: 20pt, shadow: red),
+  square(size: 20pt, outset: 2pt, shadow: (color: rgb(0, 0, 0, 50%))),
+)
+#block(fill: white, inset: 5pt, shadow: (offset: (2pt, 2pt)))[Raised]
+
+#test(rect(shadow: gray).shadow, rect(shadow: (color: gray)).shadow)
+#test(block(shadow: (offset: (2pt, 2pt))).shadow, rect(shadow: (:)).shadow)
+#test(
+  repr(circle(shadow: (offset: (1pt, 2pt), blur: 3pt, color: luma(0))).shadow),
+  "(offset: (1pt, 2pt), blur: 3pt, color: luma(0))",
+)
+
+---
+// The shadow must not affect the layout.
+#style(styles => {
+  let plain = block(inset: 5pt)[A]
+  let shadowed = block(inset: 5pt, shadow: (offset: (10pt, 10pt), blur: 8pt))[A]
+  test(measure(plain, styles), measure(shadowed, styles))
+})
+
+---
+// Error: 15-28 unexpected key "spread", valid keys are "offset", "blur", and "color"
+#rect(shadow: (spread: 2pt))
+
+---
+// Error: 15-18 expected color, dictionary, shadow, or none, found length
+#rect(shadow: 2pt)
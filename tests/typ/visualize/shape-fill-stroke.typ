@@ -48,3 +48,11 @@
   radius: 100%, align(center+horizon)[*G*],
   inset: 8pt
 )
+
+---
+// Ref: false
+// Test gradient strokes next to other strokes at sharp corners.
+#set rect(width: 40pt, height: 20pt)
+#rect(stroke: (left: 4pt + red, top: 2pt + gradient(yellow, green), right: 4pt + green, bottom: blue))
+#rect(stroke: (x: 3pt + gradient(red, blue), y: none))
+#rect(stroke: (top: 4pt + gradient.linear(teal, purple), rest: (thickness: 1pt, dash: "dotted")))
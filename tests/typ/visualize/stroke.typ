@@ -63,6 +63,18 @@
 // Error: 29-55 expected "solid", "dotted", "densely-dotted", "loosely-dotted", "dashed", "densely-dashed", "loosely-dashed", "dash-dotted", "densely-dash-dotted", "loosely-dash-dotted", array, dictionary, or none
 #line(length: 60pt, stroke: (paint: red, dash: "dash"))
 
+---
+// Dash pattern with phase as an array.
+// Ref: false
+#test(
+  line(stroke: (dash: ((1pt, 2pt), 3pt))).stroke,
+  line(stroke: (dash: (array: (1pt, 2pt), phase: 3pt))).stroke,
+)
+
+---
+// Error: 29-55 expected "dot" or length, found boolean
+#line(length: 60pt, stroke: (dash: ((1pt, true), 2pt)))
+
 ---
 // 0pt strokes must function exactly like 'none' strokes and not draw anything
 #rect(width: 10pt, height: 10pt, stroke: none)